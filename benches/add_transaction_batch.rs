@@ -0,0 +1,66 @@
+//! `add_transaction_batch` against a loop over `add_transaction` on 1000 rows, each run into
+//! a fresh database file. Run with `cargo bench --bench add_transaction_batch`.
+
+use chrono::NaiveDate;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use financial_app::config::Config;
+use financial_app::db::connection::establish_connection;
+use financial_app::db::repository::{add_transaction, add_transaction_batch};
+use financial_app::models::transaction::{Transaction, TransactionType};
+use rusqlite::Connection;
+use rust_decimal::Decimal;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+const ROWS: usize = 1000;
+
+fn transactions() -> Vec<Transaction> {
+    (0..ROWS)
+        .map(|i| {
+            Transaction::new(
+                Uuid::new_v4().to_string(),
+                NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+                "Test Transaction".to_string(),
+                Decimal::new(10000, 2),
+                TransactionType::Income,
+                format!("Category {}", i % 20),
+            )
+        })
+        .collect()
+}
+
+/// An empty database file and the rows to insert into it. The directory is returned so the
+/// file outlives the measured insert.
+fn setup() -> (TempDir, Connection, Vec<Transaction>) {
+    let dir = TempDir::new().unwrap();
+    let config = Config { db_path: dir.path().join("bench.db"), ..Default::default() };
+    let conn = establish_connection(&config).unwrap();
+    (dir, conn, transactions())
+}
+
+fn bench_inserts(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("insert {} rows", ROWS));
+    group.sample_size(10);
+    group.bench_function("add_transaction loop", |b| {
+        b.iter_batched(
+            setup,
+            |(_dir, conn, transactions)| {
+                for transaction in &transactions {
+                    add_transaction(&conn, transaction).unwrap();
+                }
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.bench_function("add_transaction_batch", |b| {
+        b.iter_batched(
+            setup,
+            |(_dir, conn, transactions)| add_transaction_batch(&conn, &transactions).unwrap(),
+            BatchSize::PerIteration,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_inserts);
+criterion_main!(benches);
@@ -0,0 +1,66 @@
+//! `get_all_transactions` and a category query on 10,000 rows, with the transaction indexes
+//! `init_schema` creates and with them dropped. Run with
+//! `cargo bench --bench transaction_indexes`.
+
+use chrono::{Duration, NaiveDate};
+use criterion::{criterion_group, criterion_main, Criterion};
+use financial_app::config::Config;
+use financial_app::db::connection::establish_connection;
+use financial_app::db::repository::{add_transaction_batch, get_all_transactions, get_expenses_by_category_in_range};
+use financial_app::models::transaction::{Transaction, TransactionType};
+use rusqlite::Connection;
+use rust_decimal::Decimal;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+const ROWS: usize = 10_000;
+
+fn start_date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()
+}
+
+/// A database file with `ROWS` expenses spread over a few years, out of date order.
+fn seeded_database(dir: &TempDir, name: &str) -> Connection {
+    let config = Config { db_path: dir.path().join(name), ..Default::default() };
+    let conn = establish_connection(&config).unwrap();
+    let transactions: Vec<Transaction> = (0..ROWS)
+        .map(|i| {
+            Transaction::new(
+                Uuid::new_v4().to_string(),
+                start_date() + Duration::days((i * 7919 % 2000) as i64),
+                "Test Transaction".to_string(),
+                Decimal::new(10000, 2),
+                TransactionType::Expense,
+                format!("Category {}", i % 50),
+            )
+        })
+        .collect();
+    add_transaction_batch(&conn, &transactions).unwrap();
+    conn
+}
+
+fn bench_indexes(c: &mut Criterion) {
+    let dir = TempDir::new().unwrap();
+    let indexed = seeded_database(&dir, "indexed.db");
+    let plain = seeded_database(&dir, "plain.db");
+    plain
+        .execute_batch("DROP INDEX idx_transactions_date; DROP INDEX idx_transactions_category;")
+        .unwrap();
+
+    for (label, conn) in [("with indexes", &indexed), ("without indexes", &plain)] {
+        let mut group = c.benchmark_group(label);
+        group.bench_function("get_all_transactions", |b| {
+            b.iter(|| assert_eq!(get_all_transactions(conn).unwrap().len(), ROWS))
+        });
+        group.bench_function("get_expenses_by_category_in_range", |b| {
+            b.iter(|| {
+                get_expenses_by_category_in_range(conn, "category 7", start_date(), start_date() + Duration::days(2000))
+                    .unwrap()
+            })
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_indexes);
+criterion_main!(benches);
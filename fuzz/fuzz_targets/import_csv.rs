@@ -0,0 +1,17 @@
+#![no_main]
+
+use financial_app::operations::import::import_csv;
+use libfuzzer_sys::fuzz_target;
+use std::io::Write;
+
+// Any file content, including unterminated quotes, embedded newlines, NUL bytes and very
+// long lines, must give `Ok` or `Err` rows, never a panic.
+fuzz_target!(|data: &[u8]| {
+    let mut file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+    file.write_all(data).expect("Failed to write fuzz input");
+    let path = file.path().to_str().expect("Temp path is not UTF-8");
+
+    for delimiter in [b',', b';'] {
+        let _ = import_csv(path, delimiter, None);
+    }
+});
@@ -0,0 +1,365 @@
+use chrono::format::{Item, StrftimeItems};
+use crate::models::account::DEFAULT_ACCOUNT;
+use ratatui::style::Color;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// User preferences read from `~/.config/fino/config.toml`. Keys missing from the file keep their defaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Database file; the `FINO_DB_PATH` environment variable overrides it.
+    pub db_path: PathBuf,
+    /// chrono format used when listing transactions.
+    pub date_format: String,
+    /// Printed in front of amounts when listing transactions.
+    pub currency_symbol: String,
+    /// Separator of `.csv` imports.
+    pub csv_delimiter: char,
+    /// Category given to transactions added without one.
+    pub default_category: String,
+    /// Month (1-12) in which `fy:YYYY` date ranges start.
+    pub fiscal_year_start_month: u32,
+    /// Account given to transactions added without `--account`.
+    pub default_account: String,
+    /// Color preset of `browse`: `dark` or `light`.
+    pub theme_name: String,
+    /// The preset's colors with any overrides from the `[theme]` table.
+    pub theme: Theme,
+}
+
+/// Colors of the browse TUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Title and column headers.
+    pub header_fg: Color,
+    /// Income rows and confirmation messages.
+    pub income_fg: Color,
+    /// Expense rows.
+    pub expense_fg: Color,
+    /// Background of the row under the cursor.
+    pub selected_bg: Color,
+    /// Text of the row under the cursor.
+    pub highlight_fg: Color,
+    /// Key hints, placeholders and other secondary text.
+    pub muted_fg: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    pub const PRESETS: [&'static str; 2] = ["dark", "light"];
+
+    /// For dark terminal backgrounds; the colors browse always used.
+    pub fn dark() -> Self {
+        Self {
+            header_fg: Color::Cyan,
+            income_fg: Color::LightGreen,
+            expense_fg: Color::LightRed,
+            selected_bg: Color::DarkGray,
+            highlight_fg: Color::White,
+            muted_fg: Color::DarkGray,
+        }
+    }
+
+    /// For light terminal backgrounds, where the light colors are hard to read.
+    pub fn light() -> Self {
+        Self {
+            header_fg: Color::Blue,
+            income_fg: Color::Green,
+            expense_fg: Color::Red,
+            selected_bg: Color::Gray,
+            highlight_fg: Color::Black,
+            muted_fg: Color::DarkGray,
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            _ => None,
+        }
+    }
+
+    /// Reads a `[theme]` table such as `income_fg = "green"`; colors it leaves out keep the
+    /// ones of `base`. Colors are ratatui names (`lightred`, `dark-gray`), `#rrggbb` or a
+    /// 256-color index.
+    pub fn from_toml_value(value: &toml_edit::Item, base: Theme) -> Result<Self, String> {
+        let table = value.as_table_like().ok_or_else(|| "'theme' must be a table".to_string())?;
+        let mut theme = base;
+        for (key, item) in table.iter() {
+            let color = match key {
+                "header_fg" => &mut theme.header_fg,
+                "income_fg" => &mut theme.income_fg,
+                "expense_fg" => &mut theme.expense_fg,
+                "selected_bg" => &mut theme.selected_bg,
+                "highlight_fg" => &mut theme.highlight_fg,
+                "muted_fg" => &mut theme.muted_fg,
+                _ => return Err(format!("'theme.{}' is not a theme color", key)),
+            };
+            let text = item.as_str().ok_or_else(|| format!("'theme.{}' must be a string", key))?;
+            *color = Color::from_str(text).map_err(|_| format!("'theme.{}' has an invalid color '{}'", key, text))?;
+        }
+        Ok(theme)
+    }
+
+    fn to_toml(self) -> String {
+        format!(
+            "[theme]\nheader_fg = \"{}\"\nincome_fg = \"{}\"\nexpense_fg = \"{}\"\nselected_bg = \"{}\"\nhighlight_fg = \"{}\"\nmuted_fg = \"{}\"\n",
+            self.header_fg, self.income_fg, self.expense_fg, self.selected_bg, self.highlight_fg, self.muted_fg,
+        )
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            db_path: PathBuf::from("financial_app.db"),
+            date_format: "%Y-%m-%d".to_string(),
+            currency_symbol: String::new(),
+            csv_delimiter: ',',
+            default_category: "Uncategorized".to_string(),
+            fiscal_year_start_month: 1,
+            default_account: DEFAULT_ACCOUNT.to_string(),
+            theme_name: "dark".to_string(),
+            theme: Theme::dark(),
+        }
+    }
+}
+
+impl Config {
+    /// `~/.config/fino/config.toml`, or `None` when the home directory is unknown.
+    pub fn default_path() -> Option<PathBuf> {
+        std::env::home_dir().map(|home| home.join(".config").join("fino").join("config.toml"))
+    }
+
+    /// Reads the config file, falling back to the defaults when it is missing or invalid.
+    pub fn load() -> Self {
+        let Some(path) = Self::default_path() else {
+            return Self::default();
+        };
+        Self::load_from(&path).unwrap_or_else(|e| {
+            eprintln!("{} Using the default settings.", e);
+            Self::default()
+        })
+    }
+
+    /// Reads `path`. A missing file gives the defaults; a malformed one is an error.
+    pub fn load_from(path: &Path) -> Result<Self, String> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("Failed to read config file '{}': {}", path.display(), e)),
+        };
+        Self::parse(&contents).map_err(|e| format!("Invalid config file '{}': {}", path.display(), e))
+    }
+
+    fn parse(contents: &str) -> Result<Self, String> {
+        let document = contents
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|e| e.to_string())?;
+        let text = |key: &str| -> Result<Option<String>, String> {
+            match document.get(key) {
+                None => Ok(None),
+                Some(item) => item
+                    .as_str()
+                    .map(|value| Some(value.to_string()))
+                    .ok_or_else(|| format!("'{}' must be a string", key)),
+            }
+        };
+
+        let mut config = Self::default();
+        if let Some(db_path) = text("db_path")? {
+            config.db_path = PathBuf::from(db_path);
+        }
+        if let Some(date_format) = text("date_format")? {
+            if StrftimeItems::new(&date_format).any(|item| item == Item::Error) {
+                return Err(format!("'date_format' has an invalid format '{}'", date_format));
+            }
+            config.date_format = date_format;
+        }
+        if let Some(currency_symbol) = text("currency_symbol")? {
+            config.currency_symbol = currency_symbol;
+        }
+        if let Some(delimiter) = text("csv_delimiter")? {
+            let mut chars = delimiter.chars();
+            config.csv_delimiter = match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii() => c,
+                _ => return Err("'csv_delimiter' must be a single ASCII character".to_string()),
+            };
+        }
+        if let Some(default_category) = text("default_category")? {
+            if default_category.trim().is_empty() {
+                return Err("'default_category' cannot be empty".to_string());
+            }
+            config.default_category = default_category;
+        }
+        if let Some(item) = document.get("fiscal_year_start_month") {
+            config.fiscal_year_start_month = item
+                .as_integer()
+                .filter(|month| (1..=12).contains(month))
+                .map(|month| month as u32)
+                .ok_or_else(|| "'fiscal_year_start_month' must be a number from 1 to 12".to_string())?;
+        }
+        if let Some(default_account) = text("default_account")? {
+            if default_account.trim().is_empty() {
+                return Err("'default_account' cannot be empty".to_string());
+            }
+            config.default_account = default_account;
+        }
+        if let Some(theme_name) = text("theme_name")? {
+            config.theme = Theme::preset(&theme_name)
+                .ok_or_else(|| format!("'theme_name' must be one of: {}", Theme::PRESETS.join(", ")))?;
+            config.theme_name = theme_name;
+        }
+        if let Some(item) = document.get("theme") {
+            config.theme = Theme::from_toml_value(item, config.theme)?;
+        }
+        Ok(config)
+    }
+
+    /// Writes every setting to `path`, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory '{}': {}", parent.display(), e))?;
+        }
+        std::fs::write(path, self.to_toml()).map_err(|e| format!("Failed to write config file '{}': {}", path.display(), e))
+    }
+
+    fn to_toml(&self) -> String {
+        let mut toml = format!(
+            "db_path = {}\ndate_format = {}\ncurrency_symbol = {}\ncsv_delimiter = {}\ndefault_category = {}\nfiscal_year_start_month = {}\ndefault_account = {}\ntheme_name = {}\n",
+            toml_string(&self.db_path.to_string_lossy()),
+            toml_string(&self.date_format),
+            toml_string(&self.currency_symbol),
+            toml_string(&self.csv_delimiter.to_string()),
+            toml_string(&self.default_category),
+            self.fiscal_year_start_month,
+            toml_string(&self.default_account),
+            toml_string(&self.theme_name),
+        );
+        // Tables go after the top-level keys; an unchanged preset needs none.
+        if Theme::preset(&self.theme_name) != Some(self.theme) {
+            toml.push('\n');
+            toml.push_str(&self.theme.to_toml());
+        }
+        toml
+    }
+
+    /// The CSV delimiter as the byte the csv reader expects.
+    pub fn csv_delimiter_byte(&self) -> u8 {
+        // `parse` only accepts ASCII delimiters.
+        self.csv_delimiter as u8
+    }
+}
+
+/// Basic TOML string with quotes, backslashes and control characters escaped.
+fn toml_string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_missing_file_gives_defaults() {
+        let config = Config::load_from(Path::new("/nonexistent-dir/config.toml")).unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_parse_partial_config() {
+        let config = Config::parse("# fino\ncurrency_symbol = \"€\"\ncsv_delimiter = \";\"\nfiscal_year_start_month = 7\n").unwrap();
+        assert_eq!(config.currency_symbol, "€");
+        assert_eq!(config.csv_delimiter_byte(), b';');
+        assert_eq!(config.fiscal_year_start_month, 7);
+        assert_eq!(config.db_path, PathBuf::from("financial_app.db"));
+        assert_eq!(config.default_category, "Uncategorized");
+        assert_eq!(config.default_account, "default");
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_values() {
+        assert!(Config::parse("db_path = 3\n").unwrap_err().contains("'db_path' must be a string"));
+        assert!(Config::parse("csv_delimiter = \";;\"\n").unwrap_err().contains("single ASCII character"));
+        assert!(Config::parse("date_format = \"%Q\"\n").unwrap_err().contains("invalid format"));
+        assert!(Config::parse("default_category = \" \"\n").unwrap_err().contains("cannot be empty"));
+        assert!(Config::parse("default_account = \"\"\n").unwrap_err().contains("cannot be empty"));
+        assert!(Config::parse("db_path = \n").is_err());
+        assert!(Config::parse("fiscal_year_start_month = 13\n").unwrap_err().contains("from 1 to 12"));
+        assert!(Config::parse("fiscal_year_start_month = \"4\"\n").is_err());
+        assert!(Config::parse("theme_name = \"solarized\"\n").unwrap_err().contains("one of: dark, light"));
+        assert!(Config::parse("theme = \"light\"\n").unwrap_err().contains("must be a table"));
+        assert!(Config::parse("[theme]\nincome_fg = \"greenish\"\n").unwrap_err().contains("invalid color 'greenish'"));
+        assert!(Config::parse("[theme]\nborder_fg = \"red\"\n").unwrap_err().contains("not a theme color"));
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("fino").join("config.toml");
+        let config = Config {
+            db_path: PathBuf::from("C:\\Users\\me\\fino.db"),
+            date_format: "%d.%m.%Y".to_string(),
+            currency_symbol: "$".to_string(),
+            csv_delimiter: '\t',
+            default_category: "Misc \"other\"".to_string(),
+            fiscal_year_start_month: 4,
+            default_account: "Checking".to_string(),
+            theme_name: "light".to_string(),
+            theme: Theme { income_fg: Color::Rgb(0, 128, 0), selected_bg: Color::Indexed(153), ..Theme::light() },
+        };
+
+        config.save(&path).unwrap();
+        assert_eq!(Config::load_from(&path).unwrap(), config);
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        assert_eq!(Config::default().theme, Theme::default());
+        assert_eq!(Theme::default().income_fg, Color::LightGreen);
+
+        let config = Config::parse("theme_name = \"light\"\n").unwrap();
+        assert_eq!(config.theme_name, "light");
+        assert_eq!(config.theme, Theme::light());
+
+        let config = Config::parse(
+            "theme_name = \"light\"\n[theme]\nexpense_fg = \"light-magenta\"\nselected_bg = \"#202020\"\nhighlight_fg = \"15\"\nmuted_fg = \"gray\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.theme,
+            Theme {
+                expense_fg: Color::LightMagenta,
+                selected_bg: Color::Rgb(32, 32, 32),
+                highlight_fg: Color::Indexed(15),
+                muted_fg: Color::Gray,
+                ..Theme::light()
+            }
+        );
+
+        // Overrides apply to the dark preset when no name is given.
+        let config = Config::parse("[theme]\nheader_fg = \"yellow\"\n").unwrap();
+        assert_eq!(config.theme_name, "dark");
+        assert_eq!(config.theme, Theme { header_fg: Color::Yellow, ..Theme::dark() });
+    }
+}
@@ -0,0 +1,170 @@
+use crate::models::budget::BudgetPeriod;
+use chrono::{Days, Months, NaiveDate};
+
+/// Parses YYYY-MM-DD (recommended) or DD.MM.YYYY.
+pub fn parse_date(input: &str) -> Result<NaiveDate, String> {
+    let s = input.trim();
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(s, "%d.%m.%Y"))
+        .map_err(|_| format!("Invalid date '{}'. Use YYYY-MM-DD (recommended) or DD.MM.YYYY.", s))
+}
+
+/// First and last day of the fiscal year that begins in `start_month` of `year`, e.g.
+/// `fiscal_year_range(2025, 4)` is 2025-04-01 to 2026-03-31. Invalid months count as January.
+pub fn fiscal_year_range(year: i32, start_month: u32) -> (NaiveDate, NaiveDate) {
+    let start_month = if (1..=12).contains(&start_month) { start_month } else { 1 };
+    let start = NaiveDate::from_ymd_opt(year, start_month, 1).unwrap_or(NaiveDate::MIN);
+    let end = start
+        .checked_add_months(Months::new(12))
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(NaiveDate::MAX);
+    (start, end)
+}
+
+/// The range commands use when no `--range` is given: 0001-01-01 to 9999-12-31.
+pub fn all_time_range() -> (NaiveDate, NaiveDate) {
+    (NaiveDate::from_ymd_opt(1, 1, 1).unwrap(), NaiveDate::from_ymd_opt(9999, 12, 31).unwrap())
+}
+
+/// Parses `START..END` or one of the shorthands (`this-month`, `last-month`, `last-week`,
+/// `this-year`, `last-N-days`, `YYYY-MM`, `fy:YYYY[:M]`), relative to today. `fy:YYYY` uses
+/// `fiscal_year_start_month`.
+pub fn parse_date_range_or_shorthand(input: &str, fiscal_year_start_month: u32) -> Result<(NaiveDate, NaiveDate), String> {
+    parse_date_range_at(input, chrono::Local::now().date_naive(), fiscal_year_start_month)
+}
+
+/// Same as `parse_date_range_or_shorthand`, with `today` as the anchor for the shorthands.
+/// Weeks start on Monday; `last-N-days` ends today and includes it.
+pub fn parse_date_range_at(
+    input: &str,
+    today: NaiveDate,
+    fiscal_year_start_month: u32,
+) -> Result<(NaiveDate, NaiveDate), String> {
+    let s = input.trim();
+    if let Some(fiscal) = s.to_lowercase().strip_prefix("fy:") {
+        return parse_fiscal_year(fiscal, fiscal_year_start_month);
+    }
+    if let Some((start_str, end_str)) = s.split_once("..") {
+        let start = parse_date(start_str)?;
+        let end = parse_date(end_str)?;
+        if start > end {
+            return Err("Start date must be before end date.".to_string());
+        }
+        return Ok((start, end));
+    }
+
+    match s.to_lowercase().as_str() {
+        "this-month" => return Ok(BudgetPeriod::Monthly.bounds(today)),
+        "last-month" => {
+            let (this_month_start, _) = BudgetPeriod::Monthly.bounds(today);
+            return Ok(BudgetPeriod::Monthly.bounds(this_month_start - Days::new(1)));
+        }
+        "last-week" => return Ok(BudgetPeriod::Weekly.bounds(today - Days::new(7))),
+        "this-year" => return Ok(BudgetPeriod::Yearly.bounds(today)),
+        _ => {}
+    }
+
+    if let Some(count) = s.strip_prefix("last-").and_then(|rest| rest.strip_suffix("-days")) {
+        let days = count
+            .parse::<u64>()
+            .ok()
+            .filter(|days| *days > 0)
+            .ok_or_else(|| format!("Invalid day count in '{}'. Use a positive number, e.g. last-30-days.", s))?;
+        let start = today
+            .checked_sub_days(Days::new(days - 1))
+            .ok_or_else(|| format!("'{}' reaches before the earliest supported date.", s))?;
+        return Ok((start, today));
+    }
+
+    if let Ok(first) = NaiveDate::parse_from_str(&format!("{}-01", s), "%Y-%m-%d") {
+        return Ok(BudgetPeriod::Monthly.bounds(first));
+    }
+
+    Err(format!(
+        "Invalid date range '{}'. Use YYYY-MM-DD..YYYY-MM-DD, YYYY-MM, this-month, last-month, last-week, this-year, last-N-days or fy:YYYY[:M].",
+        s
+    ))
+}
+
+/// `YYYY` or `YYYY:M`, the part after `fy:`.
+fn parse_fiscal_year(input: &str, default_start_month: u32) -> Result<(NaiveDate, NaiveDate), String> {
+    let (year, start_month) = match input.split_once(':') {
+        Some((year, month)) => (year, month.trim().parse::<u32>().ok().filter(|m| (1..=12).contains(m))),
+        None => (input, Some(default_start_month)),
+    };
+    let start_month = start_month
+        .ok_or_else(|| format!("Invalid fiscal year start month in 'fy:{}'. Use a month from 1 to 12.", input))?;
+    let year = year
+        .trim()
+        .parse::<i32>()
+        .ok()
+        .filter(|year| (1..=9998).contains(year))
+        .ok_or_else(|| format!("Invalid fiscal year 'fy:{}'. Use fy:YYYY or fy:YYYY:M.", input))?;
+    Ok(fiscal_year_range(year, start_month))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    // A Wednesday.
+    fn anchor() -> NaiveDate {
+        date(2025, 3, 12)
+    }
+
+    #[test]
+    fn test_explicit_range() {
+        assert_eq!(parse_date_range_at("2025-01-01..2025-01-31", anchor(), 1), Ok((date(2025, 1, 1), date(2025, 1, 31))));
+        assert_eq!(parse_date_range_at("01.02.2025..2025-02-10", anchor(), 1), Ok((date(2025, 2, 1), date(2025, 2, 10))));
+        assert!(parse_date_range_at("2025-02-01..2025-01-01", anchor(), 1).is_err());
+        assert!(parse_date_range_at("2025-02-01..", anchor(), 1).is_err());
+    }
+
+    #[test]
+    fn test_month_shorthands() {
+        assert_eq!(parse_date_range_at("this-month", anchor(), 1), Ok((date(2025, 3, 1), date(2025, 3, 31))));
+        assert_eq!(parse_date_range_at("last-month", anchor(), 1), Ok((date(2025, 2, 1), date(2025, 2, 28))));
+        assert_eq!(parse_date_range_at("last-month", date(2025, 1, 5), 1), Ok((date(2024, 12, 1), date(2024, 12, 31))));
+        assert_eq!(parse_date_range_at("2024-02", anchor(), 1), Ok((date(2024, 2, 1), date(2024, 2, 29))));
+    }
+
+    #[test]
+    fn test_week_and_year_shorthands() {
+        assert_eq!(parse_date_range_at("last-week", anchor(), 1), Ok((date(2025, 3, 3), date(2025, 3, 9))));
+        assert_eq!(parse_date_range_at("This-Year", anchor(), 1), Ok((date(2025, 1, 1), date(2025, 12, 31))));
+    }
+
+    #[test]
+    fn test_last_n_days() {
+        assert_eq!(parse_date_range_at("last-30-days", anchor(), 1), Ok((date(2025, 2, 11), date(2025, 3, 12))));
+        assert_eq!(parse_date_range_at("last-1-days", anchor(), 1), Ok((anchor(), anchor())));
+        assert!(parse_date_range_at("last-0-days", anchor(), 1).unwrap_err().contains("positive number"));
+        assert!(parse_date_range_at("last-x-days", anchor(), 1).is_err());
+    }
+
+    #[test]
+    fn test_unknown_shorthand() {
+        assert!(parse_date_range_at("next-month", anchor(), 1).unwrap_err().contains("Invalid date range"));
+        assert!(parse_date_range_at("2025-13", anchor(), 1).is_err());
+    }
+
+    #[test]
+    fn test_fiscal_year_range() {
+        assert_eq!(fiscal_year_range(2025, 1), (date(2025, 1, 1), date(2025, 12, 31)));
+        assert_eq!(fiscal_year_range(2025, 4), (date(2025, 4, 1), date(2026, 3, 31)));
+        assert_eq!(fiscal_year_range(2023, 3), (date(2023, 3, 1), date(2024, 2, 29)));
+    }
+
+    #[test]
+    fn test_fiscal_year_shorthand() {
+        assert_eq!(parse_date_range_at("fy:2025", anchor(), 1), Ok((date(2025, 1, 1), date(2025, 12, 31))));
+        assert_eq!(parse_date_range_at("fy:2025", anchor(), 4), Ok((date(2025, 4, 1), date(2026, 3, 31))));
+        assert_eq!(parse_date_range_at("FY:2025:10", anchor(), 4), Ok((date(2025, 10, 1), date(2026, 9, 30))));
+        assert!(parse_date_range_at("fy:2025:13", anchor(), 1).unwrap_err().contains("start month"));
+        assert!(parse_date_range_at("fy:next", anchor(), 1).unwrap_err().contains("Invalid fiscal year"));
+    }
+}
@@ -0,0 +1,135 @@
+use crate::error::AppError;
+use crate::models::account::{Account, DEFAULT_ACCOUNT};
+use rusqlite::Connection;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Creates an account and returns its ID. Names are unique regardless of case.
+pub fn add_account(conn: &Connection, name: &str, currency: &str, initial_balance: &Decimal) -> Result<i32, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput("Account name cannot be empty".to_string()));
+    }
+    if get_account_by_name(conn, name)?.is_some() {
+        return Err(AppError::InvalidInput(format!("Account '{}' already exists", name)));
+    }
+
+    conn.execute(
+        "INSERT INTO accounts (name, currency, initial_balance) VALUES (?1, ?2, ?3)",
+        rusqlite::params![name, currency.trim().to_uppercase(), initial_balance.to_string()],
+    )
+    .map_err(|e| AppError::DatabaseError(format!("Failed to insert account: {}", e)))?;
+    Ok(conn.last_insert_rowid() as i32)
+}
+
+pub fn get_all_accounts(conn: &Connection) -> Result<Vec<Account>, AppError> {
+    query_accounts(conn, "SELECT id, name, currency, initial_balance FROM accounts ORDER BY id ASC", [])
+}
+
+/// Looks the account up ignoring case.
+pub fn get_account_by_name(conn: &Connection, name: &str) -> Result<Option<Account>, AppError> {
+    let accounts = query_accounts(
+        conn,
+        "SELECT id, name, currency, initial_balance FROM accounts WHERE LOWER(name) = LOWER(?1)",
+        [name.trim()],
+    )?;
+    Ok(accounts.into_iter().next())
+}
+
+/// Deletes an account that no transaction belongs to. The default account cannot be deleted.
+pub fn delete_account(conn: &Connection, id: i32) -> Result<(), AppError> {
+    let name: String = conn
+        .query_row("SELECT name FROM accounts WHERE id = ?1", [id], |row| row.get(0))
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => AppError::NotFound(format!("Account with ID {} not found", id)),
+            e => AppError::DatabaseError(format!("Failed to query account: {}", e)),
+        })?;
+    if name == DEFAULT_ACCOUNT {
+        return Err(AppError::InvalidInput("The default account cannot be deleted".to_string()));
+    }
+    let transactions: i64 = conn
+        .query_row("SELECT COUNT(*) FROM transactions WHERE account = ?1", [&name], |row| row.get(0))
+        .map_err(|e| AppError::DatabaseError(format!("Failed to count transactions: {}", e)))?;
+    if transactions > 0 {
+        return Err(AppError::InvalidInput(format!(
+            "Account '{}' still has {} transactions. Remove them first",
+            name, transactions
+        )));
+    }
+
+    conn.execute("DELETE FROM accounts WHERE id = ?1", [id])
+        .map_err(|e| AppError::DatabaseError(format!("Failed to delete account: {}", e)))?;
+    Ok(())
+}
+
+fn query_accounts<P: rusqlite::Params>(conn: &Connection, sql: &str, params: P) -> Result<Vec<Account>, AppError> {
+    let mut stmt = conn
+        .prepare(sql)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let iter = stmt
+        .query_map(params, |row| {
+            let balance_str: String = row.get(3)?;
+            Ok(Account {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                currency: row.get(2)?,
+                initial_balance: Decimal::from_str(&balance_str)
+                    .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?,
+            })
+        })
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query accounts: {}", e)))?;
+
+    let mut accounts = Vec::new();
+    for account in iter {
+        accounts.push(account.map_err(|e| AppError::ParseError(format!("Failed to parse account: {}", e)))?);
+    }
+    Ok(accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::establish_test_connection;
+    use crate::operations::add::add_transaction_to_account;
+
+    #[test]
+    fn test_default_account_exists() {
+        let conn = establish_test_connection().unwrap();
+        let accounts = get_all_accounts(&conn).unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].name, DEFAULT_ACCOUNT);
+        assert_eq!(accounts[0].initial_balance, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_add_and_find_account() {
+        let conn = establish_test_connection().unwrap();
+        let id = add_account(&conn, " Savings ", "eur", &Decimal::new(150000, 2)).unwrap();
+
+        let account = get_account_by_name(&conn, "SAVINGS").unwrap().unwrap();
+        assert_eq!(account.id, id);
+        assert_eq!(account.name, "Savings");
+        assert_eq!(account.currency, "EUR");
+        assert_eq!(account.initial_balance, Decimal::new(150000, 2));
+        assert!(matches!(add_account(&conn, "savings", "EUR", &Decimal::ZERO), Err(AppError::InvalidInput(_))));
+        assert!(matches!(add_account(&conn, " ", "EUR", &Decimal::ZERO), Err(AppError::InvalidInput(_))));
+        assert!(get_account_by_name(&conn, "Checking").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_delete_account() {
+        let conn = establish_test_connection().unwrap();
+        let empty = add_account(&conn, "Cash", "", &Decimal::ZERO).unwrap();
+        let used = add_account(&conn, "Card", "", &Decimal::ZERO).unwrap();
+        add_transaction_to_account(&conn, "2025-11-10,Coffee,3.50,expense,Food", "card").unwrap();
+
+        delete_account(&conn, empty).unwrap();
+        assert!(get_account_by_name(&conn, "Cash").unwrap().is_none());
+        let err = delete_account(&conn, used).unwrap_err();
+        assert!(err.to_string().contains("still has 1 transactions"));
+        let default_id = get_account_by_name(&conn, DEFAULT_ACCOUNT).unwrap().unwrap().id;
+        assert!(delete_account(&conn, default_id).unwrap_err().to_string().contains("cannot be deleted"));
+        assert!(matches!(delete_account(&conn, 999), Err(AppError::NotFound(_))));
+    }
+}
@@ -1,6 +1,6 @@
-use crate::models::alert::BudgetAlert;
+use crate::models::alert::{AlertFilter, BudgetAlert};
 use chrono::Utc;
-use rusqlite::Connection;
+use rusqlite::{Connection, Row};
 
 pub fn add_alert(conn: &Connection, category: &str, message: &str) -> Result<i32, String> {
     let created_at = Utc::now().to_rfc3339();
@@ -12,20 +12,18 @@ pub fn add_alert(conn: &Connection, category: &str, message: &str) -> Result<i32
     Ok(conn.last_insert_rowid() as i32)
 }
 
-pub fn get_all_alerts(conn: &Connection) -> Result<Vec<BudgetAlert>, String> {
+pub fn get_all_alerts(conn: &Connection, filter: AlertFilter) -> Result<Vec<BudgetAlert>, String> {
+    let query = if filter.include_read {
+        "SELECT id, category, message, created_at, read_at FROM budget_alerts ORDER BY id DESC"
+    } else {
+        "SELECT id, category, message, created_at, read_at FROM budget_alerts WHERE read_at IS NULL ORDER BY id DESC"
+    };
     let mut stmt = conn
-        .prepare("SELECT id, category, message, created_at FROM budget_alerts ORDER BY id DESC")
+        .prepare(query)
         .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
     let iter = stmt
-        .query_map([], |row| {
-            Ok(BudgetAlert {
-                id: row.get(0)?,
-                category: row.get(1)?,
-                message: row.get(2)?,
-                created_at: row.get(3)?,
-            })
-        })
+        .query_map([], alert_from_row)
         .map_err(|e| format!("Failed to query alerts: {}", e))?;
 
     let mut alerts = Vec::new();
@@ -38,19 +36,12 @@ pub fn get_all_alerts(conn: &Connection) -> Result<Vec<BudgetAlert>, String> {
 pub fn get_alerts_after_id(conn: &Connection, last_id: i32) -> Result<Vec<BudgetAlert>, String> {
     let mut stmt = conn
         .prepare(
-            "SELECT id, category, message, created_at FROM budget_alerts WHERE id > ?1 ORDER BY id ASC",
+            "SELECT id, category, message, created_at, read_at FROM budget_alerts WHERE id > ?1 ORDER BY id ASC",
         )
         .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
     let iter = stmt
-        .query_map([last_id], |row| {
-            Ok(BudgetAlert {
-                id: row.get(0)?,
-                category: row.get(1)?,
-                message: row.get(2)?,
-                created_at: row.get(3)?,
-            })
-        })
+        .query_map([last_id], alert_from_row)
         .map_err(|e| format!("Failed to query alerts: {}", e))?;
 
     let mut alerts = Vec::new();
@@ -67,7 +58,7 @@ pub fn get_alerts_by_ids(conn: &Connection, ids: &[i32]) -> Result<Vec<BudgetAle
 
     let placeholders = ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
     let query = format!(
-        "SELECT id, category, message, created_at FROM budget_alerts WHERE id IN ({}) ORDER BY id ASC",
+        "SELECT id, category, message, created_at, read_at FROM budget_alerts WHERE id IN ({}) ORDER BY id ASC",
         placeholders
     );
 
@@ -77,14 +68,7 @@ pub fn get_alerts_by_ids(conn: &Connection, ids: &[i32]) -> Result<Vec<BudgetAle
 
     let params: Vec<rusqlite::types::Value> = ids.iter().map(|id| (*id).into()).collect();
     let iter = stmt
-        .query_map(rusqlite::params_from_iter(params), |row| {
-            Ok(BudgetAlert {
-                id: row.get(0)?,
-                category: row.get(1)?,
-                message: row.get(2)?,
-                created_at: row.get(3)?,
-            })
-        })
+        .query_map(rusqlite::params_from_iter(params), alert_from_row)
         .map_err(|e| format!("Failed to query alerts: {}", e))?;
 
     let mut alerts = Vec::new();
@@ -106,6 +90,46 @@ pub fn get_last_alert_id(conn: &Connection) -> Result<i32, String> {
     Ok(last_id)
 }
 
+pub fn mark_alert_read(conn: &Connection, id: i32) -> Result<(), String> {
+    let rows = conn
+        .execute(
+            "UPDATE budget_alerts SET read_at = datetime('now') WHERE id = ?1 AND read_at IS NULL",
+            [id],
+        )
+        .map_err(|e| format!("Failed to mark alert as read: {}", e))?;
+
+    if rows == 0 {
+        let exists: bool = conn
+            .query_row("SELECT EXISTS(SELECT 1 FROM budget_alerts WHERE id = ?1)", [id], |row| row.get(0))
+            .map_err(|e| format!("Failed to look up alert: {}", e))?;
+        if !exists {
+            return Err(format!("Alert with ID {} not found", id));
+        }
+    }
+    Ok(())
+}
+
+pub fn mark_all_alerts_read(conn: &Connection) -> Result<usize, String> {
+    conn.execute("UPDATE budget_alerts SET read_at = datetime('now') WHERE read_at IS NULL", [])
+        .map_err(|e| format!("Failed to mark alerts as read: {}", e))
+}
+
+/// Deletes alerts created before `cutoff` (a `YYYY-MM-DD` date or RFC 3339 timestamp), read or not.
+pub fn delete_alerts_before(conn: &Connection, cutoff: &str) -> Result<usize, String> {
+    conn.execute("DELETE FROM budget_alerts WHERE created_at < ?1", [cutoff])
+        .map_err(|e| format!("Failed to delete alerts: {}", e))
+}
+
+fn alert_from_row(row: &Row) -> rusqlite::Result<BudgetAlert> {
+    Ok(BudgetAlert {
+        id: row.get(0)?,
+        category: row.get(1)?,
+        message: row.get(2)?,
+        created_at: row.get(3)?,
+        read_at: row.get(4)?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,7 +141,7 @@ mod tests {
         add_alert(&conn, "Food", "Budget exceeded").unwrap();
         add_alert(&conn, "Travel", "Budget exceeded again").unwrap();
 
-        let alerts = get_all_alerts(&conn).unwrap();
+        let alerts = get_all_alerts(&conn, AlertFilter::default()).unwrap();
         assert_eq!(alerts.len(), 2);
         assert_eq!(alerts[0].category, "Travel");
         assert_eq!(alerts[1].category, "Food");
@@ -150,4 +174,59 @@ mod tests {
         assert_eq!(alerts[0].id, id1);
         assert_eq!(alerts[1].id, id2);
     }
+
+    #[test]
+    fn test_mark_alert_read() {
+        let conn = establish_test_connection().unwrap();
+        let id1 = add_alert(&conn, "Food", "Budget exceeded").unwrap();
+        add_alert(&conn, "Travel", "Budget exceeded again").unwrap();
+
+        mark_alert_read(&conn, id1).unwrap();
+        // Marking twice keeps the first timestamp and is not an error.
+        mark_alert_read(&conn, id1).unwrap();
+
+        let unread = get_all_alerts(&conn, AlertFilter::default()).unwrap();
+        assert_eq!(unread.len(), 1);
+        assert_eq!(unread[0].category, "Travel");
+        assert!(unread[0].read_at.is_none());
+
+        let all = get_all_alerts(&conn, AlertFilter { include_read: true }).unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().find(|a| a.id == id1).unwrap().read_at.is_some());
+    }
+
+    #[test]
+    fn test_mark_alert_read_not_found() {
+        let conn = establish_test_connection().unwrap();
+        let err = mark_alert_read(&conn, 42).unwrap_err();
+        assert!(err.contains("not found"));
+    }
+
+    #[test]
+    fn test_mark_all_alerts_read() {
+        let conn = establish_test_connection().unwrap();
+        let id1 = add_alert(&conn, "Food", "Budget exceeded").unwrap();
+        add_alert(&conn, "Travel", "Budget exceeded again").unwrap();
+        mark_alert_read(&conn, id1).unwrap();
+
+        assert_eq!(mark_all_alerts_read(&conn).unwrap(), 1);
+        assert!(get_all_alerts(&conn, AlertFilter::default()).unwrap().is_empty());
+        assert_eq!(mark_all_alerts_read(&conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_delete_alerts_before() {
+        let conn = establish_test_connection().unwrap();
+        conn.execute(
+            "INSERT INTO budget_alerts (category, message, created_at) VALUES ('Food', 'Old', '2024-12-31T10:00:00+00:00')",
+            [],
+        )
+        .unwrap();
+        add_alert(&conn, "Travel", "New").unwrap();
+
+        assert_eq!(delete_alerts_before(&conn, "2025-01-01").unwrap(), 1);
+        let remaining = get_all_alerts(&conn, AlertFilter { include_read: true }).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].message, "New");
+    }
 }
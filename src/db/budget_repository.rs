@@ -1,80 +1,191 @@
-use crate::models::budget::CategoryBudget;
+use crate::db::repository::{amount_from_sql, amount_to_sql};
+use crate::error::AppError;
+use crate::models::budget::{BudgetPeriod, CategoryBudget};
 use rusqlite::Connection;
+use chrono::NaiveDate;
 use rust_decimal::Decimal;
-use std::str::FromStr;
 
-pub fn set_budget(conn: &Connection, category: &str, amount: &Decimal) -> Result<(), String> {
+/// Without a period a new budget is monthly and an existing one keeps its period.
+pub fn set_budget(
+    conn: &Connection,
+    category: &str,
+    amount: &Decimal,
+    period: Option<BudgetPeriod>,
+) -> Result<(), AppError> {
     conn.execute(
-        "INSERT INTO category_budgets (category, amount) VALUES (?1, ?2)\n         ON CONFLICT(category) DO UPDATE SET amount = excluded.amount",
-        [category, &amount.to_string()],
+        "INSERT INTO category_budgets (category, amount, period) VALUES (?1, ?2, COALESCE(?3, 'monthly'))\n         ON CONFLICT(category) DO UPDATE SET amount = excluded.amount, period = COALESCE(?3, period)",
+        rusqlite::params![category, amount_to_sql(amount)?, period.map(|p| p.as_str())],
     )
-    .map_err(|e| format!("Failed to upsert budget: {}", e))?;
+    .map_err(|e| AppError::DatabaseError(format!("Failed to upsert budget: {}", e)))?;
     Ok(())
 }
 
-pub fn get_budget(conn: &Connection, category: &str) -> Result<Option<CategoryBudget>, String> {
+pub fn get_budget(conn: &Connection, category: &str) -> Result<Option<CategoryBudget>, AppError> {
     let mut stmt = conn
-        .prepare("SELECT id, category, amount FROM category_budgets WHERE LOWER(category) = LOWER(?1)")
-        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+        .prepare(
+            "SELECT id, category, amount, period, carry_forward, alert_threshold FROM category_budgets \n             WHERE LOWER(category) = LOWER(?1)",
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
 
     let mut rows = stmt
         .query([category])
-        .map_err(|e| format!("Failed to query budget: {}", e))?;
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query budget: {}", e)))?;
 
-    if let Some(row) = rows.next().map_err(|e| format!("Failed to read budget: {}", e))? {
-        let amount_str: String = row
+    if let Some(row) = rows.next().map_err(|e| AppError::DatabaseError(format!("Failed to read budget: {}", e)))? {
+        let stored_amount: f64 = row
             .get(2)
-            .map_err(|e| format!("Failed to read budget amount: {}", e))?;
-        let amount = Decimal::from_str(&amount_str)
-            .map_err(|e| format!("Failed to parse budget amount: {}", e))?;
+            .map_err(|e| AppError::DatabaseError(format!("Failed to read budget amount: {}", e)))?;
+        let amount = amount_from_sql(stored_amount)
+            .ok_or_else(|| AppError::ParseError(format!("Failed to parse budget amount '{}'", stored_amount)))?;
 
-        let id: i32 = row.get(0).map_err(|e| format!("Failed to read budget id: {}", e))?;
+        let id: i32 = row.get(0).map_err(|e| AppError::DatabaseError(format!("Failed to read budget id: {}", e)))?;
         let category: String = row
             .get(1)
-            .map_err(|e| format!("Failed to read budget category: {}", e))?;
+            .map_err(|e| AppError::DatabaseError(format!("Failed to read budget category: {}", e)))?;
+        let period_str: String = row
+            .get(3)
+            .map_err(|e| AppError::DatabaseError(format!("Failed to read budget period: {}", e)))?;
+        let period = BudgetPeriod::parse(&period_str)
+            .ok_or_else(|| AppError::ParseError(format!("Invalid budget period '{}'", period_str)))?;
+        let carry_forward: bool = row
+            .get(4)
+            .map_err(|e| AppError::DatabaseError(format!("Failed to read budget carry-forward: {}", e)))?;
+        let alert_threshold: u8 = row
+            .get(5)
+            .map_err(|e| AppError::DatabaseError(format!("Failed to read budget alert threshold: {}", e)))?;
 
         Ok(Some(CategoryBudget {
             id,
             category,
             amount,
+            period,
+            carry_forward,
+            alert_threshold,
         }))
     } else {
         Ok(None)
     }
 }
 
-pub fn get_all_budgets(conn: &Connection) -> Result<Vec<CategoryBudget>, String> {
+pub fn get_all_budgets(conn: &Connection) -> Result<Vec<CategoryBudget>, AppError> {
     let mut stmt = conn
-        .prepare("SELECT id, category, amount FROM category_budgets ORDER BY category ASC")
-        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+        .prepare(
+            "SELECT id, category, amount, period, carry_forward, alert_threshold FROM category_budgets \n             ORDER BY category ASC",
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
 
     let iter = stmt
         .query_map([], |row| {
-            let amount_str: String = row.get(2)?;
-            let amount = Decimal::from_str(&amount_str)
-                .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?;
+            let amount = amount_from_sql(row.get(2)?)
+                .ok_or_else(|| rusqlite::Error::InvalidParameterName("Invalid budget amount".to_string()))?;
             Ok(CategoryBudget {
                 id: row.get(0)?,
                 category: row.get(1)?,
                 amount,
+                period: BudgetPeriod::parse(&row.get::<_, String>(3)?)
+                    .ok_or_else(|| rusqlite::Error::InvalidParameterName("Invalid budget period".to_string()))?,
+                carry_forward: row.get(4)?,
+                alert_threshold: row.get(5)?,
             })
         })
-        .map_err(|e| format!("Failed to query budgets: {}", e))?;
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query budgets: {}", e)))?;
 
     let mut budgets = Vec::new();
     for budget in iter {
-        budgets.push(budget.map_err(|e| format!("Failed to parse budget: {}", e))?);
+        budgets.push(budget.map_err(|e| AppError::ParseError(format!("Failed to parse budget: {}", e)))?);
     }
     Ok(budgets)
 }
 
-pub fn delete_budget(conn: &Connection, category: &str) -> Result<(), String> {
+pub fn set_carry_forward(conn: &Connection, category: &str, carry_forward: bool) -> Result<(), AppError> {
+    let rows = conn
+        .execute(
+            "UPDATE category_budgets SET carry_forward = ?1 WHERE LOWER(category) = LOWER(?2)",
+            rusqlite::params![carry_forward, category],
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update budget: {}", e)))?;
+
+    if rows == 0 {
+        return Err(AppError::NotFound(format!("Budget for category '{}' not found", category)));
+    }
+    Ok(())
+}
+
+pub fn set_alert_threshold(conn: &Connection, category: &str, threshold: u8) -> Result<(), AppError> {
+    if threshold > 100 {
+        return Err(AppError::InvalidInput(format!(
+            "Alert threshold must be between 0 and 100, got {}",
+            threshold
+        )));
+    }
+    let rows = conn
+        .execute(
+            "UPDATE category_budgets SET alert_threshold = ?1 WHERE LOWER(category) = LOWER(?2)",
+            rusqlite::params![threshold, category],
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update budget: {}", e)))?;
+
+    if rows == 0 {
+        return Err(AppError::NotFound(format!("Budget for category '{}' not found", category)));
+    }
+    Ok(())
+}
+
+/// Expense totals per category in the range next to the category's budget, if any.
+/// Budgeted categories without spending in the range are included with a zero total.
+/// `(category, spent in the range, budget amount and period)`.
+pub type SpendingWithBudget = (String, Decimal, Option<(Decimal, BudgetPeriod)>);
+
+/// Spending in the range for every category with expenses or a budget.
+pub fn get_spending_with_budgets(
+    conn: &Connection,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Vec<SpendingWithBudget>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT s.category, s.spent, b.amount, b.period FROM ( \n                 SELECT category, LOWER(category) AS category_key, SUM(amount) AS spent FROM transactions \n                 WHERE transaction_type = 'expense' AND date BETWEEN ?1 AND ?2 GROUP BY LOWER(category) \n             ) s LEFT JOIN category_budgets b ON LOWER(b.category) = s.category_key \n             UNION ALL \n             SELECT b.category, 0.0, b.amount, b.period FROM category_budgets b WHERE LOWER(b.category) NOT IN ( \n                 SELECT LOWER(category) FROM transactions WHERE transaction_type = 'expense' AND date BETWEEN ?1 AND ?2 \n             )",
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let iter = stmt
+        .query_map([start_date.to_string(), end_date.to_string()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, f64>(1)?,
+                row.get::<_, Option<f64>>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query budget utilization: {}", e)))?;
+
+    let mut rows = Vec::new();
+    for row in iter {
+        let (category, spent, amount, period) =
+            row.map_err(|e| AppError::ParseError(format!("Failed to parse budget utilization: {}", e)))?;
+        let spent = amount_from_sql(spent)
+            .ok_or_else(|| AppError::ParseError(format!("Failed to convert total for {}", category)))?;
+        let budget = match (amount, period) {
+            (Some(amount), Some(period)) => Some((
+                amount_from_sql(amount)
+                    .ok_or_else(|| AppError::ParseError(format!("Failed to parse budget amount '{}'", amount)))?,
+                BudgetPeriod::parse(&period)
+                    .ok_or_else(|| AppError::ParseError(format!("Invalid budget period '{}'", period)))?,
+            )),
+            _ => None,
+        };
+        rows.push((category, spent, budget));
+    }
+    Ok(rows)
+}
+
+pub fn delete_budget(conn: &Connection, category: &str) -> Result<(), AppError> {
     let rows = conn
         .execute("DELETE FROM category_budgets WHERE LOWER(category) = LOWER(?1)", [category])
-        .map_err(|e| format!("Failed to delete budget: {}", e))?;
+        .map_err(|e| AppError::DatabaseError(format!("Failed to delete budget: {}", e)))?;
 
     if rows == 0 {
-        return Err(format!("Budget for category '{}' not found", category));
+        return Err(AppError::NotFound(format!("Budget for category '{}' not found", category)));
     }
     Ok(())
 }
@@ -83,13 +194,14 @@ pub fn delete_budget(conn: &Connection, category: &str) -> Result<(), String> {
 mod tests {
     use super::*;
     use crate::db::connection::establish_test_connection;
+    use crate::operations::add::add_transaction_to_db;
     use rust_decimal::Decimal;
     use std::str::FromStr;
 
     #[test]
     fn test_set_and_get_budget() {
         let conn = establish_test_connection().unwrap();
-        set_budget(&conn, "Food", &Decimal::from_str("100").unwrap()).unwrap();
+        set_budget(&conn, "Food", &Decimal::from_str("100").unwrap(), None).unwrap();
 
         let budget = get_budget(&conn, "Food").unwrap().unwrap();
         assert_eq!(budget.category, "Food");
@@ -106,18 +218,47 @@ mod tests {
     #[test]
     fn test_set_budget_overwrites() {
         let conn = establish_test_connection().unwrap();
-        set_budget(&conn, "Food", &Decimal::from_str("50").unwrap()).unwrap();
-        set_budget(&conn, "Food", &Decimal::from_str("75").unwrap()).unwrap();
+        set_budget(&conn, "Food", &Decimal::from_str("50").unwrap(), None).unwrap();
+        set_budget(&conn, "Food", &Decimal::from_str("75").unwrap(), None).unwrap();
 
         let budget = get_budget(&conn, "Food").unwrap().unwrap();
         assert_eq!(budget.amount, Decimal::from_str("75").unwrap());
     }
 
+    #[test]
+    fn test_set_carry_forward() {
+        let conn = establish_test_connection().unwrap();
+        set_budget(&conn, "Food", &Decimal::from_str("100").unwrap(), None).unwrap();
+        assert!(!get_budget(&conn, "Food").unwrap().unwrap().carry_forward);
+
+        set_carry_forward(&conn, "food", true).unwrap();
+        assert!(get_budget(&conn, "Food").unwrap().unwrap().carry_forward);
+        // Updating the amount leaves the flag alone.
+        set_budget(&conn, "Food", &Decimal::from_str("80").unwrap(), None).unwrap();
+        assert!(get_all_budgets(&conn).unwrap()[0].carry_forward);
+
+        let err = set_carry_forward(&conn, "Missing", true).unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[test]
+    fn test_set_alert_threshold() {
+        let conn = establish_test_connection().unwrap();
+        set_budget(&conn, "Food", &Decimal::from_str("100").unwrap(), None).unwrap();
+        assert_eq!(get_budget(&conn, "Food").unwrap().unwrap().alert_threshold, 80);
+
+        set_alert_threshold(&conn, "Food", 95).unwrap();
+        assert_eq!(get_all_budgets(&conn).unwrap()[0].alert_threshold, 95);
+
+        assert!(matches!(set_alert_threshold(&conn, "Food", 101), Err(AppError::InvalidInput(_))));
+        assert!(matches!(set_alert_threshold(&conn, "Missing", 50), Err(AppError::NotFound(_))));
+    }
+
     #[test]
     fn test_get_all_budgets() {
         let conn = establish_test_connection().unwrap();
-        set_budget(&conn, "Food", &Decimal::from_str("10").unwrap()).unwrap();
-        set_budget(&conn, "Travel", &Decimal::from_str("20").unwrap()).unwrap();
+        set_budget(&conn, "Food", &Decimal::from_str("10").unwrap(), None).unwrap();
+        set_budget(&conn, "Travel", &Decimal::from_str("20").unwrap(), None).unwrap();
 
         let budgets = get_all_budgets(&conn).unwrap();
         assert_eq!(budgets.len(), 2);
@@ -126,7 +267,7 @@ mod tests {
     #[test]
     fn test_delete_budget_success() {
         let conn = establish_test_connection().unwrap();
-        set_budget(&conn, "Food", &Decimal::from_str("10").unwrap()).unwrap();
+        set_budget(&conn, "Food", &Decimal::from_str("10").unwrap(), None).unwrap();
 
         let result = delete_budget(&conn, "Food");
         assert!(result.is_ok());
@@ -137,7 +278,32 @@ mod tests {
     fn test_delete_budget_not_found() {
         let conn = establish_test_connection().unwrap();
         let result = delete_budget(&conn, "Missing");
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("not found"));
+        let err = result.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+        assert!(err.to_string().contains("not found"));
+    }
+
+    #[test]
+    fn test_get_spending_with_budgets() {
+        let conn = establish_test_connection().unwrap();
+        set_budget(&conn, "Food", &Decimal::from_str("100").unwrap(), None).unwrap();
+        set_budget(&conn, "Travel", &Decimal::from_str("50").unwrap(), Some(BudgetPeriod::Weekly)).unwrap();
+        add_transaction_to_db(&conn, "2025-03-02,Lunch,12.50,expense,food").unwrap();
+        add_transaction_to_db(&conn, "2025-03-03,Book,20.00,expense,Hobby").unwrap();
+        add_transaction_to_db(&conn, "2025-04-01,Dinner,30.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-03-04,Salary,900.00,income,Job").unwrap();
+
+        let mut rows = get_spending_with_budgets(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+        )
+        .unwrap();
+        rows.sort_by_key(|row| row.0.to_lowercase());
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], ("food".to_string(), Decimal::new(1250, 2), Some((Decimal::from(100), BudgetPeriod::Monthly))));
+        assert_eq!(rows[1], ("Hobby".to_string(), Decimal::from(20), None));
+        assert_eq!(rows[2], ("Travel".to_string(), Decimal::ZERO, Some((Decimal::from(50), BudgetPeriod::Weekly))));
     }
 }
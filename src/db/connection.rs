@@ -1,7 +1,253 @@
-use rusqlite::{Connection, Result};
+use crate::config::Config;
+use crate::models::account::DEFAULT_ACCOUNT;
+use chrono::NaiveDateTime;
+use rusqlite::{Connection, Result, MAIN_DB};
+use std::path::{Path, PathBuf};
 
-pub fn establish_connection() -> Result<Connection> {
-    let conn = Connection::open("financial_app.db")?;
+pub const DB_PATH_ENV: &str = "FINO_DB_PATH";
+
+pub fn establish_connection(config: &Config) -> Result<Connection> {
+    let conn = Connection::open(resolve_db_path(config))?;
+    configure_connection(&conn)?;
+    init_schema(&conn)?;
+    Ok(conn)
+}
+
+/// Pragmas reported by `fino db-info`.
+pub const INFO_PRAGMAS: [&str; 3] = ["journal_mode", "foreign_keys", "synchronous"];
+
+fn configure_connection(conn: &Connection) -> Result<()> {
+    // WAL lets the TUI keep reading while a command in another terminal writes, instead of
+    // both blocking on the rollback journal. The mode is stored in the file, so this only
+    // switches older databases over once.
+    // foreign_keys is off by default in SQLite and has to be enabled on every connection.
+    // synchronous=NORMAL is durable under WAL except for the last commits on power loss,
+    // and avoids an fsync on every write.
+    conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON; PRAGMA synchronous=NORMAL;")
+}
+
+/// Current value of each of `INFO_PRAGMAS`, as SQLite reports it.
+pub fn pragma_values(conn: &Connection) -> Result<Vec<(&'static str, String)>> {
+    INFO_PRAGMAS
+        .iter()
+        .map(|&name| {
+            let value = conn.query_row(&format!("PRAGMA {}", name), [], |row| {
+                row.get::<_, rusqlite::types::Value>(0)
+            })?;
+            let value = match value {
+                rusqlite::types::Value::Integer(n) => n.to_string(),
+                rusqlite::types::Value::Text(text) => text,
+                other => format!("{:?}", other),
+            };
+            Ok((name, value))
+        })
+        .collect()
+}
+
+// Tests always run against a fresh in-memory database, never the configured file.
+#[cfg(test)]
+pub fn establish_test_connection() -> Result<Connection> {
+    let conn = Connection::open_in_memory()?;
+    init_schema(&conn)?;
+    Ok(conn)
+}
+
+/// Database file to open: `FINO_DB_PATH`, then `db_path` from the config file
+/// (`financial_app.db` in the working directory unless configured).
+pub fn resolve_db_path(config: &Config) -> PathBuf {
+    resolve_db_path_from(std::env::var(DB_PATH_ENV).ok(), config)
+}
+
+fn resolve_db_path_from(from_env: Option<String>, config: &Config) -> PathBuf {
+    match from_env.filter(|path| !path.trim().is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => config.db_path.clone(),
+    }
+}
+
+/// Copies the open database to `dest_path` with SQLite's online backup, so it can run while
+/// other connections keep reading and writing.
+pub fn backup_database(conn: &Connection, dest_path: &str) -> Result<(), String> {
+    conn.backup(MAIN_DB, dest_path, None)
+        .map_err(|e| format!("Failed to back up the database to '{}': {}", dest_path, e))
+}
+
+/// Rebuilds the database file without the free pages that deletes leave behind. Under WAL the
+/// rebuilt pages land in the `-wal` file first, so it is checkpointed before and after the
+/// `VACUUM` to get them into the database file and shrink the WAL again.
+///
+/// The description index keeps entries for deleted transactions until it is rebuilt, so that
+/// happens first. SQLite does not promise that `VACUUM` keeps the rowids of `transactions`,
+/// which the index refers to; if the index no longer matches the table afterwards (`integrity-check`
+/// with rank 1 compares the two) it is rebuilt again.
+pub fn vacuum_database(conn: &Connection) -> Result<(), String> {
+    let vacuum_err = |e: rusqlite::Error| format!("Failed to vacuum the database: {}", e);
+    conn.execute_batch(
+        "PRAGMA wal_checkpoint(FULL);
+         INSERT INTO transactions_fts (transactions_fts) VALUES ('rebuild');
+         VACUUM;",
+    )
+    .map_err(vacuum_err)?;
+    if conn
+        .execute_batch("INSERT INTO transactions_fts (transactions_fts, rank) VALUES ('integrity-check', 1)")
+        .is_err()
+    {
+        conn.execute_batch("INSERT INTO transactions_fts (transactions_fts) VALUES ('rebuild')")
+            .map_err(vacuum_err)?;
+    }
+    conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);").map_err(vacuum_err)
+}
+
+/// Bytes the database takes on disk: the file plus its `-wal` file, if any. 0 when the file
+/// does not exist.
+pub fn database_size(path: &Path) -> u64 {
+    let mut wal = path.as_os_str().to_owned();
+    wal.push("-wal");
+    [path, Path::new(&wal)]
+        .iter()
+        .filter_map(|file| std::fs::metadata(file).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Problems reported by `PRAGMA integrity_check`; empty when the database is healthy.
+pub fn check_integrity(conn: &Connection) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare("PRAGMA integrity_check")
+        .map_err(|e| format!("Failed to check database integrity: {}", e))?;
+    let messages = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .and_then(|rows| rows.collect::<Result<Vec<_>>>())
+        .map_err(|e| format!("Failed to check database integrity: {}", e))?;
+    Ok(messages.into_iter().filter(|message| message != "ok").collect())
+}
+
+/// `dest` itself, or a timestamped file name inside it when it is an existing directory.
+pub fn backup_destination(dest: &Path, now: NaiveDateTime) -> PathBuf {
+    if dest.is_dir() {
+        dest.join(format!("financial_app-{}.db", now.format("%Y%m%d-%H%M%S")))
+    } else {
+        dest.to_path_buf()
+    }
+}
+
+/// A schema change: what it does, and the function that applies it.
+type Migration = (&'static str, fn(&Connection) -> Result<()>);
+
+/// Every schema change in the order it was made. The version stored in `schema_version` is the
+/// number of migrations applied, so new ones go at the end and released ones never change.
+///
+/// Databases from before versioning have no `schema_version` table and start at 0. Each
+/// migration therefore skips whatever already exists (`IF NOT EXISTS`, `ensure_column`).
+const MIGRATIONS: &[Migration] = &[
+    ("create transactions, rules, budgets and alerts", create_initial_tables),
+    ("add tags to transactions", |conn| ensure_column(conn, "transactions", "tags", "TEXT NOT NULL DEFAULT ''")),
+    ("add status to transactions", |conn| {
+        ensure_column(
+            conn,
+            "transactions",
+            "status",
+            "TEXT NOT NULL DEFAULT 'cleared' CHECK (status IN ('pending', 'cleared', 'reconciled'))",
+        )
+    }),
+    ("add notes to transactions", |conn| ensure_column(conn, "transactions", "notes", "TEXT DEFAULT ''")),
+    ("add match types and priorities to rules", add_rule_columns),
+    ("add periods, carry-forward and alert thresholds to budgets", add_budget_columns),
+    ("add read_at to budget alerts", |conn| ensure_column(conn, "budget_alerts", "read_at", "TEXT")),
+    ("create recurring transactions", create_recurring_table),
+    ("index transactions by date and category", create_transaction_indexes),
+    ("create accounts", create_accounts),
+    ("add currency codes to transactions", |conn| {
+        ensure_column(conn, "transactions", "currency_code", "TEXT NOT NULL DEFAULT ''")
+    }),
+    ("create exchange rates", create_exchange_rates_table),
+    ("store transaction and budget amounts as REAL", store_amounts_as_real),
+    ("index descriptions for full-text search", create_description_index),
+    ("index budget alerts by creation date", |conn| {
+        // `alert clear --before` deletes by `created_at`.
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_budget_alerts_created_at ON budget_alerts(created_at)", [])
+            .map(|_| ())
+    }),
+];
+
+/// Schema version this build creates and expects.
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Version stored in the database; 0 when it predates versioning or is empty.
+pub fn schema_version(conn: &Connection) -> Result<u32> {
+    let has_table: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_version')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_table {
+        return Ok(0);
+    }
+    let version: Option<u32> = conn
+        .query_row("SELECT version FROM schema_version", [], |row| row.get(0))
+        .or_else(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => Ok(None),
+            e => Err(e),
+        })?;
+    Ok(version.unwrap_or(0))
+}
+
+/// Brings the schema up to `SCHEMA_VERSION` by running the pending migrations in order. Each
+/// runs in its own transaction together with the version bump, so a failed one leaves the
+/// database at the previous version. A database from a newer build is refused rather than
+/// used with a schema this build does not know.
+fn init_schema(conn: &Connection) -> Result<()> {
+    run_migrations(conn, MIGRATIONS)
+}
+
+fn run_migrations(conn: &Connection, migrations: &[Migration]) -> Result<()> {
+    conn.execute("CREATE TABLE IF NOT EXISTS schema_version (version INTEGER NOT NULL)", [])?;
+    let current = schema_version(conn)?;
+    let latest = migrations.len() as u32;
+    if current > latest {
+        return Err(rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_ERROR),
+            Some(format!(
+                "database schema version {} is newer than this version of fino supports ({}). Upgrade fino",
+                current, latest
+            )),
+        ));
+    }
+    if current == latest {
+        return Ok(());
+    }
+
+    // SQLite refuses to add a REFERENCES column with a non-NULL default while foreign keys are
+    // enforced, and the pragma cannot change inside a transaction.
+    let foreign_keys: bool = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0))?;
+    conn.execute_batch("PRAGMA foreign_keys=OFF")?;
+    let result = migrations[current as usize..]
+        .iter()
+        .zip(current + 1..)
+        .try_for_each(|((_, migrate), version)| apply_migration(conn, *migrate, version));
+    if foreign_keys {
+        conn.execute_batch("PRAGMA foreign_keys=ON")?;
+    }
+    result
+}
+
+fn apply_migration(conn: &Connection, migrate: fn(&Connection) -> Result<()>, version: u32) -> Result<()> {
+    conn.execute_batch("BEGIN")?;
+    let result = migrate(conn).and_then(|_| {
+        conn.execute("DELETE FROM schema_version", [])?;
+        conn.execute("INSERT INTO schema_version (version) VALUES (?1)", [version])?;
+        Ok(())
+    });
+    match result {
+        Ok(()) => conn.execute_batch("COMMIT"),
+        Err(e) => {
+            let _ = conn.execute_batch("ROLLBACK");
+            Err(e)
+        }
+    }
+}
+
+fn create_initial_tables(conn: &Connection) -> Result<()> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS transactions (
             id TEXT PRIMARY KEY,
@@ -38,47 +284,490 @@ pub fn establish_connection() -> Result<Connection> {
         )",
         [],
     )?;
-    Ok(conn)
+    Ok(())
 }
 
-#[cfg(test)]
-pub fn establish_test_connection() -> Result<Connection> {
-    let conn = Connection::open_in_memory()?;
+fn add_rule_columns(conn: &Connection) -> Result<()> {
+    // Rules created before match types existed were always applied as regexes. `add_rule`
+    // always sets the match type, so the default only matters for those.
+    ensure_column(
+        conn,
+        "category_rules",
+        "match_type",
+        "TEXT NOT NULL DEFAULT 'regex' CHECK (match_type IN ('substring', 'regex'))",
+    )?;
+    ensure_column(conn, "category_rules", "priority", "INTEGER NOT NULL DEFAULT 0")
+}
+
+fn add_budget_columns(conn: &Connection) -> Result<()> {
+    ensure_column(
+        conn,
+        "category_budgets",
+        "period",
+        "TEXT NOT NULL DEFAULT 'monthly' CHECK (period IN ('weekly', 'monthly', 'quarterly', 'yearly'))",
+    )?;
+    ensure_column(conn, "category_budgets", "carry_forward", "INTEGER NOT NULL DEFAULT 0")?;
+    ensure_column(
+        conn,
+        "category_budgets",
+        "alert_threshold",
+        "INTEGER NOT NULL DEFAULT 80 CHECK (alert_threshold BETWEEN 0 AND 100)",
+    )
+}
+
+fn create_recurring_table(conn: &Connection) -> Result<()> {
     conn.execute(
-        "CREATE TABLE transactions (
-            id TEXT PRIMARY KEY,
-            date TEXT NOT NULL,
-            description TEXT NOT NULL,
+        "CREATE TABLE IF NOT EXISTS recurring_transactions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            template_description TEXT NOT NULL,
             amount TEXT NOT NULL,
             transaction_type TEXT NOT NULL CHECK (transaction_type IN ('income', 'expense')),
-            category TEXT NOT NULL
+            category TEXT NOT NULL,
+            interval_days INTEGER NOT NULL CHECK (interval_days > 0),
+            next_due TEXT NOT NULL,
+            active INTEGER NOT NULL DEFAULT 1
         )",
         [],
     )?;
+    Ok(())
+}
+
+fn create_transaction_indexes(conn: &Connection) -> Result<()> {
+    // SQLite walks the index backwards for `ORDER BY date DESC`.
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_transactions_date ON transactions(date)", [])?;
+    // Category searches compare case-insensitively.
     conn.execute(
-        "CREATE TABLE category_rules (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            pattern TEXT NOT NULL,
-            category TEXT NOT NULL
-        )",
+        "CREATE INDEX IF NOT EXISTS idx_transactions_category ON transactions(LOWER(category))",
         [],
     )?;
+    Ok(())
+}
+
+fn create_accounts(conn: &Connection) -> Result<()> {
     conn.execute(
-        "CREATE TABLE category_budgets (
+        "CREATE TABLE IF NOT EXISTS accounts (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
-            category TEXT NOT NULL UNIQUE,
-            amount TEXT NOT NULL
+            name TEXT NOT NULL UNIQUE,
+            currency TEXT NOT NULL DEFAULT '',
+            initial_balance TEXT NOT NULL DEFAULT '0'
         )",
         [],
     )?;
+    conn.execute("INSERT OR IGNORE INTO accounts (name) VALUES (?1)", [DEFAULT_ACCOUNT])?;
+    // Existing rows all get 'default', which was inserted above.
+    ensure_column(
+        conn,
+        "transactions",
+        "account",
+        "TEXT NOT NULL DEFAULT 'default' REFERENCES accounts(name)",
+    )
+}
+
+fn create_exchange_rates_table(conn: &Connection) -> Result<()> {
     conn.execute(
-        "CREATE TABLE budget_alerts (
+        "CREATE TABLE IF NOT EXISTS exchange_rates (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
-            category TEXT NOT NULL,
-            message TEXT NOT NULL,
-            created_at TEXT NOT NULL
+            from_currency TEXT NOT NULL,
+            to_currency TEXT NOT NULL,
+            rate TEXT NOT NULL,
+            as_of TEXT NOT NULL,
+            UNIQUE (from_currency, to_currency, as_of)
         )",
         [],
     )?;
-    Ok(conn)
-}
\ No newline at end of file
+    Ok(())
+}
+
+/// SQLite cannot change a column's type in place, so both tables are rebuilt with `amount REAL`
+/// and their rows copied over. REAL affinity turns every numeric text into a number on the way;
+/// text that is not a number stays as it is, for `validate` to report.
+fn store_amounts_as_real(conn: &Connection) -> Result<()> {
+    if column_type(conn, "transactions", "amount")?.as_deref() != Some("REAL") {
+        conn.execute_batch(
+            "CREATE TABLE transactions_new (
+                id TEXT PRIMARY KEY,
+                date TEXT NOT NULL,
+                description TEXT NOT NULL,
+                amount REAL NOT NULL,
+                transaction_type TEXT NOT NULL CHECK (transaction_type IN ('income', 'expense')),
+                category TEXT NOT NULL,
+                tags TEXT NOT NULL DEFAULT '',
+                status TEXT NOT NULL DEFAULT 'cleared' CHECK (status IN ('pending', 'cleared', 'reconciled')),
+                notes TEXT DEFAULT '',
+                account TEXT NOT NULL DEFAULT 'default' REFERENCES accounts(name),
+                currency_code TEXT NOT NULL DEFAULT ''
+            );
+            INSERT INTO transactions_new (id, date, description, amount, transaction_type, category, tags, status, notes, account, currency_code)
+                SELECT id, date, description, amount, transaction_type, category, tags, status, notes, account, currency_code FROM transactions;
+            DROP TABLE transactions;
+            ALTER TABLE transactions_new RENAME TO transactions;",
+        )?;
+        create_transaction_indexes(conn)?;
+    }
+    if column_type(conn, "category_budgets", "amount")?.as_deref() != Some("REAL") {
+        conn.execute_batch(
+            "CREATE TABLE category_budgets_new (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                category TEXT NOT NULL UNIQUE,
+                amount REAL NOT NULL,
+                period TEXT NOT NULL DEFAULT 'monthly' CHECK (period IN ('weekly', 'monthly', 'quarterly', 'yearly')),
+                carry_forward INTEGER NOT NULL DEFAULT 0,
+                alert_threshold INTEGER NOT NULL DEFAULT 80 CHECK (alert_threshold BETWEEN 0 AND 100)
+            );
+            INSERT INTO category_budgets_new (id, category, amount, period, carry_forward, alert_threshold)
+                SELECT id, category, amount, period, carry_forward, alert_threshold FROM category_budgets;
+            DROP TABLE category_budgets;
+            ALTER TABLE category_budgets_new RENAME TO category_budgets;",
+        )?;
+    }
+    Ok(())
+}
+
+/// `transactions_fts` indexes descriptions by trigram, which lets SQLite answer `LIKE`
+/// patterns (substrings, prefixes and suffixes alike) from the index. It stores no text of its
+/// own, only rowids into `transactions`; the triggers keep it in step.
+fn create_description_index(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS transactions_fts USING fts5(
+            description, content='transactions', content_rowid='rowid', tokenize='trigram'
+        );
+        CREATE TRIGGER IF NOT EXISTS transactions_fts_insert AFTER INSERT ON transactions BEGIN
+            INSERT INTO transactions_fts (rowid, description) VALUES (new.rowid, new.description);
+        END;
+        CREATE TRIGGER IF NOT EXISTS transactions_fts_delete AFTER DELETE ON transactions BEGIN
+            INSERT INTO transactions_fts (transactions_fts, rowid, description) VALUES ('delete', old.rowid, old.description);
+        END;
+        CREATE TRIGGER IF NOT EXISTS transactions_fts_update AFTER UPDATE OF description ON transactions BEGIN
+            INSERT INTO transactions_fts (transactions_fts, rowid, description) VALUES ('delete', old.rowid, old.description);
+            INSERT INTO transactions_fts (rowid, description) VALUES (new.rowid, new.description);
+        END;
+        INSERT INTO transactions_fts (transactions_fts) VALUES ('rebuild');",
+    )
+}
+
+/// Declared type of `column`, or `None` when the table has no such column.
+fn column_type(conn: &Connection, table: &str, column: &str) -> Result<Option<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let columns = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(1)?, row.get::<_, String>(2)?)))?
+        .collect::<Result<Vec<_>>>()?;
+    Ok(columns.into_iter().find(|(name, _)| name == column).map(|(_, declared)| declared))
+}
+
+fn ensure_column(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>>>()?
+        .iter()
+        .any(|name| name == column);
+
+    if !exists {
+        conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_db_path_prefers_env() {
+        let config = Config { db_path: PathBuf::from("/data/from-config.db"), ..Config::default() };
+        let path = resolve_db_path_from(Some("/data/from-env.db".to_string()), &config);
+        assert_eq!(path, PathBuf::from("/data/from-env.db"));
+    }
+
+    #[test]
+    fn test_resolve_db_path_from_config() {
+        let config = Config { db_path: PathBuf::from("/data/from-config.db"), ..Config::default() };
+        assert_eq!(resolve_db_path_from(None, &config), PathBuf::from("/data/from-config.db"));
+        // An empty variable does not count as set.
+        assert_eq!(resolve_db_path_from(Some(" ".to_string()), &config), PathBuf::from("/data/from-config.db"));
+        assert_eq!(resolve_db_path_from(None, &Config::default()), PathBuf::from("financial_app.db"));
+    }
+
+    #[test]
+    fn test_init_schema_adds_missing_column() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON").unwrap();
+        conn.execute(
+            "CREATE TABLE transactions (
+                id TEXT PRIMARY KEY,
+                date TEXT NOT NULL,
+                description TEXT NOT NULL,
+                amount TEXT NOT NULL,
+                transaction_type TEXT NOT NULL,
+                category TEXT NOT NULL
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO transactions VALUES ('1', '2025-01-01', 'Old', '1.00', 'expense', 'Food')",
+            [],
+        )
+        .unwrap();
+
+        init_schema(&conn).unwrap();
+        init_schema(&conn).unwrap();
+
+        let tags: String = conn
+            .query_row("SELECT tags FROM transactions WHERE id = '1'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(tags, "");
+
+        let status: String = conn
+            .query_row("SELECT status FROM transactions WHERE id = '1'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(status, "cleared");
+
+        let account: String = conn
+            .query_row("SELECT account FROM transactions WHERE id = '1'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(account, "default");
+        let foreign_keys: bool = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0)).unwrap();
+        assert!(foreign_keys);
+    }
+
+    #[test]
+    fn test_init_schema_keeps_old_rules_as_regex() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE category_rules (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                pattern TEXT NOT NULL,
+                category TEXT NOT NULL
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute("INSERT INTO category_rules (pattern, category) VALUES ('^Uber', 'Transport')", [])
+            .unwrap();
+
+        init_schema(&conn).unwrap();
+
+        let match_type: String = conn
+            .query_row("SELECT match_type FROM category_rules", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(match_type, "regex");
+    }
+
+    #[test]
+    fn test_init_schema_creates_indexes() {
+        let conn = establish_test_connection().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'index' AND tbl_name = 'transactions'")
+            .unwrap();
+        let names: Vec<String> = stmt.query_map([], |row| row.get(0)).unwrap().map(|r| r.unwrap()).collect();
+        assert!(names.contains(&"idx_transactions_date".to_string()));
+        assert!(names.contains(&"idx_transactions_category".to_string()));
+
+        let plan: String = conn
+            .query_row("EXPLAIN QUERY PLAN SELECT id FROM transactions ORDER BY date DESC", [], |row| row.get(3))
+            .unwrap();
+        assert!(plan.contains("idx_transactions_date"), "{}", plan);
+        let plan: String = conn
+            .query_row("EXPLAIN QUERY PLAN DELETE FROM budget_alerts WHERE created_at < '2025-01-01'", [], |row| row.get(3))
+            .unwrap();
+        assert!(plan.contains("idx_budget_alerts_created_at"), "{}", plan);
+    }
+
+    #[test]
+    fn test_configure_connection_sets_pragmas() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let conn = Connection::open(dir.path().join("fino.db")).unwrap();
+        configure_connection(&conn).unwrap();
+
+        let values = pragma_values(&conn).unwrap();
+        assert_eq!(
+            values,
+            vec![
+                ("journal_mode", "wal".to_string()),
+                ("foreign_keys", "1".to_string()),
+                ("synchronous", "1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_backup_database_copies_transactions() {
+        let conn = establish_test_connection().unwrap();
+        crate::operations::add::add_transaction_to_db(&conn, "2025-01-02,Coffee,3.50,expense,Food").unwrap();
+        crate::operations::add::add_transaction_to_db(&conn, "2025-01-03,Salary,1500.00,income,Job").unwrap();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let dest = dir.path().join("backup.db");
+        backup_database(&conn, dest.to_str().unwrap()).unwrap();
+
+        let copy = Connection::open(&dest).unwrap();
+        assert_eq!(
+            crate::db::repository::count_transactions(&copy).unwrap(),
+            crate::db::repository::count_transactions(&conn).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_backup_destination_in_directory() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let now = chrono::NaiveDate::from_ymd_opt(2025, 3, 4).unwrap().and_hms_opt(5, 6, 7).unwrap();
+
+        assert_eq!(backup_destination(dir.path(), now), dir.path().join("financial_app-20250304-050607.db"));
+        let file = dir.path().join("copy.db");
+        assert_eq!(backup_destination(&file, now), file);
+    }
+
+    #[test]
+    fn test_check_integrity_healthy_database() {
+        let conn = establish_test_connection().unwrap();
+        crate::operations::add::add_transaction_to_db(&conn, "2025-01-02,Coffee,3.50,expense,Food").unwrap();
+
+        assert_eq!(check_integrity(&conn).unwrap(), Vec::<String>::new());
+    }
+
+    /// The transactions table as the first release created it, at schema version 1.
+    fn v1_database() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON").unwrap();
+        run_migrations(&conn, &MIGRATIONS[..1]).unwrap();
+        conn.execute(
+            "INSERT INTO transactions VALUES ('1', '2025-01-01', 'Old', '1.00', 'expense', 'Food')",
+            [],
+        )
+        .unwrap();
+        conn
+    }
+
+    fn columns(conn: &Connection, table: &str) -> Vec<String> {
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table)).unwrap();
+        stmt.query_map([], |row| row.get(1)).unwrap().map(|r| r.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_migrates_v1_database_to_v2() {
+        let conn = v1_database();
+        assert_eq!(schema_version(&conn).unwrap(), 1);
+        assert!(!columns(&conn, "transactions").contains(&"tags".to_string()));
+
+        run_migrations(&conn, &MIGRATIONS[..2]).unwrap();
+
+        assert_eq!(schema_version(&conn).unwrap(), 2);
+        assert!(columns(&conn, "transactions").contains(&"tags".to_string()));
+        let tags: String = conn
+            .query_row("SELECT tags FROM transactions WHERE id = '1'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(tags, "");
+    }
+
+    #[test]
+    fn test_migrates_v1_database_to_latest() {
+        let conn = v1_database();
+        init_schema(&conn).unwrap();
+
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        let stored = crate::db::repository::get_transaction_by_id(&conn, "1").unwrap().unwrap();
+        assert_eq!(stored.account, DEFAULT_ACCOUNT);
+        let foreign_keys: bool = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0)).unwrap();
+        assert!(foreign_keys);
+    }
+
+    #[test]
+    fn test_failed_migration_keeps_previous_version() {
+        let conn = v1_database();
+        let failing: &[Migration] = &[
+            MIGRATIONS[0],
+            MIGRATIONS[1],
+            ("broken", |conn| conn.execute_batch("ALTER TABLE missing ADD COLUMN x TEXT")),
+        ];
+
+        assert!(run_migrations(&conn, failing).is_err());
+        assert_eq!(schema_version(&conn).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_refuses_newer_schema() {
+        let conn = establish_test_connection().unwrap();
+        conn.execute("UPDATE schema_version SET version = ?1", [SCHEMA_VERSION + 1]).unwrap();
+
+        let err = init_schema(&conn).unwrap_err();
+        assert!(err.to_string().contains("newer than this version of fino"));
+    }
+
+    #[test]
+    fn test_vacuum_in_memory_database() {
+        let conn = establish_test_connection().unwrap();
+        crate::operations::add::add_transaction_to_db(&conn, "2025-01-02,Coffee,3.50,expense,Food").unwrap();
+
+        vacuum_database(&conn).unwrap();
+        assert_eq!(crate::db::repository::count_transactions(&conn).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_vacuum_keeps_description_index_in_step() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let conn = Connection::open(dir.path().join("fino.db")).unwrap();
+        configure_connection(&conn).unwrap();
+        init_schema(&conn).unwrap();
+        let add = |input: &str| crate::operations::add::add_transaction_to_db(&conn, input).unwrap();
+        let first = add("2025-01-01,Bus ticket,2.50,expense,Transport");
+        add("2025-01-02,Coffee,3.50,expense,Food");
+        crate::db::repository::remove_transaction(&conn, &first).unwrap();
+
+        vacuum_database(&conn).unwrap();
+
+        let found = crate::db::repository::search_by_description(&conn, "coffee").unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].description, "Coffee");
+        assert!(crate::db::repository::search_by_description(&conn, "bus").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_vacuum_reclaims_space_after_deletes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("fino.db");
+        let conn = Connection::open(&path).unwrap();
+        configure_connection(&conn).unwrap();
+        init_schema(&conn).unwrap();
+        vacuum_database(&conn).unwrap();
+        let empty = database_size(&path);
+
+        for day in 1..=28 {
+            let input = format!("2025-02-{:02},{},3.50,expense,Food", day, "x".repeat(200));
+            for _ in 0..20 {
+                crate::operations::add::add_transaction_to_db(&conn, &input).unwrap();
+            }
+        }
+        conn.execute("DELETE FROM transactions", []).unwrap();
+        let before = database_size(&path);
+        vacuum_database(&conn).unwrap();
+        let after = database_size(&path);
+
+        assert!(after < before, "{} -> {}", before, after);
+        assert!(after <= empty, "{} grew to {}", empty, after);
+        assert_eq!(database_size(&dir.path().join("missing.db")), 0);
+    }
+
+    #[test]
+    fn test_migration_converts_text_amounts() {
+        let conn = v1_database();
+        let text_amounts = MIGRATIONS.iter().position(|(name, _)| name.contains("as REAL")).unwrap();
+        run_migrations(&conn, &MIGRATIONS[..text_amounts]).unwrap();
+        conn.execute("INSERT INTO category_budgets (category, amount) VALUES ('Food', '250.50')", []).unwrap();
+        assert_eq!(column_type(&conn, "transactions", "amount").unwrap().as_deref(), Some("TEXT"));
+
+        init_schema(&conn).unwrap();
+
+        assert_eq!(column_type(&conn, "transactions", "amount").unwrap().as_deref(), Some("REAL"));
+        assert_eq!(column_type(&conn, "category_budgets", "amount").unwrap().as_deref(), Some("REAL"));
+        let amount: f64 = conn.query_row("SELECT amount FROM transactions WHERE id = '1'", [], |row| row.get(0)).unwrap();
+        assert_eq!(amount, 1.0);
+        let budget = crate::db::budget_repository::get_budget(&conn, "Food").unwrap().unwrap();
+        assert_eq!(budget.amount, rust_decimal::Decimal::new(25050, 2));
+        assert!(conn
+            .prepare("SELECT name FROM sqlite_master WHERE name = 'idx_transactions_date'")
+            .unwrap()
+            .exists([])
+            .unwrap());
+    }
+}
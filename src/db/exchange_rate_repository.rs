@@ -0,0 +1,154 @@
+use crate::error::AppError;
+use crate::models::exchange_rate::ExchangeRate;
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+const SELECT_RATES: &str = "SELECT id, from_currency, to_currency, rate, as_of FROM exchange_rates";
+
+/// Stores the rate from `from` to `to` on `as_of`, replacing one already stored for that day.
+/// Currency codes are uppercased. Returns the ID of the rate.
+pub fn set_exchange_rate(
+    conn: &Connection,
+    from: &str,
+    to: &str,
+    rate: &Decimal,
+    as_of: NaiveDate,
+) -> Result<i32, AppError> {
+    let (from, to) = (from.trim().to_uppercase(), to.trim().to_uppercase());
+    if from.is_empty() || to.is_empty() {
+        return Err(AppError::InvalidInput("Currency codes cannot be empty".to_string()));
+    }
+    if from == to {
+        return Err(AppError::InvalidInput(format!("Cannot set a rate from {} to itself", from)));
+    }
+    if *rate <= Decimal::ZERO {
+        return Err(AppError::InvalidInput("Exchange rate must be greater than zero".to_string()));
+    }
+
+    let as_of = as_of.format("%Y-%m-%d").to_string();
+    conn.execute(
+        "INSERT INTO exchange_rates (from_currency, to_currency, rate, as_of) VALUES (?1, ?2, ?3, ?4)\n         ON CONFLICT(from_currency, to_currency, as_of) DO UPDATE SET rate = excluded.rate",
+        rusqlite::params![from, to, rate.to_string(), as_of],
+    )
+    .map_err(|e| AppError::DatabaseError(format!("Failed to save exchange rate: {}", e)))?;
+    conn.query_row(
+        "SELECT id FROM exchange_rates WHERE from_currency = ?1 AND to_currency = ?2 AND as_of = ?3",
+        rusqlite::params![from, to, as_of],
+        |row| row.get(0),
+    )
+    .map_err(|e| AppError::DatabaseError(format!("Failed to read exchange rate: {}", e)))
+}
+
+/// Every stored rate, grouped by currency pair with the newest first.
+pub fn get_all_exchange_rates(conn: &Connection) -> Result<Vec<ExchangeRate>, AppError> {
+    query_rates(conn, &format!("{} ORDER BY from_currency, to_currency, as_of DESC", SELECT_RATES), [])
+}
+
+/// The most recent rate from `from` to `to`, ignoring case.
+pub fn get_latest_exchange_rate(conn: &Connection, from: &str, to: &str) -> Result<Option<ExchangeRate>, AppError> {
+    let rates = query_rates(
+        conn,
+        &format!(
+            "{} WHERE from_currency = UPPER(?1) AND to_currency = UPPER(?2) ORDER BY as_of DESC LIMIT 1",
+            SELECT_RATES
+        ),
+        [from.trim(), to.trim()],
+    )?;
+    Ok(rates.into_iter().next())
+}
+
+pub fn delete_exchange_rate(conn: &Connection, id: i32) -> Result<(), AppError> {
+    let deleted = conn
+        .execute("DELETE FROM exchange_rates WHERE id = ?1", [id])
+        .map_err(|e| AppError::DatabaseError(format!("Failed to delete exchange rate: {}", e)))?;
+    if deleted == 0 {
+        return Err(AppError::NotFound(format!("Exchange rate with ID {} not found", id)));
+    }
+    Ok(())
+}
+
+fn query_rates<P: rusqlite::Params>(conn: &Connection, sql: &str, params: P) -> Result<Vec<ExchangeRate>, AppError> {
+    let mut stmt = conn
+        .prepare(sql)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let iter = stmt
+        .query_map(params, |row| {
+            let rate_str: String = row.get(3)?;
+            let as_of_str: String = row.get(4)?;
+            Ok(ExchangeRate {
+                id: row.get(0)?,
+                from: row.get(1)?,
+                to: row.get(2)?,
+                rate: Decimal::from_str(&rate_str).map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?,
+                as_of: NaiveDate::parse_from_str(&as_of_str, "%Y-%m-%d")
+                    .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?,
+            })
+        })
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query exchange rates: {}", e)))?;
+
+    let mut rates = Vec::new();
+    for rate in iter {
+        rates.push(rate.map_err(|e| AppError::ParseError(format!("Failed to parse exchange rate: {}", e)))?);
+    }
+    Ok(rates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::establish_test_connection;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_set_and_get_latest_rate() {
+        let conn = establish_test_connection().unwrap();
+        set_exchange_rate(&conn, "usd", "eur", &Decimal::new(90, 2), date(2025, 1, 1)).unwrap();
+        let id = set_exchange_rate(&conn, "USD", "EUR", &Decimal::new(95, 2), date(2025, 6, 1)).unwrap();
+        set_exchange_rate(&conn, "GBP", "EUR", &Decimal::new(115, 2), date(2025, 7, 1)).unwrap();
+
+        let latest = get_latest_exchange_rate(&conn, "usd", "EUR").unwrap().unwrap();
+        assert_eq!(latest.id, id);
+        assert_eq!(latest.from, "USD");
+        assert_eq!(latest.rate, Decimal::new(95, 2));
+        assert_eq!(latest.as_of, date(2025, 6, 1));
+        assert!(get_latest_exchange_rate(&conn, "EUR", "USD").unwrap().is_none());
+        assert_eq!(get_all_exchange_rates(&conn).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_set_rate_replaces_same_day() {
+        let conn = establish_test_connection().unwrap();
+        let first = set_exchange_rate(&conn, "USD", "EUR", &Decimal::new(90, 2), date(2025, 1, 1)).unwrap();
+        let second = set_exchange_rate(&conn, "USD", "EUR", &Decimal::new(92, 2), date(2025, 1, 1)).unwrap();
+
+        assert_eq!(first, second);
+        let rates = get_all_exchange_rates(&conn).unwrap();
+        assert_eq!(rates.len(), 1);
+        assert_eq!(rates[0].rate, Decimal::new(92, 2));
+    }
+
+    #[test]
+    fn test_set_rate_rejects_bad_input() {
+        let conn = establish_test_connection().unwrap();
+        let day = date(2025, 1, 1);
+        assert!(matches!(set_exchange_rate(&conn, "", "EUR", &Decimal::ONE, day), Err(AppError::InvalidInput(_))));
+        assert!(matches!(set_exchange_rate(&conn, "eur", "EUR", &Decimal::ONE, day), Err(AppError::InvalidInput(_))));
+        assert!(matches!(set_exchange_rate(&conn, "USD", "EUR", &Decimal::ZERO, day), Err(AppError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_delete_rate() {
+        let conn = establish_test_connection().unwrap();
+        let id = set_exchange_rate(&conn, "USD", "EUR", &Decimal::new(90, 2), date(2025, 1, 1)).unwrap();
+
+        delete_exchange_rate(&conn, id).unwrap();
+        assert!(get_all_exchange_rates(&conn).unwrap().is_empty());
+        assert!(matches!(delete_exchange_rate(&conn, id), Err(AppError::NotFound(_))));
+    }
+}
@@ -3,3 +3,6 @@ pub mod connection;
 pub mod rule_repository;
 pub mod budget_repository;
 pub mod alert_repository;
+pub mod recurring_repository;
+pub mod account_repository;
+pub mod exchange_rate_repository;
@@ -0,0 +1,183 @@
+use crate::error::AppError;
+use crate::models::recurring::RecurringTransaction;
+use crate::models::transaction::TransactionType;
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+pub fn add_recurring(conn: &Connection, recurring: &RecurringTransaction) -> Result<i32, AppError> {
+    let transaction_type_str = match recurring.transaction_type {
+        TransactionType::Income => "income",
+        TransactionType::Expense => "expense",
+    };
+
+    conn.execute(
+        "INSERT INTO recurring_transactions (template_description, amount, transaction_type, category, interval_days, next_due, active) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![
+            &recurring.template_description,
+            recurring.amount.to_string(),
+            transaction_type_str,
+            &recurring.category,
+            recurring.interval_days,
+            recurring.next_due.to_string(),
+            recurring.active,
+        ],
+    )
+    .map_err(|e| AppError::DatabaseError(format!("Failed to insert recurring transaction: {}", e)))?;
+
+    Ok(conn.last_insert_rowid() as i32)
+}
+
+pub fn get_all_recurring(conn: &Connection) -> Result<Vec<RecurringTransaction>, AppError> {
+    query_recurring(
+        conn,
+        "SELECT id, template_description, amount, transaction_type, category, interval_days, next_due, active \n         FROM recurring_transactions ORDER BY next_due ASC, id ASC",
+        [],
+    )
+}
+
+/// Active recurring transactions whose next occurrence is on or before `as_of`.
+pub fn get_due_recurring(conn: &Connection, as_of: NaiveDate) -> Result<Vec<RecurringTransaction>, AppError> {
+    query_recurring(
+        conn,
+        "SELECT id, template_description, amount, transaction_type, category, interval_days, next_due, active \n         FROM recurring_transactions WHERE active = 1 AND next_due <= ?1 ORDER BY next_due ASC, id ASC",
+        [as_of.to_string()],
+    )
+}
+
+/// Advances `next_due` by `interval_days` after an occurrence has been generated.
+pub fn mark_generated(conn: &Connection, id: i32) -> Result<(), AppError> {
+    let rows = conn
+        .execute(
+            "UPDATE recurring_transactions SET next_due = date(next_due, '+' || interval_days || ' days') WHERE id = ?1",
+            [id],
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update recurring transaction: {}", e)))?;
+
+    if rows == 0 {
+        return Err(AppError::NotFound(format!("Recurring transaction with ID {} not found", id)));
+    }
+    Ok(())
+}
+
+pub fn delete_recurring(conn: &Connection, id: i32) -> Result<(), AppError> {
+    let rows = conn
+        .execute("DELETE FROM recurring_transactions WHERE id = ?1", [id])
+        .map_err(|e| AppError::DatabaseError(format!("Failed to delete recurring transaction: {}", e)))?;
+
+    if rows == 0 {
+        return Err(AppError::NotFound(format!("Recurring transaction with ID {} not found", id)));
+    }
+    Ok(())
+}
+
+fn query_recurring<P: rusqlite::Params>(
+    conn: &Connection,
+    sql: &str,
+    params: P,
+) -> Result<Vec<RecurringTransaction>, AppError> {
+    let mut stmt = conn
+        .prepare(sql)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let iter = stmt
+        .query_map(params, |row| {
+            let amount_str: String = row.get(2)?;
+            let transaction_type_str: String = row.get(3)?;
+            let next_due_str: String = row.get(6)?;
+
+            Ok(RecurringTransaction {
+                id: row.get(0)?,
+                template_description: row.get(1)?,
+                amount: Decimal::from_str(&amount_str)
+                    .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?,
+                transaction_type: match transaction_type_str.as_str() {
+                    "income" => TransactionType::Income,
+                    "expense" => TransactionType::Expense,
+                    _ => return Err(rusqlite::Error::InvalidParameterName("Invalid transaction type".to_string())),
+                },
+                category: row.get(4)?,
+                interval_days: row.get(5)?,
+                next_due: NaiveDate::parse_from_str(&next_due_str, "%Y-%m-%d")
+                    .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?,
+                active: row.get(7)?,
+            })
+        })
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query recurring transactions: {}", e)))?;
+
+    let mut recurring = Vec::new();
+    for item in iter {
+        recurring.push(item.map_err(|e| AppError::ParseError(format!("Failed to parse recurring transaction: {}", e)))?);
+    }
+    Ok(recurring)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::establish_test_connection;
+
+    fn rent(next_due: NaiveDate, active: bool) -> RecurringTransaction {
+        RecurringTransaction {
+            id: 0,
+            template_description: "Rent".to_string(),
+            amount: Decimal::new(90000, 2),
+            transaction_type: TransactionType::Expense,
+            category: "Housing".to_string(),
+            interval_days: 30,
+            next_due,
+            active,
+        }
+    }
+
+    #[test]
+    fn test_add_and_get_all_recurring() {
+        let conn = establish_test_connection().unwrap();
+        let id = add_recurring(&conn, &rent(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), true)).unwrap();
+
+        let all = get_all_recurring(&conn).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].id, id);
+        assert_eq!(all[0].amount, Decimal::new(90000, 2));
+        assert_eq!(all[0].transaction_type, TransactionType::Expense);
+        assert!(all[0].active);
+    }
+
+    #[test]
+    fn test_get_due_recurring() {
+        let conn = establish_test_connection().unwrap();
+        let due = add_recurring(&conn, &rent(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), true)).unwrap();
+        add_recurring(&conn, &rent(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(), true)).unwrap();
+        add_recurring(&conn, &rent(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), false)).unwrap();
+
+        let found = get_due_recurring(&conn, NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, due);
+    }
+
+    #[test]
+    fn test_mark_generated_advances_next_due() {
+        let conn = establish_test_connection().unwrap();
+        let id = add_recurring(&conn, &rent(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(), true)).unwrap();
+
+        mark_generated(&conn, id).unwrap();
+        let all = get_all_recurring(&conn).unwrap();
+        assert_eq!(all[0].next_due, NaiveDate::from_ymd_opt(2025, 2, 14).unwrap());
+
+        let err = mark_generated(&conn, 999).unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[test]
+    fn test_delete_recurring() {
+        let conn = establish_test_connection().unwrap();
+        let id = add_recurring(&conn, &rent(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), true)).unwrap();
+
+        delete_recurring(&conn, id).unwrap();
+        assert!(get_all_recurring(&conn).unwrap().is_empty());
+
+        let err = delete_recurring(&conn, id).unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+}
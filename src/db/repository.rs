@@ -1,179 +1,1093 @@
-use crate::models::transaction::{Transaction, TransactionType};
+use crate::error::AppError;
+use crate::models::account::Account;
+use crate::models::transaction::{CategoryTotal, Transaction, TransactionFilter, TransactionOrder, TransactionStatus, TransactionType};
 use rusqlite::Connection;
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
-use rust_decimal::prelude::FromPrimitive;
-use std::str::FromStr;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use std::collections::HashMap;
 
-pub fn add_transaction(conn: &Connection, transaction: &Transaction) -> Result<(), String> {
+/// Decimal places an amount keeps when it is stored.
+pub const STORED_AMOUNT_DECIMALS: u32 = 4;
+
+/// Rejects amounts that storing would change silently: more than `STORED_AMOUNT_DECIMALS`
+/// decimal places (trailing zeros do not count), or too many digits overall to read back
+/// exactly from the `REAL` column.
+pub fn check_amount_precision(amount: &Decimal) -> Result<(), AppError> {
+    if amount.normalize().scale() > STORED_AMOUNT_DECIMALS {
+        return Err(AppError::InvalidInput(format!(
+            "Amount '{}' has too many decimal places. Use at most {}",
+            amount, STORED_AMOUNT_DECIMALS
+        )));
+    }
+    if amount_from_sql(amount_to_sql(amount)?) != Some(*amount) {
+        return Err(AppError::InvalidInput(format!("Amount '{}' is too large to store exactly", amount)));
+    }
+    Ok(())
+}
+
+/// Amounts are stored as `REAL` so SQLite can sum and compare them without casts.
+pub(crate) fn amount_to_sql(amount: &Decimal) -> Result<f64, AppError> {
+    amount
+        .to_f64()
+        .ok_or_else(|| AppError::InvalidInput(format!("Amount '{}' cannot be stored", amount)))
+}
+
+/// A stored amount (or a sum of them) as a `Decimal`: the `f64` noise past
+/// `STORED_AMOUNT_DECIMALS` places is rounded off and at least two decimals are kept, so a
+/// stored `1500.0` reads as `1500.00`. `None` for values that are not finite.
+pub(crate) fn amount_from_sql(value: f64) -> Option<Decimal> {
+    let mut amount = Decimal::from_f64(value)?.round_dp(STORED_AMOUNT_DECIMALS).normalize();
+    if amount.scale() < 2 {
+        amount.rescale(2);
+    }
+    Some(amount)
+}
+
+const INSERT_TRANSACTION_SQL: &str = "INSERT INTO transactions (id, date, description, amount, transaction_type, category, tags, status, notes, account, currency_code) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)";
+
+pub fn add_transaction(conn: &Connection, transaction: &Transaction) -> Result<(), AppError> {
+    let mut stmt = conn
+        .prepare(INSERT_TRANSACTION_SQL)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+    insert_transaction(&mut stmt, transaction)
+}
+
+/// Inserts `transactions` in order, preparing the insert once. Runs in its own transaction
+/// unless the caller already has one open, so a failing row leaves none of the batch behind
+/// (inside the caller's transaction, rolling back is up to the caller).
+pub fn add_transaction_batch(conn: &Connection, transactions: &[Transaction]) -> Result<(), AppError> {
+    let own_transaction = conn.is_autocommit();
+    if own_transaction {
+        conn.execute("BEGIN", [])
+            .map_err(|e| AppError::DatabaseError(format!("Failed to start batch insert: {}", e)))?;
+    }
+    let result = conn
+        .prepare(INSERT_TRANSACTION_SQL)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))
+        .and_then(|mut stmt| transactions.iter().try_for_each(|transaction| insert_transaction(&mut stmt, transaction)));
+    match result {
+        Ok(()) if own_transaction => {
+            conn.execute("COMMIT", [])
+                .map_err(|e| AppError::DatabaseError(format!("Failed to commit batch insert: {}", e)))?;
+            Ok(())
+        }
+        Err(e) if own_transaction => {
+            let _ = conn.execute("ROLLBACK", []);
+            Err(e)
+        }
+        result => result,
+    }
+}
+
+fn insert_transaction(stmt: &mut rusqlite::Statement, transaction: &Transaction) -> Result<(), AppError> {
+    check_amount_precision(&transaction.amount)?;
     let transaction_type_str = match transaction.transaction_type {
         TransactionType::Income => "income",
         TransactionType::Expense => "expense",
     };
-    
-    conn.execute(
-        "INSERT INTO transactions (id, date, description, amount, transaction_type, category) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        rusqlite::params![
-            &transaction.id,
-            transaction.date.to_string(),
-            &transaction.description,
-            transaction.amount.to_string(),
-            transaction_type_str,
-            &transaction.category,
-        ],
-    )
-    .map_err(|e| format!("Failed to insert transaction: {}", e))?;
-    
+
+    stmt.execute(rusqlite::params![
+        &transaction.id,
+        transaction.date.to_string(),
+        &transaction.description,
+        amount_to_sql(&transaction.amount)?,
+        transaction_type_str,
+        &transaction.category,
+        join_tags(&transaction.tags),
+        transaction.status.as_str(),
+        &transaction.notes,
+        &transaction.account,
+        &transaction.currency_code,
+    ])
+    .map_err(|e| match e.sqlite_error_code() {
+        Some(rusqlite::ErrorCode::ConstraintViolation) if e.to_string().contains("FOREIGN KEY") => {
+            AppError::NotFound(format!("Account '{}' does not exist", transaction.account))
+        }
+        _ => AppError::DatabaseError(format!("Failed to insert transaction: {}", e)),
+    })?;
+
     Ok(())
 }
 
-pub fn get_all_transactions(conn: &Connection) -> Result<Vec<Transaction>, String> {
+pub fn get_all_transactions(conn: &Connection) -> Result<Vec<Transaction>, AppError> {
     let mut stmt = conn
-        .prepare("SELECT id, date, description, amount, transaction_type, category FROM transactions ORDER BY date DESC")
-        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+        .prepare("SELECT id, date, description, amount, transaction_type, category, tags, status, notes, account, currency_code FROM transactions ORDER BY date DESC")
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
 
     let transaction_iter = stmt
-        .query_map([], |row| {
-            let date_str: String = row.get(1)?;
-            let amount_str: String = row.get(3)?;
-            let transaction_type_str: String = row.get(4)?;
-
-            Ok(Transaction {
-                id: row.get(0)?,
-                date: NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
-                    .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?,
-                description: row.get(2)?,
-                amount: Decimal::from_str(&amount_str)
-                    .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?,
-                transaction_type: match transaction_type_str.to_lowercase().as_str() {
-                    "income" => TransactionType::Income,
-                    "expense" => TransactionType::Expense,
-                    _ => return Err(rusqlite::Error::InvalidParameterName("Invalid transaction type".to_string())),
-                },
-                category: row.get(5)?,
-            })
-        })
-        .map_err(|e| format!("Failed to query transactions: {}", e))?;
+        .query_map([], parse_transaction_row)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query transactions: {}", e)))?;
 
     let mut transactions = Vec::new();
     for transaction in transaction_iter {
-        transactions.push(transaction.map_err(|e| format!("Failed to parse transaction: {}", e))?);
+        transactions.push(transaction.map_err(|e| AppError::ParseError(format!("Failed to parse transaction: {}", e)))?);
     }
     
     Ok(transactions)
 }
 
-pub fn remove_transaction(conn: &Connection, id: &str) -> Result<(), String> {
+pub fn get_transactions_paginated(
+    conn: &Connection,
+    page: usize,
+    page_size: usize,
+) -> Result<Vec<Transaction>, AppError> {
+    if page == 0 || page_size == 0 {
+        return Err(AppError::InvalidInput("Page and page size must be greater than zero".to_string()));
+    }
+    let offset = (page - 1) * page_size;
+
+    let mut stmt = conn
+        .prepare("SELECT id, date, description, amount, transaction_type, category, tags, status, notes, account, currency_code FROM transactions ORDER BY date DESC, id DESC LIMIT ?1 OFFSET ?2")
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let transaction_iter = stmt
+        .query_map([page_size as i64, offset as i64], parse_transaction_row)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query transactions: {}", e)))?;
+
+    let mut transactions = Vec::new();
+    for transaction in transaction_iter {
+        transactions.push(transaction.map_err(|e| AppError::ParseError(format!("Failed to parse transaction: {}", e)))?);
+    }
+
+    Ok(transactions)
+}
+
+pub fn count_transactions(conn: &Connection) -> Result<usize, AppError> {
+    let count: i64 = conn
+        .query_row("SELECT COUNT(*) FROM transactions", [], |row| row.get(0))
+        .map_err(|e| AppError::DatabaseError(format!("Failed to count transactions: {}", e)))?;
+
+    Ok(count as usize)
+}
+
+/// Number of transactions per category over all dates. Categories that differ only in case
+/// are counted together under their lowercase name.
+pub fn count_transactions_by_category(conn: &Connection) -> Result<HashMap<String, usize>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT LOWER(category), COUNT(*) FROM transactions GROUP BY LOWER(category)")
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let iter = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))
+        .map_err(|e| AppError::DatabaseError(format!("Failed to count transactions: {}", e)))?;
+
+    let mut counts = HashMap::new();
+    for row in iter {
+        let (category, count) = row.map_err(|e| AppError::ParseError(format!("Failed to parse category count: {}", e)))?;
+        counts.insert(category, count as usize);
+    }
+    Ok(counts)
+}
+
+/// Dates of the oldest and newest stored transaction, `None` when there are none.
+pub fn get_transaction_date_bounds(conn: &Connection) -> Result<Option<(NaiveDate, NaiveDate)>, AppError> {
+    let (first, last): (Option<String>, Option<String>) = conn
+        .query_row("SELECT MIN(date), MAX(date) FROM transactions", [], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query transaction dates: {}", e)))?;
+    let parse = |date: String| {
+        NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .map_err(|e| AppError::ParseError(format!("Failed to parse date '{}': {}", date, e)))
+    };
+    match (first, last) {
+        (Some(first), Some(last)) => Ok(Some((parse(first)?, parse(last)?))),
+        _ => Ok(None),
+    }
+}
+
+pub fn get_transaction_by_id(conn: &Connection, id: &str) -> Result<Option<Transaction>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT id, date, description, amount, transaction_type, category, tags, status, notes, account, currency_code FROM transactions WHERE id = ?1")
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let mut transaction_iter = stmt
+        .query_map([id], parse_transaction_row)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query transaction: {}", e)))?;
+
+    match transaction_iter.next() {
+        Some(transaction) => Ok(Some(
+            transaction.map_err(|e| AppError::ParseError(format!("Failed to parse transaction: {}", e)))?,
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Overwrites every field except the ID and the account, which editing keeps.
+pub fn update_transaction(conn: &Connection, id: &str, updated: &Transaction) -> Result<(), AppError> {
+    check_amount_precision(&updated.amount)?;
+    let transaction_type_str = match updated.transaction_type {
+        TransactionType::Income => "income",
+        TransactionType::Expense => "expense",
+    };
+
+    let rows_affected = conn
+        .execute(
+            "UPDATE transactions SET date = ?1, description = ?2, amount = ?3, transaction_type = ?4, category = ?5, tags = ?6, status = ?7, notes = ?8 WHERE id = ?9",
+            rusqlite::params![
+                updated.date.to_string(),
+                &updated.description,
+                amount_to_sql(&updated.amount)?,
+                transaction_type_str,
+                &updated.category,
+                join_tags(&updated.tags),
+                updated.status.as_str(),
+                &updated.notes,
+                id,
+            ],
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update transaction: {}", e)))?;
+
+    if rows_affected == 0 {
+        return Err(AppError::NotFound(format!("Transaction with ID {} not found", id)));
+    }
+
+    Ok(())
+}
+
+/// Moves the transaction to `account` and gives it the account's currency. The amount is kept.
+pub fn update_transaction_account(conn: &Connection, id: &str, account: &Account) -> Result<(), AppError> {
+    let rows_affected = conn
+        .execute(
+            "UPDATE transactions SET account = ?1, currency_code = ?2 WHERE id = ?3",
+            [account.name.as_str(), account.currency.as_str(), id],
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update transaction account: {}", e)))?;
+
+    if rows_affected == 0 {
+        return Err(AppError::NotFound(format!("Transaction with ID {} not found", id)));
+    }
+    Ok(())
+}
+
+pub fn remove_transaction(conn: &Connection, id: &str) -> Result<(), AppError> {
     let rows_affected = conn
         .execute("DELETE FROM transactions WHERE id = ?1", [id])
-        .map_err(|e| format!("Failed to delete transaction: {}", e))?;
+        .map_err(|e| AppError::DatabaseError(format!("Failed to delete transaction: {}", e)))?;
 
     if rows_affected == 0 {
-        return Err(format!("Transaction with ID {} not found", id));
+        return Err(AppError::NotFound(format!("Transaction with ID {} not found", id)));
     }
     
     Ok(())
 }
 
-pub fn search_by_category(conn: &Connection, category: &str) -> Result<Vec<Transaction>, String> {
+/// Deletes every transaction whose ID is in `ids` and returns how many rows were removed.
+/// IDs that do not exist are ignored.
+pub fn remove_transactions_batch(conn: &Connection, ids: &[&str]) -> Result<usize, AppError> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+
+    let placeholders = ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+    let query = format!("DELETE FROM transactions WHERE id IN ({})", placeholders);
+
+    conn.execute(&query, rusqlite::params_from_iter(ids.iter()))
+        .map_err(|e| AppError::DatabaseError(format!("Failed to delete transactions: {}", e)))
+}
+
+/// Moves every transaction whose ID is in `ids` to `category` and returns how many rows changed.
+/// IDs that do not exist are ignored.
+pub fn update_transactions_category_batch(conn: &Connection, ids: &[&str], category: &str) -> Result<usize, AppError> {
+    let category = category.trim();
+    if category.is_empty() {
+        return Err(AppError::InvalidInput("Category cannot be empty".to_string()));
+    }
+    if ids.is_empty() {
+        return Ok(0);
+    }
+
+    let placeholders = ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+    let query = format!("UPDATE transactions SET category = ? WHERE id IN ({})", placeholders);
+
+    conn.execute(
+        &query,
+        rusqlite::params_from_iter(std::iter::once(category).chain(ids.iter().copied())),
+    )
+    .map_err(|e| AppError::DatabaseError(format!("Failed to update transaction categories: {}", e)))
+}
+
+pub fn update_transaction_status(
+    conn: &Connection,
+    id: &str,
+    status: TransactionStatus,
+) -> Result<(), AppError> {
+    let rows_affected = conn
+        .execute(
+            "UPDATE transactions SET status = ?1 WHERE id = ?2",
+            [status.as_str(), id],
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update transaction status: {}", e)))?;
+
+    if rows_affected == 0 {
+        return Err(AppError::NotFound(format!("Transaction with ID {} not found", id)));
+    }
+
+    Ok(())
+}
+
+/// Sets the status of every transaction whose ID is in `ids` and returns how many rows changed.
+/// IDs that do not exist are ignored.
+pub fn batch_update_status(conn: &Connection, ids: &[&str], status: TransactionStatus) -> Result<usize, AppError> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+
+    let placeholders = ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+    let query = format!("UPDATE transactions SET status = ? WHERE id IN ({})", placeholders);
+
+    conn.execute(
+        &query,
+        rusqlite::params_from_iter(std::iter::once(status.as_str()).chain(ids.iter().copied())),
+    )
+    .map_err(|e| AppError::DatabaseError(format!("Failed to update transaction status: {}", e)))
+}
+
+pub fn get_transactions_by_status(
+    conn: &Connection,
+    status: TransactionStatus,
+) -> Result<Vec<Transaction>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT id, date, description, amount, transaction_type, category, tags, status, notes, account, currency_code FROM transactions WHERE status = ?1 ORDER BY date DESC")
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let transaction_iter = stmt
+        .query_map([status.as_str()], parse_transaction_row)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query transactions: {}", e)))?;
+
+    let mut transactions = Vec::new();
+    for transaction in transaction_iter {
+        transactions.push(transaction.map_err(|e| AppError::ParseError(format!("Failed to parse transaction: {}", e)))?);
+    }
+
+    Ok(transactions)
+}
+
+/// Categories used by stored transactions, sorted. Spellings that differ only in case are listed once.
+pub fn get_distinct_categories(conn: &Connection) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT MIN(category) FROM transactions GROUP BY LOWER(category) ORDER BY LOWER(category) ASC")
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let iter = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query categories: {}", e)))?;
+
+    let mut categories = Vec::new();
+    for category in iter {
+        categories.push(category.map_err(|e| AppError::ParseError(format!("Failed to parse category: {}", e)))?);
+    }
+    Ok(categories)
+}
+
+pub fn search_by_category(conn: &Connection, category: &str) -> Result<Vec<Transaction>, AppError> {
     let mut stmt = conn
-        .prepare("SELECT id, date, description, amount, transaction_type, category FROM transactions WHERE LOWER(category) = LOWER(?1)")
-        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+        .prepare("SELECT id, date, description, amount, transaction_type, category, tags, status, notes, account, currency_code FROM transactions WHERE LOWER(category) = LOWER(?1)")
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
     
     let transaction_iter = stmt
-        .query_map([category], |row| {
-            let date_str: String = row.get(1)?;
-            let amount_str: String = row.get(3)?;
-            let transaction_type_str: String = row.get(4)?;
-
-            Ok(Transaction {
-                id: row.get(0)?,
-                date: NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
-                    .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?,
-                description: row.get(2)?,
-                amount: Decimal::from_str(&amount_str)
-                    .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?,
-                transaction_type: match transaction_type_str.to_lowercase().as_str() {
-                    "income" => TransactionType::Income,
-                    "expense" => TransactionType::Expense,
-                    _ => return Err(rusqlite::Error::InvalidParameterName("Invalid transaction type".to_string())),
-                },
-                category: row.get(5)?,
-            })
-        })
-        .map_err(|e| format!("Failed to search transactions: {}", e))?;
+        .query_map([category], parse_transaction_row)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to search transactions: {}", e)))?;
     
     let mut transactions = Vec::new();
     for transaction in transaction_iter {
-        transactions.push(transaction.map_err(|e| format!("Failed to parse transaction: {}", e))?);
+        transactions.push(transaction.map_err(|e| AppError::ParseError(format!("Failed to parse transaction: {}", e)))?);
     }
     
     Ok(transactions)
 }
 
+/// Transactions whose description contains `keyword`, ignoring case.
+pub fn search_by_description(conn: &Connection, keyword: &str) -> Result<Vec<Transaction>, AppError> {
+    get_transactions_with_description_like(conn, &format!("%{}%", escape_like(keyword)), None, None, None)
+}
+
+/// `text` with the `LIKE` wildcards `%` and `_` (and the escape character `\`) escaped, so
+/// it only matches itself in a pattern used with `ESCAPE '\'`.
+pub fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Transactions whose description matches the SQL `LIKE` pattern (`%` any text, `_` one
+/// character, `\` escapes the next character, ASCII letters ignoring case), newest first. The
+/// other filters are optional and combined with `AND`; `start` and `end` are inclusive.
+///
+/// The pattern is matched through `transactions_fts`, a trigram full-text index, so patterns
+/// with three or more characters in a row do not scan every description. SQLite cannot use
+/// the index for `LIKE ... ESCAPE`, so the clause is only added when the pattern has a `\`.
+pub fn get_transactions_with_description_like(
+    conn: &Connection,
+    pattern: &str,
+    tx_type: Option<TransactionType>,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+) -> Result<Vec<Transaction>, AppError> {
+    let fts_match = if pattern.contains('\\') {
+        "rowid IN (SELECT rowid FROM transactions_fts WHERE description LIKE ?1 ESCAPE '\\')"
+    } else {
+        "rowid IN (SELECT rowid FROM transactions_fts WHERE description LIKE ?1)"
+    };
+    let mut conditions = vec![fts_match.to_string()];
+    let mut params: Vec<rusqlite::types::Value> = vec![rusqlite::types::Value::Text(pattern.to_string())];
+    if let Some(tx_type) = tx_type {
+        let type_str = match tx_type {
+            TransactionType::Income => "income",
+            TransactionType::Expense => "expense",
+        };
+        params.push(rusqlite::types::Value::Text(type_str.to_string()));
+        conditions.push(format!("transaction_type = ?{}", params.len()));
+    }
+    if let Some(start) = start {
+        params.push(rusqlite::types::Value::Text(start.to_string()));
+        conditions.push(format!("date >= ?{}", params.len()));
+    }
+    if let Some(end) = end {
+        params.push(rusqlite::types::Value::Text(end.to_string()));
+        conditions.push(format!("date <= ?{}", params.len()));
+    }
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT id, date, description, amount, transaction_type, category, tags, status, notes, account, currency_code FROM transactions WHERE {} ORDER BY date DESC",
+            conditions.join(" AND ")
+        ))
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let transaction_iter = stmt
+        .query_map(rusqlite::params_from_iter(params), parse_transaction_row)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to search transactions: {}", e)))?;
+
+    let mut transactions = Vec::new();
+    for transaction in transaction_iter {
+        transactions.push(transaction.map_err(|e| AppError::ParseError(format!("Failed to parse transaction: {}", e)))?);
+    }
+
+    Ok(transactions)
+}
+
 pub fn get_expense_transactions_in_range(
     conn: &Connection,
     start_date: NaiveDate,
     end_date: NaiveDate,
-) -> Result<Vec<Transaction>, String> {
+) -> Result<Vec<Transaction>, AppError> {
     let mut stmt = conn
         .prepare(
-            "SELECT id, date, description, amount, transaction_type, category \n 
+            "SELECT id, date, description, amount, transaction_type, category, tags, status, notes, account, currency_code \n 
             FROM transactions \n 
             WHERE transaction_type = 'expense' AND date >= ?1 AND date <= ?2 \n 
             ORDER BY date ASC",
         )
-        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
 
     let transaction_iter = stmt
-        .query_map([start_date.to_string(), end_date.to_string()], |row| {
-            let date_str: String = row.get(1)?;
-            let description_str: String = row.get(2)?;
-            let amount_str: String = row.get(3)?;
-            let transaction_type_str: String = row.get(4)?;
-            let category_str: String = row.get(5)?;
-
-            Ok(Transaction {
-                id: row.get(0)?,
-                date: NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
-                    .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?,
-                description: description_str,
-                amount: Decimal::from_str(&amount_str)
-                    .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?,
-                transaction_type: match transaction_type_str.to_lowercase().as_str() {
-                    "income" => TransactionType::Income, // Ne asteptam doar la expenses for the moment
-                    "expense" => TransactionType::Expense,
-                    _ => {
-                        return Err(rusqlite::Error::InvalidParameterName(
-                            "Invalid transaction type".to_string(),
-                        ))
-                    }
-                },
-                category: category_str,
-            })
+        .query_map([start_date.to_string(), end_date.to_string()], parse_transaction_row)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query transactions: {}", e)))?;
+
+    let mut transactions = Vec::new();
+    for transaction in transaction_iter {
+        transactions.push(transaction.map_err(|e| AppError::ParseError(format!("Failed to parse transaction: {}", e)))?);
+    }
+
+    Ok(transactions)
+}
+
+pub fn get_transactions_in_date_range(
+    conn: &Connection,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Vec<Transaction>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, date, description, amount, transaction_type, category, tags, status, notes, account, currency_code \n 
+            FROM transactions \n 
+            WHERE date >= ?1 AND date <= ?2 \n 
+            ORDER BY date ASC",
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let transaction_iter = stmt
+        .query_map([start_date.to_string(), end_date.to_string()], parse_transaction_row)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query transactions: {}", e)))?;
+
+    let mut transactions = Vec::new();
+    for transaction in transaction_iter {
+        transactions.push(transaction.map_err(|e| AppError::ParseError(format!("Failed to parse transaction: {}", e)))?);
+    }
+
+    Ok(transactions)
+}
+
+/// Transactions whose amount lies within `min..=max`, oldest first. A missing bound is open;
+/// `tx_type` keeps only income or only expenses.
+pub fn get_transactions_with_amount_range(
+    conn: &Connection,
+    min: Option<Decimal>,
+    max: Option<Decimal>,
+    tx_type: Option<TransactionType>,
+) -> Result<Vec<Transaction>, AppError> {
+    let mut conditions = Vec::new();
+    let mut params: Vec<rusqlite::types::Value> = Vec::new();
+    if let Some(min) = min {
+        params.push(rusqlite::types::Value::Real(min.to_f64().unwrap_or(f64::MIN)));
+        conditions.push(format!("amount >= ?{}", params.len()));
+    }
+    if let Some(max) = max {
+        params.push(rusqlite::types::Value::Real(max.to_f64().unwrap_or(f64::MAX)));
+        conditions.push(format!("amount <= ?{}", params.len()));
+    }
+    if let Some(tx_type) = tx_type {
+        let type_str = match tx_type {
+            TransactionType::Income => "income",
+            TransactionType::Expense => "expense",
+        };
+        params.push(rusqlite::types::Value::Text(type_str.to_string()));
+        conditions.push(format!("transaction_type = ?{}", params.len()));
+    }
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {} ", conditions.join(" AND "))
+    };
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT id, date, description, amount, transaction_type, category, tags, status, notes, account, currency_code FROM transactions {}ORDER BY date ASC",
+            where_clause
+        ))
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let transaction_iter = stmt
+        .query_map(rusqlite::params_from_iter(params), parse_transaction_row)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query transactions: {}", e)))?;
+
+    let mut transactions = Vec::new();
+    for transaction in transaction_iter {
+        transactions.push(transaction.map_err(|e| AppError::ParseError(format!("Failed to parse transaction: {}", e)))?);
+    }
+
+    Ok(transactions)
+}
+
+/// Number of transactions matching `filter`.
+pub fn count_filtered_transactions(conn: &Connection, filter: &TransactionFilter) -> Result<usize, AppError> {
+    let (where_clause, params) = filter_sql(filter)?;
+    let count: i64 = conn
+        .query_row(
+            &format!("SELECT COUNT(*) FROM transactions {}", where_clause),
+            rusqlite::params_from_iter(params),
+            |row| row.get(0),
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to count transactions: {}", e)))?;
+
+    Ok(count as usize)
+}
+
+/// Income and expense totals of the transactions matching `filter`, one
+/// `(currency_code, income, expenses)` per currency so amounts in different currencies are
+/// never added up.
+pub fn sum_filtered_transactions(conn: &Connection, filter: &TransactionFilter) -> Result<Vec<(String, Decimal, Decimal)>, AppError> {
+    let (where_clause, params) = filter_sql(filter)?;
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT currency_code, \n             IFNULL(SUM(CASE WHEN transaction_type = 'income' THEN amount END), 0), \n             IFNULL(SUM(CASE WHEN transaction_type = 'expense' THEN amount END), 0) \n             FROM transactions {}GROUP BY currency_code ORDER BY currency_code",
+            where_clause
+        ))
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let total_iter = stmt
+        .query_map(rusqlite::params_from_iter(params), |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?, row.get::<_, f64>(2)?))
+        })
+        .map_err(|e| AppError::DatabaseError(format!("Failed to sum transactions: {}", e)))?;
+
+    let mut totals = Vec::new();
+    for row in total_iter {
+        let (currency_code, income, expenses) =
+            row.map_err(|e| AppError::ParseError(format!("Failed to parse transaction totals: {}", e)))?;
+        let convert = |total: f64| {
+            amount_from_sql(total)
+                .ok_or_else(|| AppError::ParseError(format!("Failed to convert totals for currency '{}'", currency_code)))
+        };
+        let (income, expenses) = (convert(income)?, convert(expenses)?);
+        totals.push((currency_code, income, expenses));
+    }
+
+    Ok(totals)
+}
+
+/// Totals of the transactions matching `filter` per category (case-insensitive), type and
+/// currency: expenses first, then income, each largest total first.
+pub fn sum_filtered_transactions_by_category(conn: &Connection, filter: &TransactionFilter) -> Result<Vec<CategoryTotal>, AppError> {
+    let (where_clause, params) = filter_sql(filter)?;
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT category, transaction_type, currency_code, SUM(amount), COUNT(*) FROM transactions \n             {}GROUP BY LOWER(category), transaction_type, currency_code \n             ORDER BY transaction_type = 'income', 4 DESC, LOWER(category)",
+            where_clause
+        ))
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let total_iter = stmt
+        .query_map(rusqlite::params_from_iter(params), |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, f64>(3)?,
+                row.get::<_, i64>(4)?,
+            ))
         })
-        .map_err(|e| format!("Failed to query transactions: {}", e))?;
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query category totals: {}", e)))?;
+
+    let mut totals = Vec::new();
+    for row in total_iter {
+        let (category, transaction_type, currency_code, total, count) =
+            row.map_err(|e| AppError::ParseError(format!("Failed to parse category totals: {}", e)))?;
+        let transaction_type = match transaction_type.to_lowercase().as_str() {
+            "income" => TransactionType::Income,
+            "expense" => TransactionType::Expense,
+            other => return Err(AppError::ParseError(format!("Invalid transaction type '{}'", other))),
+        };
+        let total = amount_from_sql(total)
+            .ok_or_else(|| AppError::ParseError(format!("Failed to convert total for {}", category)))?;
+        totals.push(CategoryTotal { category, transaction_type, currency_code, total, count: count as usize });
+    }
+
+    Ok(totals)
+}
+
+/// At most `limit` transactions matching `filter` in `order`, skipping the first `offset`.
+pub fn get_filtered_transactions(
+    conn: &Connection,
+    filter: &TransactionFilter,
+    order: TransactionOrder,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<Transaction>, AppError> {
+    let (where_clause, mut params) = filter_sql(filter)?;
+    params.push(rusqlite::types::Value::Integer(limit as i64));
+    params.push(rusqlite::types::Value::Integer(offset as i64));
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT id, date, description, amount, transaction_type, category, tags, status, notes, account, currency_code FROM transactions {}ORDER BY {} LIMIT ?{} OFFSET ?{}",
+            where_clause,
+            order_by_sql(order),
+            params.len() - 1,
+            params.len()
+        ))
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let transaction_iter = stmt
+        .query_map(rusqlite::params_from_iter(params), parse_transaction_row)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query transactions: {}", e)))?;
+
+    let mut transactions = Vec::new();
+    for transaction in transaction_iter {
+        transactions.push(transaction.map_err(|e| AppError::ParseError(format!("Failed to parse transaction: {}", e)))?);
+    }
+
+    Ok(transactions)
+}
+
+/// Positions (0-based, in `order`) of the first and the last transaction matching `filter`
+/// that is dated on or before `date`; `None` when every match is newer.
+pub fn find_filtered_rows_on_or_before(
+    conn: &Connection,
+    filter: &TransactionFilter,
+    order: TransactionOrder,
+    date: NaiveDate,
+) -> Result<Option<(usize, usize)>, AppError> {
+    let (where_clause, mut params) = filter_sql(filter)?;
+    params.push(rusqlite::types::Value::Text(date.to_string()));
+
+    let positions: (Option<i64>, Option<i64>) = conn
+        .query_row(
+            &format!(
+                "SELECT MIN(position), MAX(position) FROM ( \
+                     SELECT date, ROW_NUMBER() OVER (ORDER BY {}) - 1 AS position FROM transactions {}\
+                 ) WHERE date <= ?{}",
+                order_by_sql(order),
+                where_clause,
+                params.len()
+            ),
+            rusqlite::params_from_iter(params),
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find transactions: {}", e)))?;
+
+    Ok(match positions {
+        (Some(first), Some(last)) => Some((first as usize, last as usize)),
+        _ => None,
+    })
+}
+
+/// Accounts that have transactions, by name.
+pub fn get_distinct_accounts(conn: &Connection) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT DISTINCT account FROM transactions ORDER BY account ASC")
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let iter = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query accounts: {}", e)))?;
+
+    let mut accounts = Vec::new();
+    for account in iter {
+        accounts.push(account.map_err(|e| AppError::ParseError(format!("Failed to parse account: {}", e)))?);
+    }
+    Ok(accounts)
+}
+
+/// `WHERE ...` (with a trailing space, or empty) and its parameters for `filter`.
+fn filter_sql(filter: &TransactionFilter) -> Result<(String, Vec<rusqlite::types::Value>), AppError> {
+    let mut conditions = Vec::new();
+    let mut params: Vec<rusqlite::types::Value> = Vec::new();
+    let mut add = |condition: &str, value: rusqlite::types::Value| {
+        params.push(value);
+        conditions.push(condition.replace('?', &format!("?{}", params.len())));
+    };
+    if let Some(category) = &filter.category {
+        add("LOWER(category) = LOWER(?)", rusqlite::types::Value::Text(category.clone()));
+    }
+    if let Some(keyword) = &filter.description {
+        // instr rather than LIKE so `%` and `_` in the keyword match themselves.
+        add("instr(LOWER(description), LOWER(?)) > 0", rusqlite::types::Value::Text(keyword.clone()));
+    }
+    if let Some(tx_type) = filter.transaction_type {
+        let type_str = match tx_type {
+            TransactionType::Income => "income",
+            TransactionType::Expense => "expense",
+        };
+        add("transaction_type = ?", rusqlite::types::Value::Text(type_str.to_string()));
+    }
+    if let Some(account) = &filter.account {
+        add("account = ?", rusqlite::types::Value::Text(account.clone()));
+    }
+    if let Some(from) = filter.from {
+        add("date >= ?", rusqlite::types::Value::Text(from.to_string()));
+    }
+    if let Some(to) = filter.to {
+        add("date <= ?", rusqlite::types::Value::Text(to.to_string()));
+    }
+    if let Some(min) = filter.amount_min {
+        add("amount >= ?", rusqlite::types::Value::Real(amount_to_sql(&min)?));
+    }
+    if let Some(max) = filter.amount_max {
+        add("amount <= ?", rusqlite::types::Value::Real(amount_to_sql(&max)?));
+    }
+
+    if conditions.is_empty() {
+        Ok((String::new(), params))
+    } else {
+        Ok((format!("WHERE {} ", conditions.join(" AND ")), params))
+    }
+}
+
+fn order_by_sql(order: TransactionOrder) -> &'static str {
+    match order {
+        TransactionOrder::DateDesc => "date DESC, id DESC",
+        TransactionOrder::DateAsc => "date ASC, id ASC",
+        TransactionOrder::AmountDesc => "amount DESC, date DESC, id DESC",
+        TransactionOrder::AmountAsc => "amount ASC, date DESC, id DESC",
+        TransactionOrder::CategoryAsc => "LOWER(category) ASC, date DESC, id DESC",
+        TransactionOrder::CategoryDesc => "LOWER(category) DESC, date DESC, id DESC",
+    }
+}
+
+pub fn get_income_transactions_in_range(
+    conn: &Connection,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Vec<Transaction>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, date, description, amount, transaction_type, category, tags, status, notes, account, currency_code \n 
+            FROM transactions \n 
+            WHERE transaction_type = 'income' AND date >= ?1 AND date <= ?2 \n 
+            ORDER BY date ASC",
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let transaction_iter = stmt
+        .query_map([start_date.to_string(), end_date.to_string()], parse_transaction_row)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query transactions: {}", e)))?;
 
     let mut transactions = Vec::new();
     for transaction in transaction_iter {
-        transactions.push(transaction.map_err(|e| format!("Failed to parse transaction: {}", e))?);
+        transactions.push(transaction.map_err(|e| AppError::ParseError(format!("Failed to parse transaction: {}", e)))?);
     }
 
     Ok(transactions)
 }
 
-pub fn get_total_expenses_by_category(conn: &Connection, category: &str) -> Result<Decimal, String> {
+pub fn get_total_expenses_by_category(conn: &Connection, category: &str) -> Result<Decimal, AppError> {
     let mut stmt = conn
         .prepare(
-            "SELECT IFNULL(SUM(CAST(amount AS REAL)), 0) FROM transactions \n             WHERE LOWER(category) = LOWER(?1) AND transaction_type = 'expense'",
+            "SELECT IFNULL(SUM(amount), 0) FROM transactions \n             WHERE LOWER(category) = LOWER(?1) AND transaction_type = 'expense'",
         )
-        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
 
     let total: f64 = stmt
         .query_row([category], |row| row.get(0))
-        .map_err(|e| format!("Failed to calculate total expenses: {}", e))?;
+        .map_err(|e| AppError::DatabaseError(format!("Failed to calculate total expenses: {}", e)))?;
+
+    amount_from_sql(total).ok_or_else(|| AppError::ParseError("Failed to convert total expenses".to_string()))
+}
+
+pub fn get_expenses_by_category_in_range(
+    conn: &Connection,
+    category: &str,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Decimal, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT IFNULL(SUM(amount), 0) FROM transactions \n             WHERE LOWER(category) = LOWER(?1) AND transaction_type = 'expense' AND date >= ?2 AND date <= ?3",
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let total: f64 = stmt
+        .query_row([category.to_string(), start_date.to_string(), end_date.to_string()], |row| row.get(0))
+        .map_err(|e| AppError::DatabaseError(format!("Failed to calculate total expenses: {}", e)))?;
+
+    amount_from_sql(total).ok_or_else(|| AppError::ParseError("Failed to convert total expenses".to_string()))
+}
+
+/// Income minus expenses in the range, one `(currency_code, net)` per currency so amounts in
+/// different currencies are never added up. Empty when the range has no transactions.
+pub fn get_net_balance_in_range(
+    conn: &Connection,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Vec<(String, Decimal)>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT currency_code, SUM(CASE WHEN transaction_type = 'income' THEN amount ELSE -amount END) \n             FROM transactions WHERE date >= ?1 AND date <= ?2 GROUP BY currency_code ORDER BY currency_code",
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let balance_iter = stmt
+        .query_map([start_date.to_string(), end_date.to_string()], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
+        })
+        .map_err(|e| AppError::DatabaseError(format!("Failed to calculate net balance: {}", e)))?;
+
+    let mut balances = Vec::new();
+    for row in balance_iter {
+        let (currency_code, total) =
+            row.map_err(|e| AppError::ParseError(format!("Failed to parse net balance: {}", e)))?;
+        let net = amount_from_sql(total)
+            .ok_or_else(|| AppError::ParseError(format!("Failed to convert net balance for currency '{}'", currency_code)))?;
+        balances.push((currency_code, net));
+    }
+
+    Ok(balances)
+}
+
+/// The balance at the end of every day in the range that has transactions, starting from
+/// `initial_balance`. Income adds to it and expenses subtract from it.
+pub fn get_running_balance(
+    conn: &Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+    initial_balance: Decimal,
+) -> Result<Vec<(NaiveDate, Decimal)>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT date, amount, transaction_type FROM transactions \n             WHERE date >= ?1 AND date <= ?2 ORDER BY date ASC",
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let rows = stmt
+        .query_map([start.to_string(), end.to_string()], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?, row.get::<_, String>(2)?))
+        })
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query transactions: {}", e)))?;
+
+    let mut balances: Vec<(NaiveDate, Decimal)> = Vec::new();
+    let mut balance = initial_balance;
+    for row in rows {
+        let (date_str, stored_amount, transaction_type_str) =
+            row.map_err(|e| AppError::ParseError(format!("Failed to parse transaction: {}", e)))?;
+        let date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
+            .map_err(|e| AppError::ParseError(format!("Failed to parse date '{}': {}", date_str, e)))?;
+        let amount = amount_from_sql(stored_amount)
+            .ok_or_else(|| AppError::ParseError(format!("Failed to parse amount '{}'", stored_amount)))?;
+        match transaction_type_str.to_lowercase().as_str() {
+            "income" => balance += amount,
+            "expense" => balance -= amount,
+            _ => return Err(AppError::ParseError(format!("Invalid transaction type '{}'", transaction_type_str))),
+        }
+        match balances.last_mut() {
+            Some((last_date, last_balance)) if *last_date == date => *last_balance = balance,
+            _ => balances.push((date, balance)),
+        }
+    }
+
+    Ok(balances)
+}
+
+pub fn get_daily_totals(
+    conn: &Connection,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    transaction_type: TransactionType,
+) -> Result<Vec<(NaiveDate, Decimal)>, AppError> {
+    let rows = query_grouped_totals(
+        conn,
+        "SELECT date, SUM(amount) FROM transactions \n             WHERE transaction_type = ?1 AND date >= ?2 AND date <= ?3 \n             GROUP BY date ORDER BY date ASC",
+        transaction_type,
+        start_date,
+        end_date,
+    )?;
+
+    rows.into_iter()
+        .map(|(day, total)| {
+            let date = NaiveDate::parse_from_str(&day, "%Y-%m-%d")
+                .map_err(|e| AppError::ParseError(format!("Failed to parse date '{}': {}", day, e)))?;
+            Ok((date, total))
+        })
+        .collect()
+}
+
+/// Totals per calendar month; each month is keyed by its first day.
+pub fn get_monthly_totals(
+    conn: &Connection,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    transaction_type: TransactionType,
+) -> Result<Vec<(NaiveDate, Decimal)>, AppError> {
+    let rows = query_grouped_totals(
+        conn,
+        "SELECT strftime('%Y-%m', date) AS month, SUM(amount) FROM transactions \n             WHERE transaction_type = ?1 AND date >= ?2 AND date <= ?3 \n             GROUP BY month ORDER BY month ASC",
+        transaction_type,
+        start_date,
+        end_date,
+    )?;
+
+    rows.into_iter()
+        .map(|(month, total)| {
+            let date = NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+                .map_err(|e| AppError::ParseError(format!("Failed to parse month '{}': {}", month, e)))?;
+            Ok((date, total))
+        })
+        .collect()
+}
+
+/// Expense spend and transaction count per category, largest spend first.
+pub fn get_category_summary(
+    conn: &Connection,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Vec<(String, Decimal, usize)>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT category, SUM(amount), COUNT(*) FROM transactions \n             WHERE date BETWEEN ?1 AND ?2 AND transaction_type = 'expense' \n             GROUP BY LOWER(category) ORDER BY 2 DESC",
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let summary_iter = stmt
+        .query_map([start_date.to_string(), end_date.to_string()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, f64>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query category summary: {}", e)))?;
+
+    let mut summary = Vec::new();
+    for row in summary_iter {
+        let (category, total, count) = row
+            .map_err(|e| AppError::ParseError(format!("Failed to parse category summary: {}", e)))?;
+        let total = amount_from_sql(total)
+            .ok_or_else(|| AppError::ParseError(format!("Failed to convert total for {}", category)))?;
+        summary.push((category, total, count as usize));
+    }
+
+    Ok(summary)
+}
+
+fn query_grouped_totals(
+    conn: &Connection,
+    sql: &str,
+    transaction_type: TransactionType,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Vec<(String, Decimal)>, AppError> {
+    let transaction_type_str = match transaction_type {
+        TransactionType::Income => "income",
+        TransactionType::Expense => "expense",
+    };
+
+    let mut stmt = conn
+        .prepare(sql)
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prepare statement: {}", e)))?;
+
+    let total_iter = stmt
+        .query_map(
+            [transaction_type_str.to_string(), start_date.to_string(), end_date.to_string()],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?)),
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to query totals: {}", e)))?;
+
+    let mut totals = Vec::new();
+    for row in total_iter {
+        let (key, total) =
+            row.map_err(|e| AppError::ParseError(format!("Failed to parse totals: {}", e)))?;
+        let total = amount_from_sql(total)
+            .ok_or_else(|| AppError::ParseError(format!("Failed to convert total for {}", key)))?;
+        totals.push((key, total));
+    }
+
+    Ok(totals)
+}
+
+/// Maps a row selected as `id, date, description, amount, transaction_type, category, tags,
+/// status, notes, account, currency_code` to a `Transaction`.
+pub(crate) fn parse_transaction_row(row: &rusqlite::Row) -> rusqlite::Result<Transaction> {
+    let date_str: String = row.get(1)?;
+    let transaction_type_str: String = row.get(4)?;
 
-    Decimal::from_f64(total).ok_or_else(|| "Failed to convert total expenses".to_string())
+    Ok(Transaction {
+        id: row.get(0)?,
+        date: NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
+            .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?,
+        description: row.get(2)?,
+        amount: amount_from_sql(row.get(3)?)
+            .ok_or_else(|| rusqlite::Error::InvalidParameterName("Invalid amount".to_string()))?,
+        transaction_type: match transaction_type_str.to_lowercase().as_str() {
+            "income" => TransactionType::Income,
+            "expense" => TransactionType::Expense,
+            _ => return Err(rusqlite::Error::InvalidParameterName("Invalid transaction type".to_string())),
+        },
+        category: row.get(5)?,
+        tags: split_tags(&row.get::<_, String>(6)?),
+        status: TransactionStatus::parse(&row.get::<_, String>(7)?)
+            .ok_or_else(|| rusqlite::Error::InvalidParameterName("Invalid transaction status".to_string()))?,
+        notes: row.get::<_, Option<String>>(8)?.unwrap_or_default(),
+        account: row.get(9)?,
+        currency_code: row.get(10)?,
+    })
+}
+
+// Tags are stored in a single TEXT column as a comma-separated list.
+fn join_tags(tags: &[String]) -> String {
+    tags.join(",")
+}
+
+fn split_tags(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|tag| tag.trim())
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| tag.to_string())
+        .collect()
 }
 
 #[cfg(test)]
@@ -196,12 +1110,228 @@ mod tests {
     }
 
     #[test]
-    fn test_add_transaction_success() {
+    fn test_add_transaction_success() {
+        let conn = establish_test_connection().unwrap();
+        let transaction = create_test_transaction(&Uuid::new_v4().to_string(), "Salary");
+
+        let result = add_transaction(&conn, &transaction);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_amount_round_trip_keeps_four_decimals() {
+        let conn = establish_test_connection().unwrap();
+        let amounts = [
+            "0.0001", "0.10", "19.99", "-5.50", "1234567.8912", "99999999.9999", "0.3333", "1500.00",
+            "5000000000000.50", "-123456789012.34", "900000000000000.00",
+        ];
+        for (i, amount) in amounts.iter().enumerate() {
+            let mut transaction = create_test_transaction(&format!("id-{}", i), "Food");
+            transaction.amount = Decimal::from_str_exact(amount).unwrap();
+            add_transaction(&conn, &transaction).unwrap();
+
+            let stored = get_transaction_by_id(&conn, &transaction.id).unwrap().unwrap();
+            assert_eq!(stored.amount.to_string(), *amount);
+        }
+        // Whole amounts read back with cents.
+        let mut whole = create_test_transaction("whole", "Food");
+        whole.amount = Decimal::new(42, 0);
+        add_transaction(&conn, &whole).unwrap();
+        assert_eq!(get_transaction_by_id(&conn, "whole").unwrap().unwrap().amount.to_string(), "42.00");
+
+        // Too many digits for the REAL column: rejected instead of stored as a nearby value.
+        let mut huge = create_test_transaction("huge", "Food");
+        huge.amount = Decimal::from_str_exact("12345678901234.5678").unwrap();
+        assert!(matches!(add_transaction(&conn, &huge), Err(AppError::InvalidInput(_))));
+        assert!(matches!(update_transaction(&conn, "whole", &huge), Err(AppError::InvalidInput(_))));
+        assert!(get_transaction_by_id(&conn, "huge").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_count_transactions_by_category() {
+        let conn = establish_test_connection().unwrap();
+        assert!(count_transactions_by_category(&conn).unwrap().is_empty());
+
+        add_transaction(&conn, &create_test_transaction("a", "Food")).unwrap();
+        assert_eq!(count_transactions_by_category(&conn).unwrap(), HashMap::from([("food".to_string(), 1)]));
+
+        add_transaction(&conn, &create_test_transaction("b", "FOOD")).unwrap();
+        add_transaction(&conn, &create_test_transaction("c", "food")).unwrap();
+        add_transaction(&conn, &create_test_transaction("d", "Transport")).unwrap();
+        assert_eq!(
+            count_transactions_by_category(&conn).unwrap(),
+            HashMap::from([("food".to_string(), 3), ("transport".to_string(), 1)])
+        );
+    }
+
+    #[test]
+    fn test_add_transaction_with_tags_round_trip() {
+        let conn = establish_test_connection().unwrap();
+        let id = Uuid::new_v4().to_string();
+        let mut transaction = create_test_transaction(&id, "Travel");
+        transaction.tags = vec!["vacation".to_string(), "tax-deductible".to_string()];
+        add_transaction(&conn, &transaction).unwrap();
+
+        let stored = get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert_eq!(stored.tags, vec!["vacation", "tax-deductible"]);
+        assert_eq!(search_by_category(&conn, "travel").unwrap()[0].tags, stored.tags);
+
+        let mut updated = stored.clone();
+        updated.tags = vec!["work".to_string()];
+        update_transaction(&conn, &id, &updated).unwrap();
+        assert_eq!(get_all_transactions(&conn).unwrap()[0].tags, vec!["work"]);
+    }
+
+    #[test]
+    fn test_add_transaction_with_notes_round_trip() {
+        let conn = establish_test_connection().unwrap();
+        let id = Uuid::new_v4().to_string();
+        let mut transaction = create_test_transaction(&id, "Food");
+        transaction.notes = "Card ending 1234, split with Alex".repeat(20);
+        add_transaction(&conn, &transaction).unwrap();
+
+        let stored = get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert_eq!(stored.notes, transaction.notes);
+    }
+
+    #[test]
+    fn test_null_notes_read_as_empty() {
+        let conn = establish_test_connection().unwrap();
+        conn.execute(
+            "INSERT INTO transactions (id, date, description, amount, transaction_type, category, notes) VALUES ('n1', '2025-01-01', 'Old', '1.00', 'expense', 'Food', NULL)",
+            [],
+        )
+        .unwrap();
+
+        let stored = get_transaction_by_id(&conn, "n1").unwrap().unwrap();
+        assert_eq!(stored.notes, "");
+    }
+
+    #[test]
+    fn test_transaction_status_defaults_to_cleared() {
+        let conn = establish_test_connection().unwrap();
+        let id = Uuid::new_v4().to_string();
+        add_transaction(&conn, &create_test_transaction(&id, "Food")).unwrap();
+
+        let stored = get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert_eq!(stored.status, TransactionStatus::Cleared);
+    }
+
+    #[test]
+    fn test_get_transactions_by_status() {
+        let conn = establish_test_connection().unwrap();
+        let pending_id = Uuid::new_v4().to_string();
+        let mut pending = create_test_transaction(&pending_id, "Food");
+        pending.status = TransactionStatus::Pending;
+        add_transaction(&conn, &pending).unwrap();
+        add_transaction(&conn, &create_test_transaction(&Uuid::new_v4().to_string(), "Food")).unwrap();
+
+        let found = get_transactions_by_status(&conn, TransactionStatus::Pending).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, pending_id);
+        assert!(get_transactions_by_status(&conn, TransactionStatus::Reconciled).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_update_transaction_status() {
+        let conn = establish_test_connection().unwrap();
+        let id = Uuid::new_v4().to_string();
+        add_transaction(&conn, &create_test_transaction(&id, "Food")).unwrap();
+
+        update_transaction_status(&conn, &id, TransactionStatus::Reconciled).unwrap();
+        let stored = get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert_eq!(stored.status, TransactionStatus::Reconciled);
+
+        let err = update_transaction_status(&conn, &Uuid::new_v4().to_string(), TransactionStatus::Pending).unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[test]
+    fn test_get_distinct_categories() {
+        let conn = establish_test_connection().unwrap();
+        for category in ["Travel", "Food", "food", "FOOD", "Food", "Bills"] {
+            add_transaction(&conn, &create_test_transaction(&Uuid::new_v4().to_string(), category)).unwrap();
+        }
+
+        let categories = get_distinct_categories(&conn).unwrap();
+        assert_eq!(categories, vec!["Bills", "FOOD", "Travel"]);
+    }
+
+    #[test]
+    fn test_get_distinct_categories_empty() {
+        let conn = establish_test_connection().unwrap();
+        assert!(get_distinct_categories(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_update_transactions_category_batch() {
+        let conn = establish_test_connection().unwrap();
+        let ids: Vec<String> = (0..3).map(|_| Uuid::new_v4().to_string()).collect();
+        for id in &ids {
+            add_transaction(&conn, &create_test_transaction(id, "Food")).unwrap();
+        }
+
+        let updated = update_transactions_category_batch(&conn, &[&ids[0], &ids[1]], " Groceries ").unwrap();
+        assert_eq!(updated, 2);
+        assert_eq!(get_transaction_by_id(&conn, &ids[0]).unwrap().unwrap().category, "Groceries");
+        assert_eq!(get_transaction_by_id(&conn, &ids[2]).unwrap().unwrap().category, "Food");
+    }
+
+    #[test]
+    fn test_update_transactions_category_batch_some_missing() {
+        let conn = establish_test_connection().unwrap();
+        let id = Uuid::new_v4().to_string();
+        add_transaction(&conn, &create_test_transaction(&id, "Food")).unwrap();
+        let missing = Uuid::new_v4().to_string();
+
+        let updated = update_transactions_category_batch(&conn, &[&id, &missing], "Dining").unwrap();
+        assert_eq!(updated, 1);
+        assert_eq!(get_transaction_by_id(&conn, &id).unwrap().unwrap().category, "Dining");
+        assert_eq!(update_transactions_category_batch(&conn, &[], "Dining").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_update_transactions_category_batch_empty_category() {
+        let conn = establish_test_connection().unwrap();
+        let id = Uuid::new_v4().to_string();
+        add_transaction(&conn, &create_test_transaction(&id, "Food")).unwrap();
+
+        let err = update_transactions_category_batch(&conn, &[&id], "  ").unwrap_err();
+        assert!(matches!(err, AppError::InvalidInput(_)));
+        assert_eq!(get_transaction_by_id(&conn, &id).unwrap().unwrap().category, "Food");
+    }
+
+    #[test]
+    fn test_batch_update_status() {
+        let conn = establish_test_connection().unwrap();
+        let ids: Vec<String> = (0..3).map(|_| Uuid::new_v4().to_string()).collect();
+        for id in &ids {
+            add_transaction(&conn, &create_test_transaction(id, "Food")).unwrap();
+        }
+        let missing = Uuid::new_v4().to_string();
+
+        let updated = batch_update_status(&conn, &[&ids[0], &ids[2], &missing], TransactionStatus::Reconciled).unwrap();
+        assert_eq!(updated, 2);
+
+        let reconciled = get_transactions_by_status(&conn, TransactionStatus::Reconciled).unwrap();
+        let mut reconciled_ids: Vec<&str> = reconciled.iter().map(|t| t.id.as_str()).collect();
+        reconciled_ids.sort();
+        let mut expected = vec![ids[0].as_str(), ids[2].as_str()];
+        expected.sort();
+        assert_eq!(reconciled_ids, expected);
+        assert_eq!(get_transaction_by_id(&conn, &ids[1]).unwrap().unwrap().status, TransactionStatus::Cleared);
+
+        assert_eq!(batch_update_status(&conn, &[], TransactionStatus::Pending).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_status_check_constraint() {
         let conn = establish_test_connection().unwrap();
-        let transaction = create_test_transaction(&Uuid::new_v4().to_string(), "Salary");
-
-        let result = add_transaction(&conn, &transaction);
-        assert!(result.is_ok());
+        let result = conn.execute(
+            "INSERT INTO transactions (id, date, description, amount, transaction_type, category, status) VALUES ('x', '2025-01-01', 'Bad', '1.00', 'expense', 'Food', 'lost')",
+            [],
+        );
+        assert!(result.is_err());
     }
 
     #[test]
@@ -213,8 +1343,9 @@ mod tests {
         add_transaction(&conn, &transaction).unwrap();
         let result = add_transaction(&conn, &transaction);
         
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("UNIQUE constraint failed"));
+        let err = result.unwrap_err();
+        assert!(matches!(err, AppError::DatabaseError(_)));
+        assert!(err.to_string().contains("UNIQUE constraint failed"));
     }
 
     #[test]
@@ -264,8 +1395,486 @@ mod tests {
         let non_existent_id = Uuid::new_v4().to_string();
 
         let result = remove_transaction(&conn, &non_existent_id);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("not found"));
+        let err = result.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+        assert!(err.to_string().contains("not found"));
+    }
+
+    #[test]
+    fn test_remove_transactions_batch_all_exist() {
+        let conn = establish_test_connection().unwrap();
+        let ids: Vec<String> = (0..3).map(|_| Uuid::new_v4().to_string()).collect();
+        for id in &ids {
+            add_transaction(&conn, &create_test_transaction(id, "Food")).unwrap();
+        }
+
+        let id_refs: Vec<&str> = ids.iter().map(String::as_str).collect();
+        let removed = remove_transactions_batch(&conn, &id_refs).unwrap();
+        assert_eq!(removed, 3);
+        assert_eq!(count_transactions(&conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_remove_transactions_batch_some_missing() {
+        let conn = establish_test_connection().unwrap();
+        let kept = Uuid::new_v4().to_string();
+        let removed_id = Uuid::new_v4().to_string();
+        let missing = Uuid::new_v4().to_string();
+        add_transaction(&conn, &create_test_transaction(&kept, "Food")).unwrap();
+        add_transaction(&conn, &create_test_transaction(&removed_id, "Food")).unwrap();
+
+        let removed = remove_transactions_batch(&conn, &[&removed_id, &missing]).unwrap();
+        assert_eq!(removed, 1);
+
+        let remaining = get_all_transactions(&conn).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, kept);
+    }
+
+    #[test]
+    fn test_remove_transactions_batch_empty() {
+        let conn = establish_test_connection().unwrap();
+        let id = Uuid::new_v4().to_string();
+        add_transaction(&conn, &create_test_transaction(&id, "Food")).unwrap();
+
+        assert_eq!(remove_transactions_batch(&conn, &[]).unwrap(), 0);
+        assert_eq!(count_transactions(&conn).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_get_transactions_paginated() {
+        let conn = establish_test_connection().unwrap();
+        for day in 1..=10 {
+            let transaction = Transaction::new(
+                Uuid::new_v4().to_string(),
+                NaiveDate::from_ymd_opt(2025, 1, day).unwrap(),
+                format!("Transaction {}", day),
+                Decimal::new(1000, 2),
+                TransactionType::Expense,
+                "Food".to_string(),
+            );
+            add_transaction(&conn, &transaction).unwrap();
+        }
+
+        let page = get_transactions_paginated(&conn, 2, 3).unwrap();
+        assert_eq!(page.len(), 3);
+        assert_eq!(page[0].date, NaiveDate::from_ymd_opt(2025, 1, 7).unwrap());
+        assert_eq!(page[1].date, NaiveDate::from_ymd_opt(2025, 1, 6).unwrap());
+        assert_eq!(page[2].date, NaiveDate::from_ymd_opt(2025, 1, 5).unwrap());
+
+        let last = get_transactions_paginated(&conn, 4, 3).unwrap();
+        assert_eq!(last.len(), 1);
+        assert!(get_transactions_paginated(&conn, 5, 3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_transactions_paginated_rejects_zero() {
+        let conn = establish_test_connection().unwrap();
+
+        let result = get_transactions_paginated(&conn, 0, 50);
+        assert!(matches!(result.unwrap_err(), AppError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn test_count_transactions() {
+        let conn = establish_test_connection().unwrap();
+        assert_eq!(count_transactions(&conn).unwrap(), 0);
+
+        add_transaction(&conn, &create_test_transaction(&Uuid::new_v4().to_string(), "Food")).unwrap();
+        add_transaction(&conn, &create_test_transaction(&Uuid::new_v4().to_string(), "Food")).unwrap();
+        assert_eq!(count_transactions(&conn).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_get_transaction_by_id_found() {
+        let conn = establish_test_connection().unwrap();
+        let id = Uuid::new_v4().to_string();
+        let original = create_test_transaction(&id, "Salary");
+        add_transaction(&conn, &original).unwrap();
+        add_transaction(&conn, &create_test_transaction(&Uuid::new_v4().to_string(), "Food")).unwrap();
+
+        let result = get_transaction_by_id(&conn, &id);
+        assert!(result.is_ok());
+
+        let transaction = result.unwrap().unwrap();
+        assert_eq!(transaction, original);
+    }
+
+    #[test]
+    fn test_get_transaction_by_id_not_found() {
+        let conn = establish_test_connection().unwrap();
+
+        let result = get_transaction_by_id(&conn, &Uuid::new_v4().to_string());
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_get_transaction_by_id_malformed_row() {
+        let conn = establish_test_connection().unwrap();
+        let id = Uuid::new_v4().to_string();
+        conn.execute(
+            "INSERT INTO transactions (id, date, description, amount, transaction_type, category) VALUES (?1, 'not-a-date', 'Broken', '10.00', 'expense', 'Food')",
+            [&id],
+        )
+        .unwrap();
+
+        let result = get_transaction_by_id(&conn, &id);
+        let err = result.unwrap_err();
+        assert!(matches!(err, AppError::ParseError(_)));
+        assert!(err.to_string().contains("Failed to parse transaction"));
+    }
+
+    #[test]
+    fn test_update_transaction_success() {
+        let conn = establish_test_connection().unwrap();
+        let id = Uuid::new_v4().to_string();
+        add_transaction(&conn, &create_test_transaction(&id, "Salary")).unwrap();
+
+        let updated = Transaction::new(
+            id.clone(),
+            NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(),
+            "Groceries".to_string(),
+            Decimal::new(4250, 2),
+            TransactionType::Expense,
+            "Food".to_string(),
+        );
+        let result = update_transaction(&conn, &id, &updated);
+        assert!(result.is_ok());
+
+        let all = get_all_transactions(&conn).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].id, id);
+        assert_eq!(all[0].date, NaiveDate::from_ymd_opt(2025, 2, 1).unwrap());
+        assert_eq!(all[0].description, "Groceries");
+        assert_eq!(all[0].amount, Decimal::new(4250, 2));
+        assert_eq!(all[0].transaction_type, TransactionType::Expense);
+        assert_eq!(all[0].category, "Food");
+    }
+
+    #[test]
+    fn test_update_transaction_not_found() {
+        let conn = establish_test_connection().unwrap();
+        let id = Uuid::new_v4().to_string();
+        let transaction = create_test_transaction(&id, "Salary");
+
+        let result = update_transaction(&conn, &id, &transaction);
+        let err = result.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+        assert!(err.to_string().contains("not found"));
+    }
+
+    fn seed_two_months(conn: &Connection) {
+        let rows = [
+            (2025, 1, 5, TransactionType::Income, "Job"),
+            (2025, 1, 10, TransactionType::Expense, "Food"),
+            (2025, 1, 31, TransactionType::Expense, "Rent"),
+            (2025, 2, 1, TransactionType::Income, "Job"),
+            (2025, 2, 14, TransactionType::Expense, "Food"),
+        ];
+        for (y, m, d, transaction_type, category) in rows {
+            let transaction = Transaction::new(
+                Uuid::new_v4().to_string(),
+                NaiveDate::from_ymd_opt(y, m, d).unwrap(),
+                "Test Transaction".to_string(),
+                Decimal::new(1000, 2),
+                transaction_type,
+                category.to_string(),
+            );
+            add_transaction(conn, &transaction).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_get_transactions_in_date_range_all_types() {
+        let conn = establish_test_connection().unwrap();
+        seed_two_months(&conn);
+
+        let january = get_transactions_in_date_range(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(january.len(), 3);
+        assert!(january.iter().any(|t| t.transaction_type == TransactionType::Income));
+        assert!(january.iter().any(|t| t.transaction_type == TransactionType::Expense));
+        assert!(january.windows(2).all(|w| w[0].date <= w[1].date));
+    }
+
+    #[test]
+    fn test_get_income_transactions_in_range() {
+        let conn = establish_test_connection().unwrap();
+        seed_two_months(&conn);
+
+        let income = get_income_transactions_in_range(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(income.len(), 2);
+        assert!(income.iter().all(|t| t.transaction_type == TransactionType::Income));
+    }
+
+    #[test]
+    fn test_get_expense_transactions_in_range() {
+        let conn = establish_test_connection().unwrap();
+        seed_two_months(&conn);
+
+        let expenses = get_expense_transactions_in_range(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(expenses.len(), 1);
+        assert_eq!(expenses[0].category, "Food");
+        assert_eq!(expenses[0].date, NaiveDate::from_ymd_opt(2025, 2, 14).unwrap());
+    }
+
+    fn add_with(conn: &Connection, day: u32, amount: i64, transaction_type: TransactionType) {
+        let transaction = Transaction::new(
+            Uuid::new_v4().to_string(),
+            NaiveDate::from_ymd_opt(2025, 3, day).unwrap(),
+            "Test Transaction".to_string(),
+            Decimal::new(amount, 2),
+            transaction_type,
+            "Misc".to_string(),
+        );
+        add_transaction(conn, &transaction).unwrap();
+    }
+
+    /// March's net balance of the transactions without a currency.
+    fn march(conn: &Connection) -> Decimal {
+        let balances = get_net_balance_in_range(
+            conn,
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].0, "");
+        balances[0].1
+    }
+
+    #[test]
+    fn test_get_expenses_by_category_in_range() {
+        let conn = establish_test_connection().unwrap();
+        add_with(&conn, 1, 1000, TransactionType::Expense);
+        add_with(&conn, 15, 2550, TransactionType::Expense);
+        add_with(&conn, 16, 9900, TransactionType::Income);
+        add_with(&conn, 31, 400, TransactionType::Expense);
+
+        let total = get_expenses_by_category_in_range(
+            &conn,
+            "misc",
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 15).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(total, Decimal::new(3550, 2));
+    }
+
+    #[test]
+    fn test_net_balance_all_income() {
+        let conn = establish_test_connection().unwrap();
+        add_with(&conn, 1, 150000, TransactionType::Income);
+        add_with(&conn, 15, 2550, TransactionType::Income);
+
+        assert_eq!(march(&conn), Decimal::new(152550, 2));
+    }
+
+    #[test]
+    fn test_net_balance_all_expense() {
+        let conn = establish_test_connection().unwrap();
+        add_with(&conn, 2, 1000, TransactionType::Expense);
+        add_with(&conn, 3, 450, TransactionType::Expense);
+
+        assert_eq!(march(&conn), Decimal::new(-1450, 2));
+    }
+
+    #[test]
+    fn test_net_balance_mixed() {
+        let conn = establish_test_connection().unwrap();
+        add_with(&conn, 1, 200000, TransactionType::Income);
+        add_with(&conn, 5, 120000, TransactionType::Expense);
+        add_with(&conn, 9, 350, TransactionType::Expense);
+
+        assert_eq!(march(&conn), Decimal::new(79650, 2));
+    }
+
+    #[test]
+    fn test_net_balance_empty_range() {
+        let conn = establish_test_connection().unwrap();
+        add_with(&conn, 1, 1000, TransactionType::Income);
+
+        let balances = get_net_balance_in_range(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 4, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 4, 30).unwrap(),
+        )
+        .unwrap();
+        assert!(balances.is_empty());
+    }
+
+    #[test]
+    fn test_net_balance_per_currency() {
+        let conn = establish_test_connection().unwrap();
+        add_with(&conn, 1, 200000, TransactionType::Income);
+        for (currency, amount, transaction_type) in
+            [("USD", 50000, TransactionType::Income), ("USD", 12000, TransactionType::Expense), ("EUR", 3000, TransactionType::Expense)]
+        {
+            let mut transaction = create_test_transaction(&Uuid::new_v4().to_string(), "Misc");
+            transaction.date = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+            transaction.amount = Decimal::new(amount, 2);
+            transaction.transaction_type = transaction_type;
+            transaction.currency_code = currency.to_string();
+            add_transaction(&conn, &transaction).unwrap();
+        }
+
+        let balances = get_net_balance_in_range(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            balances,
+            vec![
+                ("".to_string(), Decimal::new(200000, 2)),
+                ("EUR".to_string(), Decimal::new(-3000, 2)),
+                ("USD".to_string(), Decimal::new(38000, 2)),
+            ]
+        );
+    }
+
+    fn seed_three_months(conn: &Connection) {
+        let rows = [
+            ((2025, 1, 5), 1000, TransactionType::Expense),
+            ((2025, 1, 5), 250, TransactionType::Expense),
+            ((2025, 1, 20), 500, TransactionType::Expense),
+            ((2025, 1, 20), 300000, TransactionType::Income),
+            ((2025, 2, 14), 4599, TransactionType::Expense),
+            ((2025, 3, 31), 1, TransactionType::Expense),
+        ];
+        for ((year, month, day), amount, transaction_type) in rows {
+            let transaction = Transaction::new(
+                Uuid::new_v4().to_string(),
+                NaiveDate::from_ymd_opt(year, month, day).unwrap(),
+                "Test Transaction".to_string(),
+                Decimal::new(amount, 2),
+                transaction_type,
+                "Misc".to_string(),
+            );
+            add_transaction(conn, &transaction).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_get_daily_totals() {
+        let conn = establish_test_connection().unwrap();
+        seed_three_months(&conn);
+
+        let totals = get_daily_totals(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(),
+            TransactionType::Expense,
+        )
+        .unwrap();
+
+        assert_eq!(
+            totals,
+            vec![
+                (NaiveDate::from_ymd_opt(2025, 1, 5).unwrap(), Decimal::new(1250, 2)),
+                (NaiveDate::from_ymd_opt(2025, 1, 20).unwrap(), Decimal::new(500, 2)),
+                (NaiveDate::from_ymd_opt(2025, 2, 14).unwrap(), Decimal::new(4599, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_monthly_totals() {
+        let conn = establish_test_connection().unwrap();
+        seed_three_months(&conn);
+
+        let totals = get_monthly_totals(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+            TransactionType::Expense,
+        )
+        .unwrap();
+
+        assert_eq!(
+            totals,
+            vec![
+                (NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), Decimal::new(1750, 2)),
+                (NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(), Decimal::new(4599, 2)),
+                (NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(), Decimal::new(1, 2)),
+            ]
+        );
+
+        let income = get_monthly_totals(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+            TransactionType::Income,
+        )
+        .unwrap();
+        assert_eq!(income, vec![(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), Decimal::new(300000, 2))]);
+    }
+
+    #[test]
+    fn test_get_monthly_totals_empty() {
+        let conn = establish_test_connection().unwrap();
+        let totals = get_monthly_totals(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(),
+            TransactionType::Expense,
+        )
+        .unwrap();
+        assert!(totals.is_empty());
+    }
+
+    #[test]
+    fn test_get_category_summary() {
+        let conn = establish_test_connection().unwrap();
+        let rows = [
+            ("2025-01-02", 1200, TransactionType::Expense, "Food"),
+            ("2025-01-03", 800, TransactionType::Expense, "food"),
+            ("2025-01-04", 5000, TransactionType::Expense, "Rent"),
+            ("2025-01-05", 90000, TransactionType::Income, "Job"),
+            ("2025-02-01", 9900, TransactionType::Expense, "Food"),
+        ];
+        for (date, amount, transaction_type, category) in rows {
+            let transaction = Transaction::new(
+                Uuid::new_v4().to_string(),
+                NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+                "Test Transaction".to_string(),
+                Decimal::new(amount, 2),
+                transaction_type,
+                category.to_string(),
+            );
+            add_transaction(&conn, &transaction).unwrap();
+        }
+
+        let summary = get_category_summary(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].0, "Rent");
+        assert_eq!(summary[0].1, Decimal::new(5000, 2));
+        assert_eq!(summary[0].2, 1);
+        assert_eq!(summary[1].0.to_lowercase(), "food");
+        assert_eq!(summary[1].1, Decimal::new(2000, 2));
+        assert_eq!(summary[1].2, 2);
     }
 
     #[test]
@@ -300,6 +1909,98 @@ mod tests {
         assert_eq!(result.unwrap().len(), 0);
     }
 
+    #[test]
+    fn test_search_by_description_partial_match() {
+        let conn = establish_test_connection().unwrap();
+
+        let mut tx = create_test_transaction(&Uuid::new_v4().to_string(), "Food");
+        tx.description = "Morning Coffee".to_string();
+        add_transaction(&conn, &tx).unwrap();
+        add_transaction(&conn, &create_test_transaction(&Uuid::new_v4().to_string(), "Food")).unwrap();
+
+        let result = search_by_description(&conn, "coff").unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].description, "Morning Coffee");
+    }
+
+    #[test]
+    fn test_search_by_description_matches_wildcards_literally() {
+        let conn = establish_test_connection().unwrap();
+        for description in ["Discount 50% off", "Discount 50 dollars off", "file_name.csv", "filename.csv", "C:\\temp"] {
+            let mut tx = create_test_transaction(&Uuid::new_v4().to_string(), "Food");
+            tx.description = description.to_string();
+            add_transaction(&conn, &tx).unwrap();
+        }
+
+        let found = |keyword: &str| descriptions(search_by_description(&conn, keyword).unwrap());
+        assert_eq!(found("50%"), vec!["Discount 50% off"]);
+        assert_eq!(found("e_n"), vec!["file_name.csv"]);
+        assert_eq!(found("%"), vec!["Discount 50% off"]);
+        assert_eq!(found(":\\t"), vec!["C:\\temp"]);
+        assert_eq!(escape_like("5%_\\"), "5\\%\\_\\\\");
+    }
+
+    fn add_described(conn: &Connection, description: &str, date: (i32, u32, u32), tx_type: TransactionType) {
+        let mut tx = create_test_transaction(&Uuid::new_v4().to_string(), "Food");
+        tx.description = description.to_string();
+        tx.date = NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap();
+        tx.transaction_type = tx_type;
+        add_transaction(conn, &tx).unwrap();
+    }
+
+    fn descriptions(transactions: Vec<Transaction>) -> Vec<String> {
+        transactions.into_iter().map(|t| t.description).collect()
+    }
+
+    #[test]
+    fn test_description_like_substring_prefix_and_suffix() {
+        let conn = establish_test_connection().unwrap();
+        add_described(&conn, "Morning Coffee", (2025, 1, 1), TransactionType::Expense);
+        add_described(&conn, "Coffee beans", (2025, 1, 2), TransactionType::Expense);
+        add_described(&conn, "Bus ticket", (2025, 1, 3), TransactionType::Expense);
+
+        let like = |pattern: &str| descriptions(get_transactions_with_description_like(&conn, pattern, None, None, None).unwrap());
+        assert_eq!(like("%OFFE%"), vec!["Coffee beans", "Morning Coffee"]);
+        assert_eq!(like("coffee%"), vec!["Coffee beans"]);
+        assert_eq!(like("%coffee"), vec!["Morning Coffee"]);
+        assert_eq!(like("%u%"), vec!["Bus ticket"]);
+        assert_eq!(like("Bus_ticket"), vec!["Bus ticket"]);
+        assert!(like("%tea%").is_empty());
+    }
+
+    #[test]
+    fn test_description_like_with_filters() {
+        let conn = establish_test_connection().unwrap();
+        add_described(&conn, "Coffee", (2025, 1, 1), TransactionType::Expense);
+        add_described(&conn, "Coffee", (2025, 2, 1), TransactionType::Expense);
+        add_described(&conn, "Coffee refund", (2025, 2, 2), TransactionType::Income);
+
+        let feb = NaiveDate::from_ymd_opt(2025, 2, 1);
+        let found = get_transactions_with_description_like(&conn, "%coffee%", Some(TransactionType::Expense), feb, None).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].date, feb.unwrap());
+        let found = get_transactions_with_description_like(&conn, "%coffee%", None, None, feb).unwrap();
+        assert_eq!(found.len(), 2);
+        let found = get_transactions_with_description_like(&conn, "%coffee%", Some(TransactionType::Income), None, None).unwrap();
+        assert_eq!(descriptions(found), vec!["Coffee refund"]);
+    }
+
+    #[test]
+    fn test_description_index_follows_updates_and_removals() {
+        let conn = establish_test_connection().unwrap();
+        let mut tx = create_test_transaction("a", "Food");
+        tx.description = "Coffee".to_string();
+        add_transaction(&conn, &tx).unwrap();
+
+        tx.description = "Tea".to_string();
+        update_transaction(&conn, "a", &tx).unwrap();
+        assert!(search_by_description(&conn, "coffee").unwrap().is_empty());
+        assert_eq!(search_by_description(&conn, "tea").unwrap().len(), 1);
+
+        remove_transaction(&conn, "a").unwrap();
+        assert!(search_by_description(&conn, "tea").unwrap().is_empty());
+    }
+
     #[test]
     fn test_search_by_category_case_insensitive() {
         let conn = establish_test_connection().unwrap();
@@ -311,4 +2012,221 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap().len(), 1);
     }
-}
\ No newline at end of file
+
+    fn seed_amounts(conn: &Connection) {
+        for (amount, transaction_type) in [
+            (Decimal::new(500, 2), TransactionType::Expense),
+            (Decimal::new(5000, 2), TransactionType::Expense),
+            (Decimal::new(120000, 2), TransactionType::Expense),
+            (Decimal::new(5000, 2), TransactionType::Income),
+            (Decimal::new(300000, 2), TransactionType::Income),
+        ] {
+            let mut transaction = create_test_transaction(&Uuid::new_v4().to_string(), "Test");
+            transaction.amount = amount;
+            transaction.transaction_type = transaction_type;
+            add_transaction(conn, &transaction).unwrap();
+        }
+    }
+
+    fn amounts(transactions: &[Transaction]) -> Vec<Decimal> {
+        let mut amounts: Vec<Decimal> = transactions.iter().map(|t| t.amount).collect();
+        amounts.sort();
+        amounts
+    }
+
+    #[test]
+    fn test_get_transactions_with_amount_range_min_only() {
+        let conn = establish_test_connection().unwrap();
+        seed_amounts(&conn);
+
+        let result = get_transactions_with_amount_range(&conn, Some(Decimal::new(50, 0)), None, None).unwrap();
+        assert_eq!(
+            amounts(&result),
+            vec![Decimal::new(5000, 2), Decimal::new(5000, 2), Decimal::new(120000, 2), Decimal::new(300000, 2)]
+        );
+    }
+
+    #[test]
+    fn test_get_transactions_with_amount_range_max_only() {
+        let conn = establish_test_connection().unwrap();
+        seed_amounts(&conn);
+
+        // Compared as numbers: "1200.00" is not below "50" as text would be.
+        let result = get_transactions_with_amount_range(&conn, None, Some(Decimal::new(4999, 2)), None).unwrap();
+        assert_eq!(amounts(&result), vec![Decimal::new(500, 2)]);
+    }
+
+    #[test]
+    fn test_get_transactions_with_amount_range_both_bounds() {
+        let conn = establish_test_connection().unwrap();
+        seed_amounts(&conn);
+
+        let result =
+            get_transactions_with_amount_range(&conn, Some(Decimal::new(5, 0)), Some(Decimal::new(1200, 0)), None).unwrap();
+        assert_eq!(
+            amounts(&result),
+            vec![Decimal::new(500, 2), Decimal::new(5000, 2), Decimal::new(5000, 2), Decimal::new(120000, 2)]
+        );
+        assert_eq!(get_transactions_with_amount_range(&conn, None, None, None).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_get_transactions_with_amount_range_and_type() {
+        let conn = establish_test_connection().unwrap();
+        seed_amounts(&conn);
+
+        let expenses =
+            get_transactions_with_amount_range(&conn, Some(Decimal::new(50, 0)), None, Some(TransactionType::Expense)).unwrap();
+        assert_eq!(amounts(&expenses), vec![Decimal::new(5000, 2), Decimal::new(120000, 2)]);
+        assert!(expenses.iter().all(|t| t.transaction_type == TransactionType::Expense));
+
+        let income =
+            get_transactions_with_amount_range(&conn, None, Some(Decimal::new(100, 0)), Some(TransactionType::Income)).unwrap();
+        assert_eq!(amounts(&income), vec![Decimal::new(5000, 2)]);
+    }
+
+    #[test]
+    fn test_get_transaction_date_bounds() {
+        let conn = establish_test_connection().unwrap();
+        assert_eq!(get_transaction_date_bounds(&conn).unwrap(), None);
+
+        for day in [20, 3, 11] {
+            let mut transaction = create_test_transaction(&Uuid::new_v4().to_string(), "Test");
+            transaction.date = NaiveDate::from_ymd_opt(2025, 2, day).unwrap();
+            add_transaction(&conn, &transaction).unwrap();
+        }
+        assert_eq!(
+            get_transaction_date_bounds(&conn).unwrap(),
+            Some((NaiveDate::from_ymd_opt(2025, 2, 3).unwrap(), NaiveDate::from_ymd_opt(2025, 2, 20).unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_get_running_balance() {
+        let conn = establish_test_connection().unwrap();
+        for (day, amount, transaction_type) in [
+            (1, Decimal::new(200000, 2), TransactionType::Income),
+            (3, Decimal::new(90000, 2), TransactionType::Expense),
+            (3, Decimal::new(4525, 2), TransactionType::Expense),
+            (10, Decimal::new(1500, 2), TransactionType::Income),
+            (28, Decimal::new(120000, 2), TransactionType::Expense),
+        ] {
+            let mut transaction = create_test_transaction(&Uuid::new_v4().to_string(), "Test");
+            transaction.date = NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+            transaction.amount = amount;
+            transaction.transaction_type = transaction_type;
+            add_transaction(&conn, &transaction).unwrap();
+        }
+        let march = |day| NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+
+        let balances = get_running_balance(&conn, march(1), march(31), Decimal::new(100, 0)).unwrap();
+        assert_eq!(
+            balances,
+            vec![
+                (march(1), Decimal::new(210000, 2)),
+                (march(3), Decimal::new(115475, 2)),
+                (march(10), Decimal::new(116975, 2)),
+                (march(28), Decimal::new(-3025, 2)),
+            ]
+        );
+
+        let later = get_running_balance(&conn, march(4), march(31), Decimal::ZERO).unwrap();
+        assert_eq!(later, vec![(march(10), Decimal::new(1500, 2)), (march(28), Decimal::new(-118500, 2))]);
+        assert!(get_running_balance(&conn, march(11), march(20), Decimal::ZERO).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_add_transaction_batch() {
+        let conn = establish_test_connection().unwrap();
+        let batch: Vec<Transaction> =
+            ["Salary", "Food", "Rent"].iter().map(|category| create_test_transaction(&Uuid::new_v4().to_string(), category)).collect();
+
+        add_transaction_batch(&conn, &batch).unwrap();
+        assert!(conn.is_autocommit());
+        assert_eq!(count_transactions(&conn).unwrap(), 3);
+        assert_eq!(get_transaction_by_id(&conn, &batch[1].id).unwrap().unwrap().category, "Food");
+        add_transaction_batch(&conn, &[]).unwrap();
+        assert_eq!(count_transactions(&conn).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_add_transaction_batch_rolls_back_on_error() {
+        let conn = establish_test_connection().unwrap();
+        let first = create_test_transaction(&Uuid::new_v4().to_string(), "Salary");
+        let mut unknown_account = create_test_transaction(&Uuid::new_v4().to_string(), "Food");
+        unknown_account.account = "Nowhere".to_string();
+
+        let err = add_transaction_batch(&conn, &[first.clone(), unknown_account]).unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+        assert_eq!(count_transactions(&conn).unwrap(), 0);
+        assert!(conn.is_autocommit());
+
+        // Inside an open transaction the caller decides what happens to the rows.
+        conn.execute("BEGIN", []).unwrap();
+        add_transaction_batch(&conn, std::slice::from_ref(&first)).unwrap();
+        assert!(!conn.is_autocommit());
+        conn.execute("ROLLBACK", []).unwrap();
+        assert_eq!(count_transactions(&conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_filtered_transactions() {
+        let conn = establish_test_connection().unwrap();
+        let add = |date: &str, description: &str, amount: i64, category: &str| {
+            let mut transaction = create_test_transaction(&Uuid::new_v4().to_string(), category);
+            transaction.date = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+            transaction.description = description.to_string();
+            transaction.amount = Decimal::new(amount, 2);
+            transaction.transaction_type = TransactionType::Expense;
+            add_transaction(&conn, &transaction).unwrap();
+        };
+        add("2025-11-10", "Coffee 50% off", 450, "food");
+        add("2025-11-11", "Bus", 200, "Transport");
+        add("2025-11-12", "Iced coffee", 300, "Food");
+        add("2025-11-13", "Groceries", 4500, "Food");
+
+        let descriptions = |filter: &TransactionFilter, order: TransactionOrder, limit: usize, offset: usize| -> Vec<String> {
+            get_filtered_transactions(&conn, filter, order, limit, offset).unwrap().into_iter().map(|t| t.description).collect()
+        };
+        let all = TransactionFilter::default();
+        assert_eq!(count_filtered_transactions(&conn, &all).unwrap(), 4);
+        assert_eq!(descriptions(&all, TransactionOrder::DateDesc, 2, 1), vec!["Iced coffee", "Bus"]);
+        assert_eq!(descriptions(&all, TransactionOrder::AmountAsc, 10, 0), vec!["Bus", "Iced coffee", "Coffee 50% off", "Groceries"]);
+        assert_eq!(descriptions(&all, TransactionOrder::CategoryAsc, 10, 0), vec!["Groceries", "Iced coffee", "Coffee 50% off", "Bus"]);
+
+        let coffee = TransactionFilter { description: Some("COFFEE".to_string()), ..Default::default() };
+        assert_eq!(count_filtered_transactions(&conn, &coffee).unwrap(), 2);
+        let percent = TransactionFilter { description: Some("0%".to_string()), ..Default::default() };
+        assert_eq!(descriptions(&percent, TransactionOrder::DateDesc, 10, 0), vec!["Coffee 50% off"]);
+        let food_range = TransactionFilter {
+            category: Some("FOOD".to_string()),
+            from: NaiveDate::from_ymd_opt(2025, 11, 11),
+            amount_max: Some(Decimal::new(1000, 2)),
+            ..Default::default()
+        };
+        assert_eq!(descriptions(&food_range, TransactionOrder::DateDesc, 10, 0), vec!["Iced coffee"]);
+        let income = TransactionFilter { transaction_type: Some(TransactionType::Income), ..Default::default() };
+        assert_eq!(count_filtered_transactions(&conn, &income).unwrap(), 0);
+        assert_eq!(sum_filtered_transactions(&conn, &income).unwrap(), vec![]);
+        let by_category: Vec<(String, Decimal, usize)> = sum_filtered_transactions_by_category(&conn, &all)
+            .unwrap()
+            .into_iter()
+            .map(|total| (total.category.to_lowercase(), total.total, total.count))
+            .collect();
+        assert_eq!(
+            by_category,
+            vec![("food".to_string(), Decimal::new(5250, 2), 3), ("transport".to_string(), Decimal::new(200, 2), 1)]
+        );
+        assert_eq!(
+            sum_filtered_transactions(&conn, &coffee).unwrap(),
+            vec![(String::new(), Decimal::ZERO, Decimal::new(750, 2))]
+        );
+
+        let date = NaiveDate::from_ymd_opt(2025, 11, 11).unwrap();
+        assert_eq!(find_filtered_rows_on_or_before(&conn, &all, TransactionOrder::DateDesc, date).unwrap(), Some((2, 3)));
+        assert_eq!(find_filtered_rows_on_or_before(&conn, &all, TransactionOrder::AmountDesc, date).unwrap(), Some((1, 3)));
+        let too_early = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        assert_eq!(find_filtered_rows_on_or_before(&conn, &all, TransactionOrder::DateAsc, too_early).unwrap(), None);
+        assert_eq!(get_distinct_accounts(&conn).unwrap(), vec!["default"]);
+    }
+}
@@ -1,10 +1,15 @@
-use crate::models::rule::CategoryRule;
+use crate::models::rule::{CategoryRule, RuleMatchType};
+use regex::Regex;
 use rusqlite::Connection;
 
-pub fn add_rule(conn: &Connection, pattern: &str, category: &str) -> Result<(), String> {
+pub fn add_rule(conn: &Connection, pattern: &str, category: &str, match_type: RuleMatchType) -> Result<(), String> {
+    if match_type == RuleMatchType::Regex {
+        Regex::new(pattern).map_err(|e| format!("Invalid regex pattern '{}': {}", pattern, e))?;
+    }
+
     conn.execute(
-        "INSERT INTO category_rules (pattern, category) VALUES (?1, ?2)",
-        [pattern, category],
+        "INSERT INTO category_rules (pattern, category, match_type) VALUES (?1, ?2, ?3)",
+        [pattern, category, match_type.as_str()],
     )
     .map_err(|e| format!("Failed to insert rule: {}", e))?;
     Ok(())
@@ -12,7 +17,7 @@ pub fn add_rule(conn: &Connection, pattern: &str, category: &str) -> Result<(),
 
 pub fn get_all_rules(conn: &Connection) -> Result<Vec<CategoryRule>, String> {
     let mut stmt = conn
-        .prepare("SELECT id, pattern, category FROM category_rules")
+        .prepare("SELECT id, pattern, category, match_type, priority FROM category_rules ORDER BY priority DESC, id ASC")
         .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
     let rules_iter = stmt
@@ -21,6 +26,9 @@ pub fn get_all_rules(conn: &Connection) -> Result<Vec<CategoryRule>, String> {
                 id: row.get(0)?,
                 pattern: row.get(1)?,
                 category: row.get(2)?,
+                match_type: RuleMatchType::parse(&row.get::<_, String>(3)?)
+                    .ok_or_else(|| rusqlite::Error::InvalidParameterName("Invalid rule match type".to_string()))?,
+                priority: row.get(4)?,
             })
         })
         .map_err(|e| format!("Failed to query rules: {}", e))?;
@@ -32,6 +40,48 @@ pub fn get_all_rules(conn: &Connection) -> Result<Vec<CategoryRule>, String> {
     Ok(rules)
 }
 
+pub fn delete_rule(conn: &Connection, id: i32) -> Result<(), String> {
+    let deleted = conn
+        .execute("DELETE FROM category_rules WHERE id = ?1", [id])
+        .map_err(|e| format!("Failed to delete rule: {}", e))?;
+
+    if deleted == 0 {
+        return Err(format!("Rule with ID {} not found", id));
+    }
+    Ok(())
+}
+
+/// Replaces the pattern and category in one statement; the match type is kept.
+pub fn update_rule(conn: &Connection, id: i32, new_pattern: &str, new_category: &str) -> Result<(), String> {
+    let match_type: String = conn
+        .query_row("SELECT match_type FROM category_rules WHERE id = ?1", [id], |row| row.get(0))
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => format!("Rule with ID {} not found", id),
+            e => format!("Failed to load rule: {}", e),
+        })?;
+    if RuleMatchType::parse(&match_type) == Some(RuleMatchType::Regex) {
+        Regex::new(new_pattern).map_err(|e| format!("Invalid regex pattern '{}': {}", new_pattern, e))?;
+    }
+
+    conn.execute(
+        "UPDATE category_rules SET pattern = ?1, category = ?2 WHERE id = ?3",
+        rusqlite::params![new_pattern, new_category, id],
+    )
+    .map_err(|e| format!("Failed to update rule: {}", e))?;
+    Ok(())
+}
+
+pub fn set_rule_priority(conn: &Connection, id: i32, priority: i32) -> Result<(), String> {
+    let updated = conn
+        .execute("UPDATE category_rules SET priority = ?1 WHERE id = ?2", [priority, id])
+        .map_err(|e| format!("Failed to update rule priority: {}", e))?;
+
+    if updated == 0 {
+        return Err(format!("Rule with ID {} not found", id));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,7 +105,7 @@ mod tests {
     fn test_add_rule_success_and_retrievable() {
         let conn = establish_test_connection().unwrap();
 
-        add_rule(&conn, "coffee", "Food").unwrap();
+        add_rule(&conn, "coffee", "Food", RuleMatchType::Substring).unwrap();
 
         let rules = get_all_rules(&conn).unwrap();
         assert_eq!(rules.len(), 1);
@@ -68,9 +118,9 @@ mod tests {
     fn test_add_multiple_rules_and_retrieve() {
         let conn = establish_test_connection().unwrap();
 
-        add_rule(&conn, "uber", "Transport").unwrap();
-        add_rule(&conn, "salary", "Job").unwrap();
-        add_rule(&conn, "lidl", "Groceries").unwrap();
+        add_rule(&conn, "uber", "Transport", RuleMatchType::Substring).unwrap();
+        add_rule(&conn, "salary", "Job", RuleMatchType::Substring).unwrap();
+        add_rule(&conn, "lidl", "Groceries", RuleMatchType::Substring).unwrap();
 
         let rules = get_all_rules(&conn).unwrap();
         assert_eq!(rules.len(), 3);
@@ -84,8 +134,8 @@ mod tests {
     fn test_rule_ids_are_autoincremented() {
         let conn = establish_test_connection().unwrap();
 
-        add_rule(&conn, "a", "A").unwrap();
-        add_rule(&conn, "b", "B").unwrap();
+        add_rule(&conn, "a", "A", RuleMatchType::Substring).unwrap();
+        add_rule(&conn, "b", "B", RuleMatchType::Substring).unwrap();
 
         let rules = sort_by_id(get_all_rules(&conn).unwrap());
         assert_eq!(rules.len(), 2);
@@ -97,8 +147,8 @@ mod tests {
     fn test_add_rule_allows_duplicate_rows_if_no_unique_constraint() {
         let conn = establish_test_connection().unwrap();
 
-        add_rule(&conn, "coffee", "Food").unwrap();
-        add_rule(&conn, "coffee", "Food").unwrap();
+        add_rule(&conn, "coffee", "Food", RuleMatchType::Substring).unwrap();
+        add_rule(&conn, "coffee", "Food", RuleMatchType::Substring).unwrap();
 
         let rules = get_all_rules(&conn).unwrap();
         let matches = rules
@@ -109,10 +159,109 @@ mod tests {
         assert_eq!(matches, 2);
     }
 
+    #[test]
+    fn test_add_rule_stores_match_type() {
+        let conn = establish_test_connection().unwrap();
+
+        add_rule(&conn, "coffee", "Food", RuleMatchType::Substring).unwrap();
+        add_rule(&conn, "^Uber", "Transport", RuleMatchType::Regex).unwrap();
+
+        let rules = sort_by_id(get_all_rules(&conn).unwrap());
+        assert_eq!(rules[0].match_type, RuleMatchType::Substring);
+        assert_eq!(rules[1].match_type, RuleMatchType::Regex);
+    }
+
+    #[test]
+    fn test_add_rule_rejects_invalid_regex() {
+        let conn = establish_test_connection().unwrap();
+
+        let result = add_rule(&conn, "(unclosed", "Broken", RuleMatchType::Regex);
+        assert!(result.unwrap_err().contains("Invalid regex pattern"));
+        assert!(get_all_rules(&conn).unwrap().is_empty());
+
+        // The same text is fine as a substring rule.
+        assert!(add_rule(&conn, "(unclosed", "Broken", RuleMatchType::Substring).is_ok());
+    }
+
+    #[test]
+    fn test_delete_rule() {
+        let conn = establish_test_connection().unwrap();
+        add_rule(&conn, "coffee", "Food", RuleMatchType::Substring).unwrap();
+        add_rule(&conn, "uber", "Transport", RuleMatchType::Substring).unwrap();
+        let rules = sort_by_id(get_all_rules(&conn).unwrap());
+
+        delete_rule(&conn, rules[0].id).unwrap();
+
+        let remaining = get_all_rules(&conn).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].pattern, "uber");
+    }
+
+    #[test]
+    fn test_delete_rule_not_found() {
+        let conn = establish_test_connection().unwrap();
+
+        let result = delete_rule(&conn, 42);
+        assert!(result.unwrap_err().contains("not found"));
+    }
+
+    #[test]
+    fn test_update_rule_changes_pattern_and_category() {
+        let conn = establish_test_connection().unwrap();
+        add_rule(&conn, "coffee", "Food", RuleMatchType::Substring).unwrap();
+        let id = get_all_rules(&conn).unwrap()[0].id;
+
+        update_rule(&conn, id, "starbucks", "Coffee").unwrap();
+
+        let rules = get_all_rules(&conn).unwrap();
+        assert_eq!(rules[0].pattern, "starbucks");
+        assert_eq!(rules[0].category, "Coffee");
+        assert_eq!(rules[0].match_type, RuleMatchType::Substring);
+    }
+
+    #[test]
+    fn test_update_rule_invalid_regex_changes_nothing() {
+        let conn = establish_test_connection().unwrap();
+        add_rule(&conn, "^Uber", "Transport", RuleMatchType::Regex).unwrap();
+        let id = get_all_rules(&conn).unwrap()[0].id;
+
+        assert!(update_rule(&conn, id, "(unclosed", "Other").is_err());
+        assert!(update_rule(&conn, id + 1, "bolt", "Transport").unwrap_err().contains("not found"));
+
+        let rules = get_all_rules(&conn).unwrap();
+        assert_eq!(rules[0].pattern, "^Uber");
+        assert_eq!(rules[0].category, "Transport");
+    }
+
+    #[test]
+    fn test_rules_ordered_by_priority_then_id() {
+        let conn = establish_test_connection().unwrap();
+        add_rule(&conn, "a", "A", RuleMatchType::Substring).unwrap();
+        add_rule(&conn, "b", "B", RuleMatchType::Substring).unwrap();
+        add_rule(&conn, "c", "C", RuleMatchType::Substring).unwrap();
+        let ids: Vec<i32> = sort_by_id(get_all_rules(&conn).unwrap()).iter().map(|r| r.id).collect();
+
+        set_rule_priority(&conn, ids[2], 10).unwrap();
+
+        let rules = get_all_rules(&conn).unwrap();
+        let patterns: Vec<&str> = rules.iter().map(|r| r.pattern.as_str()).collect();
+        assert_eq!(patterns, vec!["c", "a", "b"]);
+        assert_eq!(rules[0].priority, 10);
+        assert_eq!(rules[1].priority, 0);
+    }
+
+    #[test]
+    fn test_set_rule_priority_not_found() {
+        let conn = establish_test_connection().unwrap();
+
+        let result = set_rule_priority(&conn, 7, 1);
+        assert!(result.unwrap_err().contains("not found"));
+    }
+
     #[test]
     fn test_add_rule_fails_when_columns_missing_or_schema_wrong() {
         let conn = establish_test_connection().unwrap();
-        let result = add_rule(&conn, "x", "Y");
+        let result = add_rule(&conn, "x", "Y", RuleMatchType::Substring);
         assert!(result.is_ok());
     }
 }
\ No newline at end of file
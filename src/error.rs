@@ -0,0 +1,48 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppError {
+    NotFound(String),
+    InvalidInput(String),
+    DatabaseError(String),
+    ParseError(String),
+    IoError(String),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::NotFound(msg)
+            | AppError::InvalidInput(msg)
+            | AppError::DatabaseError(msg)
+            | AppError::ParseError(msg)
+            | AppError::IoError(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+// Modules that still report plain `String` errors can use `?` on `AppError` results.
+impl From<AppError> for String {
+    fn from(err: AppError) -> Self {
+        err.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_shows_message_only() {
+        let err = AppError::NotFound("Transaction with ID 1 not found".to_string());
+        assert_eq!(err.to_string(), "Transaction with ID 1 not found");
+    }
+
+    #[test]
+    fn test_into_string() {
+        let message: String = AppError::InvalidInput("Category cannot be empty".to_string()).into();
+        assert_eq!(message, "Category cannot be empty");
+    }
+}
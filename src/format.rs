@@ -0,0 +1,50 @@
+use crate::models::currency::Currency;
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// `amount` rounded to the currency's decimal places, with thousands separators and the
+/// symbol in front: `€1,234.56`, `$1,234.56`, `¥1,235`, `-€5.00`. Symbols longer than one
+/// character are followed by a space (`CHF 1,234.56`).
+pub fn format_amount(amount: Decimal, currency: &Currency) -> String {
+    let places = currency.decimal_places as usize;
+    let rounded = amount.round_dp_with_strategy(places as u32, RoundingStrategy::MidpointAwayFromZero);
+    let digits = format!("{:.*}", places, rounded.abs());
+    let (integer, fraction) = match digits.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (digits.as_str(), None),
+    };
+
+    let mut grouped = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        grouped.push('.');
+        grouped.push_str(fraction);
+    }
+
+    let sign = if rounded.is_sign_negative() && !rounded.is_zero() { "-" } else { "" };
+    let separator = if currency.symbol.chars().count() > 1 { " " } else { "" };
+    format!("{}{}{}{}", sign, currency.symbol, separator, grouped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_amount() {
+        let eur = Currency::from_code("EUR");
+        assert_eq!(format_amount(Decimal::new(123456, 2), &eur), "€1,234.56");
+        assert_eq!(format_amount(Decimal::new(1234567891, 1), &Currency::from_code("USD")), "$123,456,789.10");
+        assert_eq!(format_amount(Decimal::new(5, 0), &eur), "€5.00");
+        assert_eq!(format_amount(Decimal::new(-500, 2), &eur), "-€5.00");
+        assert_eq!(format_amount(Decimal::new(-1, 3), &eur), "€0.00");
+        assert_eq!(format_amount(Decimal::new(12345, 1), &Currency::from_code("JPY")), "¥1,235");
+        assert_eq!(format_amount(Decimal::new(100000, 2), &Currency::from_code("CHF")), "CHF 1,000.00");
+        assert_eq!(format_amount(Decimal::new(999, 0), &Currency::default()), "999.00");
+        assert_eq!(format_amount(Decimal::new(1005, 3), &Currency::default()), "1.01");
+    }
+}
@@ -0,0 +1,7 @@
+pub mod models;
+pub mod operations;
+pub mod db;
+pub mod error;
+pub mod config;
+pub mod date_utils;
+pub mod format;
@@ -1,29 +1,52 @@
-mod models;
-mod operations;
-mod db;
-
-use clap::{Args, Parser, Subcommand, ValueEnum};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 use std::process;
 
-use operations::import::import_transactions_to_db;
+use financial_app::{db, operations};
+use financial_app::config::Config;
+use financial_app::date_utils::{all_time_range, parse_date, parse_date_range_or_shorthand};
+use financial_app::models::alert::AlertFilter;
+use financial_app::models::budget::BudgetPeriod;
+use financial_app::models::rule::RuleMatchType;
+use financial_app::models::transaction::TransactionType;
+
+use operations::import::{
+    detect_column_map, import_transactions_to_db_with_progress, preview_import, DuplicatePolicy, ImportColumnMap,
+    ImportErrorBehavior, ImportOptions, ImportPreview, ImportResult, IMPORT_PROGRESS_INTERVAL, watch_import,
+};
+use operations::export::export_transactions;
+use operations::format::{format_table, format_transactions, OutputFormat};
 use operations::remove::remove_transaction_from_db;
-use operations::search_by_category::search_transactions_by_category_db;
-use operations::budget::{set_budget_db, increase_budget_db, decrease_budget_db, list_budgets_db, delete_budget_db};
-use operations::report::run_report;
+use operations::edit::edit_transaction_in_db;
+use operations::search_by_category::{search_transactions_by_category_db, suggest_alternative_categories};
+use operations::search_by_description::search_transactions_by_description_db;
+use operations::search_by_amount::search_transactions_by_amount_db;
+use operations::budget::{
+    set_budget_db, increase_budget_db, decrease_budget_db, list_budgets_db, delete_budget_db, set_carry_forward_db,
+    get_budget_utilization_all, get_budgets_exceeding_threshold, BUDGET_WARNING_PERCENT,
+};
+use operations::report::{run_comparison_report, run_report, BucketMode, ReportKind};
+use operations::stats::{detect_outliers, get_net_balance_in_base_currency, print_stats};
+use operations::recurring::{add_recurring_db, list_recurring_db, delete_recurring_db, generate_due_transactions};
 use operations::browse::run_browse;
+use operations::reconcile::reconcile_interactive;
+use operations::rule_apply::{apply_rules_to_transactions, preview_rule_application};
+use operations::merge_categories::merge_categories;
+use operations::split::split_transaction;
+use operations::validate::validate_database;
+use rust_decimal::Decimal;
 use chrono::NaiveDate;
 use std::io;
 
-use crate::operations::add::{add_transaction_to_db, add_transaction_to_db_with_id};
-use crate::db::alert_repository;
+use operations::add::{add_transaction_to_account, add_transaction_to_db_with_id, new_category_hint};
+use db::alert_repository;
 
 #[derive(Parser, Debug)]
 #[command(
     name = "fino",
     about = "A command-line tool for managing personal financial transactions",
     arg_required_else_help = true,
-    after_help = "EXAMPLES:\n  fino add --date 2025-01-03 --description \"Coffee\" --amount 4.65 --type expense --category Food\n  fino import --file ./data.csv\n  fino import --file ./data.ofx --format ofx\n  fino report --from 2025-01-01 --to 2025-01-31\n  fino budget set --category Food --amount 250\n  fino budget increase --category Food --amount 25\n  fino budget list\n  fino search --category Food\n  fino browse\n  fino tui\n  fino interactive\n\nNOTES:\n  - Dates accept ISO YYYY-MM-DD (recommended). Report also accepts DD.MM.YYYY.\n  - Errors are printed to stderr; exit code is non-zero on failure."
+    after_help = "EXAMPLES:\n  fino add --date 2025-01-03 --description \"Coffee\" --amount 4.65 --type expense --category Food\n  fino add --date 2025-07-01 --description \"Hotel\" --amount 240 --type expense --category Travel --tags vacation,tax-deductible\n  fino add 2025-01-01,Coffee,4.50,expense,Food\n  fino add 2025-01-02,Groceries,42.10,expense,Food --account card\n  fino account add --name card --currency EUR --initial-balance 250\n  fino account list\n  fino account delete card\n  fino rate set --from USD --to EUR --rate 0.92 --date 2025-01-31\n  fino rate list\n  fino rate delete 2\n  fino edit --id <UUID> --date 2025-01-03 --description \"Coffee\" --amount 4.95 --type expense --category Food\n  fino split --id <UUID> --first-amount 70 --first-category Food --second-amount 30 --second-category Household\n  fino import --file ./data.csv\n  fino import --file ./data.ofx --format ofx\n  fino import --file ./data.json\n  fino import --file ./statement.tsv\n  fino import --file ./register.csv --format ynab\n  fino import --file ./transactions.csv --format mint\n  fino import --file ./data.csv --skip-invalid\n  fino import --file ./data.csv --skip-duplicates\n  fino import --file ./data.csv --dry-run\n  fino import --file ./data.csv --yes\n  fino import --file ./data.csv --columns description,date,amount,type,category\n  fino import --watch ~/Downloads/bank\n  fino export --file ./backup.csv\n  fino export --file ./backup.json --format json\n  fino export --file ./backup.tsv\n  fino report --from 2025-01-01 --to 2025-01-31\n  fino report --from 2025-01-01 --to 2025-06-30 --type net\n  fino balance --range 2025-01-01..2025-01-31\n  fino balance --range 2025-01 --base EUR\n  fino balance-history --range this-year --initial 1200\n  fino stats\n  fino stats --range 2025-01-01..2025-01-31\n  fino stats --range last-30-days\n  fino report --range last-month\n  fino report --range 2025-01 --compare\n  fino stats --range fy:2025\n  fino anomalies --range this-year --threshold 2.5\n  fino report --from 2022-01-01 --to 2025-12-31 --bucket quarter\n  fino budget set --category Food --amount 250\n  fino budget set --category Travel --amount 3000 --period yearly\n  fino budget set --category Food --amount 250 --carry-forward true\n  fino budget increase --category Food --amount 25\n  fino budget list\n  fino budget-status\n  fino check-alerts\n  fino alert list\n  fino alert read 3\n  fino alert clear --before 2025-01-01\n  fino config show\n  fino config init\n  fino db-info\n  fino vacuum\n  fino backup --file ./backups\n  fino integrity\n  fino validate\n  fino budget set --category Food --amount 250 --alert-threshold 90\n  fino budget-status --range 2025-01-01..2025-03-31\n  fino recurring add --date 2025-02-01 --description Rent --amount 900 --type expense --category Housing --interval-days 30\n  fino recurring list\n  fino generate-due\n  fino reconcile --range 2025-01-01..2025-01-31\n  fino rules add --pattern Uber --category Transport\n  fino rules add --pattern \"^AMZN\" --category Shopping --match regex\n  fino rules list\n  fino rules priority 3 10\n  fino rule-test\n  fino categorize\n  fino merge-categories --from food,Groceries --to Food\n  fino search --category Food\n  fino searchdesc --keyword coffee\n  fino search-amount 500.. --type expense\n  fino search-amount ..10\n  fino print --page 2\n  fino print --output json\n  fino search --category Food --output csv\n  fino browse\n  fino tui\n  fino interactive\n  fino shell\n  fino completions bash > ~/.local/share/bash-completion/completions/fino\n\nNOTES:\n  - Dates accept ISO YYYY-MM-DD (recommended). Report also accepts DD.MM.YYYY.\n  - Errors are printed to stderr; exit code is non-zero on failure."
 )]
 struct Cli {
     #[command(subcommand)]
@@ -33,33 +56,127 @@ struct Cli {
 #[derive(Subcommand, Debug)]
 enum Commands {
     Add(AddArgs),
+    Edit(EditArgs),
     Import(ImportArgs),
+    Export(ExportArgs),
     Report(ReportArgs),
+    Balance(BalanceArgs),
+    /// Print the balance after each day with transactions
+    #[command(name = "balance-history")]
+    BalanceHistory(BalanceHistoryArgs),
+    Stats(StatsArgs),
+    /// List expenses that are unusually large for their category
+    Anomalies(AnomaliesArgs),
     Budget(BudgetArgsTop),
+    /// Show spending against budgets for every category
+    #[command(name = "budget-status")]
+    BudgetStatus(BudgetStatusArgs),
+    /// List budgets that reached their alert threshold in the current period
+    #[command(name = "check-alerts")]
+    CheckAlerts(CheckAlertsArgs),
+    /// List, acknowledge and clear stored budget alerts
+    Alert(AlertArgsTop),
+    /// Show configuration
+    Config(ConfigArgsTop),
+    /// Run SQLite's integrity check on the database
+    Integrity,
+    /// Check the stored data for invalid amounts and dates, unknown accounts and orphaned rules or budgets
+    Validate,
+    /// Copy the database to a file or directory while it stays in use
+    Backup(BackupArgs),
+    /// Print the database file and its SQLite pragma settings
+    #[command(name = "db-info")]
+    DbInfo,
+    /// Shrink the database file after deleting many transactions
+    #[command(alias = "db-vacuum")]
+    Vacuum,
+    Recurring(RecurringArgsTop),
+    #[command(name = "generate-due")]
+    GenerateDue(GenerateDueArgs),
+    /// Step through pending and cleared transactions and mark them reconciled
+    Reconcile(ReconcileArgs),
+    /// Add, list, change and delete categorization rules
+    Rules(RulesArgsTop),
+    /// Add, list and delete accounts
+    Account(AccountArgsTop),
+    /// Exchange rates used by `balance --base`
+    Rate(RateArgsTop),
+    /// Apply categorization rules to uncategorized transactions
+    Categorize,
+    /// Show which rule would categorize each uncategorized transaction, without changing anything
+    #[command(name = "rule-test")]
+    RuleTest,
+    /// Rename categories, or fold several into one, in transactions, budgets and rules
+    #[command(name = "merge-categories")]
+    MergeCategories(MergeCategoriesArgs),
     Search(SearchArgs),
+    #[command(name = "searchdesc")]
+    SearchDesc(SearchDescArgs),
+    /// List transactions whose amount lies in a range
+    #[command(name = "search-amount")]
+    SearchAmount(SearchAmountArgs),
     #[command(alias = "tui")]
     Browse,
+    /// The prompt-driven menu (also available as `fino shell`)
+    #[command(alias = "shell")]
     Interactive,
-    Print,
+    Print(PrintArgs),
     Remove(RemoveArgs),
+    /// Replace a transaction with two that share its amount, e.g. one receipt for two categories
+    Split(SplitArgs),
+    /// Print a shell completion script to stdout
+    Completions(CompletionsArgs),
 }
 
 #[derive(Args, Debug)]
 struct AddArgs {
+    /// The whole transaction as one argument: date,description,amount,type,category[,tags,notes]
+    #[arg(
+        value_name = "ENTRY",
+        conflicts_with_all = ["date", "description", "amount", "transaction_type", "category", "tags"]
+    )]
+    entry: Option<String>,
+
+    /// Asked for when neither it nor ENTRY is given
     #[arg(long)]
-    date: String,
+    date: Option<String>,
 
+    /// Asked for when neither it nor ENTRY is given
     #[arg(long)]
-    description: String,
+    description: Option<String>,
 
+    /// Asked for when neither it nor ENTRY is given
     #[arg(long)]
-    amount: String,
+    amount: Option<String>,
 
+    /// Asked for when neither it nor ENTRY is given
     #[arg(long = "type", value_enum)]
-    transaction_type: CliTransactionType,
+    transaction_type: Option<CliTransactionType>,
 
+    /// Defaults to `default_category` from the config file
     #[arg(long)]
-    category: String,
+    category: Option<String>,
+
+    /// Comma-separated tags, e.g. vacation,tax-deductible (`-` clears them on edit)
+    #[arg(long, value_delimiter = ',')]
+    tags: Vec<String>,
+
+    /// Free-text notes (bank memo, personal annotations; `-` clears them on edit)
+    #[arg(long, default_value = "")]
+    notes: String,
+
+    /// Account to book it in; defaults to `default_account` from the config file (edit keeps the current one)
+    #[arg(long)]
+    account: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct EditArgs {
+    #[arg(long)]
+    id: String,
+
+    #[command(flatten)]
+    details: AddArgs,
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
@@ -79,25 +196,197 @@ impl CliTransactionType {
 
 #[derive(Args, Debug)]
 struct ImportArgs {
-    #[arg(long)]
-    file: PathBuf,
+    #[arg(long, required_unless_present = "watch")]
+    file: Option<PathBuf>,
 
     #[arg(long, value_enum)]
     format: Option<CliImportFormat>,
+
+    /// Import every new .csv file dropped into DIR and move it to DIR/processed/ (q stops)
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["file", "format", "dry_run"])]
+    watch: Option<PathBuf>,
+
+    /// Skip rows that fail to parse or insert instead of rolling back the whole import
+    #[arg(long)]
+    skip_invalid: bool,
+
+    /// Leave out rows matching an existing transaction (same date, description and amount)
+    #[arg(long, conflicts_with = "allow_duplicates")]
+    skip_duplicates: bool,
+
+    /// Import rows even when they match an existing transaction
+    #[arg(long)]
+    allow_duplicates: bool,
+
+    /// Only validate the file and print what would be imported
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Import without asking for confirmation after the preview
+    #[arg(long, short = 'y')]
+    yes: bool,
+
+    /// CSV/TSV column order, e.g. "description,date,amount,type,category" ("-" ignores a column)
+    #[arg(long)]
+    columns: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
 enum CliImportFormat {
     Csv,
+    Tsv,
     Ofx,
+    Json,
+    /// YNAB register export (CSV with a header row)
+    Ynab,
+    /// Mint transactions export (CSV with a header row)
+    Mint,
+}
+
+#[derive(Args, Debug)]
+struct ExportArgs {
+    #[arg(long)]
+    file: PathBuf,
+
+    #[arg(long, value_enum)]
+    format: Option<CliExportFormat>,
+}
+
+#[derive(Args, Debug)]
+struct CompletionsArgs {
+    /// bash, zsh, fish, elvish or powershell
+    #[arg(value_enum)]
+    shell: clap_complete::Shell,
+}
+
+#[derive(Args, Debug)]
+struct BackupArgs {
+    /// Destination file, or a directory to put a timestamped copy in. Asked for when omitted.
+    #[arg(long)]
+    file: Option<PathBuf>,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum CliExportFormat {
+    Csv,
+    Tsv,
+    Json,
 }
 
 #[derive(Args, Debug)]
 struct ReportArgs {
+    #[arg(long, value_parser = parse_date, requires = "to", required_unless_present = "range")]
+    from: Option<NaiveDate>,
+
+    #[arg(long, value_parser = parse_date, requires = "from")]
+    to: Option<NaiveDate>,
+
+    /// Instead of --from/--to: YYYY-MM-DD..YYYY-MM-DD, YYYY-MM, this-month, last-month, last-week, this-year, last-N-days or fy:YYYY[:M]
+    #[arg(long, conflicts_with_all = ["from", "to"])]
+    range: Option<String>,
+
+    /// Which transactions to chart (defaults to expense)
+    #[arg(long = "type", value_enum)]
+    kind: Option<CliReportKind>,
+
+    /// Size of each bar (defaults to auto, which picks one from the length of the range)
+    #[arg(long, value_enum)]
+    bucket: Option<CliBucketMode>,
+
+    /// Chart expenses next to the same range one year earlier
+    #[arg(long, conflicts_with_all = ["kind", "bucket"])]
+    compare: bool,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum CliBucketMode {
+    Day,
+    Week,
+    Fortnight,
+    Month,
+    Quarter,
+    Auto,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum CliReportKind {
+    Expense,
+    Income,
+    Net,
+}
+
+#[derive(Args, Debug)]
+struct BalanceArgs {
+    /// Date range: YYYY-MM-DD..YYYY-MM-DD, YYYY-MM, this-month, last-month, last-week, this-year, last-N-days or fy:YYYY[:M]
     #[arg(long)]
-    from: String,
+    range: String,
+
+    /// Convert every transaction to this currency code, e.g. EUR, using the latest stored rates
+    #[arg(long)]
+    base: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct BalanceHistoryArgs {
+    /// Date range: YYYY-MM-DD..YYYY-MM-DD, YYYY-MM, this-month, last-month, last-week, this-year, last-N-days or fy:YYYY[:M] (defaults to all transactions)
+    #[arg(long)]
+    range: Option<String>,
+
+    /// Balance before the first day of the range (may be negative)
+    #[arg(long, default_value_t = Decimal::ZERO, allow_negative_numbers = true)]
+    initial: Decimal,
+}
+
+#[derive(Args, Debug)]
+struct StatsArgs {
+    /// Date range: YYYY-MM-DD..YYYY-MM-DD, YYYY-MM, this-month, last-month, last-week, this-year, last-N-days or fy:YYYY[:M] (defaults to all transactions)
+    #[arg(long)]
+    range: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct BudgetStatusArgs {
+    /// Date range: YYYY-MM-DD..YYYY-MM-DD, YYYY-MM, this-month, last-month, last-week, this-year, last-N-days or fy:YYYY[:M] (defaults to the current month)
+    #[arg(long)]
+    range: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct CheckAlertsArgs {
+    /// Date in YYYY-MM-DD format that selects the budget periods (defaults to today)
+    #[arg(long)]
+    date: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct AnomaliesArgs {
+    /// Date range: YYYY-MM-DD..YYYY-MM-DD, YYYY-MM, this-month, last-month, last-week, this-year, last-N-days or fy:YYYY[:M] (defaults to all transactions)
+    #[arg(long)]
+    range: Option<String>,
+
+    /// How many standard deviations from the category mean count as unusual
+    #[arg(long, default_value_t = 3.0)]
+    threshold: f64,
 
+    #[arg(long, value_enum, default_value = "table")]
+    output: CliOutputFormat,
+}
+
+#[derive(Args, Debug)]
+struct ReconcileArgs {
+    /// Date range: YYYY-MM-DD..YYYY-MM-DD, YYYY-MM, this-month, last-month, last-week, this-year, last-N-days or fy:YYYY[:M] (defaults to all transactions)
     #[arg(long)]
+    range: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct MergeCategoriesArgs {
+    /// Comma-separated categories to rename (matched case-insensitively), e.g. food,Groceries
+    #[arg(long, value_delimiter = ',', required = true)]
+    from: Vec<String>,
+
+    /// The category they all become
+    #[arg(long, alias = "into")]
     to: String,
 }
 
@@ -105,6 +394,59 @@ struct ReportArgs {
 struct SearchArgs {
     #[arg(long)]
     category: String,
+
+    #[arg(long, value_enum, default_value = "table")]
+    output: CliOutputFormat,
+}
+
+#[derive(Args, Debug)]
+struct SearchDescArgs {
+    #[arg(long)]
+    keyword: String,
+
+    #[arg(long, value_enum, default_value = "table")]
+    output: CliOutputFormat,
+}
+
+#[derive(Args, Debug)]
+struct SearchAmountArgs {
+    /// MIN..MAX, MIN.. or ..MAX, e.g. 100..500
+    #[arg(value_name = "RANGE", allow_hyphen_values = true)]
+    range: String,
+
+    /// Only income or only expenses
+    #[arg(long = "type", value_enum)]
+    transaction_type: Option<CliTransactionType>,
+
+    #[arg(long, value_enum, default_value = "table")]
+    output: CliOutputFormat,
+}
+
+#[derive(Args, Debug)]
+struct PrintArgs {
+    #[arg(long, default_value_t = 1)]
+    page: usize,
+
+    #[arg(long, value_enum, default_value = "table")]
+    output: CliOutputFormat,
+}
+
+/// `table` is for reading; `json` and `csv` print only the data, for scripts.
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum CliOutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+impl CliOutputFormat {
+    fn format(self) -> OutputFormat {
+        match self {
+            CliOutputFormat::Table => OutputFormat::Table,
+            CliOutputFormat::Json => OutputFormat::Json,
+            CliOutputFormat::Csv => OutputFormat::Csv,
+        }
+    }
 }
 
 #[derive(Args, Debug)]
@@ -113,6 +455,25 @@ struct RemoveArgs {
     id: String,
 }
 
+#[derive(Args, Debug)]
+struct SplitArgs {
+    #[arg(long)]
+    id: String,
+
+    #[arg(long)]
+    first_amount: Decimal,
+
+    #[arg(long)]
+    first_category: String,
+
+    /// The two amounts must add up to the original amount
+    #[arg(long)]
+    second_amount: Decimal,
+
+    #[arg(long)]
+    second_category: String,
+}
+
 #[derive(Args, Debug)]
 struct BudgetArgsTop {
     #[command(subcommand)]
@@ -134,6 +495,15 @@ struct BudgetSetArgs {
     category: String,
     #[arg(long)]
     amount: String,
+    /// weekly, monthly, quarterly or yearly (new budgets default to monthly)
+    #[arg(long)]
+    period: Option<String>,
+    /// Add unspent budget from the previous period to the current one (true or false)
+    #[arg(long)]
+    carry_forward: Option<bool>,
+    /// Percent of the budget (0-100) at which check-alerts reports it (new budgets default to 80)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=100))]
+    alert_threshold: Option<u8>,
 }
 
 #[derive(Args, Debug)]
@@ -150,30 +520,282 @@ struct BudgetDeleteArgs {
     category: String,
 }
 
+#[derive(Args, Debug)]
+struct ConfigArgsTop {
+    #[command(subcommand)]
+    command: ConfigCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+    /// Print the database path in use and the values loaded from the config file
+    Show,
+    /// Write a config file with the default settings
+    Init(ConfigInitArgs),
+}
+
+#[derive(Args, Debug)]
+struct ConfigInitArgs {
+    /// Overwrite an existing config file
+    #[arg(long)]
+    force: bool,
+}
+
+#[derive(Args, Debug)]
+struct AlertArgsTop {
+    #[command(subcommand)]
+    command: AlertCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum AlertCommand {
+    List(AlertListArgs),
+    /// Mark one alert as read
+    Read(AlertReadArgs),
+    /// Mark all alerts as read, or delete alerts created before a date
+    Clear(AlertClearArgs),
+}
+
+#[derive(Args, Debug)]
+struct AlertListArgs {
+    /// Include alerts that were already read
+    #[arg(long)]
+    all: bool,
+}
+
+#[derive(Args, Debug)]
+struct AlertReadArgs {
+    id: i32,
+}
+
+#[derive(Args, Debug)]
+struct AlertClearArgs {
+    /// Delete alerts created before this date (YYYY-MM-DD) instead of marking them read
+    #[arg(long)]
+    before: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct AccountArgsTop {
+    #[command(subcommand)]
+    command: AccountCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum AccountCommand {
+    Add(AccountAddArgs),
+    List,
+    /// Delete an account that has no transactions
+    Delete(AccountDeleteArgs),
+}
+
+#[derive(Args, Debug)]
+struct AccountAddArgs {
+    #[arg(long)]
+    name: String,
+
+    /// Currency code, e.g. EUR
+    #[arg(long, default_value = "")]
+    currency: String,
+
+    /// Balance before the first transaction of the account
+    #[arg(long, default_value = "0", allow_negative_numbers = true)]
+    initial_balance: Decimal,
+}
+
+#[derive(Args, Debug)]
+struct AccountDeleteArgs {
+    name: String,
+}
+
+#[derive(Args, Debug)]
+struct RateArgsTop {
+    #[command(subcommand)]
+    command: RateCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum RateCommand {
+    /// Store how many units of TO one unit of FROM is worth
+    Set(RateSetArgs),
+    List,
+    Delete(RateIdArgs),
+}
+
+#[derive(Args, Debug)]
+struct RateSetArgs {
+    #[arg(long)]
+    from: String,
+
+    #[arg(long)]
+    to: String,
+
+    #[arg(long)]
+    rate: Decimal,
+
+    /// Day the rate applies to, YYYY-MM-DD (defaults to today)
+    #[arg(long)]
+    date: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct RateIdArgs {
+    id: i32,
+}
+
+#[derive(Args, Debug)]
+struct RulesArgsTop {
+    #[command(subcommand)]
+    command: RulesCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum RulesCommand {
+    Add(RuleAddArgs),
+    List,
+    Delete(RuleIdArgs),
+    /// Replace the pattern and category of a rule
+    Update(RuleUpdateArgs),
+    /// Set the priority of a rule (higher runs first)
+    Priority(RulePriorityArgs),
+}
+
+#[derive(Args, Debug)]
+struct RuleAddArgs {
+    #[arg(long)]
+    pattern: String,
+
+    #[arg(long)]
+    category: String,
+
+    /// How the pattern is matched against descriptions (defaults to substring)
+    #[arg(long = "match", value_enum)]
+    match_type: Option<CliRuleMatchType>,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum CliRuleMatchType {
+    Substring,
+    Regex,
+}
+
+#[derive(Args, Debug)]
+struct RuleIdArgs {
+    id: i32,
+}
+
+#[derive(Args, Debug)]
+struct RuleUpdateArgs {
+    id: i32,
+
+    #[arg(long)]
+    pattern: String,
+
+    #[arg(long)]
+    category: String,
+}
+
+#[derive(Args, Debug)]
+struct RulePriorityArgs {
+    id: i32,
+
+    priority: i32,
+}
+
+#[derive(Args, Debug)]
+struct RecurringArgsTop {
+    #[command(subcommand)]
+    command: RecurringCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum RecurringCommand {
+    Add(RecurringAddArgs),
+    Delete(RecurringDeleteArgs),
+    List,
+}
+
+#[derive(Args, Debug)]
+struct RecurringAddArgs {
+    /// First due date (YYYY-MM-DD)
+    #[arg(long)]
+    date: String,
+
+    #[arg(long)]
+    description: String,
+
+    #[arg(long)]
+    amount: String,
+
+    #[arg(long = "type", value_enum)]
+    transaction_type: CliTransactionType,
+
+    #[arg(long)]
+    category: String,
+
+    #[arg(long)]
+    interval_days: i64,
+}
+
+#[derive(Args, Debug)]
+struct RecurringDeleteArgs {
+    #[arg(long)]
+    id: i32,
+}
+
+#[derive(Args, Debug)]
+struct GenerateDueArgs {
+    /// Generate occurrences due on or before this date (defaults to today)
+    #[arg(long)]
+    as_of: Option<String>,
+}
+
 pub enum UserCommands {
     Add,
+    Edit,
     Remove,
     Exit,
     Print,
     Search,
+    SearchDesc,
     Import,
+    Export,
     Rules,
     Budgets,
+    BudgetStatus,
+    CheckAlerts,
     Report,
+    Balance,
+    Stats,
+    GenerateDue,
+    Reconcile,
+    Categorize,
+    RuleTest,
 }
 
 fn main() {
     let cli = Cli::parse();
 
-    let conn = match db::connection::establish_connection() {
+    // Completions only describe the CLI, so they work without a config or database.
+    if let Commands::Completions(args) = &cli.command {
+        print_completions(args.shell);
+        return;
+    }
+
+    let config = Config::load();
+    let conn = match db::connection::establish_connection(&config) {
         Ok(conn) => conn,
         Err(e) => {
             eprintln!("Failed to connect to the database: {}", e);
             process::exit(1);
         }
     };
+    // The integrity command prints the full report itself.
+    if !matches!(cli.command, Commands::Integrity) {
+        warn_on_integrity_problems(&conn);
+    }
 
-    let exit_code = match run_command(&conn, cli.command) {
+    let exit_code = match run_command(&conn, &config, cli.command) {
         Ok(()) => 0,
         Err(message) => {
             eprintln!("{}", message);
@@ -184,27 +806,27 @@ fn main() {
     process::exit(exit_code);
 }
 
-fn run_command(conn: &rusqlite::Connection, cmd: Commands) -> Result<(), String> {
+fn print_completions(shell: clap_complete::Shell) {
+    clap_complete::generate(shell, &mut Cli::command(), "fino", &mut io::stdout());
+}
+
+fn run_command(conn: &rusqlite::Connection, config: &Config, cmd: Commands) -> Result<(), String> {
     match cmd {
+        Commands::Completions(args) => {
+            print_completions(args.shell);
+            Ok(())
+        }
         Commands::Add(args) => {
-            if args.description.contains(',') {
-                return Err("Description must not contain commas (',') because the current parser is comma-separated.".to_string());
-            }
-            if args.category.contains(',') {
-                return Err("Category must not contain commas (',') because the current parser is comma-separated.".to_string());
-            }
+            let raw_input = resolve_transaction_input(&args, &config.default_category)?;
 
-            let raw_input = format!(
-                "{},{},{},{},{}",
-                args.date,
-                args.description,
-                args.amount,
-                args.transaction_type.as_str(),
-                args.category
-            );
-
-            let (transaction_id, alert_id) = add_transaction_to_db_with_id(conn, &raw_input)?;
+            let category = raw_input.split(',').nth(4).unwrap_or("").trim();
+            let category_hint = new_category_hint(conn, category)?;
+            let account = args.account.as_deref().unwrap_or(&config.default_account);
+            let (transaction_id, alert_id) = add_transaction_to_account(conn, &raw_input, account)?;
             println!("Transaction added successfully. ID: {}", transaction_id);
+            if let Some(hint) = category_hint {
+                println!("{}", hint);
+            }
             if let Some(alert_id) = alert_id {
                 let alerts = alert_repository::get_alerts_by_ids(conn, &[alert_id]).unwrap_or_default();
                 if !alerts.is_empty() {
@@ -214,41 +836,187 @@ fn run_command(conn: &rusqlite::Connection, cmd: Commands) -> Result<(), String>
                     }
                 }
             }
+            print_threshold_alerts(conn, chrono::Local::now().date_naive())?;
+            Ok(())
+        }
+        Commands::Edit(args) => {
+            let raw_input = resolve_transaction_input(&args.details, &config.default_category)?;
+            // Checked first so a typo in the account name does not leave a half-applied edit.
+            let account = match &args.details.account {
+                Some(name) => Some(
+                    db::account_repository::get_account_by_name(conn, name)?
+                        .ok_or_else(|| format!("Account '{}' does not exist. Create it with `fino account add`", name.trim()))?,
+                ),
+                None => None,
+            };
+            edit_transaction_in_db(conn, args.id.trim(), &raw_input)?;
+            if let Some(account) = account {
+                db::repository::update_transaction_account(conn, args.id.trim(), &account)?;
+            }
+            println!("Transaction updated successfully.");
             Ok(())
         }
         Commands::Import(args) => {
-            let path_str = args
-                .file
+            let on_error = if args.skip_invalid {
+                ImportErrorBehavior::SkipRow
+            } else {
+                ImportErrorBehavior::AbortAll
+            };
+
+            let duplicates = if args.skip_duplicates {
+                DuplicatePolicy::Skip
+            } else if args.allow_duplicates {
+                DuplicatePolicy::Allow
+            } else {
+                DuplicatePolicy::Error
+            };
+
+            if let Some(dir) = &args.watch {
+                let dir_str = dir.to_str().ok_or_else(|| "Invalid directory path (non-UTF8).".to_string())?;
+                let options = ImportOptions {
+                    dry_run: false,
+                    on_error,
+                    duplicates,
+                    column_map: args.columns.as_deref().map(ImportColumnMap::from_order).transpose()?,
+                    csv_delimiter: Some(config.csv_delimiter_byte()),
+                };
+                return watch_import(conn, dir_str, &options);
+            }
+
+            let file = args.file.as_ref().ok_or_else(|| "Use --file or --watch.".to_string())?;
+            let path_str = file
                 .to_str()
                 .ok_or_else(|| "Invalid file path (non-UTF8).".to_string())?;
 
             let format = match args.format {
-                Some(CliImportFormat::Csv) => operations::import::ImportFormat::CSV,
-                Some(CliImportFormat::Ofx) => operations::import::ImportFormat::OFX,
+                Some(CliImportFormat::Csv) => operations::import::ImportFormat::Csv,
+                Some(CliImportFormat::Tsv) => operations::import::ImportFormat::Tsv,
+                Some(CliImportFormat::Ofx) => operations::import::ImportFormat::Ofx,
+                Some(CliImportFormat::Json) => operations::import::ImportFormat::Json,
+                Some(CliImportFormat::Ynab) => operations::import::ImportFormat::YnabCsv,
+                Some(CliImportFormat::Mint) => operations::import::ImportFormat::MintCsv,
                 None => detect_import_format(path_str)?,
             };
 
-            let (count, alert_ids) = import_transactions_to_db(conn, format, path_str)?;
-            println!("Successfully imported {} transactions.", count);
-            if !alert_ids.is_empty() {
-                let alerts = alert_repository::get_alerts_by_ids(conn, &alert_ids).unwrap_or_default();
-                if !alerts.is_empty() {
-                    println!("Alerts generated during import:");
-                    for alert in alerts {
-                        println!("[{}] {}", alert.category, alert.message);
-                    }
+            let column_map = match &args.columns {
+                Some(order) => Some(ImportColumnMap::from_order(order)?),
+                None => confirm_column_map(format, path_str, !args.yes, config.csv_delimiter_byte())?,
+            };
+
+            let options = ImportOptions {
+                dry_run: false,
+                on_error,
+                duplicates,
+                column_map,
+                csv_delimiter: Some(config.csv_delimiter_byte()),
+            };
+
+            let preview = preview_import(conn, format, path_str, &options)?;
+            print_import_preview(&preview);
+            if args.dry_run {
+                return Ok(());
+            }
+            if !args.yes {
+                println!("Proceed with the import? (y/N)");
+                if !read_user_input()?.eq_ignore_ascii_case("y") {
+                    println!("Import cancelled.");
+                    return Ok(());
                 }
             }
+
+            let result = import_with_progress_line(conn, format, path_str, &options)?;
+            print_import_result(conn, &result);
+            Ok(())
+        }
+        Commands::Export(args) => {
+            let path_str = args
+                .file
+                .to_str()
+                .ok_or_else(|| "Invalid file path (non-UTF8).".to_string())?;
+
+            let format = match args.format {
+                Some(CliExportFormat::Csv) => operations::export::ExportFormat::Csv,
+                Some(CliExportFormat::Tsv) => operations::export::ExportFormat::Tsv,
+                Some(CliExportFormat::Json) => operations::export::ExportFormat::Json,
+                None => detect_export_format(path_str)?,
+            };
+
+            let count = export_transactions(conn, format, path_str)?;
+            println!("Successfully exported {} transactions to {}.", count, path_str);
             Ok(())
         }
         Commands::Report(args) => {
-            let start = parse_cli_date(&args.from)?;
-            let end = parse_cli_date(&args.to)?;
-            run_report(conn, start, end)
+            let kind = match args.kind {
+                None | Some(CliReportKind::Expense) => ReportKind::Expense,
+                Some(CliReportKind::Income) => ReportKind::Income,
+                Some(CliReportKind::Net) => ReportKind::Net,
+            };
+            let (start, end) = match (args.range, args.from, args.to) {
+                (Some(range), _, _) => parse_date_range_or_shorthand(&range, config.fiscal_year_start_month)?,
+                (None, Some(from), Some(to)) => (from, to),
+                _ => return Err("Use --from and --to, or --range.".to_string()),
+            };
+            if args.compare {
+                return run_comparison_report(conn, start, end, &config.theme);
+            }
+            let bucket_mode = args.bucket.map(|mode| match mode {
+                CliBucketMode::Day => BucketMode::Day,
+                CliBucketMode::Week => BucketMode::Week,
+                CliBucketMode::Fortnight => BucketMode::Fortnight,
+                CliBucketMode::Month => BucketMode::Month,
+                CliBucketMode::Quarter => BucketMode::Quarter,
+                CliBucketMode::Auto => BucketMode::Auto,
+            });
+            run_report(conn, start, end, kind, bucket_mode, &config.theme)
+        }
+        Commands::Balance(args) => {
+            let (start, end) = parse_date_range_or_shorthand(&args.range, config.fiscal_year_start_month)?;
+            match args.base {
+                Some(base) => print_net_balance_in_base_currency(conn, start, end, &base),
+                None => print_net_balance(conn, start, end),
+            }
+        }
+        Commands::BalanceHistory(args) => {
+            let (start, end) = parse_optional_date_range(args.range.as_deref(), config)?;
+            print_balance_history(conn, start, end, args.initial)
+        }
+        Commands::CheckAlerts(args) => {
+            let as_of = match args.date {
+                Some(date) => parse_date(&date)?,
+                None => chrono::Local::now().date_naive(),
+            };
+            if print_threshold_alerts(conn, as_of)? == 0 {
+                println!("No budgets at or above their alert threshold.");
+            }
+            Ok(())
+        }
+        Commands::BudgetStatus(args) => {
+            let (start, end) = parse_budget_status_range(args.range.as_deref(), config)?;
+            print_budget_status(conn, start, end)
+        }
+        Commands::Stats(args) => {
+            let (start, end) = parse_optional_date_range(args.range.as_deref(), config)?;
+            print_stats(conn, start, end)
+        }
+        Commands::Anomalies(args) => {
+            let (start, end) = parse_optional_date_range(args.range.as_deref(), config)?;
+            let outliers = detect_outliers(conn, start, end, args.threshold)?;
+            if !matches!(args.output, CliOutputFormat::Table) {
+                print_transactions(&outliers, args.output.format(), config);
+            } else if outliers.is_empty() {
+                println!("No unusual expenses found.");
+            } else {
+                println!("Expenses more than {} standard deviations from their category average:", args.threshold);
+                print_transactions(&outliers, OutputFormat::Table, config);
+            }
+            Ok(())
         }
         Commands::Budget(budget) => match budget.command {
             BudgetCommand::Set(args) => {
-                set_budget_db(conn, &args.category, &args.amount)?;
+                set_budget_db(conn, &args.category, &args.amount, args.period.as_deref(), args.alert_threshold)?;
+                if let Some(carry_forward) = args.carry_forward {
+                    set_carry_forward_db(conn, &args.category, carry_forward)?;
+                }
                 println!("Budget set for category '{}'", args.category.trim());
                 Ok(())
             }
@@ -274,67 +1042,779 @@ fn run_command(conn: &rusqlite::Connection, cmd: Commands) -> Result<(), String>
                 } else {
                     println!("Budgets:");
                     for budget in budgets {
-                        println!("Category: {}, Amount: {}", budget.category, budget.amount);
+                        println!(
+                            "Category: {}, Amount: {}, Period: {}, Carry forward: {}, Alert at: {}%",
+                            budget.category,
+                            budget.amount,
+                            budget.period.as_str(),
+                            if budget.carry_forward { "yes" } else { "no" },
+                            budget.alert_threshold
+                        );
+                    }
+                }
+                Ok(())
+            }
+        },
+        Commands::Config(config_args) => match config_args.command {
+            ConfigCommand::Show => print_config(config),
+            ConfigCommand::Init(args) => init_config(args.force),
+        },
+        Commands::Backup(args) => {
+            let dest = match args.file {
+                Some(file) => file,
+                None => {
+                    println!("Enter the backup file or directory:");
+                    let answer = read_user_input()?;
+                    if answer.is_empty() {
+                        return Err("No backup destination given.".to_string());
+                    }
+                    PathBuf::from(answer)
+                }
+            };
+            let dest = db::connection::backup_destination(&dest, chrono::Local::now().naive_local());
+            let dest_str = dest.to_str().ok_or_else(|| "Invalid file path (non-UTF8).".to_string())?;
+            db::connection::backup_database(conn, dest_str)?;
+            println!("Database backed up to {}", dest.display());
+            Ok(())
+        }
+        Commands::DbInfo => print_db_info(conn, config),
+        Commands::Vacuum => {
+            let path = db::connection::resolve_db_path(config);
+            let before = db::connection::database_size(&path);
+            db::connection::vacuum_database(conn)?;
+            let after = db::connection::database_size(&path);
+            println!("Vacuumed {}: {} bytes -> {} bytes", path.display(), before, after);
+            Ok(())
+        }
+        Commands::Integrity => {
+            let problems = db::connection::check_integrity(conn)?;
+            if problems.is_empty() {
+                println!("Database OK");
+                return Ok(());
+            }
+            for problem in &problems {
+                println!("{}", problem);
+            }
+            Err(format!("Database integrity check found {} problem(s).", problems.len()))
+        }
+        Commands::Validate => {
+            let issues = validate_database(conn)?;
+            if issues.is_empty() {
+                println!("No issues found.");
+                return Ok(());
+            }
+            for issue in &issues {
+                println!("{}", issue);
+            }
+            Err(format!("Validation found {} issue(s).", issues.len()))
+        }
+        Commands::Alert(alert) => match alert.command {
+            AlertCommand::List(args) => print_alerts(conn, AlertFilter { include_read: args.all }),
+            AlertCommand::Read(args) => {
+                alert_repository::mark_alert_read(conn, args.id)?;
+                println!("Alert {} marked as read.", args.id);
+                Ok(())
+            }
+            AlertCommand::Clear(args) => {
+                match args.before {
+                    Some(before) => {
+                        let cutoff = parse_date(&before)?;
+                        let deleted = alert_repository::delete_alerts_before(conn, &cutoff.format("%Y-%m-%d").to_string())?;
+                        println!("Deleted {} alerts created before {}.", deleted, cutoff.format("%Y-%m-%d"));
+                    }
+                    None => {
+                        let marked = alert_repository::mark_all_alerts_read(conn)?;
+                        println!("Marked {} alerts as read.", marked);
+                    }
+                }
+                Ok(())
+            }
+        },
+        Commands::Recurring(recurring) => match recurring.command {
+            RecurringCommand::Add(args) => {
+                if args.description.contains(',') || args.category.contains(',') {
+                    return Err("Description and category must not contain commas (',').".to_string());
+                }
+                let raw_input = format!(
+                    "{},{},{},{},{}",
+                    args.date,
+                    args.description,
+                    args.amount,
+                    args.transaction_type.as_str(),
+                    args.category
+                );
+                let id = add_recurring_db(conn, &raw_input, args.interval_days)?;
+                println!("Recurring transaction added. ID: {}", id);
+                Ok(())
+            }
+            RecurringCommand::Delete(args) => {
+                delete_recurring_db(conn, args.id)?;
+                println!("Recurring transaction {} deleted.", args.id);
+                Ok(())
+            }
+            RecurringCommand::List => {
+                let items = list_recurring_db(conn)?;
+                if items.is_empty() {
+                    println!("No recurring transactions defined.");
+                } else {
+                    println!("Recurring transactions:");
+                    for item in items {
+                        println!(
+                            "ID: {}, {} {} ({}), Category: {}, every {} days, next due {}{}",
+                            item.id,
+                            item.template_description,
+                            item.amount,
+                            match item.transaction_type {
+                                TransactionType::Income => "income",
+                                TransactionType::Expense => "expense",
+                            },
+                            item.category,
+                            item.interval_days,
+                            item.next_due.format("%Y-%m-%d"),
+                            if item.active { "" } else { " (inactive)" }
+                        );
                     }
                 }
                 Ok(())
             }
         },
+        Commands::GenerateDue(args) => {
+            let as_of = match args.as_of {
+                Some(date) => parse_date(&date)?,
+                None => chrono::Local::now().date_naive(),
+            };
+            run_generate_due(conn, as_of)
+        }
+        Commands::Reconcile(args) => {
+            let (start, end) = parse_optional_date_range(args.range.as_deref(), config)?;
+            reconcile_interactive(conn, start, end)
+        }
+        Commands::Categorize => {
+            let updated = apply_rules_to_transactions(conn)?;
+            println!("Categorized {} transactions.", updated);
+            Ok(())
+        }
+        Commands::RuleTest => print_rule_preview(conn),
+        Commands::MergeCategories(args) => {
+            let from: Vec<&str> = args.from.iter().map(String::as_str).collect();
+            let updated = merge_categories(conn, &from, &args.to)?;
+            println!("Merged {} into '{}': {} transactions updated.", from.join(", "), args.to.trim(), updated);
+            Ok(())
+        }
+        Commands::Account(account) => match account.command {
+            AccountCommand::Add(args) => {
+                db::account_repository::add_account(conn, &args.name, &args.currency, &args.initial_balance)?;
+                println!("Account '{}' added.", args.name.trim());
+                Ok(())
+            }
+            AccountCommand::List => print_accounts(conn),
+            AccountCommand::Delete(args) => {
+                let account = db::account_repository::get_account_by_name(conn, &args.name)?
+                    .ok_or_else(|| format!("Account '{}' does not exist", args.name.trim()))?;
+                db::account_repository::delete_account(conn, account.id)?;
+                println!("Account '{}' deleted.", account.name);
+                Ok(())
+            }
+        },
+        Commands::Rate(rate) => match rate.command {
+            RateCommand::Set(args) => {
+                let as_of = match args.date {
+                    Some(date) => parse_date(&date)?,
+                    None => chrono::Local::now().date_naive(),
+                };
+                db::exchange_rate_repository::set_exchange_rate(conn, &args.from, &args.to, &args.rate, as_of)?;
+                println!(
+                    "Rate set: 1 {} = {} {} on {}",
+                    args.from.trim().to_uppercase(),
+                    args.rate,
+                    args.to.trim().to_uppercase(),
+                    as_of.format("%Y-%m-%d")
+                );
+                Ok(())
+            }
+            RateCommand::List => print_exchange_rates(conn),
+            RateCommand::Delete(args) => {
+                db::exchange_rate_repository::delete_exchange_rate(conn, args.id)?;
+                println!("Exchange rate {} deleted.", args.id);
+                Ok(())
+            }
+        },
+        Commands::Rules(rules) => match rules.command {
+            RulesCommand::Add(args) => {
+                let match_type = match args.match_type {
+                    None | Some(CliRuleMatchType::Substring) => RuleMatchType::Substring,
+                    Some(CliRuleMatchType::Regex) => RuleMatchType::Regex,
+                };
+                db::rule_repository::add_rule(conn, args.pattern.trim(), args.category.trim(), match_type)?;
+                println!("Rule added: '{}' -> '{}'", args.pattern.trim(), args.category.trim());
+                Ok(())
+            }
+            RulesCommand::List => print_rules(conn),
+            RulesCommand::Delete(args) => {
+                db::rule_repository::delete_rule(conn, args.id)?;
+                println!("Rule {} deleted.", args.id);
+                Ok(())
+            }
+            RulesCommand::Update(args) => {
+                db::rule_repository::update_rule(conn, args.id, args.pattern.trim(), args.category.trim())?;
+                println!("Rule {} updated: '{}' -> '{}'", args.id, args.pattern.trim(), args.category.trim());
+                Ok(())
+            }
+            RulesCommand::Priority(args) => {
+                db::rule_repository::set_rule_priority(conn, args.id, args.priority)?;
+                println!("Rule {} priority set to {}.", args.id, args.priority);
+                Ok(())
+            }
+        },
         Commands::Search(args) => {
             let transactions = search_transactions_by_category_db(conn, &args.category)?;
-            if transactions.is_empty() {
+            if !matches!(args.output, CliOutputFormat::Table) {
+                print_transactions(&transactions, args.output.format(), config);
+            } else if transactions.is_empty() {
                 println!("No transactions found for category: {}", args.category);
+                print_category_suggestions(conn, &args.category);
             } else {
                 println!("Transactions found for category '{}':", args.category);
-                for transaction in transactions {
-                    println!("{:?}", transaction);
-                }
+                print_transactions(&transactions, OutputFormat::Table, config);
+            }
+            Ok(())
+        }
+        Commands::SearchDesc(args) => {
+            let transactions = search_transactions_by_description_db(conn, &args.keyword)?;
+            if !matches!(args.output, CliOutputFormat::Table) {
+                print_transactions(&transactions, args.output.format(), config);
+            } else if transactions.is_empty() {
+                println!("No transactions found matching: {}", args.keyword);
+            } else {
+                println!("Transactions matching '{}':", args.keyword);
+                print_transactions(&transactions, OutputFormat::Table, config);
+            }
+            Ok(())
+        }
+        Commands::SearchAmount(args) => {
+            let tx_type = args.transaction_type.map(|t| match t {
+                CliTransactionType::Income => TransactionType::Income,
+                CliTransactionType::Expense => TransactionType::Expense,
+            });
+            let transactions = search_transactions_by_amount_db(conn, &args.range, tx_type)?;
+            if !matches!(args.output, CliOutputFormat::Table) {
+                print_transactions(&transactions, args.output.format(), config);
+            } else if transactions.is_empty() {
+                println!("No transactions found with an amount in {}", args.range.trim());
+            } else {
+                println!("Transactions with an amount in {}:", args.range.trim());
+                print_transactions(&transactions, OutputFormat::Table, config);
+            }
+            Ok(())
+        }
+        Commands::Browse => run_browse(conn, &config.theme),
+        Commands::Interactive => {
+            println!("Welcome to FINO interactive mode!");
+            run_interactive(conn, config);
+            Ok(())
+        }
+        Commands::Print(args) => print_transactions_page(conn, config, args.page, args.output.format()),
+        Commands::Remove(args) => {
+            remove_transaction_from_db(conn, &args.id)?;
+            println!("Transaction removed successfully.");
+            Ok(())
+        }
+        Commands::Split(args) => {
+            let (first_id, second_id) = split_transaction(
+                conn,
+                &args.id,
+                args.first_amount,
+                &args.first_category,
+                args.second_amount,
+                &args.second_category,
+            )?;
+            println!("Transaction split into {} and {}.", first_id, second_id);
+            Ok(())
+        }
+    }
+}
+
+/// The comma-separated record `create_transaction` parses: ENTRY as given, or the named flags
+/// with every missing one asked for on stdin. Scripts that pass all fields are never prompted.
+fn resolve_transaction_input(args: &AddArgs, default_category: &str) -> Result<String, String> {
+    match &args.entry {
+        Some(entry) => Ok(entry.trim().to_string()),
+        None => build_raw_transaction_input(args, default_category),
+    }
+}
+
+fn flag_or_prompt(value: Option<String>, prompt: &str) -> Result<String, String> {
+    match value {
+        Some(value) => Ok(value),
+        None => {
+            println!("{}:", prompt);
+            read_user_input()
+        }
+    }
+}
+
+fn build_raw_transaction_input(args: &AddArgs, default_category: &str) -> Result<String, String> {
+    let date = flag_or_prompt(args.date.clone(), "Date (YYYY-MM-DD)")?;
+    let description = flag_or_prompt(args.description.clone(), "Description")?;
+    let amount = flag_or_prompt(args.amount.clone(), "Amount")?;
+    let transaction_type = flag_or_prompt(
+        args.transaction_type.map(|t| t.as_str().to_string()),
+        "Type (income/expense)",
+    )?;
+    let category = args.category.as_deref().unwrap_or(default_category);
+    if description.contains(',') {
+        return Err("Description must not contain commas (',') because the current parser is comma-separated.".to_string());
+    }
+    if category.contains(',') {
+        return Err("Category must not contain commas (',') because the current parser is comma-separated.".to_string());
+    }
+
+    if args.tags.iter().any(|tag| tag.contains(';')) {
+        return Err("Tags must not contain semicolons (';').".to_string());
+    }
+
+    Ok(format!(
+        "{},{},{},{},{},{},{}",
+        date,
+        description,
+        amount,
+        transaction_type,
+        category,
+        args.tags.join(";"),
+        args.notes
+    ))
+}
+
+const PRINT_PAGE_SIZE: usize = 50;
+
+fn print_transactions_page(
+    conn: &rusqlite::Connection,
+    config: &Config,
+    page: usize,
+    output: OutputFormat,
+) -> Result<(), String> {
+    let total = db::repository::count_transactions(conn)?;
+    let total_pages = total.div_ceil(PRINT_PAGE_SIZE).max(1);
+    let list = db::repository::get_transactions_paginated(conn, page, PRINT_PAGE_SIZE)?;
+
+    if output == OutputFormat::Table {
+        println!("Current Transactions (page {} of {}, {} total):", page, total_pages, total);
+    }
+    print_transactions(&list, output, config);
+    Ok(())
+}
+
+/// The table follows the config's date format and currency symbol; JSON and CSV stay ISO and plain.
+fn print_transactions(transactions: &[financial_app::models::transaction::Transaction], output: OutputFormat, config: &Config) {
+    let text = match output {
+        OutputFormat::Table => format_table(transactions, config),
+        other => format_transactions(transactions, other),
+    };
+    println!("{}", text.trim_end());
+}
+
+fn detect_import_format(path: &str) -> Result<operations::import::ImportFormat, String> {
+    let lower = path.to_lowercase();
+    if lower.ends_with(".ofx") {
+        Ok(operations::import::ImportFormat::Ofx)
+    } else if lower.ends_with(".csv") {
+        Ok(operations::import::ImportFormat::Csv)
+    } else if lower.ends_with(".tsv") {
+        Ok(operations::import::ImportFormat::Tsv)
+    } else if lower.ends_with(".json") {
+        Ok(operations::import::ImportFormat::Json)
+    } else {
+        Err("Unrecognized file format. Use --format csv|tsv|ofx|json|ynab|mint or provide a .csv/.tsv/.ofx/.json file.".to_string())
+    }
+}
+
+fn detect_export_format(path: &str) -> Result<operations::export::ExportFormat, String> {
+    let lower = path.to_lowercase();
+    if lower.ends_with(".csv") {
+        Ok(operations::export::ExportFormat::Csv)
+    } else if lower.ends_with(".tsv") {
+        Ok(operations::export::ExportFormat::Tsv)
+    } else if lower.ends_with(".json") {
+        Ok(operations::export::ExportFormat::Json)
+    } else {
+        Err("Unrecognized file format. Use --format csv|tsv|json or provide a .csv/.tsv/.json file.".to_string())
+    }
+}
+
+/// Shows the column order read from a CSV/TSV header row and lets the user replace it.
+fn confirm_column_map(
+    format: operations::import::ImportFormat,
+    path: &str,
+    ask: bool,
+    csv_delimiter: u8,
+) -> Result<Option<ImportColumnMap>, String> {
+    let Some(detected) = detect_column_map(format, path, csv_delimiter)? else {
+        return Ok(None);
+    };
+    println!("Detected columns: {}", detected);
+    if !ask {
+        return Ok(Some(detected));
+    }
+
+    println!("Press Enter to use this order, or type another one (e.g. date,description,amount,type,category):");
+    let answer = read_user_input()?;
+    if answer.is_empty() {
+        Ok(Some(detected))
+    } else {
+        ImportColumnMap::from_order(&answer).map(Some)
+    }
+}
+
+fn print_accounts(conn: &rusqlite::Connection) -> Result<(), String> {
+    println!("{:<4}  {:<20}  {:<8}  {:>15}", "ID", "Name", "Currency", "Initial balance");
+    for account in db::account_repository::get_all_accounts(conn)? {
+        let currency = if account.currency.is_empty() { "-" } else { account.currency.as_str() };
+        println!("{:<4}  {:<20}  {:<8}  {:>15}", account.id, account.name, currency, account.initial_balance);
+    }
+    Ok(())
+}
+
+fn print_exchange_rates(conn: &rusqlite::Connection) -> Result<(), String> {
+    let rates = db::exchange_rate_repository::get_all_exchange_rates(conn)?;
+    if rates.is_empty() {
+        println!("No exchange rates stored.");
+        return Ok(());
+    }
+    println!("{:<4}  {:<4}  {:<4}  {:>12}  Date", "ID", "From", "To", "Rate");
+    for rate in rates {
+        println!(
+            "{:<4}  {:<4}  {:<4}  {:>12}  {}",
+            rate.id,
+            rate.from,
+            rate.to,
+            rate.rate,
+            rate.as_of.format("%Y-%m-%d")
+        );
+    }
+    Ok(())
+}
+
+fn print_rules(conn: &rusqlite::Connection) -> Result<(), String> {
+    let rules = db::rule_repository::get_all_rules(conn)?;
+    if rules.is_empty() {
+        println!("No rules defined.");
+        return Ok(());
+    }
+    println!("Categorization Rules:");
+    for rule in rules {
+        println!(
+            "ID: {}, Priority: {}, Pattern: '{}' ({}) -> Category: '{}'",
+            rule.id,
+            rule.priority,
+            rule.pattern,
+            rule.match_type.as_str(),
+            rule.category
+        );
+    }
+    Ok(())
+}
+
+fn print_rule_preview(conn: &rusqlite::Connection) -> Result<(), String> {
+    let matches = preview_rule_application(conn)?;
+    if matches.is_empty() {
+        println!("No uncategorized transactions.");
+        return Ok(());
+    }
+
+    println!("{:<10}  {:<30}  {:<20}  Pattern", "Date", "Description", "Proposed category");
+    for (transaction, rule) in &matches {
+        let (category, pattern) = match rule {
+            Some(rule) => (rule.category.as_str(), rule.pattern.as_str()),
+            None => ("-", "(no match)"),
+        };
+        println!(
+            "{:<10}  {:<30}  {:<20}  {}",
+            transaction.date.format("%Y-%m-%d"),
+            transaction.description,
+            category,
+            pattern
+        );
+    }
+
+    let matched = matches.iter().filter(|(_, rule)| rule.is_some()).count();
+    println!("{} of {} uncategorized transactions would be categorized.", matched, matches.len());
+    Ok(())
+}
+
+fn print_import_preview(preview: &ImportPreview) {
+    println!(
+        "Preview: {} valid, {} duplicates, {} invalid.",
+        preview.valid,
+        preview.duplicates,
+        preview.errors.len()
+    );
+    for (row, message) in &preview.errors {
+        println!("  row {}: {}", row, message);
+    }
+}
+
+/// Imports like `import_transactions_to_db`, showing an `Importing... 100/1500` line (rewritten
+/// in place) for files with more than `IMPORT_PROGRESS_INTERVAL` rows.
+fn import_with_progress_line(
+    conn: &rusqlite::Connection,
+    format: operations::import::ImportFormat,
+    path: &str,
+    options: &ImportOptions,
+) -> Result<ImportResult, String> {
+    let shown = std::cell::Cell::new(false);
+    let result = import_transactions_to_db_with_progress(conn, format, path, options, |current, total| {
+        if total > IMPORT_PROGRESS_INTERVAL {
+            print!("\rImporting... {}/{}", current, total);
+            let _ = io::Write::flush(&mut io::stdout());
+            shown.set(true);
+        }
+    });
+    if shown.get() {
+        println!();
+    }
+    result
+}
+
+fn print_import_result(conn: &rusqlite::Connection, result: &ImportResult) {
+    println!("Successfully imported {} transactions.", result.imported);
+    if result.duplicates > 0 {
+        println!("Skipped {} duplicate transactions.", result.duplicates);
+    }
+    if !result.errors.is_empty() {
+        println!("Skipped {} invalid rows:", result.errors.len());
+        for (row, message) in &result.errors {
+            println!("  row {}: {}", row, message);
+        }
+    }
+    if !result.alert_ids.is_empty() {
+        let alerts = alert_repository::get_alerts_by_ids(conn, &result.alert_ids).unwrap_or_default();
+        if !alerts.is_empty() {
+            println!("Alerts generated during import:");
+            for alert in alerts {
+                println!("[{}] {}", alert.category, alert.message);
             }
-            Ok(())
         }
-        Commands::Browse => run_browse(conn),
-        Commands::Interactive => {
-            println!("Welcome to FINO interactive mode!");
-            run_interactive(conn);
-            Ok(())
+    }
+}
+
+fn run_generate_due(conn: &rusqlite::Connection, as_of: NaiveDate) -> Result<(), String> {
+    let (count, alert_ids) = generate_due_transactions(conn, as_of)?;
+    println!("Generated {} transactions from recurring items.", count);
+    if !alert_ids.is_empty() {
+        let alerts = alert_repository::get_alerts_by_ids(conn, &alert_ids).unwrap_or_default();
+        if !alerts.is_empty() {
+            println!("Alerts generated:");
+            for alert in alerts {
+                println!("[{}] {}", alert.category, alert.message);
+            }
         }
-        Commands::Print => {
-            println!("Current Transactions:");
-            let list = db::repository::get_all_transactions(conn).unwrap_or_else(|_| vec![]);
-            for transaction in &list {
-                println!("{:?}", transaction);
+    }
+    Ok(())
+}
+
+fn parse_optional_date_range(input: Option<&str>, config: &Config) -> Result<(NaiveDate, NaiveDate), String> {
+    match input.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(range) => parse_date_range_or_shorthand(range, config.fiscal_year_start_month),
+        None => Ok(all_time_range()),
+    }
+}
+
+fn print_category_suggestions(conn: &rusqlite::Connection, category: &str) {
+    let suggestions = suggest_alternative_categories(conn, category).unwrap_or_default();
+    if !suggestions.is_empty() {
+        println!("Known categories: {}", suggestions.join(", "));
+    }
+}
+
+fn print_config(config: &Config) -> Result<(), String> {
+    println!("Database path: {}", db::connection::resolve_db_path(config).display());
+    if let Ok(path) = std::env::var(db::connection::DB_PATH_ENV) {
+        println!("{}: {}", db::connection::DB_PATH_ENV, path);
+    }
+
+    let Some(config_path) = Config::default_path() else {
+        println!("Config file: (home directory not found)");
+        return Ok(());
+    };
+    let state = if config_path.exists() { "" } else { " (not found, using defaults)" };
+    println!("Config file: {}{}", config_path.display(), state);
+    // Surface parse errors here instead of the silent fallback used at startup.
+    let loaded = Config::load_from(&config_path)?;
+    println!("  db_path = {}", loaded.db_path.display());
+    println!("  date_format = {}", loaded.date_format);
+    println!("  currency_symbol = {}", loaded.currency_symbol);
+    println!("  csv_delimiter = {:?}", loaded.csv_delimiter);
+    println!("  default_category = {}", loaded.default_category);
+    println!("  fiscal_year_start_month = {}", loaded.fiscal_year_start_month);
+    println!("  default_account = {}", loaded.default_account);
+    println!("  theme_name = {}", loaded.theme_name);
+    let theme = loaded.theme;
+    println!(
+        "  theme = header_fg {}, income_fg {}, expense_fg {}, selected_bg {}, highlight_fg {}, muted_fg {}",
+        theme.header_fg, theme.income_fg, theme.expense_fg, theme.selected_bg, theme.highlight_fg, theme.muted_fg
+    );
+    Ok(())
+}
+
+fn warn_on_integrity_problems(conn: &rusqlite::Connection) {
+    match db::connection::check_integrity(conn) {
+        Ok(problems) if problems.is_empty() => {}
+        Ok(problems) => {
+            eprintln!("Warning: the database failed its integrity check:");
+            for problem in &problems {
+                eprintln!("  {}", problem);
             }
-            Ok(())
+            eprintln!("Restore a backup or export what is still readable. Run `fino integrity` for the full report.");
         }
-        Commands::Remove(args) => {
-            remove_transaction_from_db(conn, &args.id)?;
-            println!("Transaction removed successfully.");
-            Ok(())
+        Err(e) => eprintln!("Warning: {}", e),
+    }
+}
+
+fn print_db_info(conn: &rusqlite::Connection, config: &Config) -> Result<(), String> {
+    println!("Database path: {}", db::connection::resolve_db_path(config).display());
+    let version = db::connection::schema_version(conn).map_err(|e| format!("Failed to read the schema version: {}", e))?;
+    println!("Schema version: {}", version);
+    let values = db::connection::pragma_values(conn).map_err(|e| format!("Failed to read database settings: {}", e))?;
+    for (name, value) in values {
+        println!("  {} = {}", name, value);
+    }
+    Ok(())
+}
+
+fn init_config(force: bool) -> Result<(), String> {
+    let path = Config::default_path().ok_or_else(|| "Could not determine the home directory.".to_string())?;
+    if path.exists() && !force {
+        return Err(format!("Config file '{}' already exists. Use --force to overwrite it.", path.display()));
+    }
+    Config::default().save(&path)?;
+    println!("Config file written to {}", path.display());
+    Ok(())
+}
+
+fn print_alerts(conn: &rusqlite::Connection, filter: AlertFilter) -> Result<(), String> {
+    let alerts = alert_repository::get_all_alerts(conn, filter)?;
+    if alerts.is_empty() {
+        println!("{}", if filter.include_read { "No alerts." } else { "No unread alerts." });
+        return Ok(());
+    }
+    for alert in alerts {
+        let read = if alert.read_at.is_some() { " (read)" } else { "" };
+        println!("ID: {}, {} [{}] {}{}", alert.id, alert.created_at, alert.category, alert.message, read);
+    }
+    Ok(())
+}
+
+/// Prints budgets at or above their alert threshold and returns how many there were.
+fn print_threshold_alerts(conn: &rusqlite::Connection, as_of: NaiveDate) -> Result<usize, String> {
+    let exceeding = get_budgets_exceeding_threshold(conn, as_of)?;
+    if !exceeding.is_empty() {
+        println!("Budgets at or above their alert threshold:");
+        for (budget, spent, percent) in &exceeding {
+            println!(
+                "[{}] {:.1}% of {} {} budget used (spent {}, threshold {}%)",
+                budget.category,
+                percent,
+                budget.amount,
+                budget.period.as_str(),
+                spent,
+                budget.alert_threshold
+            );
         }
     }
+    Ok(exceeding.len())
 }
 
-fn detect_import_format(path: &str) -> Result<operations::import::ImportFormat, String> {
-    let lower = path.to_lowercase();
-    if lower.ends_with(".ofx") {
-        Ok(operations::import::ImportFormat::OFX)
-    } else if lower.ends_with(".csv") {
-        Ok(operations::import::ImportFormat::CSV)
-    } else {
-        Err("Unrecognized file format. Use --format csv|ofx or provide a .csv/.ofx file.".to_string())
+/// Without a range the status covers the current calendar month.
+fn parse_budget_status_range(input: Option<&str>, config: &Config) -> Result<(NaiveDate, NaiveDate), String> {
+    match input.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(range) => parse_date_range_or_shorthand(range, config.fiscal_year_start_month),
+        None => Ok(BudgetPeriod::Monthly.bounds(chrono::Local::now().date_naive())),
+    }
+}
+
+fn print_budget_status(conn: &rusqlite::Connection, start: NaiveDate, end: NaiveDate) -> Result<(), String> {
+    let rows = get_budget_utilization_all(conn, start, end)?;
+    println!("Budget status for {}..{}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"));
+    if rows.is_empty() {
+        println!("No expenses or budgets in this range.");
+        return Ok(());
+    }
+
+    println!("{:<20}  {:>12}  {:>12}  {:>7}", "Category", "Spent", "Budget", "Used");
+    for (category, spent, budget, percent) in &rows {
+        match budget {
+            Some(budget) => {
+                let marker = if *percent > BUDGET_WARNING_PERCENT { "  !" } else { "" };
+                println!("{:<20}  {:>12.2}  {:>12.2}  {:>6.1}%{}", category, spent, budget, percent, marker);
+            }
+            None => println!("{:<20}  {:>12.2}  {:>12}  {:>7}", category, spent, "-", "-"),
+        }
+    }
+    Ok(())
+}
+
+/// One net balance per currency; amounts in different currencies are only combined with `--base`.
+fn print_net_balance(conn: &rusqlite::Connection, start: NaiveDate, end: NaiveDate) -> Result<(), String> {
+    let balances = db::repository::get_net_balance_in_range(conn, start, end)?;
+    let label = format!("Net balance for {}..{}:", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"));
+    let signed = |(currency_code, balance): &(String, Decimal)| {
+        let sign = if *balance > Decimal::ZERO { "+" } else { "" };
+        format!("{}{:.2} {}", sign, balance, currency_code).trim_end().to_string()
+    };
+    match balances.as_slice() {
+        [] => println!("{} 0.00", label),
+        [balance] => println!("{} {}", label, signed(balance)),
+        _ => {
+            println!("{}", label);
+            for balance in &balances {
+                println!("  {}", signed(balance));
+            }
+            println!("Use --base to combine the currencies into one balance.");
+        }
     }
+    Ok(())
 }
 
-fn parse_cli_date(input: &str) -> Result<NaiveDate, String> {
-    let s = input.trim();
-    NaiveDate::parse_from_str(s, "%Y-%m-%d")
-        .or_else(|_| NaiveDate::parse_from_str(s, "%d.%m.%Y"))
-        .map_err(|_| format!("Invalid date '{}'. Use YYYY-MM-DD (recommended) or DD.MM.YYYY.", s))
+fn print_net_balance_in_base_currency(
+    conn: &rusqlite::Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+    base: &str,
+) -> Result<(), String> {
+    let balance = get_net_balance_in_base_currency(conn, start, end, base)?;
+    let sign = if balance > rust_decimal::Decimal::ZERO { "+" } else { "" };
+    println!(
+        "Net balance for {}..{}: {}{:.2} {}",
+        start.format("%Y-%m-%d"),
+        end.format("%Y-%m-%d"),
+        sign,
+        balance,
+        base.trim().to_uppercase()
+    );
+    Ok(())
 }
 
-fn run_interactive(conn: &rusqlite::Connection) {
+fn print_balance_history(
+    conn: &rusqlite::Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+    initial_balance: Decimal,
+) -> Result<(), String> {
+    let balances = db::repository::get_running_balance(conn, start, end, initial_balance)?;
+    if balances.is_empty() {
+        println!("No transactions in this range.");
+        return Ok(());
+    }
+    println!("{:<10}  {:>12}", "Date", "Balance");
+    for (date, balance) in balances {
+        println!("{:<10}  {:>12.2}", date.format("%Y-%m-%d"), balance);
+    }
+    Ok(())
+}
+
+fn run_interactive(conn: &rusqlite::Connection, config: &Config) {
     loop {
-        println!("Please enter a command (add, import, remove, search, print, rules, budgets, report, exit):");
+        println!("Please enter a command (add, edit, import, export, remove, search, searchdesc, print [page], rules, budgets, budget-status, check-alerts, report, balance, stats, generate-due, reconcile, categorize, rule-test, exit):");
 
         let input = match read_user_input() {
             Ok(cmd) => cmd,
@@ -351,7 +1831,7 @@ fn run_interactive(conn: &rusqlite::Connection) {
         let command = check_for_command(parts[0]);
         match command {
             UserCommands::Add => {
-                println!("Add command selected. Please enter transaction details in the format:\ndate(YYYY-MM-DD), description, amount, type(income/expense), category");
+                println!("Add command selected. Please enter transaction details in the format:\ndate(YYYY-MM-DD), description, amount, type(income/expense), category[, tags separated by ;][, notes]");
                 let input = match read_user_input() {
                     Ok(details) => details,
                     Err(e) => {
@@ -359,9 +1839,16 @@ fn run_interactive(conn: &rusqlite::Connection) {
                         continue;
                     }
                 };
-                match add_transaction_to_db(conn, &input) {
-                    Ok(alert_id) => {
-                        println!("Transaction added successfully!");
+                let category_hint = input
+                    .split(',')
+                    .nth(4)
+                    .and_then(|category| new_category_hint(conn, category).ok().flatten());
+                match add_transaction_to_db_with_id(conn, &input) {
+                    Ok((transaction_id, alert_id)) => {
+                        println!("Transaction added successfully. ID: {}", transaction_id);
+                        if let Some(hint) = category_hint {
+                            println!("{}", hint);
+                        }
                         if let Some(alert_id) = alert_id {
                             println!("Alerts generated:");
                             let alerts = alert_repository::get_alerts_by_ids(conn, &[alert_id]).unwrap_or_default();
@@ -369,6 +1856,9 @@ fn run_interactive(conn: &rusqlite::Connection) {
                                 println!("[{}] {}", alert.category, alert.message);
                             }
                         }
+                        if let Err(e) = print_threshold_alerts(conn, chrono::Local::now().date_naive()) {
+                            println!("Failed to check budget thresholds: {}", e);
+                        }
                     }
                     Err(e) => {
                         println!("Error adding transaction: {}", e);
@@ -376,8 +1866,34 @@ fn run_interactive(conn: &rusqlite::Connection) {
                     }
                 }
             }
+            UserCommands::Edit => {
+                println!("Edit command selected. Provide the transaction ID to edit:");
+                let id = match read_user_input() {
+                    Ok(details) => details,
+                    Err(e) => {
+                        println!("Error reading input: {}", e);
+                        continue;
+                    }
+                };
+                println!("Please enter the new transaction details in the format:\ndate(YYYY-MM-DD), description, amount, type(income/expense), category, tags(separated by ';'), notes");
+                println!("Leave tags or notes empty to keep them, or enter '-' to clear them.");
+                let input = match read_user_input() {
+                    Ok(details) => details,
+                    Err(e) => {
+                        println!("Error reading input: {}", e);
+                        continue;
+                    }
+                };
+                match edit_transaction_in_db(conn, &id, &input) {
+                    Ok(_) => println!("Transaction updated successfully!"),
+                    Err(e) => {
+                        println!("Error editing transaction: {}", e);
+                        println!("Please try again.");
+                    }
+                }
+            }
             UserCommands::Import => {
-                println!("Import command selected. Please enter the file path to import from (supported formats: .csv, .ofx):");
+                println!("Import command selected. Please enter the file path to import from (supported formats: .csv, .ofx, .json):");
                 let input = match read_user_input() {
                     Ok(details) => details,
                     Err(e) => {
@@ -387,9 +1903,13 @@ fn run_interactive(conn: &rusqlite::Connection) {
                 };
 
                 let format = if input.to_lowercase().ends_with(".ofx") {
-                    Some(operations::import::ImportFormat::OFX)
+                    Some(operations::import::ImportFormat::Ofx)
                 } else if input.to_lowercase().ends_with(".csv") {
-                    Some(operations::import::ImportFormat::CSV)
+                    Some(operations::import::ImportFormat::Csv)
+                } else if input.to_lowercase().ends_with(".tsv") {
+                    Some(operations::import::ImportFormat::Tsv)
+                } else if input.to_lowercase().ends_with(".json") {
+                    Some(operations::import::ImportFormat::Json)
                 } else {
                     None
                 };
@@ -397,26 +1917,104 @@ fn run_interactive(conn: &rusqlite::Connection) {
                 let format = match format {
                     Some(fmt) => fmt,
                     None => {
-                        println!("Unrecognized file format for import. Supported formats are .csv and .ofx.");
+                        println!("Unrecognized file format for import. Supported formats are .csv, .tsv, .ofx and .json.");
                         continue;
                     }
                 };
 
-                let import_result = import_transactions_to_db(conn, format, &input);
-                match import_result {
-                    Ok((number_of_imported_transactions, alert_ids)) => {
-                        println!("Successfully imported {} transactions.", number_of_imported_transactions);
-                        if !alert_ids.is_empty() {
-                            println!("Alerts generated during import:");
-                            let alerts = alert_repository::get_alerts_by_ids(conn, &alert_ids).unwrap_or_default();
-                            for alert in alerts {
-                                println!("[{}] {}", alert.category, alert.message);
-                            }
-                        }
+                println!("Skip invalid rows instead of aborting the whole import? (y/N)");
+                let on_error = match read_user_input() {
+                    Ok(answer) if answer.eq_ignore_ascii_case("y") => ImportErrorBehavior::SkipRow,
+                    Ok(_) => ImportErrorBehavior::AbortAll,
+                    Err(e) => {
+                        println!("Error reading input: {}", e);
+                        continue;
+                    }
+                };
+
+                println!("Skip transactions that already exist instead of failing on them? (y/N)");
+                let duplicates = match read_user_input() {
+                    Ok(answer) if answer.eq_ignore_ascii_case("y") => DuplicatePolicy::Skip,
+                    Ok(_) => DuplicatePolicy::Error,
+                    Err(e) => {
+                        println!("Error reading input: {}", e);
+                        continue;
+                    }
+                };
+
+                let column_map = match confirm_column_map(format, &input, true, config.csv_delimiter_byte()) {
+                    Ok(map) => map,
+                    Err(err) => {
+                        println!("Error importing transactions: {}", err);
+                        continue;
+                    }
+                };
+
+                let options = ImportOptions {
+                    dry_run: false,
+                    on_error,
+                    duplicates,
+                    column_map,
+                    csv_delimiter: Some(config.csv_delimiter_byte()),
+                };
+                match preview_import(conn, format, &input, &options) {
+                    Ok(preview) => print_import_preview(&preview),
+                    Err(err) => {
+                        println!("Error importing transactions: {}", err);
+                        continue;
                     }
+                }
+                println!("Proceed with the import? (y/N)");
+                match read_user_input() {
+                    Ok(answer) if answer.eq_ignore_ascii_case("y") => {}
+                    Ok(_) => {
+                        println!("Import cancelled.");
+                        continue;
+                    }
+                    Err(e) => {
+                        println!("Error reading input: {}", e);
+                        continue;
+                    }
+                }
+
+                match import_with_progress_line(conn, format, &input, &options) {
+                    Ok(result) => print_import_result(conn, &result),
                     Err(err) => println!("Error importing transactions: {}", err),
                 }
             }
+            UserCommands::Export => {
+                println!("Export command selected. Please enter the file path to export to:");
+                let path = match read_user_input() {
+                    Ok(details) => details,
+                    Err(e) => {
+                        println!("Error reading input: {}", e);
+                        continue;
+                    }
+                };
+                println!("Enter the export format (csv, tsv, json):");
+                let format_input = match read_user_input() {
+                    Ok(details) => details,
+                    Err(e) => {
+                        println!("Error reading input: {}", e);
+                        continue;
+                    }
+                };
+
+                let format = match format_input.to_lowercase().as_str() {
+                    "csv" => operations::export::ExportFormat::Csv,
+                    "tsv" => operations::export::ExportFormat::Tsv,
+                    "json" => operations::export::ExportFormat::Json,
+                    _ => {
+                        println!("Unrecognized export format. Supported formats are csv, tsv and json.");
+                        continue;
+                    }
+                };
+
+                match export_transactions(conn, format, &path) {
+                    Ok(count) => println!("Successfully exported {} transactions to {}.", count, path),
+                    Err(err) => println!("Error exporting transactions: {}", err),
+                }
+            }
             UserCommands::Remove => {
                 println!("Remove command selected. Provide the transaction ID to remove:");
                 let input = match read_user_input() {
@@ -433,10 +2031,18 @@ fn run_interactive(conn: &rusqlite::Connection) {
                 }
             }
             UserCommands::Print => {
-                println!("Current Transactions:");
-                let list = db::repository::get_all_transactions(conn).unwrap_or_else(|_| vec![]);
-                for transaction in &list {
-                    println!("{:?}", transaction);
+                let page = match parts.get(1) {
+                    Some(raw) => match raw.parse::<usize>() {
+                        Ok(page) => page,
+                        Err(_) => {
+                            println!("Invalid page number '{}'.", raw);
+                            continue;
+                        }
+                    },
+                    None => 1,
+                };
+                if let Err(e) = print_transactions_page(conn, config, page, OutputFormat::Table) {
+                    println!("Error printing transactions: {}", e);
                 }
             }
             UserCommands::Search => {
@@ -458,15 +2064,37 @@ fn run_interactive(conn: &rusqlite::Connection) {
                 };
                 if transactions.is_empty() {
                     println!("No transactions found for category: {}", input);
+                    print_category_suggestions(conn, &input);
                 } else {
                     println!("Transactions found for category '{}':", input);
-                    for transaction in transactions {
-                        println!("{:?}", transaction);
+                    print_transactions(&transactions, OutputFormat::Table, config);
+                }
+            }
+            UserCommands::SearchDesc => {
+                println!("Description search selected. Provide the keyword to search for:");
+                let input = match read_user_input() {
+                    Ok(details) => details,
+                    Err(e) => {
+                        println!("Error reading input: {}", e);
+                        continue;
+                    }
+                };
+                let transactions = match search_transactions_by_description_db(conn, &input) {
+                    Ok(transactions) => transactions,
+                    Err(err) => {
+                        println!("Error searching transactions: {}", err);
+                        continue;
                     }
+                };
+                if transactions.is_empty() {
+                    println!("No transactions found matching: {}", input);
+                } else {
+                    println!("Transactions matching '{}':", input);
+                    print_transactions(&transactions, OutputFormat::Table, config);
                 }
             }
             UserCommands::Rules => {
-                println!("Rules command selected. Enter 'add' to create a new rule or 'list' to view existing rules:");
+                println!("Rules command selected. Options: add, list, delete, update, priority");
                 let input = match read_user_input() {
                     Ok(details) => details,
                     Err(e) => {
@@ -486,36 +2114,101 @@ fn run_interactive(conn: &rusqlite::Connection) {
                             }
                         };
 
+                        println!("Match type: substring (case-insensitive) or regex? [substring]");
+                        let match_type = match read_user_input() {
+                            Ok(answer) if answer.is_empty() => RuleMatchType::Substring,
+                            Ok(answer) => match RuleMatchType::parse(&answer) {
+                                Some(match_type) => match_type,
+                                None => {
+                                    println!("Invalid match type. Use 'substring' or 'regex'.");
+                                    continue;
+                                }
+                            },
+                            Err(e) => {
+                                println!("Error reading input: {}", e);
+                                continue;
+                            }
+                        };
+
                         if let Some((pattern, category)) = rule_input.rsplit_once(' ') {
-                            match db::rule_repository::add_rule(conn, pattern.trim(), category.trim()) {
+                            match db::rule_repository::add_rule(conn, pattern.trim(), category.trim(), match_type) {
                                 Ok(_) => println!("Rule added: '{}' -> '{}'", pattern.trim(), category.trim()),
                                 Err(e) => println!("Failed to add rule: {}", e),
                             }
                         } else {
-                            println!("Invalid format. Please use: <regex_pattern> <category>");
+                            println!("Invalid format. Please use: <pattern> <category>");
                         }
                     }
-                    "list" => match db::rule_repository::get_all_rules(conn) {
-                        Ok(rules) => {
-                            if rules.is_empty() {
-                                println!("No rules defined.");
-                            } else {
-                                println!("Categorization Rules:");
-                                for rule in rules {
-                                    println!(
-                                        "ID: {}, Pattern: '{}' -> Category: '{}'",
-                                        rule.id, rule.pattern, rule.category
-                                    );
+                    "list" => {
+                        if let Err(e) = print_rules(conn) {
+                            println!("Failed to fetch rules: {}", e);
+                        }
+                    }
+                    "delete" => {
+                        println!("Enter the ID of the rule to delete:");
+                        let id = match read_user_input().map(|s| s.parse::<i32>()) {
+                            Ok(Ok(id)) => id,
+                            Ok(Err(_)) => {
+                                println!("Invalid rule ID.");
+                                continue;
+                            }
+                            Err(e) => {
+                                println!("Error reading input: {}", e);
+                                continue;
+                            }
+                        };
+                        match db::rule_repository::delete_rule(conn, id) {
+                            Ok(_) => println!("Rule {} deleted.", id),
+                            Err(e) => println!("Failed to delete rule: {}", e),
+                        }
+                    }
+                    "update" => {
+                        println!("Enter: id pattern category (e.g., '3 Uber Transport')");
+                        let rule_input = match read_user_input() {
+                            Ok(details) => details,
+                            Err(e) => {
+                                println!("Error reading rule details: {}", e);
+                                continue;
+                            }
+                        };
+
+                        let parsed = rule_input
+                            .split_once(' ')
+                            .and_then(|(id, rest)| Some((id.trim().parse::<i32>().ok()?, rest.trim().rsplit_once(' ')?)));
+                        match parsed {
+                            Some((id, (pattern, category))) => {
+                                match db::rule_repository::update_rule(conn, id, pattern.trim(), category.trim()) {
+                                    Ok(_) => println!("Rule {} updated: '{}' -> '{}'", id, pattern.trim(), category.trim()),
+                                    Err(e) => println!("Failed to update rule: {}", e),
                                 }
                             }
+                            None => println!("Invalid format. Please use: <id> <pattern> <category>"),
                         }
-                        Err(e) => println!("Failed to fetch rules: {}", e),
-                    },
-                    _ => println!("Invalid option. Use 'add' or 'list'."),
+                    }
+                    "priority" => {
+                        println!("Enter: id priority (higher runs first, e.g., '3 10')");
+                        let parsed = match read_user_input() {
+                            Ok(input) => input
+                                .split_once(' ')
+                                .and_then(|(id, priority)| Some((id.trim().parse::<i32>().ok()?, priority.trim().parse::<i32>().ok()?))),
+                            Err(e) => {
+                                println!("Error reading input: {}", e);
+                                continue;
+                            }
+                        };
+                        match parsed {
+                            Some((id, priority)) => match db::rule_repository::set_rule_priority(conn, id, priority) {
+                                Ok(_) => println!("Rule {} priority set to {}.", id, priority),
+                                Err(e) => println!("Failed to set rule priority: {}", e),
+                            },
+                            None => println!("Invalid format. Please use: <id> <priority>"),
+                        }
+                    }
+                    _ => println!("Invalid option. Use 'add', 'list', 'delete', 'update' or 'priority'."),
                 }
             }
             UserCommands::Budgets => {
-                println!("Budgets command selected. Options: set, increase, decrease, delete, list, back");
+                println!("Budgets command selected. Options: set, increase, decrease, carry, delete, list, back");
                 let input = match read_user_input() {
                     Ok(details) => details,
                     Err(e) => {
@@ -526,7 +2219,7 @@ fn run_interactive(conn: &rusqlite::Connection) {
 
                 match input.trim() {
                     "set" => {
-                        println!("Enter budget details in format: category,amount");
+                        println!("Enter budget details in format: category,amount[,period[,alert threshold %]] (period: weekly, monthly, quarterly, yearly)");
                         let budget_input = match read_user_input() {
                             Ok(details) => details,
                             Err(e) => {
@@ -535,11 +2228,19 @@ fn run_interactive(conn: &rusqlite::Connection) {
                             }
                         };
                         let parts: Vec<&str> = budget_input.split(',').map(|s| s.trim()).collect();
-                        if parts.len() != 2 {
-                            println!("Invalid format. Use: category,amount");
+                        if !(2..=4).contains(&parts.len()) {
+                            println!("Invalid format. Use: category,amount[,period[,alert threshold %]]");
                             continue;
                         }
-                        match set_budget_db(conn, parts[0], parts[1]) {
+                        let period = parts.get(2).copied().filter(|p| !p.is_empty());
+                        let threshold = match parts.get(3).map(|t| t.parse::<u8>()).transpose() {
+                            Ok(threshold) => threshold,
+                            Err(_) => {
+                                println!("Invalid alert threshold '{}'. Must be between 0 and 100", parts[3]);
+                                continue;
+                            }
+                        };
+                        match set_budget_db(conn, parts[0], parts[1], period, threshold) {
                             Ok(_) => println!("Budget set for category '{}'", parts[0]),
                             Err(e) => println!("Failed to set budget: {}", e),
                         }
@@ -582,6 +2283,29 @@ fn run_interactive(conn: &rusqlite::Connection) {
                             Err(e) => println!("Failed to decrease budget: {}", e),
                         }
                     }
+                    "carry" => {
+                        println!("Enter carry-forward setting in format: category,on|off");
+                        let budget_input = match read_user_input() {
+                            Ok(details) => details,
+                            Err(e) => {
+                                println!("Error reading budget details: {}", e);
+                                continue;
+                            }
+                        };
+                        let parts: Vec<&str> = budget_input.split(',').map(|s| s.trim()).collect();
+                        let carry_forward = match parts.as_slice() {
+                            [_, "on"] => true,
+                            [_, "off"] => false,
+                            _ => {
+                                println!("Invalid format. Use: category,on|off");
+                                continue;
+                            }
+                        };
+                        match set_carry_forward_db(conn, parts[0], carry_forward) {
+                            Ok(_) => println!("Carry-forward {} for category '{}'", parts[1], parts[0]),
+                            Err(e) => println!("Failed to update budget: {}", e),
+                        }
+                    }
                     "delete" => {
                         println!("Enter category to delete budget:");
                         let category_input = match read_user_input() {
@@ -603,7 +2327,14 @@ fn run_interactive(conn: &rusqlite::Connection) {
                             } else {
                                 println!("Budgets:");
                                 for budget in budgets {
-                                    println!("Category: {}, Amount: {}", budget.category, budget.amount);
+                                    println!(
+                                        "Category: {}, Amount: {}, Period: {}, Carry forward: {}, Alert at: {}%",
+                                        budget.category,
+                                        budget.amount,
+                                        budget.period.as_str(),
+                                        if budget.carry_forward { "yes" } else { "no" },
+                                        budget.alert_threshold
+                                    );
                                 }
                             }
                         }
@@ -647,10 +2378,89 @@ fn run_interactive(conn: &rusqlite::Connection) {
                     }
                 };
 
-                if let Err(e) = run_report(conn, start_date, end_date) {
+                if let Err(e) = run_report(conn, start_date, end_date, ReportKind::Expense, None, &config.theme) {
                     println!("Failed to generate report: {}", e);
                 }
             }
+            UserCommands::Balance => {
+                println!("Balance command selected. Enter date range in format: YYYY-MM-DD..YYYY-MM-DD or a shorthand like last-month");
+                let input = match read_user_input() {
+                    Ok(details) => details,
+                    Err(e) => {
+                        println!("Error reading input: {}", e);
+                        continue;
+                    }
+                };
+                let result = parse_date_range_or_shorthand(&input, config.fiscal_year_start_month)
+                    .and_then(|(start, end)| print_net_balance(conn, start, end));
+                if let Err(e) = result {
+                    println!("Failed to calculate balance: {}", e);
+                }
+            }
+            UserCommands::CheckAlerts => match print_threshold_alerts(conn, chrono::Local::now().date_naive()) {
+                Ok(0) => println!("No budgets at or above their alert threshold."),
+                Ok(_) => {}
+                Err(e) => println!("Failed to check budget thresholds: {}", e),
+            },
+            UserCommands::BudgetStatus => {
+                println!("Budget status selected. Enter date range in format: YYYY-MM-DD..YYYY-MM-DD or a shorthand like last-month (leave empty for the current month)");
+                let input = match read_user_input() {
+                    Ok(details) => details,
+                    Err(e) => {
+                        println!("Error reading input: {}", e);
+                        continue;
+                    }
+                };
+                let result = parse_budget_status_range(Some(&input), config)
+                    .and_then(|(start, end)| print_budget_status(conn, start, end));
+                if let Err(e) = result {
+                    println!("Failed to show budget status: {}", e);
+                }
+            }
+            UserCommands::Stats => {
+                println!("Stats command selected. Enter date range in format: YYYY-MM-DD..YYYY-MM-DD or a shorthand like last-month (leave empty for all transactions)");
+                let input = match read_user_input() {
+                    Ok(details) => details,
+                    Err(e) => {
+                        println!("Error reading input: {}", e);
+                        continue;
+                    }
+                };
+                let result = parse_optional_date_range(Some(&input), config)
+                    .and_then(|(start, end)| print_stats(conn, start, end));
+                if let Err(e) = result {
+                    println!("Failed to calculate stats: {}", e);
+                }
+            }
+            UserCommands::Reconcile => {
+                println!("Reconcile command selected. Enter date range in format: YYYY-MM-DD..YYYY-MM-DD or a shorthand like last-month (leave empty for all transactions)");
+                let input = match read_user_input() {
+                    Ok(details) => details,
+                    Err(e) => {
+                        println!("Error reading input: {}", e);
+                        continue;
+                    }
+                };
+                let result = parse_optional_date_range(Some(&input), config)
+                    .and_then(|(start, end)| reconcile_interactive(conn, start, end));
+                if let Err(e) = result {
+                    println!("Failed to reconcile: {}", e);
+                }
+            }
+            UserCommands::GenerateDue => {
+                if let Err(e) = run_generate_due(conn, chrono::Local::now().date_naive()) {
+                    println!("Failed to generate recurring transactions: {}", e);
+                }
+            }
+            UserCommands::Categorize => match apply_rules_to_transactions(conn) {
+                Ok(updated) => println!("Categorized {} transactions.", updated),
+                Err(e) => println!("Failed to categorize transactions: {}", e),
+            },
+            UserCommands::RuleTest => {
+                if let Err(e) = print_rule_preview(conn) {
+                    println!("Failed to preview rules: {}", e);
+                }
+            }
             UserCommands::Exit => {
                 println!("Exiting the application.");
                 break;
@@ -670,14 +2480,25 @@ fn read_user_input() -> Result<String, String> {
 fn check_for_command(input: &str) -> UserCommands {
     match input {
         "add" => UserCommands::Add,
+        "edit" => UserCommands::Edit,
         "remove" => UserCommands::Remove,
         "exit" => UserCommands::Exit,
         "print" => UserCommands::Print,
         "import" => UserCommands::Import,
+        "export" => UserCommands::Export,
         "search" => UserCommands::Search,
+        "searchdesc" => UserCommands::SearchDesc,
         "rules" => UserCommands::Rules,
         "budgets" => UserCommands::Budgets,
+        "budget-status" => UserCommands::BudgetStatus,
+        "check-alerts" => UserCommands::CheckAlerts,
         "report" => UserCommands::Report,
+        "balance" => UserCommands::Balance,
+        "stats" => UserCommands::Stats,
+        "generate-due" => UserCommands::GenerateDue,
+        "reconcile" => UserCommands::Reconcile,
+        "categorize" => UserCommands::Categorize,
+        "rule-test" => UserCommands::RuleTest,
         _ => {
             println!("No valid command found. Exiting.");
             UserCommands::Exit
@@ -0,0 +1,14 @@
+use rust_decimal::Decimal;
+
+/// Account that transactions without an explicit one belong to. It always exists.
+pub const DEFAULT_ACCOUNT: &str = "default";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Account {
+    pub id: i32,
+    /// Unique, compared case-insensitively; transactions refer to their account by name.
+    pub name: String,
+    /// Free-form currency code, e.g. "EUR". Empty for the default account.
+    pub currency: String,
+    pub initial_balance: Decimal,
+}
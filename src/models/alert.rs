@@ -4,4 +4,11 @@ pub struct BudgetAlert {
     pub category: String,
     pub message: String,
     pub created_at: String,
+    /// Set once the alert has been acknowledged.
+    pub read_at: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AlertFilter {
+    pub include_read: bool,
 }
@@ -1,8 +1,92 @@
+use chrono::{Datelike, Days, Months, NaiveDate};
 use rust_decimal::Decimal;
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BudgetPeriod {
+    Weekly,
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+impl BudgetPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetPeriod::Weekly => "weekly",
+            BudgetPeriod::Monthly => "monthly",
+            BudgetPeriod::Quarterly => "quarterly",
+            BudgetPeriod::Yearly => "yearly",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "weekly" => Some(BudgetPeriod::Weekly),
+            "monthly" => Some(BudgetPeriod::Monthly),
+            "quarterly" => Some(BudgetPeriod::Quarterly),
+            "yearly" => Some(BudgetPeriod::Yearly),
+            _ => None,
+        }
+    }
+
+    /// First and last day (inclusive) of the period containing `date`. Weeks start on Monday.
+    pub fn bounds(&self, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let (start, length) = match self {
+            BudgetPeriod::Weekly => {
+                let start = date - Days::new(date.weekday().num_days_from_monday() as u64);
+                return (start, start + Days::new(6));
+            }
+            BudgetPeriod::Monthly => (first_of_month(date.year(), date.month()), Months::new(1)),
+            BudgetPeriod::Quarterly => (first_of_month(date.year(), (date.month() - 1) / 3 * 3 + 1), Months::new(3)),
+            BudgetPeriod::Yearly => (first_of_month(date.year(), 1), Months::new(12)),
+        };
+        (start, start + length - Days::new(1))
+    }
+}
+
+fn first_of_month(year: i32, month: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, 1).expect("month is always 1-12")
+}
+
 #[derive(Debug)]
 pub struct CategoryBudget {
     pub id: i32,
     pub category: String,
     pub amount: Decimal,
+    pub period: BudgetPeriod,
+    /// Unspent budget from the previous period is added to the current one.
+    pub carry_forward: bool,
+    /// Percentage of the budget (0-100) at which the budget is reported by `check-alerts`.
+    pub alert_threshold: u8,
+}
+
+pub const DEFAULT_ALERT_THRESHOLD: u8 = 80;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_period_bounds() {
+        // 2025-02-13 is a Thursday.
+        let as_of = date(2025, 2, 13);
+        assert_eq!(BudgetPeriod::Weekly.bounds(as_of), (date(2025, 2, 10), date(2025, 2, 16)));
+        assert_eq!(BudgetPeriod::Monthly.bounds(as_of), (date(2025, 2, 1), date(2025, 2, 28)));
+        assert_eq!(BudgetPeriod::Quarterly.bounds(as_of), (date(2025, 1, 1), date(2025, 3, 31)));
+        assert_eq!(BudgetPeriod::Yearly.bounds(as_of), (date(2025, 1, 1), date(2025, 12, 31)));
+
+        assert_eq!(BudgetPeriod::Quarterly.bounds(date(2024, 12, 31)), (date(2024, 10, 1), date(2024, 12, 31)));
+        assert_eq!(BudgetPeriod::Monthly.bounds(date(2024, 2, 29)), (date(2024, 2, 1), date(2024, 2, 29)));
+    }
+
+    #[test]
+    fn test_period_parse() {
+        assert_eq!(BudgetPeriod::parse(" Weekly "), Some(BudgetPeriod::Weekly));
+        assert_eq!(BudgetPeriod::parse("yearly"), Some(BudgetPeriod::Yearly));
+        assert_eq!(BudgetPeriod::parse("daily"), None);
+    }
 }
@@ -0,0 +1,47 @@
+/// How amounts in one currency are displayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Currency {
+    /// ISO 4217 code, e.g. "EUR". Empty when no currency is set.
+    pub code: String,
+    pub symbol: String,
+    pub decimal_places: u8,
+}
+
+impl Default for Currency {
+    /// No currency: no symbol and two decimal places.
+    fn default() -> Self {
+        Currency { code: String::new(), symbol: String::new(), decimal_places: 2 }
+    }
+}
+
+impl Currency {
+    /// Symbol and decimal places of well-known codes (any case); other codes are shown as
+    /// their code with two decimal places, and an empty code gives `Currency::default()`.
+    pub fn from_code(code: &str) -> Self {
+        let code = code.trim().to_uppercase();
+        let (symbol, decimal_places) = match code.as_str() {
+            "" => return Currency::default(),
+            "EUR" => ("€", 2),
+            "USD" => ("$", 2),
+            "GBP" => ("£", 2),
+            "JPY" => ("¥", 0),
+            "RON" => ("lei", 2),
+            _ => (code.as_str(), 2),
+        };
+        Currency { symbol: symbol.to_string(), code, decimal_places }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_code() {
+        assert_eq!(Currency::from_code("eur").symbol, "€");
+        assert_eq!(Currency::from_code("JPY").decimal_places, 0);
+        let chf = Currency::from_code(" chf ");
+        assert_eq!((chf.code.as_str(), chf.symbol.as_str(), chf.decimal_places), ("CHF", "CHF", 2));
+        assert_eq!(Currency::from_code(""), Currency::default());
+    }
+}
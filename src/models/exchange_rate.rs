@@ -0,0 +1,13 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+/// One unit of `from` is worth `rate` units of `to` on `as_of`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExchangeRate {
+    pub id: i32,
+    /// Currency codes, always uppercase.
+    pub from: String,
+    pub to: String,
+    pub rate: Decimal,
+    pub as_of: NaiveDate,
+}
@@ -2,3 +2,7 @@
 pub mod rule;
 pub mod budget;
 pub mod alert;
+pub mod recurring;
+pub mod account;
+pub mod currency;
+pub mod exchange_rate;
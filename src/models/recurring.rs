@@ -0,0 +1,15 @@
+use crate::models::transaction::TransactionType;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+#[derive(Debug, Clone)]
+pub struct RecurringTransaction {
+    pub id: i32,
+    pub template_description: String,
+    pub amount: Decimal,
+    pub transaction_type: TransactionType,
+    pub category: String,
+    pub interval_days: i64,
+    pub next_due: NaiveDate,
+    pub active: bool,
+}
@@ -1,6 +1,32 @@
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RuleMatchType {
+    Substring,
+    Regex,
+}
+
+impl RuleMatchType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleMatchType::Substring => "substring",
+            RuleMatchType::Regex => "regex",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "substring" => Some(RuleMatchType::Substring),
+            "regex" => Some(RuleMatchType::Regex),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct CategoryRule {
     pub id: i32,
     pub pattern: String,
     pub category: String,
-}
\ No newline at end of file
+    pub match_type: RuleMatchType,
+    /// Higher priorities are tried first; ties go to the older rule.
+    pub priority: i32,
+}
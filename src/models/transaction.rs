@@ -1,5 +1,6 @@
 use rust_decimal::Decimal;
 use chrono::NaiveDate;
+use crate::models::account::DEFAULT_ACCOUNT;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum TransactionType {
@@ -7,7 +8,43 @@ pub enum TransactionType {
     Expense
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TransactionStatus {
+    Pending,
+    Cleared,
+    Reconciled,
+}
+
+impl TransactionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransactionStatus::Pending => "pending",
+            TransactionStatus::Cleared => "cleared",
+            TransactionStatus::Reconciled => "reconciled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "pending" => Some(TransactionStatus::Pending),
+            "cleared" => Some(TransactionStatus::Cleared),
+            "reconciled" => Some(TransactionStatus::Reconciled),
+            _ => None,
+        }
+    }
+
+    /// Pending -> Cleared -> Reconciled -> Pending
+    pub fn next(self) -> Self {
+        match self {
+            TransactionStatus::Pending => TransactionStatus::Cleared,
+            TransactionStatus::Cleared => TransactionStatus::Reconciled,
+            TransactionStatus::Reconciled => TransactionStatus::Pending,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
 pub struct Transaction {
     pub id: String,
     pub date: NaiveDate,
@@ -15,6 +52,14 @@ pub struct Transaction {
     pub amount: Decimal,
     pub transaction_type: TransactionType,
     pub category: String,
+    pub tags: Vec<String>,
+    pub status: TransactionStatus,
+    pub notes: String,
+    /// Name of the account the transaction belongs to.
+    pub account: String,
+    /// Currency of `amount`, taken from the account when the transaction is added. Empty
+    /// when the account has none.
+    pub currency_code: String,
 }
 
 impl Transaction {
@@ -26,6 +71,73 @@ impl Transaction {
             amount,
             transaction_type,
             category,
+            tags: Vec::new(),
+            status: TransactionStatus::Cleared,
+            notes: String::new(),
+            account: DEFAULT_ACCOUNT.to_string(),
+            currency_code: String::new(),
         }
     }
-}
\ No newline at end of file
+}
+/// Which transactions a filtered query returns; `None` fields match everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransactionFilter {
+    /// Compared ignoring case.
+    pub category: Option<String>,
+    /// Text the description must contain, ignoring case.
+    pub description: Option<String>,
+    pub transaction_type: Option<TransactionType>,
+    pub account: Option<String>,
+    /// Inclusive date bounds.
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    /// Inclusive amount bounds.
+    pub amount_min: Option<Decimal>,
+    pub amount_max: Option<Decimal>,
+}
+
+/// Row order of a filtered query. Date orders break ties by ID; amount and category orders
+/// show the newest transaction first.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TransactionOrder {
+    DateDesc,
+    DateAsc,
+    AmountDesc,
+    AmountAsc,
+    CategoryAsc,
+    CategoryDesc,
+}
+
+impl TransactionOrder {
+    pub fn next(self) -> Self {
+        match self {
+            TransactionOrder::DateDesc => TransactionOrder::DateAsc,
+            TransactionOrder::DateAsc => TransactionOrder::AmountDesc,
+            TransactionOrder::AmountDesc => TransactionOrder::AmountAsc,
+            TransactionOrder::AmountAsc => TransactionOrder::CategoryAsc,
+            TransactionOrder::CategoryAsc => TransactionOrder::CategoryDesc,
+            TransactionOrder::CategoryDesc => TransactionOrder::DateDesc,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TransactionOrder::DateDesc => "date ↓",
+            TransactionOrder::DateAsc => "date ↑",
+            TransactionOrder::AmountDesc => "amount ↓",
+            TransactionOrder::AmountAsc => "amount ↑",
+            TransactionOrder::CategoryAsc => "category ↑",
+            TransactionOrder::CategoryDesc => "category ↓",
+        }
+    }
+}
+
+/// Sum and number of the transactions of one category, type and currency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryTotal {
+    pub category: String,
+    pub transaction_type: TransactionType,
+    pub currency_code: String,
+    pub total: Decimal,
+    pub count: usize,
+}
@@ -1,88 +1,185 @@
+use crate::error::AppError;
 use crate::models::transaction::{Transaction, TransactionType};
-use crate::db::{repository, budget_repository, alert_repository};
+use crate::db::{repository, budget_repository, alert_repository, account_repository};
+use crate::models::account::DEFAULT_ACCOUNT;
 use rusqlite::Connection;
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use uuid::Uuid;
 
-pub fn create_transaction(input: &str) -> Result<Transaction, String> {
+/// Longest accepted description, in characters.
+pub const MAX_DESCRIPTION_LENGTH: usize = 255;
+/// Longest accepted category, in characters.
+pub const MAX_CATEGORY_LENGTH: usize = 50;
+
+pub fn create_transaction(input: &str) -> Result<Transaction, AppError> {
     let details_string = input.to_string();
     let details = details_string.trim();
-    let detail_parts: Vec<&str> = details.split(',').map(|s| s.trim()).collect();
-    
-    if detail_parts.len() != 5 {
-        return Err(format!(
-            "Invalid input format. Expected 5 fields (date,description,amount,type,category), got {}",
+    // Notes are the last field, so anything after the sixth comma belongs to them.
+    let detail_parts: Vec<&str> = details.splitn(7, ',').map(|s| s.trim()).collect();
+    create_transaction_from_fields(&detail_parts)
+}
+
+/// Builds a transaction from already separated fields, in the same order as `create_transaction`.
+/// Used by importers whose fields may themselves contain commas.
+pub fn create_transaction_from_fields(detail_parts: &[&str]) -> Result<Transaction, AppError> {
+    if detail_parts.len() < 5 {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid input format. Expected 5 fields (date,description,amount,type,category) and optional tags and notes, got {}",
             detail_parts.len()
-        ));
+        )));
     }
 
     let date = NaiveDate::parse_from_str(detail_parts[0], "%Y-%m-%d")
-        .map_err(|_| format!("Invalid date format '{}'. Expected YYYY-MM-DD", detail_parts[0]))?;
+        .map_err(|_| AppError::InvalidInput(format!("Invalid date format '{}'. Expected YYYY-MM-DD", detail_parts[0])))?;
 
     let description = detail_parts[1].to_string();
     if description.is_empty() {
-        return Err("Description cannot be empty".to_string());
+        return Err(AppError::InvalidInput("Description cannot be empty".to_string()));
+    }
+    if description.chars().count() > MAX_DESCRIPTION_LENGTH {
+        return Err(AppError::InvalidInput(format!(
+            "Description is too long ({} characters). Use at most {}",
+            description.chars().count(),
+            MAX_DESCRIPTION_LENGTH
+        )));
     }
 
     let amount = detail_parts[2]
         .parse::<Decimal>()
-        .map_err(|_| format!("Invalid amount '{}'. Must be a valid number", detail_parts[2]))?;
+        .map_err(|_| AppError::InvalidInput(format!("Invalid amount '{}'. Must be a valid number", detail_parts[2])))?;
+    repository::check_amount_precision(&amount)?;
 
     let transaction_type = match detail_parts[3].to_lowercase().as_str() {
         "income" => TransactionType::Income,
         "expense" => TransactionType::Expense,
-        _ => return Err(format!("Invalid transaction type '{}'. Must be 'income' or 'expense'", detail_parts[3])),
+        _ => return Err(AppError::InvalidInput(format!("Invalid transaction type '{}'. Must be 'income' or 'expense'", detail_parts[3]))),
     };
 
     let category = detail_parts[4].to_string();
     if category.is_empty() {
-        return Err("Category cannot be empty".to_string());
+        return Err(AppError::InvalidInput("Category cannot be empty".to_string()));
+    }
+    if category.chars().count() > MAX_CATEGORY_LENGTH {
+        return Err(AppError::InvalidInput(format!(
+            "Category is too long ({} characters). Use at most {}",
+            category.chars().count(),
+            MAX_CATEGORY_LENGTH
+        )));
     }
 
     let id = Uuid::new_v4().to_string();
 
-    Ok(Transaction::new(
+    let mut transaction = Transaction::new(
         id,
         date,
         description,
         amount,
         transaction_type,
         category,
-    ))
+    );
+    if let Some(tags) = detail_parts.get(5) {
+        transaction.tags = parse_tags(tags);
+    }
+    if let Some(notes) = detail_parts.get(6) {
+        transaction.notes = notes.to_string();
+    }
+    Ok(transaction)
 }
 
-pub fn add_transaction_to_db(conn: &Connection, input: &str) -> Result<Option<i32>, String> {
-    let transaction = create_transaction(input)?;
-    repository::add_transaction(conn, &transaction)?;
-    let alert_id = check_budget_and_alert(conn, &transaction)?;
-    Ok(alert_id)
+/// Parses the tags field, where tags are separated by ';' (commas already separate the fields).
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(';').map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if !tags.iter().any(|existing| existing == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
 }
 
+/// Parses `input` (see `create_transaction`), stores it and checks its budget.
+/// Returns the generated UUID of the new transaction.
+pub fn add_transaction_to_db(conn: &Connection, input: &str) -> Result<String, String> {
+    let (id, _) = add_transaction_to_db_with_id(conn, input)?;
+    Ok(id)
+}
+
+/// Like `add_transaction_to_db`, also returning the ID of the budget alert the transaction raised.
 pub fn add_transaction_to_db_with_id(
     conn: &Connection,
     input: &str,
-) -> Result<(String, Option<i32>), String> {
-    let transaction = create_transaction(input)?;
+) -> Result<(String, Option<i32>), AppError> {
+    add_transaction_to_account(conn, input, DEFAULT_ACCOUNT)
+}
+
+/// Like `add_transaction_to_db_with_id`, booking the transaction in `account` (matched ignoring
+/// case), which must exist.
+pub fn add_transaction_to_account(
+    conn: &Connection,
+    input: &str,
+    account: &str,
+) -> Result<(String, Option<i32>), AppError> {
+    let mut transaction = create_transaction(input)?;
+    let account = account_repository::get_account_by_name(conn, account)?
+        .ok_or_else(|| AppError::NotFound(format!("Account '{}' does not exist. Create it with `fino account add`", account.trim())))?;
+    transaction.account = account.name;
+    transaction.currency_code = account.currency;
     let id = transaction.id.clone();
     repository::add_transaction(conn, &transaction)?;
     let alert_id = check_budget_and_alert(conn, &transaction)?;
     Ok((id, alert_id))
 }
 
-pub fn check_budget_and_alert(conn: &Connection, transaction: &Transaction) -> Result<Option<i32>, String> {
+/// Stored categories that look like `input`: one starts with or contains the other, ignoring case.
+/// `input` itself is not included.
+pub fn suggest_categories(conn: &Connection, input: &str) -> Result<Vec<String>, AppError> {
+    let needle = input.trim().to_lowercase();
+    if needle.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(repository::get_distinct_categories(conn)?
+        .into_iter()
+        .filter(|category| {
+            let candidate = category.to_lowercase();
+            candidate != needle && (candidate.contains(&needle) || needle.contains(&candidate))
+        })
+        .collect())
+}
+
+/// A hint to print when `category` has not been used before, listing similar categories
+/// or, if there are none, every stored category. `None` for a known category or an empty database.
+pub fn new_category_hint(conn: &Connection, category: &str) -> Result<Option<String>, AppError> {
+    let categories = repository::get_distinct_categories(conn)?;
+    let category = category.trim();
+    if categories.is_empty() || categories.iter().any(|c| c.eq_ignore_ascii_case(category)) {
+        return Ok(None);
+    }
+
+    let similar = suggest_categories(conn, category)?;
+    let hint = if similar.is_empty() {
+        format!("'{}' is a new category. Existing categories: {}", category, categories.join(", "))
+    } else {
+        format!("'{}' is a new category. Did you mean: {}?", category, similar.join(", "))
+    };
+    Ok(Some(hint))
+}
+
+pub fn check_budget_and_alert(conn: &Connection, transaction: &Transaction) -> Result<Option<i32>, AppError> {
     if transaction.transaction_type != TransactionType::Expense {
         return Ok(None);
     }
 
     if let Some(budget) = budget_repository::get_budget(conn, &transaction.category)? {
-        let total = repository::get_total_expenses_by_category(conn, &transaction.category)?;
+        let (start, end) = budget.period.bounds(transaction.date);
+        let total = repository::get_expenses_by_category_in_range(conn, &transaction.category, start, end)?;
         if total > budget.amount {
             let message = format!(
                 "Budget exceeded for category '{}': budget {}, spent {}",
                 budget.category, budget.amount, total
             );
-            let alert_id = alert_repository::add_alert(conn, &budget.category, &message)?;
+            let alert_id = alert_repository::add_alert(conn, &budget.category, &message)
+                .map_err(AppError::DatabaseError)?;
             return Ok(Some(alert_id));
         }
     }
@@ -94,6 +191,7 @@ mod tests {
     use super::*;
     use crate::db::connection::establish_test_connection;
     use crate::db::budget_repository;
+    use crate::models::budget::BudgetPeriod;
     use rust_decimal::Decimal;
 
     #[test]
@@ -107,36 +205,72 @@ mod tests {
         assert_eq!(transaction.category, "Job");
     }
 
+    #[test]
+    fn test_create_transaction_with_tags() {
+        let transaction =
+            create_transaction("2025-07-01,Hotel,240.00,expense,Travel,vacation; tax-deductible;;vacation").unwrap();
+        assert_eq!(transaction.tags, vec!["vacation", "tax-deductible"]);
+
+        let untagged = create_transaction("2025-07-01,Hotel,240.00,expense,Travel").unwrap();
+        assert!(untagged.tags.is_empty());
+    }
+
+    #[test]
+    fn test_create_transaction_with_notes() {
+        let transaction =
+            create_transaction("2025-07-01,Hotel,240.00,expense,Travel,,Booked via agency, paid in full").unwrap();
+        assert!(transaction.tags.is_empty());
+        assert_eq!(transaction.notes, "Booked via agency, paid in full");
+
+        let untagged = create_transaction("2025-07-01,Hotel,240.00,expense,Travel,vacation").unwrap();
+        assert_eq!(untagged.notes, "");
+    }
+
     #[test]
     fn test_create_transaction_invalid_fields() {
         let input = "2025-11-10,Salary,1500.00,income";
         let result = create_transaction(input);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Expected 5 fields"));
+        let err = result.unwrap_err();
+        assert!(matches!(err, AppError::InvalidInput(_)));
+        assert!(err.to_string().contains("Expected 5 fields"));
     }
 
     #[test]
     fn test_create_transaction_invalid_date() {
         let input = "invalid-date,Salary,1500.00,income,Job";
         let result = create_transaction(input);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Invalid date format"));
+        let err = result.unwrap_err();
+        assert!(matches!(err, AppError::InvalidInput(_)));
+        assert!(err.to_string().contains("Invalid date format"));
     }
 
     #[test]
     fn test_create_transaction_invalid_amount() {
         let input = "2025-11-10,Salary,not-a-number,income,Job";
         let result = create_transaction(input);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Invalid amount"));
+        let err = result.unwrap_err();
+        assert!(matches!(err, AppError::InvalidInput(_)));
+        assert!(err.to_string().contains("Invalid amount"));
+    }
+
+    #[test]
+    fn test_create_transaction_rejects_extra_decimals() {
+        for amount in ["0.00001", "12.345678"] {
+            let err = create_transaction(&format!("2025-11-10,Coffee,{},expense,Food", amount)).unwrap_err();
+            assert!(matches!(err, AppError::InvalidInput(_)));
+            assert!(err.to_string().contains("too many decimal places"));
+        }
+        let transaction = create_transaction("2025-11-10,Coffee,12.3456000,expense,Food").unwrap();
+        assert_eq!(transaction.amount, Decimal::new(123456, 4));
     }
 
     #[test]
     fn test_create_transaction_invalid_type() {
         let input = "2025-11-10,Salary,1500.00,invalid,Job";
         let result = create_transaction(input);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Invalid transaction type"));
+        let err = result.unwrap_err();
+        assert!(matches!(err, AppError::InvalidInput(_)));
+        assert!(err.to_string().contains("Invalid transaction type"));
     }
 
     #[test]
@@ -144,9 +278,23 @@ mod tests {
         let conn = establish_test_connection().unwrap();
         let input = "2025-11-10,Salary,1500.00,income,Job";
         
-        let result = add_transaction_to_db(&conn, input);
-        assert!(result.is_ok());
-        assert!(result.unwrap().is_none());
+        let id = add_transaction_to_db(&conn, input).unwrap();
+        let stored = repository::get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert_eq!(stored.description, "Salary");
+        assert_eq!(stored.amount, Decimal::new(150000, 2));
+        assert!(uuid::Uuid::parse_str(&id).is_ok());
+    }
+
+    #[test]
+    fn test_add_transaction_from_single_entry() {
+        let conn = establish_test_connection().unwrap();
+        let (id, _) = add_transaction_to_db_with_id(&conn, "2025-01-01,Coffee,4.50,expense,Food").unwrap();
+
+        let stored = repository::get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert_eq!(stored.date, NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+        assert_eq!(stored.description, "Coffee");
+        assert_eq!(stored.amount, Decimal::new(450, 2));
+        assert_eq!(stored.category, "Food");
     }
 
     #[test]
@@ -154,26 +302,170 @@ mod tests {
         let conn = establish_test_connection().unwrap();
         let input = "invalid-date,Salary,1500.00,income,Job";
         
-        let result = add_transaction_to_db(&conn, input);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Invalid date format"));
+        let err = add_transaction_to_db_with_id(&conn, input).unwrap_err();
+        assert!(matches!(err, AppError::InvalidInput(_)));
+        assert!(err.to_string().contains("Invalid date format"));
+        assert!(add_transaction_to_db(&conn, input).unwrap_err().contains("Invalid date format"));
+        assert_eq!(repository::count_transactions(&conn).unwrap(), 0);
     }
 
     #[test]
     fn test_budget_alert_generated_on_exceed() {
         let conn = establish_test_connection().unwrap();
-        budget_repository::set_budget(&conn, "Food", &Decimal::new(500, 2)).unwrap();
-        let alert_id = add_transaction_to_db(&conn, "2025-11-10,Dinner,6.00,expense,Food").unwrap();
+        budget_repository::set_budget(&conn, "Food", &Decimal::new(500, 2), None).unwrap();
+        let (_, alert_id) = add_transaction_to_db_with_id(&conn, "2025-11-10,Dinner,6.00,expense,Food").unwrap();
 
         assert!(alert_id.is_some());
     }
 
+    #[test]
+    fn test_budget_alert_counts_only_current_period() {
+        let conn = establish_test_connection().unwrap();
+        budget_repository::set_budget(&conn, "Food", &Decimal::new(1000, 2), None).unwrap();
+        add_transaction_to_db(&conn, "2025-10-31,Dinner,8.00,expense,Food").unwrap();
+
+        let (_, alert_id) = add_transaction_to_db_with_id(&conn, "2025-11-01,Lunch,8.00,expense,Food").unwrap();
+        assert!(alert_id.is_none());
+
+        budget_repository::set_budget(&conn, "Food", &Decimal::new(1000, 2), Some(BudgetPeriod::Yearly)).unwrap();
+        let (_, alert_id) = add_transaction_to_db_with_id(&conn, "2025-11-02,Snack,1.00,expense,Food").unwrap();
+        assert!(alert_id.is_some());
+    }
+
     #[test]
     fn test_no_alert_for_income() {
         let conn = establish_test_connection().unwrap();
-        budget_repository::set_budget(&conn, "Salary", &Decimal::new(100, 2)).unwrap();
-        let alert_id = add_transaction_to_db(&conn, "2025-11-10,Salary,1000.00,income,Salary").unwrap();
+        budget_repository::set_budget(&conn, "Salary", &Decimal::new(100, 2), None).unwrap();
+        let (_, alert_id) = add_transaction_to_db_with_id(&conn, "2025-11-10,Salary,1000.00,income,Salary").unwrap();
 
         assert!(alert_id.is_none());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_suggest_categories() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Lunch,12.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Snacks,3.00,expense,Fast food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Bus,2.00,expense,Transport").unwrap();
+
+        assert_eq!(suggest_categories(&conn, "foo").unwrap(), vec!["Fast food", "Food"]);
+        assert_eq!(suggest_categories(&conn, "Transport costs").unwrap(), vec!["Transport"]);
+        assert_eq!(suggest_categories(&conn, "FOOD").unwrap(), vec!["Fast food"]);
+        assert!(suggest_categories(&conn, " ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_new_category_hint() {
+        let conn = establish_test_connection().unwrap();
+        assert_eq!(new_category_hint(&conn, "Food").unwrap(), None);
+
+        add_transaction_to_db(&conn, "2025-11-10,Lunch,12.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Bus,2.00,expense,Transport").unwrap();
+
+        assert_eq!(new_category_hint(&conn, "food").unwrap(), None);
+        assert_eq!(
+            new_category_hint(&conn, "Foods").unwrap().unwrap(),
+            "'Foods' is a new category. Did you mean: Food?"
+        );
+        assert_eq!(
+            new_category_hint(&conn, "Rent").unwrap().unwrap(),
+            "'Rent' is a new category. Existing categories: Food, Transport"
+        );
+    }
+
+    // Field generators for the randomized tests below. None of them produce commas, which
+    // would shift the fields, or surrounding whitespace, which is trimmed.
+
+    const TEXT_CHARS: &[char] = &['a', 'Z', '7', ' ', '-', '&', 'é', 'ß', '€', '日'];
+
+    fn random_text(rng: &mut fastrand::Rng, max_len: usize) -> String {
+        let len = rng.usize(1..=max_len);
+        let mut text: String = (0..len).map(|_| TEXT_CHARS[rng.usize(..TEXT_CHARS.len())]).collect();
+        text.replace_range(..text.chars().next().unwrap().len_utf8(), "x");
+        if text.ends_with(' ') {
+            text.pop();
+            text.push('y');
+        }
+        text
+    }
+
+    /// Zero-padded `Y-M-D` with each part possibly out of range; also whether it is a real date.
+    fn random_date_field(rng: &mut fastrand::Rng) -> (String, bool) {
+        let (year, month, day) = (rng.i32(1900..2100), rng.u32(0..=13), rng.u32(0..=32));
+        let valid = NaiveDate::from_ymd_opt(year, month, day).is_some();
+        (format!("{:04}-{:02}-{:02}", year, month, day), valid)
+    }
+
+    fn random_amount(rng: &mut fastrand::Rng) -> Decimal {
+        Decimal::new(rng.i64(-10_000_000_000..10_000_000_000), rng.u32(0..=10))
+    }
+
+    fn random_type_field(rng: &mut fastrand::Rng) -> (String, Option<TransactionType>) {
+        let (word, parsed) = match rng.usize(0..6) {
+            0 | 1 => ("income", Some(TransactionType::Income)),
+            2 | 3 => ("expense", Some(TransactionType::Expense)),
+            4 => ("transfer", None),
+            _ => ("incomes", None),
+        };
+        let field = word.chars().map(|c| if rng.bool() { c.to_ascii_uppercase() } else { c }).collect();
+        (field, parsed)
+    }
+
+    #[test]
+    fn test_create_transaction_random_fields() {
+        let mut rng = fastrand::Rng::with_seed(0xadd);
+        for _ in 0..3000 {
+            let (date, date_valid) = random_date_field(&mut rng);
+            let description = random_text(&mut rng, 300);
+            let amount = random_amount(&mut rng);
+            let (transaction_type, parsed_type) = random_type_field(&mut rng);
+            let category = random_text(&mut rng, 60);
+            let input = format!("{},{},{},{},{}", date, description, amount, transaction_type, category);
+
+            let valid = date_valid
+                && description.chars().count() <= MAX_DESCRIPTION_LENGTH
+                && amount.normalize().scale() <= repository::STORED_AMOUNT_DECIMALS
+                && parsed_type.is_some()
+                && category.chars().count() <= MAX_CATEGORY_LENGTH;
+            match create_transaction(&input) {
+                Ok(transaction) => {
+                    assert!(valid, "accepted invalid input: {:?}", input);
+                    assert_eq!(transaction.date.format("%Y-%m-%d").to_string(), date);
+                    assert_eq!(transaction.description, description);
+                    assert_eq!(transaction.amount, amount);
+                    assert_eq!(transaction.amount.scale(), amount.scale());
+                    assert_eq!(Some(transaction.transaction_type), parsed_type);
+                    assert_eq!(transaction.category, category);
+                }
+                Err(e) => assert!(!valid, "rejected valid input {:?}: {}", input, e),
+            }
+        }
+    }
+
+    #[test]
+    fn test_create_transaction_length_limits() {
+        let at_limit = |description: usize, category: usize| {
+            create_transaction(&format!("2025-01-01,{},1.00,expense,{}", "€".repeat(description), "日".repeat(category)))
+        };
+        assert!(at_limit(MAX_DESCRIPTION_LENGTH, MAX_CATEGORY_LENGTH).is_ok());
+        assert!(at_limit(MAX_DESCRIPTION_LENGTH + 1, 1).unwrap_err().to_string().contains("Description is too long"));
+        assert!(at_limit(1, MAX_CATEGORY_LENGTH + 1).unwrap_err().to_string().contains("Category is too long"));
+    }
+
+    #[test]
+    fn test_add_transaction_to_account() {
+        let conn = establish_test_connection().unwrap();
+        account_repository::add_account(&conn, "Savings", "EUR", &Decimal::ZERO).unwrap();
+
+        let (id, _) = add_transaction_to_account(&conn, "2025-11-10,Interest,2.00,income,Bank", "savings").unwrap();
+        let stored = repository::get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert_eq!(stored.account, "Savings");
+        assert_eq!(stored.currency_code, "EUR");
+        let (id, _) = add_transaction_to_db_with_id(&conn, "2025-11-10,Coffee,3.50,expense,Food").unwrap();
+        assert_eq!(repository::get_transaction_by_id(&conn, &id).unwrap().unwrap().account, DEFAULT_ACCOUNT);
+
+        let err = add_transaction_to_account(&conn, "2025-11-10,Coffee,3.50,expense,Food", "Checking").unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+        assert_eq!(repository::count_transactions(&conn).unwrap(), 2);
+    }
+}
@@ -1,6 +1,13 @@
-use crate::db::repository;
-use crate::models::transaction::{Transaction, TransactionType};
+use crate::config::Theme;
+use crate::db::{budget_repository, repository};
+use crate::error::AppError;
+use crate::format::format_amount;
+use crate::models::currency::Currency;
+use crate::models::budget::BudgetPeriod;
+use crate::models::transaction::{CategoryTotal, Transaction, TransactionFilter, TransactionOrder, TransactionType};
+use crate::operations::budget::{get_budget_utilization, BudgetUtilization};
 use chrono::NaiveDate;
+use base64::Engine;
 use crossterm::{
     event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     execute,
@@ -12,89 +19,204 @@ use ratatui::{
     widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
 };
 use rusqlite::Connection;
+use rust_decimal::Decimal;
 use std::cmp::{max, min};
-use std::io;
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+const FLASH_DURATION: Duration = Duration::from_millis(1500);
+const MAX_UNDO_ACTIONS: usize = 10;
+/// Rows fetched at a time; only this window of the filtered list is kept in memory.
+const WINDOW_ROWS: usize = 300;
+/// The window is refetched, centered on the selection, once fewer than this many rows are
+/// loaded beyond a page in either direction.
+const WINDOW_MARGIN: usize = 50;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum SortOrder {
-    DateDesc,
-    DateAsc,
+enum BudgetBarMode {
+    Off,
+    Inline,
 }
 
-impl SortOrder {
+impl BudgetBarMode {
     fn toggle(self) -> Self {
         match self {
-            SortOrder::DateDesc => SortOrder::DateAsc,
-            SortOrder::DateAsc => SortOrder::DateDesc,
-        }
-    }
-
-    fn label(self) -> &'static str {
-        match self {
-            SortOrder::DateDesc => "date ↓",
-            SortOrder::DateAsc => "date ↑",
+            BudgetBarMode::Off => BudgetBarMode::Inline,
+            BudgetBarMode::Inline => BudgetBarMode::Off,
         }
     }
 }
 
+/// The budget section never grows beyond this many bars; the rest is reached by scrolling.
+const MAX_BUDGET_BAR_ROWS: usize = 6;
+const BUDGET_BAR_WIDTH: usize = 20;
+const MAX_VISIBLE_COMPLETIONS: usize = 5;
+
+/// Shown by `?`; keep it in step with `handle_key`.
+const HELP_TEXT: &str = "\
+Moving around
+  ↑/↓            move the selection
+  PgUp/PgDn      move one page
+  Home/End       first/last row
+  g              go to the newest row on or before a date
+  Enter          details of the selected transaction (y copies its ID)
+
+Filtering and sorting
+  c              filter by category (Tab/→ completes, ↑/↓ pick a completion)
+  f              filter by text in the description
+  d              filter by date range, e.g. 2025-01-01..2025-01-31
+  a              filter by amount range, e.g. 10..500, 10.. or ..500
+  t              cycle the type filter: expense, income, any
+  A              cycle the account filter
+  o              cycle the sort order: date, amount, category
+  x              clear all filters
+
+Changing transactions
+  s              cycle the status: pending, cleared, reconciled
+  Space          mark or unmark the row and move down
+  D              delete the marked rows (asks first)
+  R              re-categorize the marked rows
+  u              undo the last delete or re-categorize
+
+Display
+  b              show or hide the budget bars of the current periods
+  S              show or hide the category totals of the filtered rows
+  [ / ]          scroll the budget bars
+  C              turn row colors on or off
+  r              reload from the database
+
+  ?              show or hide this help
+  q/Esc          quit (Esc first clears the marks)";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Mode {
     List,
     Details,
     Input(InputKind),
+    ConfirmDelete,
+    Help,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum InputKind {
     Category,
     DateRange,
+    AmountRange,
+    DescriptionFilter,
+    BulkCategory,
+    JumpToDate,
+}
+
+impl InputKind {
+    fn completes_categories(self) -> bool {
+        matches!(self, InputKind::Category | InputKind::BulkCategory)
+    }
+}
+
+/// A change made from the browse view that `u` can revert.
+#[derive(Debug, Clone)]
+enum UndoAction {
+    // One entry per batch delete so a single `u` restores the whole batch
+    DeletedTransactions(Vec<Transaction>),
+    // (id, old category)
+    RecategorizedTransactions(Vec<(String, String)>),
 }
 
 struct BrowseState {
     mode: Mode,
 
+    // Rows `window_start..window_start + transactions.len()` of the filtered, sorted list
     transactions: Vec<Transaction>,
-    filtered_indices: Vec<usize>,
+    window_start: usize,
+    // Number of rows matching the filters and their income/expense/net sums
+    virtual_count: usize,
+    totals_line: String,
 
+    // The selection is a position in the filtered list; the scroll offset is the first row shown
     table_state: TableState,
+    scroll_offset: usize,
+    // Rows marked with Space, as positions in the filtered list; cleared whenever the list is reloaded
+    selected_set: HashSet<usize>,
 
-    filter_category: Option<String>,
-    filter_type: Option<TransactionType>,
-    filter_from: Option<NaiveDate>,
-    filter_to: Option<NaiveDate>,
+    filter: TransactionFilter,
+    sort_order: TransactionOrder,
+    // Income/expense row colors, off for terminals that render them badly
+    use_colors: bool,
 
-    sort_order: SortOrder,
+    // Budget bars for each budget's current period, reloaded on refresh
+    budget_bar_mode: BudgetBarMode,
+    budget_utilization: Vec<BudgetUtilization>,
+    budget_scroll: usize,
+
+    // Category totals of the filtered rows, kept up to date only while the sidebar is shown
+    sidebar_visible: bool,
+    category_totals: Vec<CategoryTotal>,
 
     // Input modal
     input_buffer: String,
     input_error: Option<String>,
 
+    // Category autocomplete: every known category, loaded when the category input opens,
+    // and the ones matching the typed prefix
+    known_categories: Vec<String>,
+    completions: Vec<String>,
+    completion_index: Option<usize>,
+
     // Details view
     details_tx: Option<Transaction>,
 
+    // First line of `HELP_TEXT` shown in the help overlay
+    help_scroll: u16,
+
+    // Short-lived footer message and when it was set
+    flash_message: Option<(String, Instant)>,
+
+    // Most recent last, at most MAX_UNDO_ACTIONS entries
+    undo_stack: Vec<UndoAction>,
+
     // Cached per-draw
     last_page_size: usize,
 }
 
 impl BrowseState {
-    fn new(transactions: Vec<Transaction>) -> Self {
-        let mut state = Self {
+    /// An empty list; `load` fills it from the database.
+    fn new() -> Self {
+        Self {
             mode: Mode::List,
-            transactions,
-            filtered_indices: Vec::new(),
+            transactions: Vec::new(),
+            window_start: 0,
+            virtual_count: 0,
+            totals_line: String::new(),
             table_state: TableState::default(),
-            filter_category: None,
-            filter_type: None,
-            filter_from: None,
-            filter_to: None,
-            sort_order: SortOrder::DateDesc,
+            scroll_offset: 0,
+            selected_set: HashSet::new(),
+            filter: TransactionFilter::default(),
+            sort_order: TransactionOrder::DateDesc,
+            use_colors: true,
+            budget_bar_mode: BudgetBarMode::Off,
+            budget_utilization: Vec::new(),
+            budget_scroll: 0,
+            sidebar_visible: false,
+            category_totals: Vec::new(),
             input_buffer: String::new(),
             input_error: None,
+            known_categories: Vec::new(),
+            completions: Vec::new(),
+            completion_index: None,
             details_tx: None,
+            help_scroll: 0,
+            flash_message: None,
+            undo_stack: Vec::new(),
             last_page_size: 10,
-        };
-        state.recompute();
-        state
+        }
+    }
+
+    fn load(conn: &Connection) -> Result<Self, String> {
+        let mut state = Self::new();
+        state.reload(conn)?;
+        Ok(state)
     }
 
     fn selected_index(&self) -> Option<usize> {
@@ -103,87 +225,61 @@ impl BrowseState {
 
     fn selected_transaction(&self) -> Option<&Transaction> {
         let selected = self.selected_index()?;
-        let idx = *self.filtered_indices.get(selected)?;
-        self.transactions.get(idx)
+        self.transactions.get(selected.checked_sub(self.window_start)?)
     }
 
-    fn recompute(&mut self) {
-        self.filtered_indices = (0..self.transactions.len())
-            .filter(|&i| self.matches_filters(&self.transactions[i]))
-            .collect();
-
-        self.sort_filtered();
+    /// Counts the rows matching the filters and fetches the window around the selection.
+    /// Called whenever the filters, the sort order or the stored transactions change.
+    fn reload(&mut self, conn: &Connection) -> Result<(), String> {
+        self.virtual_count = repository::count_filtered_transactions(conn, &self.filter)?;
+        self.totals_line = totals_line(&repository::sum_filtered_transactions(conn, &self.filter)?);
+        if self.sidebar_visible {
+            self.category_totals = repository::sum_filtered_transactions_by_category(conn, &self.filter)?;
+        }
+        self.transactions.clear();
+        self.window_start = 0;
+        self.selected_set.clear();
 
-        if self.filtered_indices.is_empty() {
+        if self.virtual_count == 0 {
             self.table_state.select(None);
         } else {
             let new_selected = match self.table_state.selected() {
-                Some(sel) => min(sel, self.filtered_indices.len().saturating_sub(1)),
+                Some(sel) => min(sel, self.virtual_count - 1),
                 None => 0,
             };
             self.table_state.select(Some(new_selected));
         }
+        self.fill_window(conn)
     }
 
-    fn matches_filters(&self, tx: &Transaction) -> bool {
-        if let Some(t) = self.filter_type {
-            if tx.transaction_type != t {
-                return false;
-            }
-        }
-
-        if let Some(from) = self.filter_from {
-            if tx.date < from {
-                return false;
-            }
-        }
-        if let Some(to) = self.filter_to {
-            if tx.date > to {
-                return false;
-            }
-        }
-
-        if let Some(ref category) = self.filter_category {
-            if tx.category.to_lowercase() != category.to_lowercase() {
-                return false;
-            }
+    /// Refetches the window when the rows a page away from the selection (plus
+    /// `WINDOW_MARGIN`) are not all loaded.
+    fn fill_window(&mut self, conn: &Connection) -> Result<(), String> {
+        let Some(selected) = self.selected_index() else {
+            return Ok(());
+        };
+        let reach = max(1, self.last_page_size) + WINDOW_MARGIN;
+        let wanted_start = selected.saturating_sub(reach);
+        let wanted_end = min(self.virtual_count, selected + reach + 1);
+        if wanted_start >= self.window_start && wanted_end <= self.window_start + self.transactions.len() {
+            return Ok(());
         }
 
-        true
-    }
-
-    fn sort_filtered(&mut self) {
-        let txs = &self.transactions;
-        match self.sort_order {
-            SortOrder::DateDesc => {
-                self.filtered_indices.sort_by(|&a, &b| {
-                    let ta = &txs[a];
-                    let tb = &txs[b];
-                    tb.date
-                        .cmp(&ta.date)
-                        .then_with(|| tb.id.cmp(&ta.id))
-                });
-            }
-            SortOrder::DateAsc => {
-                self.filtered_indices.sort_by(|&a, &b| {
-                    let ta = &txs[a];
-                    let tb = &txs[b];
-                    ta.date
-                        .cmp(&tb.date)
-                        .then_with(|| ta.id.cmp(&tb.id))
-                });
-            }
-        }
+        let rows = max(WINDOW_ROWS, 2 * reach + 1);
+        self.window_start = selected.saturating_sub(rows / 2);
+        self.transactions =
+            repository::get_filtered_transactions(conn, &self.filter, self.sort_order, rows, self.window_start)?;
+        Ok(())
     }
 
     fn move_selection(&mut self, delta: i32) {
-        if self.filtered_indices.is_empty() {
+        if self.virtual_count == 0 {
             self.table_state.select(None);
             return;
         }
 
         let current = self.table_state.selected().unwrap_or(0) as i32;
-        let max_index = self.filtered_indices.len().saturating_sub(1) as i32;
+        let max_index = self.virtual_count.saturating_sub(1) as i32;
         let next = (current + delta).clamp(0, max_index) as usize;
         self.table_state.select(Some(next));
     }
@@ -199,26 +295,188 @@ impl BrowseState {
     }
 
     fn refresh_from_db(&mut self, conn: &Connection) -> Result<(), String> {
-        self.transactions = repository::get_all_transactions(conn)?;
-        self.recompute();
+        self.reload(conn)?;
+        self.refresh_budgets(conn)
+    }
+
+    /// Each budget is measured over its own current period, including carried-forward
+    /// amounts, like `check_budget_and_alert` does. Fullest first.
+    fn refresh_budgets(&mut self, conn: &Connection) -> Result<(), String> {
+        let today = chrono::Local::now().date_naive();
+        let mut rows = Vec::new();
+        for budget in budget_repository::get_all_budgets(conn)? {
+            let (spent, allowance, percent) = get_budget_utilization(conn, &budget.category, today)?;
+            // Only monthly budgets are the default, so the others name their period.
+            let label = match budget.period {
+                BudgetPeriod::Monthly => budget.category,
+                period => format!("{} ({})", budget.category, period.as_str()),
+            };
+            rows.push((label, spent, Some(allowance), percent));
+        }
+        rows.sort_by(|a, b| b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.0.cmp(&b.0)));
+        self.budget_utilization = rows;
+        self.scroll_budgets(0);
         Ok(())
     }
 
-    fn cycle_type_filter(&mut self) {
-        self.filter_type = match self.filter_type {
+    fn toggle_budget_bars(&mut self) {
+        self.budget_bar_mode = self.budget_bar_mode.toggle();
+    }
+
+    fn budget_bar_rows(&self) -> usize {
+        match self.budget_bar_mode {
+            BudgetBarMode::Off => 0,
+            BudgetBarMode::Inline => self.budget_utilization.len().clamp(1, MAX_BUDGET_BAR_ROWS),
+        }
+    }
+
+    fn scroll_budgets(&mut self, delta: i32) {
+        let max_scroll = self.budget_utilization.len().saturating_sub(MAX_BUDGET_BAR_ROWS) as i32;
+        self.budget_scroll = (self.budget_scroll as i32 + delta).clamp(0, max_scroll) as usize;
+    }
+
+    fn cycle_selected_status(&mut self, conn: &Connection) -> Result<(), String> {
+        let Some(idx) = self
+            .selected_index()
+            .and_then(|selected| selected.checked_sub(self.window_start))
+            .filter(|&idx| idx < self.transactions.len())
+        else {
+            return Ok(());
+        };
+
+        let tx = &mut self.transactions[idx];
+        let next = tx.status.next();
+        repository::update_transaction_status(conn, &tx.id, next)?;
+        tx.status = next;
+        Ok(())
+    }
+
+    /// Marks or unmarks the current row and moves to the next one.
+    fn toggle_row_selection(&mut self) {
+        let Some(selected) = self.selected_index() else {
+            return;
+        };
+        if !self.selected_set.remove(&selected) {
+            self.selected_set.insert(selected);
+        }
+        self.move_selection(1);
+    }
+
+    fn request_delete_selected(&mut self) {
+        if !self.selected_set.is_empty() {
+            self.mode = Mode::ConfirmDelete;
+        }
+    }
+
+    fn open_bulk_category_input(&mut self, conn: &Connection) -> Result<(), String> {
+        if self.selected_set.is_empty() {
+            return Ok(());
+        }
+        self.known_categories = repository::get_distinct_categories(conn)?;
+        self.start_input(InputKind::BulkCategory);
+        Ok(())
+    }
+
+    /// Applies the typed category to every marked row. An empty category keeps the modal open with an error.
+    fn commit_bulk_category(&mut self, conn: &Connection) -> Result<(), String> {
+        let marked = self.marked_transactions(conn)?;
+        let ids: Vec<&str> = marked.iter().map(|tx| tx.id.as_str()).collect();
+        match repository::update_transactions_category_batch(conn, &ids, &self.input_buffer) {
+            Ok(_) => {}
+            Err(AppError::InvalidInput(message)) => {
+                self.input_error = Some(message);
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        }
+        let old_categories = marked.into_iter().map(|tx| (tx.id, tx.category)).collect();
+        self.push_undo(UndoAction::RecategorizedTransactions(old_categories));
+        self.input_error = None;
+        self.mode = Mode::List;
+        self.refresh_from_db(conn)
+    }
+
+    fn delete_selected(&mut self, conn: &Connection) -> Result<usize, String> {
+        let marked = self.marked_transactions(conn)?;
+        let ids: Vec<&str> = marked.iter().map(|tx| tx.id.as_str()).collect();
+        let removed = repository::remove_transactions_batch(conn, &ids)?;
+        self.push_undo(UndoAction::DeletedTransactions(marked));
+        self.mode = Mode::List;
+        self.refresh_from_db(conn)?;
+        Ok(removed)
+    }
+
+    /// The marked rows, from the window when loaded and from the database otherwise.
+    fn marked_transactions(&self, conn: &Connection) -> Result<Vec<Transaction>, String> {
+        let mut marked = Vec::new();
+        for &row in &self.selected_set {
+            match row.checked_sub(self.window_start).and_then(|idx| self.transactions.get(idx)) {
+                Some(tx) => marked.push(tx.clone()),
+                None => marked.extend(repository::get_filtered_transactions(conn, &self.filter, self.sort_order, 1, row)?),
+            }
+        }
+        Ok(marked)
+    }
+
+    fn push_undo(&mut self, action: UndoAction) {
+        self.undo_stack.push(action);
+        if self.undo_stack.len() > MAX_UNDO_ACTIONS {
+            self.undo_stack.remove(0);
+        }
+    }
+
+    /// Reverts the most recent delete or re-categorize and reloads the list.
+    fn undo(&mut self, conn: &Connection) -> Result<(), String> {
+        let Some(action) = self.undo_stack.pop() else {
+            return Ok(());
+        };
+        let message = match action {
+            UndoAction::DeletedTransactions(transactions) => {
+                for tx in &transactions {
+                    repository::add_transaction(conn, tx)?;
+                }
+                format!("Restored {} deleted transactions", transactions.len())
+            }
+            UndoAction::RecategorizedTransactions(old_categories) => {
+                for (id, category) in &old_categories {
+                    repository::update_transactions_category_batch(conn, &[id.as_str()], category)?;
+                }
+                format!("Restored the category of {} transactions", old_categories.len())
+            }
+        };
+        self.refresh_from_db(conn)?;
+        self.flash(&message, Instant::now());
+        Ok(())
+    }
+
+    fn cycle_type_filter(&mut self, conn: &Connection) -> Result<(), String> {
+        self.filter.transaction_type = match self.filter.transaction_type {
             None => Some(TransactionType::Expense),
             Some(TransactionType::Expense) => Some(TransactionType::Income),
             Some(TransactionType::Income) => None,
         };
-        self.recompute();
+        self.reload(conn)
     }
 
-    fn clear_filters(&mut self) {
-        self.filter_category = None;
-        self.filter_type = None;
-        self.filter_from = None;
-        self.filter_to = None;
-        self.recompute();
+    /// Steps through (any) and then every account that has transactions, by name.
+    fn cycle_account_filter(&mut self, conn: &Connection) -> Result<(), String> {
+        let accounts = repository::get_distinct_accounts(conn)?;
+        let next = match &self.filter.account {
+            None => 0,
+            Some(current) => accounts.iter().position(|account| account == current).map_or(0, |i| i + 1),
+        };
+        self.filter.account = accounts.get(next).cloned();
+        self.reload(conn)
+    }
+
+    fn cycle_sort_order(&mut self, conn: &Connection) -> Result<(), String> {
+        self.sort_order = self.sort_order.next();
+        self.reload(conn)
+    }
+
+    fn clear_filters(&mut self, conn: &Connection) -> Result<(), String> {
+        self.filter = TransactionFilter::default();
+        self.reload(conn)
     }
 
     fn open_details(&mut self) {
@@ -231,78 +489,216 @@ impl BrowseState {
         self.mode = Mode::List;
     }
 
+    fn toggle_sidebar(&mut self, conn: &Connection) -> Result<(), String> {
+        self.sidebar_visible = !self.sidebar_visible;
+        self.category_totals = if self.sidebar_visible {
+            repository::sum_filtered_transactions_by_category(conn, &self.filter)?
+        } else {
+            Vec::new()
+        };
+        Ok(())
+    }
+
+    fn open_help(&mut self) {
+        self.help_scroll = 0;
+        self.mode = Mode::Help;
+    }
+
+    fn scroll_help(&mut self, delta: i32) {
+        let max_scroll = HELP_TEXT.lines().count().saturating_sub(1) as i32;
+        self.help_scroll = (self.help_scroll as i32 + delta).clamp(0, max_scroll) as u16;
+    }
+
+    /// Asks the terminal to copy the ID of the transaction shown in the details view to the
+    /// clipboard. Terminals without OSC 52 support ignore the request, so success is not known.
+    fn copy_details_id(&mut self) -> Result<(), String> {
+        let Some(id) = self.details_tx.as_ref().map(|tx| tx.id.clone()) else {
+            return Ok(());
+        };
+        let mut stdout = io::stdout();
+        stdout
+            .write_all(clipboard_sequence(&id).as_bytes())
+            .and_then(|_| stdout.flush())
+            .map_err(|e| format!("Failed to send ID to terminal clipboard: {}", e))?;
+        self.flash("Sent ID to terminal clipboard", Instant::now());
+        Ok(())
+    }
+
+    fn flash(&mut self, message: &str, now: Instant) {
+        self.flash_message = Some((message.to_string(), now));
+    }
+
+    fn expire_flash(&mut self, now: Instant) {
+        if let Some((_, shown_at)) = self.flash_message
+            && now.duration_since(shown_at) >= FLASH_DURATION
+        {
+            self.flash_message = None;
+        }
+    }
+
     fn start_input(&mut self, kind: InputKind) {
         self.input_buffer.clear();
         self.input_error = None;
 
         match kind {
             InputKind::Category => {
-                if let Some(ref c) = self.filter_category {
+                if let Some(ref c) = self.filter.category {
                     self.input_buffer = c.clone();
                 }
+                self.update_completions();
             }
             InputKind::DateRange => {
                 let from = self
-                    .filter_from
+                    .filter
+                    .from
                     .map(|d| d.format("%Y-%m-%d").to_string())
                     .unwrap_or_default();
                 let to = self
-                    .filter_to
+                    .filter
+                    .to
                     .map(|d| d.format("%Y-%m-%d").to_string())
                     .unwrap_or_default();
                 if !from.is_empty() || !to.is_empty() {
                     self.input_buffer = format!("{}..{}", from, to);
                 }
             }
+            InputKind::DescriptionFilter => {
+                if let Some(ref keyword) = self.filter.description {
+                    self.input_buffer = keyword.clone();
+                }
+            }
+            InputKind::BulkCategory => self.update_completions(),
+            InputKind::JumpToDate => {
+                if let Some(tx) = self.selected_transaction() {
+                    self.input_buffer = tx.date.format("%Y-%m-%d").to_string();
+                }
+            }
+            InputKind::AmountRange => {
+                if self.filter.amount_min.is_some() || self.filter.amount_max.is_some() {
+                    self.input_buffer = amount_range_label(self.filter.amount_min, self.filter.amount_max);
+                }
+            }
         }
 
         self.mode = Mode::Input(kind);
     }
 
+    fn open_category_input(&mut self, conn: &Connection) -> Result<(), String> {
+        self.known_categories = repository::get_distinct_categories(conn)?;
+        self.start_input(InputKind::Category);
+        Ok(())
+    }
+
+    /// Case-insensitive prefix match of the typed text against the known categories.
+    fn update_completions(&mut self) {
+        let prefix = self.input_buffer.trim().to_lowercase();
+        self.completions = self
+            .known_categories
+            .iter()
+            .filter(|c| c.to_lowercase().starts_with(&prefix))
+            .cloned()
+            .collect();
+        self.completion_index = None;
+    }
+
+    fn cycle_completion(&mut self, delta: i32) {
+        let len = self.completions.len() as i32;
+        if len == 0 {
+            return;
+        }
+        let next = match self.completion_index {
+            None if delta > 0 => 0,
+            None => len - 1,
+            Some(i) => (i as i32 + delta).rem_euclid(len),
+        };
+        self.completion_index = Some(next as usize);
+    }
+
+    /// Puts the highlighted completion (or the first one) into the input.
+    fn accept_completion(&mut self) {
+        let Some(completion) = self.completions.get(self.completion_index.unwrap_or(0)) else {
+            return;
+        };
+        self.input_buffer = completion.clone();
+        self.update_completions();
+    }
+
     fn cancel_input(&mut self) {
         self.input_error = None;
         self.mode = Mode::List;
     }
 
-    fn commit_input(&mut self, kind: InputKind) {
+    /// Selects the newest row dated on or before `target`. When every row is newer,
+    /// the oldest row is selected instead.
+    fn jump_to_date(&mut self, conn: &Connection, target: NaiveDate) -> Result<(), String> {
+        if self.virtual_count == 0 {
+            return Ok(());
+        }
+        let last = self.virtual_count - 1;
+        let rows = repository::find_filtered_rows_on_or_before(conn, &self.filter, self.sort_order, target)?;
+        let row = if self.sort_order == TransactionOrder::DateAsc {
+            rows.map_or(0, |(_, last_on_or_before)| last_on_or_before)
+        } else {
+            rows.map_or(last, |(first_on_or_before, _)| first_on_or_before)
+        };
+        self.table_state.select(Some(row));
+        self.fill_window(conn)
+    }
+
+    fn commit_input(&mut self, conn: &Connection, kind: InputKind) -> Result<(), String> {
         let raw = self.input_buffer.trim();
         match kind {
             InputKind::Category => {
-                if raw.is_empty() {
-                    self.filter_category = None;
-                } else {
-                    self.filter_category = Some(raw.to_string());
-                }
+                self.filter.category = (!raw.is_empty()).then(|| raw.to_string());
                 self.mode = Mode::List;
-                self.recompute();
+                self.reload(conn)?;
             }
-            InputKind::DateRange => {
-                if raw.is_empty() {
-                    self.filter_from = None;
-                    self.filter_to = None;
+            InputKind::DateRange => match if raw.is_empty() { Ok((None, None)) } else { parse_date_range(raw) } {
+                Ok((from, to)) => {
+                    self.filter.from = from;
+                    self.filter.to = to;
+                    self.input_error = None;
                     self.mode = Mode::List;
-                    self.recompute();
-                    return;
+                    self.reload(conn)?;
                 }
-
-                match parse_date_range(raw) {
-                    Ok((from, to)) => {
-                        self.filter_from = from;
-                        self.filter_to = to;
-                        self.input_error = None;
-                        self.mode = Mode::List;
-                        self.recompute();
-                    }
-                    Err(e) => {
-                        self.input_error = Some(e);
-                    }
+                Err(e) => {
+                    self.input_error = Some(e);
                 }
+            },
+            InputKind::DescriptionFilter => {
+                self.filter.description = (!raw.is_empty()).then(|| raw.to_string());
+                self.mode = Mode::List;
+                self.reload(conn)?;
             }
+            InputKind::BulkCategory => self.commit_bulk_category(conn)?,
+            InputKind::JumpToDate => match parse_iso_date(raw) {
+                Ok(target) => {
+                    self.input_error = None;
+                    self.mode = Mode::List;
+                    self.jump_to_date(conn, target)?;
+                }
+                Err(e) => {
+                    self.input_error = Some(e);
+                }
+            },
+            InputKind::AmountRange => match parse_amount_range(raw) {
+                Ok((min_amount, max_amount)) => {
+                    self.filter.amount_min = min_amount;
+                    self.filter.amount_max = max_amount;
+                    self.input_error = None;
+                    self.mode = Mode::List;
+                    self.reload(conn)?;
+                }
+                Err(e) => {
+                    self.input_error = Some(e);
+                }
+            },
         }
+        Ok(())
     }
 }
 
-pub fn run_browse(conn: &Connection) -> Result<(), String> {
+pub fn run_browse(conn: &Connection, theme: &Theme) -> Result<(), String> {
     enable_raw_mode().map_err(|e| format!("Failed to enable raw mode: {}", e))?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)
@@ -313,25 +709,46 @@ pub fn run_browse(conn: &Connection) -> Result<(), String> {
         let mut terminal = ratatui::Terminal::new(backend)
             .map_err(|e| format!("Failed to initialize terminal: {}", e))?;
 
-        let initial = repository::get_all_transactions(conn)?;
-        let mut state = BrowseState::new(initial);
+        let mut state = BrowseState::load(conn)?;
+        state.refresh_budgets(conn)?;
 
         loop {
+            state.expire_flash(Instant::now());
+            state.fill_window(conn)?;
             terminal
                 .draw(|frame| {
                     let size = frame.area();
+                    let budget_rows = state.budget_bar_rows();
+                    let budget_height = if budget_rows == 0 { 0 } else { budget_rows as u16 + 2 };
                     let layout = Layout::default()
                         .direction(Direction::Vertical)
                         .constraints([
                             Constraint::Length(3),
+                            Constraint::Length(budget_height),
                             Constraint::Min(5),
-                            Constraint::Length(2),
+                            Constraint::Length(1),
+                            Constraint::Length(1),
                         ])
                         .split(size);
 
-                    render_header(frame, layout[0], &state);
-                    render_table(frame, layout[1], &mut state);
-                    render_footer(frame, layout[2], &state);
+                    render_header(frame, layout[0], &state, theme);
+                    if budget_rows > 0 {
+                        render_budget_bars(frame, layout[1], &state);
+                    }
+                    if state.sidebar_visible {
+                        // The table gets the narrower area, so its page size and scrolling
+                        // follow the space it really has.
+                        let columns = Layout::default()
+                            .direction(Direction::Horizontal)
+                            .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+                            .split(layout[2]);
+                        render_table(frame, columns[0], &mut state, theme);
+                        render_category_sidebar(frame, columns[1], &state, theme);
+                    } else {
+                        render_table(frame, layout[2], &mut state, theme);
+                    }
+                    render_totals(frame, layout[3], &state, theme);
+                    render_footer(frame, layout[4], &state, theme);
 
                     if let Mode::Input(kind) = state.mode {
                         render_input_modal(frame, size, &state, kind);
@@ -340,6 +757,14 @@ pub fn run_browse(conn: &Connection) -> Result<(), String> {
                     if state.mode == Mode::Details {
                         render_details_modal(frame, size, &state);
                     }
+
+                    if state.mode == Mode::ConfirmDelete {
+                        render_confirm_delete_modal(frame, size, &state);
+                    }
+
+                    if state.mode == Mode::Help {
+                        render_help_modal(frame, size, &state);
+                    }
                 })
                 .map_err(|e| format!("Failed to draw terminal UI: {}", e))?;
 
@@ -348,11 +773,7 @@ pub fn run_browse(conn: &Connection) -> Result<(), String> {
             {
                 let event = event::read().map_err(|e| format!("Failed to read input: {}", e))?;
                 match event {
-                    Event::Key(key) => {
-                        if handle_key(conn, &mut state, key)? {
-                            break;
-                        }
-                    }
+                    Event::Key(key) if handle_key(conn, &mut state, key)? => break,
                     Event::Resize(_, _) => {}
                     _ => {}
                 }
@@ -376,11 +797,13 @@ fn handle_key(conn: &Connection, state: &mut BrowseState, key: KeyEvent) -> Resu
         return Ok(false);
     }
 
-    // Global quit in list mode
-    if state.mode == Mode::List {
-        if key.code == KeyCode::Char('q') || key.code == KeyCode::Esc {
-            return Ok(true);
-        }
+    // Esc first drops a multi-selection; otherwise q/Esc quit from the list
+    if state.mode == Mode::List && key.code == KeyCode::Esc && !state.selected_set.is_empty() {
+        state.selected_set.clear();
+        return Ok(false);
+    }
+    if state.mode == Mode::List && (key.code == KeyCode::Char('q') || key.code == KeyCode::Esc) {
+        return Ok(true);
     }
 
     match state.mode {
@@ -389,30 +812,53 @@ fn handle_key(conn: &Connection, state: &mut BrowseState, key: KeyEvent) -> Resu
             KeyCode::Down => state.move_selection(1),
             KeyCode::PageUp => state.page_up(),
             KeyCode::PageDown => state.page_down(),
-            KeyCode::Home => state.table_state.select(Some(0)),
-            KeyCode::End => {
-                if !state.filtered_indices.is_empty() {
-                    state
-                        .table_state
-                        .select(Some(state.filtered_indices.len().saturating_sub(1)));
-                }
-            }
+            KeyCode::Home if state.virtual_count > 0 => state.table_state.select(Some(0)),
+            KeyCode::End if state.virtual_count > 0 => state.table_state.select(Some(state.virtual_count - 1)),
             KeyCode::Enter => state.open_details(),
+            KeyCode::Char(' ') => state.toggle_row_selection(),
+            KeyCode::Char('D') => state.request_delete_selected(),
+            KeyCode::Char('R') => state.open_bulk_category_input(conn)?,
             KeyCode::Char('r') => state.refresh_from_db(conn)?,
-            KeyCode::Char('c') => state.start_input(InputKind::Category),
+            KeyCode::Char('u') => state.undo(conn)?,
+            KeyCode::Char('c') => state.open_category_input(conn)?,
             KeyCode::Char('d') => state.start_input(InputKind::DateRange),
-            KeyCode::Char('t') => state.cycle_type_filter(),
-            KeyCode::Char('s') => {
-                state.sort_order = state.sort_order.toggle();
-                state.recompute();
+            KeyCode::Char('g') => state.start_input(InputKind::JumpToDate),
+            KeyCode::Char('a') => state.start_input(InputKind::AmountRange),
+            KeyCode::Char('f') => state.start_input(InputKind::DescriptionFilter),
+            KeyCode::Char('t') => state.cycle_type_filter(conn)?,
+            KeyCode::Char('A') => state.cycle_account_filter(conn)?,
+            KeyCode::Char('s') => state.cycle_selected_status(conn)?,
+            KeyCode::Char('o') => state.cycle_sort_order(conn)?,
+            KeyCode::Char('x') => state.clear_filters(conn)?,
+            KeyCode::Char('C') => state.use_colors = !state.use_colors,
+            KeyCode::Char('b') => state.toggle_budget_bars(),
+            KeyCode::Char('S') => state.toggle_sidebar(conn)?,
+            KeyCode::Char('[') => state.scroll_budgets(-1),
+            KeyCode::Char(']') => state.scroll_budgets(1),
+            KeyCode::Char('?') => state.open_help(),
+            _ => {}
+        },
+        Mode::Help => match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => state.mode = Mode::List,
+            KeyCode::Up => state.scroll_help(-1),
+            KeyCode::Down => state.scroll_help(1),
+            KeyCode::PageUp => state.scroll_help(-(max(1, state.last_page_size) as i32)),
+            KeyCode::PageDown => state.scroll_help(max(1, state.last_page_size) as i32),
+            KeyCode::Home => state.help_scroll = 0,
+            _ => {}
+        },
+        Mode::ConfirmDelete => match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                state.delete_selected(conn)?;
             }
-            KeyCode::Char('x') => state.clear_filters(),
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => state.mode = Mode::List,
             _ => {}
         },
         Mode::Details => match key.code {
             KeyCode::Esc => state.close_details(),
             KeyCode::Char('q') => state.close_details(),
             KeyCode::Char('b') => state.close_details(),
+            KeyCode::Char('y') => state.copy_details_id()?,
             _ => {}
         },
         Mode::Input(kind) => {
@@ -426,13 +872,22 @@ fn handle_key(conn: &Connection, state: &mut BrowseState, key: KeyEvent) -> Resu
 
             match key.code {
                 KeyCode::Esc => state.cancel_input(),
-                KeyCode::Enter => state.commit_input(kind),
+                KeyCode::Enter => state.commit_input(conn, kind)?,
                 KeyCode::Backspace => {
                     state.input_buffer.pop();
+                    if kind.completes_categories() {
+                        state.update_completions();
+                    }
                 }
                 KeyCode::Char(ch) => {
                     state.input_buffer.push(ch);
+                    if kind.completes_categories() {
+                        state.update_completions();
+                    }
                 }
+                KeyCode::Down if kind.completes_categories() => state.cycle_completion(1),
+                KeyCode::Up if kind.completes_categories() => state.cycle_completion(-1),
+                KeyCode::Tab | KeyCode::Right if kind.completes_categories() => state.accept_completion(),
                 _ => {}
             }
         }
@@ -441,65 +896,187 @@ fn handle_key(conn: &Connection, state: &mut BrowseState, key: KeyEvent) -> Resu
     Ok(false)
 }
 
-fn render_header(frame: &mut ratatui::Frame, area: Rect, state: &BrowseState) {
+fn render_header(frame: &mut ratatui::Frame, area: Rect, state: &BrowseState, theme: &Theme) {
     let category = state
-        .filter_category
+        .filter
+        .category
         .as_deref()
         .unwrap_or("(any)")
         .to_string();
 
-    let ttype = match state.filter_type {
+    let description = match state.filter.description {
+        Some(ref keyword) => format!("\"{}\"", keyword),
+        None => "(any)".to_string(),
+    };
+
+    let ttype = match state.filter.transaction_type {
         None => "(any)",
         Some(TransactionType::Income) => "income",
         Some(TransactionType::Expense) => "expense",
     };
 
+    let account = state.filter.account.as_deref().unwrap_or("(any)");
+
     let from = state
-        .filter_from
+        .filter
+        .from
         .map(|d| d.format("%Y-%m-%d").to_string())
         .unwrap_or_else(|| "(any)".to_string());
     let to = state
-        .filter_to
+        .filter
+        .to
         .map(|d| d.format("%Y-%m-%d").to_string())
         .unwrap_or_else(|| "(any)".to_string());
 
-    let line = Line::from(vec![
-        Span::styled("FINO Browse", Style::default().fg(Color::Cyan).bold()),
+    let amount = if state.filter.amount_min.is_none() && state.filter.amount_max.is_none() {
+        "(any)".to_string()
+    } else {
+        amount_range_label(state.filter.amount_min, state.filter.amount_max)
+    };
+
+    let mut line = Line::from(vec![
+        Span::styled("FINO Browse", Style::default().fg(theme.header_fg).bold()),
         Span::raw("  "),
-        Span::styled(format!("Sort: {}", state.sort_order.label()), Style::default().fg(Color::White)),
+        Span::raw(format!("Sort: {}", state.sort_order.label())),
         Span::raw("  |  "),
         Span::raw(format!("Category: {}", category)),
         Span::raw("  |  "),
+        Span::raw(format!("Description: {}", description)),
+        Span::raw("  |  "),
         Span::raw(format!("Type: {}", ttype)),
         Span::raw("  |  "),
+        Span::raw(format!("Account: {}", account)),
+        Span::raw("  |  "),
         Span::raw(format!("Date: {}..{}", from, to)),
         Span::raw("  |  "),
-        Span::raw(format!("Rows: {}", state.filtered_indices.len())),
+        Span::raw(format!("Amount: {}", amount)),
+        Span::raw("  |  "),
+        Span::raw(format!("Rows: {}", state.virtual_count)),
     ]);
+    if !state.selected_set.is_empty() {
+        line.spans.push(Span::raw("  |  "));
+        line.spans.push(Span::styled(
+            format!("{} selected", state.selected_set.len()),
+            Style::default().fg(Color::Yellow).bold(),
+        ));
+    }
 
     let block = Block::default().borders(Borders::ALL);
     let paragraph = Paragraph::new(line).block(block).alignment(Alignment::Left);
     frame.render_widget(paragraph, area);
 }
 
-fn render_footer(frame: &mut ratatui::Frame, area: Rect, state: &BrowseState) {
-    let hint = match state.mode {
-        Mode::List => "↑/↓ move  PgUp/PgDn page  Enter details  c category  d dates  t type  s sort  r refresh  x clear  q/Esc exit",
-        Mode::Details => "Esc/q/ -> quits the app === b -> back",
-        Mode::Input(_) => "Type, Enter apply, Esc cancel",
+fn render_budget_bars(frame: &mut ratatui::Frame, area: Rect, state: &BrowseState) {
+    let total = state.budget_utilization.len();
+    let title = if total > MAX_BUDGET_BAR_ROWS {
+        format!(
+            "Budgets this period ({}-{} of {})",
+            state.budget_scroll + 1,
+            min(state.budget_scroll + MAX_BUDGET_BAR_ROWS, total),
+            total
+        )
+    } else {
+        "Budgets this period".to_string()
     };
+    let block = Block::default().title(title).borders(Borders::ALL);
 
-    let block = Block::default().borders(Borders::ALL);
+    if state.budget_utilization.is_empty() {
+        let empty = Paragraph::new("No budgets set").block(block).alignment(Alignment::Center);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let lines: Vec<Line> = state
+        .budget_utilization
+        .iter()
+        .skip(state.budget_scroll)
+        .take(MAX_BUDGET_BAR_ROWS)
+        .map(|(category, _, _, percent)| {
+            let color = if *percent > 100.0 { Color::Red } else { Color::Green };
+            Line::from(vec![
+                Span::styled(budget_bar(*percent, BUDGET_BAR_WIDTH), Style::default().fg(color)),
+                Span::styled(format!(" {:>4.0}% ", percent), Style::default().fg(color)),
+                Span::raw(category.clone()),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// `[████░░░]`-style bar; spending above the budget fills the whole bar.
+fn budget_bar(percent: f64, width: usize) -> String {
+    let filled = ((percent.clamp(0.0, 100.0) / 100.0) * width as f64).round() as usize;
+    format!("[{}{}]", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+/// OSC 52 escape that asks the terminal to put `text` on the system clipboard.
+/// Works over SSH and needs no display server, unlike talking to X11/Wayland directly.
+fn clipboard_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64::engine::general_purpose::STANDARD.encode(text))
+}
+
+/// `Income: €1,200.00  Expenses: €850.00  Net: +€350.00`, repeated per currency when the
+/// filtered rows use several.
+fn totals_line(totals: &[(String, Decimal, Decimal)]) -> String {
+    if totals.is_empty() {
+        return totals_line(&[(String::new(), Decimal::ZERO, Decimal::ZERO)]);
+    }
+    totals
+        .iter()
+        .map(|(currency_code, income, expenses)| {
+            let currency = Currency::from_code(currency_code);
+            let net = income - expenses;
+            let sign = if net > Decimal::ZERO { "+" } else { "" };
+            format!(
+                "Income: {}  Expenses: {}  Net: {}{}",
+                format_amount(*income, &currency),
+                format_amount(*expenses, &currency),
+                sign,
+                format_amount(net, &currency)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("  |  ")
+}
+
+fn render_totals(frame: &mut ratatui::Frame, area: Rect, state: &BrowseState, theme: &Theme) {
     frame.render_widget(
-        Paragraph::new(hint)
-            .block(block)
-            .alignment(Alignment::Left)
-            .wrap(Wrap { trim: true }),
+        Paragraph::new(state.totals_line.as_str()).style(Style::default().fg(theme.header_fg)),
         area,
     );
 }
 
-fn render_table(frame: &mut ratatui::Frame, area: Rect, state: &mut BrowseState) {
+fn footer_hint(state: &BrowseState) -> String {
+    match state.mode {
+        Mode::List => {
+            let undo = if state.undo_stack.is_empty() { "" } else { "u undo  " };
+            format!(
+                "? help  ↑/↓ move  PgUp/PgDn page  Enter details  Space select  D delete selected  R re-categorize selected  c category  f find  d dates  g go to date  a amount  t type  A account  s status  o sort  C colors  b budgets  S sidebar  [/] scroll budgets  {}r refresh  x clear  q/Esc exit",
+                undo
+            )
+        }
+        Mode::Details => "Esc/q/ -> quits the app === b -> back === y -> copy id".to_string(),
+        Mode::Input(_) => "Type, Enter apply, Esc cancel".to_string(),
+        Mode::ConfirmDelete => "y delete  n/Esc cancel".to_string(),
+        Mode::Help => "↑/↓ scroll  PgUp/PgDn page  Home top  Esc/q/? close".to_string(),
+    }
+}
+
+fn render_footer(frame: &mut ratatui::Frame, area: Rect, state: &BrowseState, theme: &Theme) {
+    // One line under the totals, so no border; hints that do not fit are cut off.
+    if let Some((ref message, _)) = state.flash_message {
+        frame.render_widget(
+            Paragraph::new(message.as_str()).style(Style::default().fg(theme.income_fg)),
+            area,
+        );
+        return;
+    }
+
+    frame.render_widget(Paragraph::new(footer_hint(state)).alignment(Alignment::Left), area);
+}
+
+fn render_table(frame: &mut ratatui::Frame, area: Rect, state: &mut BrowseState, theme: &Theme) {
     let block = Block::default().title("Transactions").borders(Borders::ALL);
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -510,22 +1087,45 @@ fn render_table(frame: &mut ratatui::Frame, area: Rect, state: &mut BrowseState)
         Cell::from("Amount").style(Style::default().bold()),
         Cell::from("Type").style(Style::default().bold()),
         Cell::from("Category").style(Style::default().bold()),
+        Cell::from("Status").style(Style::default().bold()),
         Cell::from("Id").style(Style::default().bold()),
     ])
-    .style(Style::default().fg(Color::White));
+    .style(Style::default().fg(theme.header_fg));
 
-    let rows = state
-        .filtered_indices
-        .iter()
-        .map(|&idx| &state.transactions[idx])
-        .map(|tx| {
+    // Estimate a page size based on the table height.
+    // Leave room for the header row.
+    state.last_page_size = inner.height.saturating_sub(2) as usize;
+    if state.last_page_size == 0 {
+        state.last_page_size = 1;
+    }
+
+    // Only the rows on screen are passed to the table, so the scroll offset that keeps the
+    // selection in view is tracked here instead of in `TableState`.
+    let visible = max(1, inner.height.saturating_sub(1) as usize);
+    state.scroll_offset = min(state.scroll_offset, state.virtual_count.saturating_sub(visible));
+    if let Some(selected) = state.selected_index() {
+        if selected < state.scroll_offset {
+            state.scroll_offset = selected;
+        } else if selected >= state.scroll_offset + visible {
+            state.scroll_offset = selected + 1 - visible;
+        }
+    }
+    let first = state.scroll_offset;
+    let mut table_state = TableState::default().with_selected(state.selected_index().map(|selected| selected - first));
+
+    let rows = (first..min(first + visible, state.virtual_count))
+        .filter_map(|row| {
+            let tx = state.transactions.get(row.checked_sub(state.window_start)?)?;
+            Some((state.selected_set.contains(&row), tx))
+        })
+        .map(|(marked, tx)| {
             let date = tx.date.format("%Y-%m-%d").to_string();
             let mut desc = tx.description.clone();
             if desc.len() > 42 {
                 desc.truncate(39);
                 desc.push_str("...");
             }
-            let amount = tx.amount.to_string();
+            let amount = format_amount(tx.amount, &Currency::from_code(&tx.currency_code));
             let ttype = match tx.transaction_type {
                 TransactionType::Income => "income",
                 TransactionType::Expense => "expense",
@@ -541,54 +1141,136 @@ fn render_table(frame: &mut ratatui::Frame, area: Rect, state: &mut BrowseState)
                 Cell::from(amount),
                 Cell::from(ttype),
                 Cell::from(tx.category.clone()),
+                Cell::from(tx.status.as_str()),
                 Cell::from(id_short),
             ])
+            .style(if marked {
+                row_style(tx, state.use_colors, theme).bg(Color::Blue)
+            } else {
+                row_style(tx, state.use_colors, theme)
+            })
         });
 
-    // Estimate a page size based on the table height.
-    // Leave room for the header row.
-    state.last_page_size = inner.height.saturating_sub(2) as usize;
-    if state.last_page_size == 0 {
-        state.last_page_size = 1;
-    }
-
     let widths = [
         Constraint::Length(10),
         Constraint::Percentage(40),
-        Constraint::Length(12),
+        Constraint::Length(14),
         Constraint::Length(8),
         Constraint::Length(14),
         Constraint::Length(10),
+        Constraint::Length(10),
     ];
 
     let table = Table::new(rows, widths)
         .header(header)
-        .row_highlight_style(Style::default().bg(Color::DarkGray).fg(Color::White).bold())
+        .row_highlight_style(Style::default().bg(theme.selected_bg).fg(theme.highlight_fg).bold())
         .highlight_symbol("➤ ")
         .column_spacing(1);
 
-    frame.render_stateful_widget(table, inner, &mut state.table_state);
+    frame.render_stateful_widget(table, inner, &mut table_state);
 
-    if state.filtered_indices.is_empty() {
+    if state.virtual_count == 0 {
         let empty = Paragraph::new("No transactions match the current filters")
             .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::DarkGray));
+            .style(Style::default().fg(theme.muted_fg));
+        frame.render_widget(empty, inner);
+    }
+}
+
+/// A compact version of the report's category table: one line per category with its count
+/// and total, colored like the rows. Lines that do not fit are summed up in a last line.
+fn render_category_sidebar(frame: &mut ratatui::Frame, area: Rect, state: &BrowseState, theme: &Theme) {
+    let block = Block::default().title("Categories").borders(Borders::ALL);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if state.category_totals.is_empty() {
+        let empty = Paragraph::new("No transactions")
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme.muted_fg));
         frame.render_widget(empty, inner);
+        return;
+    }
+
+    const COUNT_WIDTH: usize = 5;
+    const AMOUNT_WIDTH: usize = 12;
+    let category_width = (inner.width as usize).saturating_sub(COUNT_WIDTH + AMOUNT_WIDTH + 2).max(4);
+    let fit = |text: &str| -> String {
+        if text.chars().count() <= category_width {
+            format!("{:<width$}", text, width = category_width)
+        } else {
+            let cut: String = text.chars().take(category_width - 1).collect();
+            format!("{}…", cut)
+        }
+    };
+
+    let bold = Style::default().fg(theme.header_fg).bold();
+    let mut lines = vec![Line::from(vec![
+        Span::styled(fit("Category"), bold),
+        Span::styled(format!(" {:>COUNT_WIDTH$}", "Count"), bold),
+        Span::styled(format!(" {:>AMOUNT_WIDTH$}", "Amount"), bold),
+    ])];
+
+    let room = (inner.height as usize).saturating_sub(1);
+    let total = state.category_totals.len();
+    let shown = if total > room { room.saturating_sub(1) } else { total };
+    for category_total in &state.category_totals[..shown] {
+        let style = if !state.use_colors {
+            Style::default()
+        } else if category_total.transaction_type == TransactionType::Income {
+            Style::default().fg(theme.income_fg)
+        } else {
+            Style::default().fg(theme.expense_fg)
+        };
+        let amount = format_amount(category_total.total, &Currency::from_code(&category_total.currency_code));
+        lines.push(Line::from(vec![
+            Span::styled(fit(&category_total.category), style),
+            Span::styled(format!(" {:>COUNT_WIDTH$}", category_total.count), style),
+            Span::styled(format!(" {:>AMOUNT_WIDTH$}", amount), style),
+        ]));
+    }
+    if shown < total {
+        lines.push(Line::from(Span::styled(
+            format!("({} more)", total - shown),
+            Style::default().fg(theme.muted_fg),
+        )));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Base style of a table row; the selection highlight is drawn on top of it.
+fn row_style(tx: &Transaction, use_colors: bool, theme: &Theme) -> Style {
+    if !use_colors {
+        return Style::default();
+    }
+    match tx.transaction_type {
+        TransactionType::Income => Style::default().fg(theme.income_fg),
+        TransactionType::Expense => Style::default().fg(theme.expense_fg),
     }
 }
 
 fn render_input_modal(frame: &mut ratatui::Frame, area: Rect, state: &BrowseState, kind: InputKind) {
-    let popup_area = centered_rect(80, 30, area);
+    let popup_area = centered_rect(80, 40, area);
     frame.render_widget(Clear, popup_area);
 
+    let bulk_title = format!("Re-categorize {} selected transactions", state.selected_set.len());
     let title = match kind {
+        InputKind::BulkCategory => bulk_title.as_str(),
         InputKind::Category => "Filter Category",
         InputKind::DateRange => "Filter Date Range",
+        InputKind::AmountRange => "Filter Amount Range",
+        InputKind::DescriptionFilter => "Filter Description",
+        InputKind::JumpToDate => "Jump To Date",
     };
 
     let help = match kind {
-        InputKind::Category => "Enter category name (empty clears)",
+        InputKind::Category => "Enter category name (empty clears), ↑/↓ pick a completion, Tab/→ complete",
         InputKind::DateRange => "Enter range like 2025-01-01..2025-01-31 (empty clears)",
+        InputKind::AmountRange => "Enter range like 10..500, 10.. or ..500 (empty clears)",
+        InputKind::DescriptionFilter => "Enter text the description must contain (empty clears)",
+        InputKind::BulkCategory => "Enter the new category, ↑/↓ pick a completion, Tab/→ complete",
+        InputKind::JumpToDate => "Enter a date like 2025-01-15",
     };
 
     let mut lines = vec![
@@ -601,6 +1283,33 @@ fn render_input_modal(frame: &mut ratatui::Frame, area: Rect, state: &BrowseStat
         )]),
     ];
 
+    if kind.completes_categories() && !state.completions.is_empty() {
+        // Keep the highlighted completion inside the visible window.
+        let selected = state.completion_index.unwrap_or(0);
+        let first = selected.saturating_sub(MAX_VISIBLE_COMPLETIONS - 1);
+        for (i, completion) in state
+            .completions
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(MAX_VISIBLE_COMPLETIONS)
+        {
+            let style = if state.completion_index == Some(i) {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            lines.push(Line::from(vec![Span::raw("  "), Span::styled(completion.clone(), style)]));
+        }
+        let hidden = state.completions.len().saturating_sub(MAX_VISIBLE_COMPLETIONS);
+        if hidden > 0 {
+            lines.push(Line::from(Span::styled(
+                format!("  ({} more)", hidden),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+    }
+
     if let Some(ref err) = state.input_error {
         lines.push(Line::from(""));
         lines.push(Line::from(vec![Span::styled(
@@ -618,6 +1327,43 @@ fn render_input_modal(frame: &mut ratatui::Frame, area: Rect, state: &BrowseStat
     frame.render_widget(paragraph, popup_area);
 }
 
+fn render_confirm_delete_modal(frame: &mut ratatui::Frame, area: Rect, state: &BrowseState) {
+    let popup_area = centered_rect(50, 20, area);
+    frame.render_widget(Clear, popup_area);
+
+    let lines = vec![
+        Line::from(vec![Span::styled(
+            format!("Delete {} selected transactions?", state.selected_set.len()),
+            Style::default().fg(Color::Red).bold(),
+        )]),
+        Line::from(""),
+        Line::from("y = delete (u to undo), n/Esc = cancel"),
+    ];
+
+    let block = Block::default().borders(Borders::ALL).title("Confirm");
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        popup_area,
+    );
+}
+
+fn render_help_modal(frame: &mut ratatui::Frame, area: Rect, state: &BrowseState) {
+    let popup_area = centered_rect(90, 80, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default().borders(Borders::ALL).title("Keys (↑/↓ scroll, Esc/q/? close)");
+    frame.render_widget(
+        Paragraph::new(HELP_TEXT)
+            .block(block)
+            .alignment(Alignment::Left)
+            .scroll((state.help_scroll, 0)),
+        popup_area,
+    );
+}
+
 fn render_details_modal(frame: &mut ratatui::Frame, area: Rect, state: &BrowseState) {
     let popup_area = centered_rect(90, 60, area);
     frame.render_widget(Clear, popup_area);
@@ -650,10 +1396,19 @@ fn render_details_modal(frame: &mut ratatui::Frame, area: Rect, state: &BrowseSt
         Line::from(format!("Date: {}", tx.date.format("%Y-%m-%d"))),
         Line::from(format!("Type: {}", ttype)),
         Line::from(format!("Category: {}", tx.category)),
-        Line::from(format!("Amount: {}", tx.amount)),
+        Line::from(format!("Status: {}", tx.status.as_str())),
+        Line::from(format!("Account: {}", tx.account)),
+        Line::from(format!("Amount: {}", format_amount(tx.amount, &Currency::from_code(&tx.currency_code)))),
+        Line::from(format!(
+            "Tags: {}",
+            if tx.tags.is_empty() { "-".to_string() } else { tx.tags.join(", ") }
+        )),
         Line::from(""),
         Line::from("Description:"),
-        Line::from(format!("{}", tx.description)),
+        Line::from(tx.description.clone()),
+        Line::from(""),
+        Line::from("Notes:"),
+        Line::from(if tx.notes.is_empty() { "-".to_string() } else { tx.notes.clone() }),
         Line::from(""),
         Line::from(Span::styled(
             "Esc/q/b to go back",
@@ -671,7 +1426,7 @@ fn render_details_modal(frame: &mut ratatui::Frame, area: Rect, state: &BrowseSt
     );
 }
 
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+pub(crate) fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -720,33 +1475,880 @@ fn parse_date_range(input: &str) -> Result<(Option<NaiveDate>, Option<NaiveDate>
         Some(parse_iso_date(right)?)
     };
 
-    if let (Some(f), Some(t)) = (from, to) {
-        if f > t {
-            return Err("Invalid range: start date must be <= end date".to_string());
-        }
+    if let (Some(f), Some(t)) = (from, to)
+        && f > t
+    {
+        return Err("Invalid range: start date must be <= end date".to_string());
     }
 
     Ok((from, to))
 }
 
+/// Parses `min..max` where either side may be left out, e.g. `10..500`, `10..` or `..500`.
+/// An empty input clears both bounds.
+fn parse_amount_range(s: &str) -> Result<(Option<Decimal>, Option<Decimal>), String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Ok((None, None));
+    }
+
+    let (left, right) = s
+        .split_once("..")
+        .ok_or_else(|| "Invalid amount range. Use MIN..MAX, e.g. 10..500".to_string())?;
+
+    let parse = |value: &str| -> Result<Option<Decimal>, String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(None);
+        }
+        Decimal::from_str(value)
+            .map(Some)
+            .map_err(|_| format!("Invalid amount '{}'. Must be a valid number", value))
+    };
+    let min_amount = parse(left)?;
+    let max_amount = parse(right)?;
+
+    if let (Some(lo), Some(hi)) = (min_amount, max_amount)
+        && lo > hi
+    {
+        return Err("Invalid range: minimum must be <= maximum".to_string());
+    }
+
+    Ok((min_amount, max_amount))
+}
+
+fn amount_range_label(min_amount: Option<Decimal>, max_amount: Option<Decimal>) -> String {
+    format!(
+        "{}..{}",
+        min_amount.map(|v| v.to_string()).unwrap_or_default(),
+        max_amount.map(|v| v.to_string()).unwrap_or_default()
+    )
+}
+
 fn parse_iso_date(s: &str) -> Result<NaiveDate, String> {
     NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
         .map_err(|_| format!("Invalid date '{}'. Use YYYY-MM-DD.", s.trim()))
 }
 
 fn split_once_dash_range(s: &str) -> Option<(&str, &str)> {
-    // Try to split on the last '-' that separates two ISO dates.
-    // Example: 2025-01-01-2025-01-31
-    let bytes = s.as_bytes();
-    for i in (0..bytes.len()).rev() {
-        if bytes[i] == b'-' {
-            let (a, b) = s.split_at(i);
-            let b = &b[1..];
-            // Heuristic: both sides should look like ISO date lengths.
-            if a.trim().len() >= 10 && b.trim().len() >= 10 {
-                return Some((a, b));
+    // The dates contain dashes too, so use the first dash with a valid date on both sides.
+    // Example: 2025-01-01-2025-01-31, or 2025-1-5-2025-2-1 with unpadded months and days
+    s.match_indices('-')
+        .map(|(i, _)| (&s[..i], &s[i + 1..]))
+        .find(|(a, b)| parse_iso_date(a).is_ok() && parse_iso_date(b).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Datelike;
+    use crate::db::connection::establish_test_connection;
+    use crate::models::transaction::TransactionStatus;
+    use crate::db::account_repository;
+    use crate::operations::add::{add_transaction_to_account, add_transaction_to_db, add_transaction_to_db_with_id};
+    use crate::operations::budget::{set_budget_db, set_carry_forward_db};
+
+    #[test]
+    fn test_cycle_selected_status_persists() {
+        let conn = establish_test_connection().unwrap();
+        let (id, _) = add_transaction_to_db_with_id(&conn, "2025-11-10,Salary,1500.00,income,Job").unwrap();
+        let mut state = BrowseState::load(&conn).unwrap();
+
+        state.cycle_selected_status(&conn).unwrap();
+        assert_eq!(state.selected_transaction().unwrap().status, TransactionStatus::Reconciled);
+        state.cycle_selected_status(&conn).unwrap();
+        assert_eq!(state.selected_transaction().unwrap().status, TransactionStatus::Pending);
+
+        let stored = repository::get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert_eq!(stored.status, TransactionStatus::Pending);
+    }
+
+    #[test]
+    fn test_budget_bar() {
+        assert_eq!(budget_bar(70.0, 10), "[███████░░░]");
+        assert_eq!(budget_bar(0.0, 4), "[░░░░]");
+        assert_eq!(budget_bar(150.0, 4), "[████]");
+    }
+
+    #[test]
+    fn test_refresh_budgets_caches_budgeted_categories() {
+        let conn = establish_test_connection().unwrap();
+        let today = chrono::Local::now().date_naive().format("%Y-%m-%d").to_string();
+        set_budget_db(&conn, "Food", "10", None, None).unwrap();
+        add_transaction_to_db(&conn, &format!("{},Lunch,12.00,expense,Food", today)).unwrap();
+        add_transaction_to_db(&conn, &format!("{},Book,5.00,expense,Hobby", today)).unwrap();
+        let mut state = BrowseState::new();
+        assert_eq!(state.budget_bar_rows(), 0);
+
+        state.refresh_from_db(&conn).unwrap();
+        assert_eq!(state.transactions.len(), 2);
+        assert_eq!(state.budget_utilization.len(), 1);
+        assert_eq!(state.budget_utilization[0].0, "Food");
+        assert_eq!(state.budget_utilization[0].3, 120.0);
+
+        state.toggle_budget_bars();
+        assert_eq!(state.budget_bar_mode, BudgetBarMode::Inline);
+        assert_eq!(state.budget_bar_rows(), 1);
+    }
+
+    #[test]
+    fn test_budget_bars_use_each_budget_period() {
+        let conn = establish_test_connection().unwrap();
+        let today = chrono::Local::now().date_naive();
+        let (week_start, _) = BudgetPeriod::Weekly.bounds(today);
+        let (last_month_start, _) = BudgetPeriod::Monthly.bounds(BudgetPeriod::Monthly.bounds(today).0 - chrono::Days::new(1));
+        set_budget_db(&conn, "Coffee", "20", Some("weekly"), None).unwrap();
+        set_budget_db(&conn, "Travel", "1000", Some("yearly"), None).unwrap();
+        set_budget_db(&conn, "Food", "100", None, None).unwrap();
+        set_carry_forward_db(&conn, "Food", true).unwrap();
+        add_transaction_to_db(&conn, &format!("{},Espresso,10.00,expense,Coffee", week_start)).unwrap();
+        // Spent in an earlier week of the year, so outside the weekly budget's period.
+        add_transaction_to_db(&conn, &format!("{},Beans,30.00,expense,Coffee", week_start - chrono::Days::new(7))).unwrap();
+        add_transaction_to_db(&conn, &format!("{},Train,150.00,expense,Travel", today)).unwrap();
+        add_transaction_to_db(&conn, &format!("{},Groceries,60.00,expense,Food", last_month_start)).unwrap();
+        add_transaction_to_db(&conn, &format!("{},Groceries,70.00,expense,Food", today)).unwrap();
+
+        let mut state = BrowseState::new();
+        state.refresh_budgets(&conn).unwrap();
+        let bars: Vec<(&str, Option<Decimal>, f64)> =
+            state.budget_utilization.iter().map(|(label, _, budget, percent)| (label.as_str(), *budget, *percent)).collect();
+        // The weekly budget only counts this week; Food gets last month's 40 left over.
+        assert_eq!(
+            bars,
+            vec![
+                ("Coffee (weekly)", Some(Decimal::new(20, 0)), 50.0),
+                ("Food", Some(Decimal::new(140, 0)), 50.0),
+                ("Travel (yearly)", Some(Decimal::new(1000, 0)), 15.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_budget_bars_cap_rows_and_scroll() {
+        let conn = establish_test_connection().unwrap();
+        for i in 0..8 {
+            set_budget_db(&conn, &format!("Category{}", i), "100", None, None).unwrap();
+        }
+        let mut state = BrowseState::new();
+        state.refresh_budgets(&conn).unwrap();
+        state.toggle_budget_bars();
+
+        assert_eq!(state.budget_bar_rows(), MAX_BUDGET_BAR_ROWS);
+        state.scroll_budgets(5);
+        assert_eq!(state.budget_scroll, 2);
+        state.scroll_budgets(-3);
+        assert_eq!(state.budget_scroll, 0);
+    }
+
+    #[test]
+    fn test_category_completions() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Lunch,12.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Snacks,3.00,expense,Fast food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Bus,2.00,expense,Transport").unwrap();
+        let mut state = BrowseState::load(&conn).unwrap();
+
+        state.open_category_input(&conn).unwrap();
+        assert_eq!(state.mode, Mode::Input(InputKind::Category));
+        assert_eq!(state.completions.len(), 3);
+
+        state.input_buffer.push('f');
+        state.update_completions();
+        assert_eq!(state.completions, vec!["Fast food", "Food"]);
+        assert_eq!(state.completion_index, None);
+
+        state.cycle_completion(1);
+        state.cycle_completion(1);
+        assert_eq!(state.completion_index, Some(1));
+        state.cycle_completion(1);
+        assert_eq!(state.completion_index, Some(0));
+        state.cycle_completion(-1);
+        assert_eq!(state.completion_index, Some(1));
+
+        state.accept_completion();
+        assert_eq!(state.input_buffer, "Food");
+        assert_eq!(state.completions, vec!["Food"]);
+
+        state.commit_input(&conn, InputKind::Category).unwrap();
+        assert_eq!(state.filter.category.as_deref(), Some("Food"));
+        assert_eq!(state.virtual_count, 1);
+    }
+
+    #[test]
+    fn test_accept_completion_without_selection_uses_first() {
+        let mut state = BrowseState::new();
+        state.known_categories = vec!["Travel".to_string(), "Transport".to_string()];
+        state.input_buffer = "TR".to_string();
+        state.update_completions();
+
+        state.accept_completion();
+        assert_eq!(state.input_buffer, "Travel");
+
+        state.input_buffer = "x".to_string();
+        state.update_completions();
+        state.accept_completion();
+        assert_eq!(state.input_buffer, "x");
+    }
+
+    #[test]
+    fn test_parse_amount_range() {
+        assert_eq!(parse_amount_range("10..500").unwrap(), (Some(Decimal::from(10)), Some(Decimal::from(500))));
+        assert_eq!(parse_amount_range(" 2.50 .. ").unwrap(), (Some(Decimal::new(250, 2)), None));
+        assert_eq!(parse_amount_range("..500").unwrap(), (None, Some(Decimal::from(500))));
+        assert_eq!(parse_amount_range("").unwrap(), (None, None));
+        assert!(parse_amount_range("500..10").is_err());
+        assert!(parse_amount_range("abc..10").unwrap_err().contains("Invalid amount"));
+        assert!(parse_amount_range("10").is_err());
+    }
+
+    #[test]
+    fn test_amount_range_filter() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Coffee,4.50,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-11,Groceries,45.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-12,Salary,1500.00,income,Job").unwrap();
+        let mut state = BrowseState::load(&conn).unwrap();
+
+        state.start_input(InputKind::AmountRange);
+        state.input_buffer = "10..1500".to_string();
+        state.commit_input(&conn, InputKind::AmountRange).unwrap();
+        assert_eq!(state.mode, Mode::List);
+        assert_eq!(state.virtual_count, 2);
+
+        state.start_input(InputKind::AmountRange);
+        assert_eq!(state.input_buffer, "10..1500");
+        state.input_buffer = "..10".to_string();
+        state.commit_input(&conn, InputKind::AmountRange).unwrap();
+        assert_eq!(state.virtual_count, 1);
+        assert_eq!(state.selected_transaction().unwrap().description, "Coffee");
+
+        state.start_input(InputKind::AmountRange);
+        state.input_buffer = "x..".to_string();
+        state.commit_input(&conn, InputKind::AmountRange).unwrap();
+        assert_eq!(state.mode, Mode::Input(InputKind::AmountRange));
+        assert!(state.input_error.is_some());
+        state.cancel_input();
+
+        state.clear_filters(&conn).unwrap();
+        assert_eq!(state.virtual_count, 3);
+    }
+
+    #[test]
+    fn test_description_filter() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Coffee at Cafe,4.50,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-20,Iced coffee,3.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-12,Bus ticket,2.00,expense,Transport").unwrap();
+        let mut state = BrowseState::load(&conn).unwrap();
+        let matching = |state: &mut BrowseState| -> Vec<String> {
+            state.reload(&conn).unwrap();
+            let mut names: Vec<String> = state.transactions.iter().map(|t| t.description.clone()).collect();
+            names.sort();
+            names
+        };
+        assert_eq!(matching(&mut state).len(), 3);
+
+        state.start_input(InputKind::DescriptionFilter);
+        state.input_buffer = "COFFEE".to_string();
+        state.commit_input(&conn, InputKind::DescriptionFilter).unwrap();
+        assert_eq!(matching(&mut state), vec!["Coffee at Cafe", "Iced coffee"]);
+
+        // Combined with the category and date filters.
+        state.filter.category = Some("transport".to_string());
+        assert!(matching(&mut state).is_empty());
+        state.filter.category = Some("food".to_string());
+        state.filter.from = Some(NaiveDate::from_ymd_opt(2025, 11, 15).unwrap());
+        assert_eq!(matching(&mut state), vec!["Iced coffee"]);
+
+        state.start_input(InputKind::DescriptionFilter);
+        assert_eq!(state.input_buffer, "COFFEE");
+        state.input_buffer = "  ".to_string();
+        state.commit_input(&conn, InputKind::DescriptionFilter).unwrap();
+        assert_eq!(state.filter.description, None);
+        assert_eq!(matching(&mut state), vec!["Iced coffee"]);
+
+        state.filter.from = None;
+        assert_eq!(matching(&mut state), vec!["Coffee at Cafe", "Iced coffee"]);
+    }
+
+    #[test]
+    fn test_account_filter() {
+        let conn = establish_test_connection().unwrap();
+        account_repository::add_account(&conn, "Card", "EUR", &Decimal::ZERO).unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Coffee,4.50,expense,Food").unwrap();
+        add_transaction_to_account(&conn, "2025-11-11,Groceries,30.00,expense,Food", "card").unwrap();
+        add_transaction_to_account(&conn, "2025-11-12,Refund,5.00,income,Food", "card").unwrap();
+        let mut state = BrowseState::load(&conn).unwrap();
+        assert_eq!(state.virtual_count, 3);
+
+        state.cycle_account_filter(&conn).unwrap();
+        assert_eq!(state.filter.account.as_deref(), Some("Card"));
+        assert_eq!(state.virtual_count, 2);
+        state.cycle_type_filter(&conn).unwrap();
+        assert_eq!(state.virtual_count, 1);
+        state.cycle_type_filter(&conn).unwrap();
+        state.cycle_type_filter(&conn).unwrap();
+
+        state.cycle_account_filter(&conn).unwrap();
+        assert_eq!(state.filter.account.as_deref(), Some("default"));
+        assert_eq!(state.virtual_count, 1);
+        state.cycle_account_filter(&conn).unwrap();
+        assert_eq!(state.filter.account, None);
+        assert_eq!(state.virtual_count, 3);
+    }
+
+    #[test]
+    fn test_totals_follow_filters() {
+        let conn = establish_test_connection().unwrap();
+        account_repository::add_account(&conn, "Card", "EUR", &Decimal::ZERO).unwrap();
+        add_transaction_to_account(&conn, "2025-11-10,Salary,1200.00,income,Job", "card").unwrap();
+        add_transaction_to_account(&conn, "2025-11-11,Rent,800.00,expense,Housing", "card").unwrap();
+        add_transaction_to_account(&conn, "2025-11-12,Groceries,50.00,expense,Food", "card").unwrap();
+        let mut state = BrowseState::load(&conn).unwrap();
+        assert_eq!(state.totals_line, "Income: €1,200.00  Expenses: €850.00  Net: +€350.00");
+
+        state.cycle_type_filter(&conn).unwrap();
+        assert_eq!(state.totals_line, "Income: €0.00  Expenses: €850.00  Net: -€850.00");
+
+        state.clear_filters(&conn).unwrap();
+        state.input_buffer = "food".to_string();
+        state.commit_input(&conn, InputKind::Category).unwrap();
+        assert_eq!(state.totals_line, "Income: €0.00  Expenses: €50.00  Net: -€50.00");
+
+        state.input_buffer = "nothing".to_string();
+        state.commit_input(&conn, InputKind::DescriptionFilter).unwrap();
+        assert_eq!(state.totals_line, "Income: 0.00  Expenses: 0.00  Net: 0.00");
+
+        // Amounts in other currencies get their own totals.
+        state.clear_filters(&conn).unwrap();
+        add_transaction_to_db(&conn, "2025-11-13,Coffee,4.50,expense,Food").unwrap();
+        state.refresh_from_db(&conn).unwrap();
+        assert_eq!(
+            state.totals_line,
+            "Income: 0.00  Expenses: 4.50  Net: -4.50  |  Income: €1,200.00  Expenses: €850.00  Net: +€350.00"
+        );
+    }
+
+    #[test]
+    fn test_category_sidebar_follows_filters() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Salary,1200.00,income,Job").unwrap();
+        add_transaction_to_db(&conn, "2025-11-11,Groceries,45.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-12,Coffee,4.50,expense,food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-13,Bus,2.00,expense,Transport").unwrap();
+        let mut state = BrowseState::load(&conn).unwrap();
+        assert!(state.category_totals.is_empty());
+
+        handle_key(&conn, &mut state, KeyEvent::new(KeyCode::Char('S'), KeyModifiers::NONE)).unwrap();
+        assert!(state.sidebar_visible);
+        let summary = |state: &BrowseState| -> Vec<(String, usize)> {
+            state.category_totals.iter().map(|total| (total.category.to_lowercase(), total.count)).collect()
+        };
+        assert_eq!(summary(&state), vec![("food".to_string(), 2), ("transport".to_string(), 1), ("job".to_string(), 1)]);
+        assert_eq!(state.category_totals[0].total, Decimal::new(4950, 2));
+
+        state.cycle_type_filter(&conn).unwrap();
+        assert_eq!(summary(&state), vec![("food".to_string(), 2), ("transport".to_string(), 1)]);
+        state.input_buffer = "coffee".to_string();
+        state.commit_input(&conn, InputKind::DescriptionFilter).unwrap();
+        assert_eq!(summary(&state), vec![("food".to_string(), 1)]);
+        state.clear_filters(&conn).unwrap();
+
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(40, 6)).unwrap();
+        terminal.draw(|frame| render_category_sidebar(frame, frame.area(), &state, &Theme::default())).unwrap();
+        let lines: Vec<String> = terminal
+            .backend()
+            .buffer()
+            .content()
+            .chunks(40)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect();
+        assert_eq!(lines[1], "│Category            Count       Amount│");
+        assert_eq!(lines[2], "│Food                    2        49.50│");
+        assert_eq!(lines[3], "│Transport               1         2.00│");
+        assert_eq!(lines[4], "│Job                     1     1,200.00│");
+
+        // Without room for every category the last line counts the rest.
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(40, 5)).unwrap();
+        terminal.draw(|frame| render_category_sidebar(frame, frame.area(), &state, &Theme::default())).unwrap();
+        let screen: String = terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("│Food "));
+        assert!(screen.contains("│(2 more) "));
+        assert!(!screen.contains("Transport"));
+
+        handle_key(&conn, &mut state, KeyEvent::new(KeyCode::Char('S'), KeyModifiers::NONE)).unwrap();
+        assert!(!state.sidebar_visible);
+        assert!(state.category_totals.is_empty());
+    }
+
+    #[test]
+    fn test_sort_orders() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Coffee,4.50,expense,food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-12,Salary,1500.00,income,Job").unwrap();
+        add_transaction_to_db(&conn, "2025-11-11,Bus,2.00,expense,Transport").unwrap();
+        add_transaction_to_db(&conn, "2025-11-13,Groceries,45.00,expense,Food").unwrap();
+        let mut state = BrowseState::load(&conn).unwrap();
+        let order = |state: &BrowseState| -> Vec<String> {
+            state.transactions.iter().map(|t| t.description.clone()).collect()
+        };
+
+        let expected = [
+            (TransactionOrder::DateDesc, ["Groceries", "Salary", "Bus", "Coffee"]),
+            (TransactionOrder::DateAsc, ["Coffee", "Bus", "Salary", "Groceries"]),
+            (TransactionOrder::AmountDesc, ["Salary", "Groceries", "Coffee", "Bus"]),
+            (TransactionOrder::AmountAsc, ["Bus", "Coffee", "Groceries", "Salary"]),
+            // "food" and "Food" compare equal, so the newer one comes first.
+            (TransactionOrder::CategoryAsc, ["Groceries", "Coffee", "Salary", "Bus"]),
+            (TransactionOrder::CategoryDesc, ["Bus", "Salary", "Groceries", "Coffee"]),
+        ];
+        for (sort_order, descriptions) in expected {
+            assert_eq!(state.sort_order, sort_order);
+            assert_eq!(order(&state), descriptions, "{}", sort_order.label());
+            state.cycle_sort_order(&conn).unwrap();
+        }
+        assert_eq!(state.sort_order, TransactionOrder::DateDesc);
+    }
+
+    #[test]
+    fn test_only_a_window_of_rows_is_loaded() {
+        let conn = establish_test_connection().unwrap();
+        let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let transactions: Vec<Transaction> = (0..1000)
+            .map(|i| {
+                Transaction::new(
+                    uuid::Uuid::new_v4().to_string(),
+                    start + chrono::Duration::days(i),
+                    format!("Row {}", i),
+                    Decimal::ONE,
+                    TransactionType::Expense,
+                    "Food".to_string(),
+                )
+            })
+            .collect();
+        repository::add_transaction_batch(&conn, &transactions).unwrap();
+
+        let mut state = BrowseState::load(&conn).unwrap();
+        assert_eq!(state.virtual_count, 1000);
+        assert_eq!(state.transactions.len(), WINDOW_ROWS);
+        assert_eq!(state.selected_transaction().unwrap().description, "Row 999");
+
+        // The last row is marked, then the window moves away from it.
+        handle_key(&conn, &mut state, KeyEvent::new(KeyCode::End, KeyModifiers::NONE)).unwrap();
+        state.fill_window(&conn).unwrap();
+        assert!(state.window_start > 0);
+        assert_eq!(state.transactions.len(), 1000 - state.window_start);
+        assert_eq!(state.selected_transaction().unwrap().description, "Row 0");
+        state.toggle_row_selection();
+
+        handle_key(&conn, &mut state, KeyEvent::new(KeyCode::Home, KeyModifiers::NONE)).unwrap();
+        state.fill_window(&conn).unwrap();
+        assert_eq!(state.window_start, 0);
+        state.toggle_row_selection();
+        assert_eq!(state.delete_selected(&conn).unwrap(), 2);
+        assert_eq!(state.virtual_count, 998);
+        let remaining = repository::get_filtered_transactions(&conn, &TransactionFilter::default(), TransactionOrder::DateAsc, 1, 0).unwrap();
+        assert_eq!(remaining[0].description, "Row 1");
+
+        state.jump_to_date(&conn, start + chrono::Duration::days(500)).unwrap();
+        assert_eq!(state.selected_index(), Some(498));
+        assert_eq!(state.selected_transaction().unwrap().description, "Row 500");
+    }
+
+    #[test]
+    fn test_table_scrolls_to_selection() {
+        let conn = establish_test_connection().unwrap();
+        for day in 1..=20 {
+            add_transaction_to_db(&conn, &format!("2025-01-{:02},Day {},1.00,expense,Food", day, day)).unwrap();
+        }
+        let mut state = BrowseState::load(&conn).unwrap();
+        // 12 lines: two borders and the header leave nine rows.
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 12)).unwrap();
+        let selected_line = |terminal: &ratatui::Terminal<ratatui::backend::TestBackend>| -> String {
+            let buffer = terminal.backend().buffer();
+            (0..buffer.area.height)
+                .map(|y| (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+                .find(|line| line.contains('➤'))
+                .unwrap()
+        };
+
+        state.move_selection(12);
+        terminal.draw(|frame| render_table(frame, frame.area(), &mut state, &Theme::default())).unwrap();
+        assert_eq!(state.scroll_offset, 4);
+        assert!(selected_line(&terminal).contains("Day 8 "));
+
+        state.move_selection(-10);
+        terminal.draw(|frame| render_table(frame, frame.area(), &mut state, &Theme::default())).unwrap();
+        assert_eq!(state.scroll_offset, 2);
+        assert!(selected_line(&terminal).contains("Day 18 "));
+    }
+
+    #[test]
+    fn test_row_style() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Coffee,4.50,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-12,Salary,1500.00,income,Job").unwrap();
+        let transactions = repository::get_all_transactions(&conn).unwrap();
+        let coffee = transactions.iter().find(|t| t.description == "Coffee").unwrap();
+        let salary = transactions.iter().find(|t| t.description == "Salary").unwrap();
+
+        let dark = Theme::default();
+        assert_eq!(row_style(salary, true, &dark).fg, Some(Color::LightGreen));
+        assert_eq!(row_style(coffee, true, &dark).fg, Some(Color::LightRed));
+        assert_eq!(row_style(coffee, false, &dark), Style::default());
+        assert_eq!(row_style(coffee, true, &Theme::light()).fg, Some(Color::Red));
+    }
+
+    #[test]
+    fn test_multi_select_delete() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-11-13,Groceries,45.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-12,Salary,1500.00,income,Job").unwrap();
+        add_transaction_to_db(&conn, "2025-11-11,Bus,2.00,expense,Transport").unwrap();
+        let mut state = BrowseState::load(&conn).unwrap();
+
+        // Nothing selected: D does nothing.
+        state.request_delete_selected();
+        assert_eq!(state.mode, Mode::List);
+
+        state.toggle_row_selection();
+        assert_eq!(state.selected_index(), Some(1));
+        state.toggle_row_selection();
+        assert_eq!(state.selected_set, HashSet::from([0, 1]));
+        state.move_selection(-1);
+        state.toggle_row_selection();
+        assert_eq!(state.selected_set, HashSet::from([0]));
+        state.move_selection(1);
+        state.toggle_row_selection();
+        assert_eq!(state.selected_set, HashSet::from([0, 2]));
+
+        state.request_delete_selected();
+        assert_eq!(state.mode, Mode::ConfirmDelete);
+        assert_eq!(state.delete_selected(&conn).unwrap(), 2);
+        assert_eq!(state.mode, Mode::List);
+        assert!(state.selected_set.is_empty());
+
+        let remaining = repository::get_all_transactions(&conn).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].description, "Salary");
+        assert_eq!(state.transactions.len(), 1);
+    }
+
+    #[test]
+    fn test_escape_clears_selection_before_quitting() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-11-13,Groceries,45.00,expense,Food").unwrap();
+        let mut state = BrowseState::load(&conn).unwrap();
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+
+        state.toggle_row_selection();
+        assert!(!handle_key(&conn, &mut state, esc).unwrap());
+        assert!(state.selected_set.is_empty());
+        assert!(handle_key(&conn, &mut state, esc).unwrap());
+    }
+
+    #[test]
+    fn test_help_overlay() {
+        let conn = establish_test_connection().unwrap();
+        let mut state = BrowseState::load(&conn).unwrap();
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert!(footer_hint(&state).starts_with("? help"));
+
+        for close in [KeyCode::Esc, KeyCode::Char('q'), KeyCode::Char('?')] {
+            assert!(!handle_key(&conn, &mut state, key(KeyCode::Char('?'))).unwrap());
+            assert_eq!(state.mode, Mode::Help);
+            assert!(!handle_key(&conn, &mut state, key(close)).unwrap());
+            assert_eq!(state.mode, Mode::List);
+        }
+
+        handle_key(&conn, &mut state, key(KeyCode::Char('?'))).unwrap();
+        handle_key(&conn, &mut state, key(KeyCode::Up)).unwrap();
+        assert_eq!(state.help_scroll, 0);
+        handle_key(&conn, &mut state, key(KeyCode::Down)).unwrap();
+        handle_key(&conn, &mut state, key(KeyCode::Down)).unwrap();
+        assert_eq!(state.help_scroll, 2);
+        for _ in 0..100 {
+            handle_key(&conn, &mut state, key(KeyCode::Down)).unwrap();
+        }
+        assert_eq!(state.help_scroll as usize, HELP_TEXT.lines().count() - 1);
+
+        // Reopening starts at the top again.
+        handle_key(&conn, &mut state, key(KeyCode::Esc)).unwrap();
+        handle_key(&conn, &mut state, key(KeyCode::Char('?'))).unwrap();
+        assert_eq!(state.help_scroll, 0);
+
+        let backend = ratatui::backend::TestBackend::new(100, 30);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|frame| render_help_modal(frame, frame.area(), &state)).unwrap();
+        let screen: String = terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("Moving around"));
+        assert!(screen.contains("cycle the sort order"));
+    }
+
+    #[test]
+    fn test_bulk_recategorize() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-11-13,Groceries,45.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-12,Salary,1500.00,income,Job").unwrap();
+        add_transaction_to_db(&conn, "2025-11-11,Bus,2.00,expense,Transport").unwrap();
+        let mut state = BrowseState::load(&conn).unwrap();
+
+        // R without a selection does nothing.
+        state.open_bulk_category_input(&conn).unwrap();
+        assert_eq!(state.mode, Mode::List);
+
+        state.toggle_row_selection();
+        state.move_selection(1);
+        state.toggle_row_selection();
+        state.open_bulk_category_input(&conn).unwrap();
+        assert_eq!(state.mode, Mode::Input(InputKind::BulkCategory));
+        assert_eq!(state.completions.len(), 3);
+
+        state.commit_bulk_category(&conn).unwrap();
+        assert_eq!(state.input_error.as_deref(), Some("Category cannot be empty"));
+        assert_eq!(state.mode, Mode::Input(InputKind::BulkCategory));
+
+        state.input_buffer = "Misc".to_string();
+        state.commit_bulk_category(&conn).unwrap();
+        assert_eq!(state.mode, Mode::List);
+        assert!(state.selected_set.is_empty());
+
+        let stored = repository::get_all_transactions(&conn).unwrap();
+        let category_of = |name: &str| stored.iter().find(|t| t.description == name).unwrap().category.clone();
+        assert_eq!(category_of("Groceries"), "Misc");
+        assert_eq!(category_of("Bus"), "Misc");
+        assert_eq!(category_of("Salary"), "Job");
+        assert!(state.transactions.iter().filter(|t| t.category == "Misc").count() == 2);
+    }
+
+    #[test]
+    fn test_clipboard_sequence() {
+        assert_eq!(clipboard_sequence("abc-123"), "\x1b]52;c;YWJjLTEyMw==\x07");
+    }
+
+    #[test]
+    fn test_flash_message_expires() {
+        let mut state = BrowseState::new();
+        let start = Instant::now();
+        state.flash("Sent ID to terminal clipboard", start);
+
+        state.expire_flash(start + Duration::from_millis(1000));
+        assert_eq!(state.flash_message.as_ref().map(|(m, _)| m.as_str()), Some("Sent ID to terminal clipboard"));
+
+        state.expire_flash(start + FLASH_DURATION);
+        assert!(state.flash_message.is_none());
+    }
+
+    fn jump_state() -> (Connection, BrowseState) {
+        let conn = establish_test_connection().unwrap();
+        for date in ["2025-11-01", "2025-11-05", "2025-11-10", "2025-11-20"] {
+            add_transaction_to_db(&conn, &format!("{},Item,10.00,expense,Food", date)).unwrap();
+        }
+        let state = BrowseState::load(&conn).unwrap();
+        (conn, state)
+    }
+
+    fn selected_date(state: &BrowseState) -> String {
+        state.selected_transaction().unwrap().date.format("%Y-%m-%d").to_string()
+    }
+
+    #[test]
+    fn test_jump_to_missing_date_selects_previous_day() {
+        let (conn, mut state) = jump_state();
+        state.start_input(InputKind::JumpToDate);
+        state.input_buffer = "2025-11-07".to_string();
+        state.commit_input(&conn, InputKind::JumpToDate).unwrap();
+
+        assert_eq!(state.mode, Mode::List);
+        assert_eq!(state.selected_index(), Some(2));
+        assert_eq!(selected_date(&state), "2025-11-05");
+    }
+
+    #[test]
+    fn test_jump_to_date_outside_range() {
+        let (conn, mut state) = jump_state();
+
+        state.jump_to_date(&conn, NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()).unwrap();
+        assert_eq!(selected_date(&state), "2025-11-20");
+
+        state.jump_to_date(&conn, NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()).unwrap();
+        assert_eq!(state.selected_index(), Some(3));
+        assert_eq!(selected_date(&state), "2025-11-01");
+    }
+
+    #[test]
+    fn test_jump_to_date_ascending_order() {
+        let (conn, mut state) = jump_state();
+        state.sort_order = TransactionOrder::DateAsc;
+        state.reload(&conn).unwrap();
+
+        state.jump_to_date(&conn, NaiveDate::from_ymd_opt(2025, 11, 7).unwrap()).unwrap();
+        assert_eq!(selected_date(&state), "2025-11-05");
+        state.jump_to_date(&conn, NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()).unwrap();
+        assert_eq!(selected_date(&state), "2025-11-01");
+    }
+
+    #[test]
+    fn test_jump_to_date_invalid_input() {
+        let (conn, mut state) = jump_state();
+        state.start_input(InputKind::JumpToDate);
+        state.input_buffer = "next week".to_string();
+        state.commit_input(&conn, InputKind::JumpToDate).unwrap();
+
+        assert_eq!(state.mode, Mode::Input(InputKind::JumpToDate));
+        assert!(state.input_error.as_ref().unwrap().contains("Invalid date"));
+    }
+
+    #[test]
+    fn test_undo_delete_restores_rows() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-11-13,Groceries,45.00,expense,Food,weekly,Big shop").unwrap();
+        add_transaction_to_db(&conn, "2025-11-12,Salary,1500.00,income,Job").unwrap();
+        let mut state = BrowseState::load(&conn).unwrap();
+        assert!(!footer_hint(&state).contains("u undo"));
+
+        let original = state.selected_transaction().unwrap().clone();
+        state.toggle_row_selection();
+        state.delete_selected(&conn).unwrap();
+        assert_eq!(state.transactions.len(), 1);
+        assert!(footer_hint(&state).contains("u undo"));
+
+        state.undo(&conn).unwrap();
+        assert_eq!(state.transactions.len(), 2);
+        assert!(state.undo_stack.is_empty());
+        assert!(!footer_hint(&state).contains("u undo"));
+        let restored = repository::get_transaction_by_id(&conn, &original.id).unwrap().unwrap();
+        assert_eq!(restored.description, "Groceries");
+        assert_eq!(restored.amount, original.amount);
+        assert_eq!(restored.tags, original.tags);
+        assert_eq!(restored.notes, "Big shop");
+
+        // Nothing left to undo.
+        state.undo(&conn).unwrap();
+        assert_eq!(state.transactions.len(), 2);
+    }
+
+    #[test]
+    fn test_undo_recategorize() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-11-13,Groceries,45.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-12,Bus,2.00,expense,Transport").unwrap();
+        let mut state = BrowseState::load(&conn).unwrap();
+
+        state.toggle_row_selection();
+        state.move_selection(1);
+        state.toggle_row_selection();
+        state.open_bulk_category_input(&conn).unwrap();
+        state.input_buffer = "Misc".to_string();
+        state.commit_bulk_category(&conn).unwrap();
+        assert!(state.transactions.iter().all(|t| t.category == "Misc"));
+
+        state.undo(&conn).unwrap();
+        let category_of = |name: &str| state.transactions.iter().find(|t| t.description == name).unwrap().category.clone();
+        assert_eq!(category_of("Groceries"), "Food");
+        assert_eq!(category_of("Bus"), "Transport");
+    }
+
+    #[test]
+    fn test_undo_stack_is_capped() {
+        let mut state = BrowseState::new();
+        for i in 0..(MAX_UNDO_ACTIONS + 3) {
+            state.push_undo(UndoAction::RecategorizedTransactions(vec![(i.to_string(), "Food".to_string())]));
+        }
+        assert_eq!(state.undo_stack.len(), MAX_UNDO_ACTIONS);
+        match &state.undo_stack[0] {
+            UndoAction::RecategorizedTransactions(entries) => assert_eq!(entries[0].0, "3"),
+            other => panic!("unexpected undo action {:?}", other),
+        }
+    }
+
+    /// A date in 2000-01-01..=2099-12-31, drawn from `rng`.
+    fn random_date(rng: &mut fastrand::Rng) -> NaiveDate {
+        let first = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+        let last = NaiveDate::from_ymd_opt(2099, 12, 31).unwrap();
+        first + chrono::Duration::days(rng.i64(0..=(last - first).num_days()))
+    }
+
+    /// ISO date, with leading zeros of month and day dropped half of the time.
+    fn random_date_text(rng: &mut fastrand::Rng, date: NaiveDate) -> String {
+        if rng.bool() {
+            date.format("%Y-%m-%d").to_string()
+        } else {
+            format!("{}-{}-{}", date.year(), date.month(), date.day())
+        }
+    }
+
+    fn random_range_text(rng: &mut fastrand::Rng, from: &str, to: &str) -> String {
+        let separator = ["..", ",", "-"][rng.usize(0..3)];
+        let space = if rng.bool() { " " } else { "" };
+        format!("{space}{from}{space}{separator}{space}{to}{space}")
+    }
+
+    #[test]
+    fn test_parse_date_range_random_valid_ranges() {
+        let mut rng = fastrand::Rng::with_seed(0x5eed);
+        for _ in 0..2000 {
+            let (a, b) = (random_date(&mut rng), random_date(&mut rng));
+            let (from, to) = (a.min(b), a.max(b));
+            let (from_text, to_text) = (random_date_text(&mut rng, from), random_date_text(&mut rng, to));
+            let input = random_range_text(&mut rng, &from_text, &to_text);
+
+            assert_eq!(parse_date_range(&input), Ok((Some(from), Some(to))), "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_date_range_random_reversed_ranges() {
+        let mut rng = fastrand::Rng::with_seed(0xbad);
+        for _ in 0..500 {
+            let (a, b) = (random_date(&mut rng), random_date(&mut rng));
+            if a == b {
+                continue;
             }
+            let (from, to) = (a.max(b), a.min(b));
+            let (from_text, to_text) = (random_date_text(&mut rng, from), random_date_text(&mut rng, to));
+            let input = random_range_text(&mut rng, &from_text, &to_text);
+
+            assert!(parse_date_range(&input).is_err(), "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_date_range_random_malformed_input() {
+        let mut rng = fastrand::Rng::with_seed(0xf00d);
+        for _ in 0..2000 {
+            let (from, to) = (random_date(&mut rng), random_date(&mut rng));
+            let broken = match rng.usize(0..5) {
+                // Month out of range
+                0 => format!("{}-{}-{:02}", from.year(), rng.u32(13..100), from.day()),
+                // Day out of range
+                1 => format!("{}-{:02}-{}", from.year(), from.month(), rng.u32(32..100)),
+                // A letter in the year
+                2 => format!("{}x-{:02}-{:02}", from.year() / 10, from.month(), from.day()),
+                // Day/month order
+                3 => from.format("%d.%m.%Y").to_string(),
+                // Missing day
+                _ => format!("{}-{:02}", from.year(), from.month()),
+            };
+            let valid = random_date_text(&mut rng, to);
+            let input = if rng.bool() {
+                random_range_text(&mut rng, &broken, &valid)
+            } else {
+                random_range_text(&mut rng, &valid, &broken)
+            };
+
+            assert!(parse_date_range(&input).is_err(), "input: {:?}", input);
         }
+        for input in ["", "2025-01-01", "2025-01-01 2025-01-31", "2025-01-01--2025-01-31"] {
+            assert!(parse_date_range(input).is_err(), "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_date_range_open_ends() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+        assert_eq!(parse_date_range("2025-01-31.."), Ok((Some(date), None)));
+        assert_eq!(parse_date_range(",2025-01-31"), Ok((None, Some(date))));
+        assert_eq!(parse_date_range(".."), Ok((None, None)));
     }
-    None
 }
@@ -1,21 +1,68 @@
-use crate::db::budget_repository;
-use crate::models::budget::CategoryBudget;
+use crate::db::{budget_repository, repository};
+use crate::error::AppError;
+use crate::models::budget::{BudgetPeriod, CategoryBudget};
+use chrono::{Days, NaiveDate};
 use rusqlite::Connection;
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use std::str::FromStr;
 
-pub fn set_budget_db(conn: &Connection, category: &str, amount_str: &str) -> Result<(), String> {
-    let amount = Decimal::from_str(amount_str)
-        .map_err(|_| format!("Invalid budget amount '{}'. Must be a valid number", amount_str))?;
+/// Budgets used beyond this percentage are highlighted as close to their limit.
+pub const BUDGET_WARNING_PERCENT: f64 = 90.0;
+
+/// `period` is weekly, monthly, quarterly or yearly; `None` keeps the current period (monthly for a new budget).
+/// `alert_threshold` is a percentage (0-100); `None` keeps the current one (80 for a new budget).
+pub fn set_budget_db(
+    conn: &Connection,
+    category: &str,
+    amount_str: &str,
+    period: Option<&str>,
+    alert_threshold: Option<u8>,
+) -> Result<(), String> {
+    let amount = parse_budget_amount(amount_str)?;
     if category.trim().is_empty() {
         return Err("Category cannot be empty".to_string());
     }
-    budget_repository::set_budget(conn, category.trim(), &amount)
+    if let Some(threshold) = alert_threshold
+        && threshold > 100
+    {
+        return Err(format!("Invalid alert threshold '{}'. Must be between 0 and 100", threshold));
+    }
+    let period = period.map(parse_budget_period).transpose()?;
+    budget_repository::set_budget(conn, category.trim(), &amount, period)?;
+    if let Some(threshold) = alert_threshold {
+        budget_repository::set_alert_threshold(conn, category.trim(), threshold)?;
+    }
+    Ok(())
+}
+
+/// A budget amount (or change of one), with at most `STORED_AMOUNT_DECIMALS` decimal places.
+fn parse_budget_amount(amount_str: &str) -> Result<Decimal, AppError> {
+    let amount = Decimal::from_str(amount_str.trim()).map_err(|_| {
+        AppError::InvalidInput(format!("Invalid budget amount '{}'. Must be a valid number", amount_str))
+    })?;
+    repository::check_amount_precision(&amount)?;
+    Ok(amount)
+}
+
+fn parse_budget_period(value: &str) -> Result<BudgetPeriod, String> {
+    BudgetPeriod::parse(value).ok_or_else(|| {
+        format!(
+            "Invalid budget period '{}'. Must be weekly, monthly, quarterly or yearly",
+            value
+        )
+    })
+}
+
+pub fn set_carry_forward_db(conn: &Connection, category: &str, carry_forward: bool) -> Result<(), String> {
+    if category.trim().is_empty() {
+        return Err("Category cannot be empty".to_string());
+    }
+    budget_repository::set_carry_forward(conn, category.trim(), carry_forward).map_err(String::from)
 }
 
 pub fn increase_budget_db(conn: &Connection, category: &str, amount_str: &str) -> Result<(), String> {
-    let delta = Decimal::from_str(amount_str)
-        .map_err(|_| format!("Invalid budget amount '{}'. Must be a valid number", amount_str))?;
+    let delta = parse_budget_amount(amount_str)?;
     if category.trim().is_empty() {
         return Err("Category cannot be empty".to_string());
     }
@@ -23,12 +70,11 @@ pub fn increase_budget_db(conn: &Connection, category: &str, amount_str: &str) -
         .map(|b| b.amount)
         .unwrap_or(Decimal::ZERO);
     let new_amount = current + delta;
-    budget_repository::set_budget(conn, category.trim(), &new_amount)
+    budget_repository::set_budget(conn, category.trim(), &new_amount, None).map_err(String::from)
 }
 
 pub fn decrease_budget_db(conn: &Connection, category: &str, amount_str: &str) -> Result<(), String> {
-    let delta = Decimal::from_str(amount_str)
-        .map_err(|_| format!("Invalid budget amount '{}'. Must be a valid number", amount_str))?;
+    let delta = parse_budget_amount(amount_str)?;
     if category.trim().is_empty() {
         return Err("Category cannot be empty".to_string());
     }
@@ -39,29 +85,141 @@ pub fn decrease_budget_db(conn: &Connection, category: &str, amount_str: &str) -
     if new_amount < Decimal::ZERO {
         return Err("Budget cannot be negative".to_string());
     }
-    budget_repository::set_budget(conn, category.trim(), &new_amount)
+    budget_repository::set_budget(conn, category.trim(), &new_amount, None).map_err(String::from)
 }
 
 pub fn list_budgets_db(conn: &Connection) -> Result<Vec<CategoryBudget>, String> {
-    budget_repository::get_all_budgets(conn)
+    budget_repository::get_all_budgets(conn).map_err(String::from)
+}
+
+/// Spending in the budget period containing `as_of`, as `(spent, budget, percent of budget)`.
+/// The budget includes any amount carried forward from the previous period.
+pub fn get_budget_utilization(
+    conn: &Connection,
+    category: &str,
+    as_of: NaiveDate,
+) -> Result<(Decimal, Decimal, f64), String> {
+    let budget = budget_repository::get_budget(conn, category.trim())?
+        .ok_or_else(|| format!("No budget set for category '{}'", category.trim()))?;
+
+    let (start, end) = budget.period.bounds(as_of);
+    let spent = repository::get_expenses_by_category_in_range(conn, &budget.category, start, end)?;
+    let allowance = budget.amount + carried_amount(conn, &budget, start)?;
+    Ok((spent, allowance, utilization_percent(spent, allowance)))
+}
+
+/// Budgets whose spending in the current period (including carried-forward amounts)
+/// reached their alert threshold, with the amount spent and the percent used.
+pub fn get_budgets_exceeding_threshold(
+    conn: &Connection,
+    as_of_date: NaiveDate,
+) -> Result<Vec<(CategoryBudget, Decimal, f64)>, String> {
+    let mut exceeding = Vec::new();
+    for budget in budget_repository::get_all_budgets(conn)? {
+        let (spent, _, percent) = get_budget_utilization(conn, &budget.category, as_of_date)?;
+        if percent >= f64::from(budget.alert_threshold) {
+            exceeding.push((budget, spent, percent));
+        }
+    }
+    Ok(exceeding)
+}
+
+/// What was left of the budget in the period before the one containing `as_of`.
+/// Zero if the budget does not carry forward or the previous period was overspent.
+pub fn compute_carried_amount(conn: &Connection, category: &str, as_of: NaiveDate) -> Result<Decimal, String> {
+    let budget = budget_repository::get_budget(conn, category.trim())?
+        .ok_or_else(|| format!("No budget set for category '{}'", category.trim()))?;
+    let (start, _) = budget.period.bounds(as_of);
+    carried_amount(conn, &budget, start)
+}
+
+// Only the previous period is looked at, using the current budget amount.
+fn carried_amount(conn: &Connection, budget: &CategoryBudget, period_start: NaiveDate) -> Result<Decimal, String> {
+    if !budget.carry_forward {
+        return Ok(Decimal::ZERO);
+    }
+    let (prev_start, prev_end) = budget.period.bounds(period_start - Days::new(1));
+    let spent = repository::get_expenses_by_category_in_range(conn, &budget.category, prev_start, prev_end)?;
+    Ok((spent - budget.amount).min(Decimal::ZERO).abs())
+}
+
+/// `(category, spent, budget, percent of budget)`; the percent is 0 for categories without a budget.
+pub type BudgetUtilization = (String, Decimal, Option<Decimal>, f64);
+
+/// What a budget of `amount` per `period` allows over `start..=end`: every period the range
+/// touches adds its share of `amount` for the days they overlap. A whole month gets exactly a
+/// monthly budget, and a 31-day month gets 31/7 of a weekly one.
+pub fn budget_for_range(amount: Decimal, period: BudgetPeriod, start: NaiveDate, end: NaiveDate) -> Decimal {
+    let mut total = Decimal::ZERO;
+    let mut day = start;
+    while day <= end {
+        let (period_start, period_end) = period.bounds(day);
+        let last = period_end.min(end);
+        let covered = (last - day).num_days() + 1;
+        let length = (period_end - period_start).num_days() + 1;
+        total += amount * Decimal::from(covered) / Decimal::from(length);
+        match last.succ_opt() {
+            Some(next) => day = next,
+            None => break,
+        }
+    }
+    total.round_dp(2)
+}
+
+/// Spending against budgets for every category with expenses or a budget in the range, each
+/// budget scaled to the range with `budget_for_range`. Carried-forward amounts are not added.
+/// Sorted by percent used, with unbudgeted categories last by spend.
+pub fn get_budget_utilization_all(
+    conn: &Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<BudgetUtilization>, String> {
+    if start > end {
+        return Err("Start date must be before end date.".to_string());
+    }
+    let mut rows: Vec<BudgetUtilization> = budget_repository::get_spending_with_budgets(conn, start, end)?
+        .into_iter()
+        .map(|(category, spent, budget)| {
+            let budget = budget.map(|(amount, period)| budget_for_range(amount, period, start, end));
+            let percent = budget.map(|b| utilization_percent(spent, b)).unwrap_or(0.0);
+            (category, spent, budget, percent)
+        })
+        .collect();
+
+    rows.sort_by(|a, b| match (a.2.is_some(), b.2.is_some()) {
+        (true, true) => b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.0.cmp(&b.0)),
+        (false, false) => b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)),
+        (true, false) => std::cmp::Ordering::Less,
+        (false, true) => std::cmp::Ordering::Greater,
+    });
+    Ok(rows)
+}
+
+/// A zero budget counts as 0% used while nothing is spent and 100% as soon as anything is.
+pub fn utilization_percent(spent: Decimal, budget: Decimal) -> f64 {
+    if budget.is_zero() {
+        return if spent.is_zero() { 0.0 } else { 100.0 };
+    }
+    (spent / budget * Decimal::ONE_HUNDRED).to_f64().unwrap_or(0.0)
 }
 
 pub fn delete_budget_db(conn: &Connection, category: &str) -> Result<(), String> {
     if category.trim().is_empty() {
         return Err("Category cannot be empty".to_string());
     }
-    budget_repository::delete_budget(conn, category.trim())
+    budget_repository::delete_budget(conn, category.trim()).map_err(String::from)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::db::connection::establish_test_connection;
+    use crate::operations::add::add_transaction_to_db;
 
     #[test]
     fn test_set_budget_success() {
         let conn = establish_test_connection().unwrap();
-        let result = set_budget_db(&conn, "Food", "100.50");
+        let result = set_budget_db(&conn, "Food", "100.50", None, None);
         assert!(result.is_ok());
 
         let budgets = list_budgets_db(&conn).unwrap();
@@ -69,18 +227,149 @@ mod tests {
         assert_eq!(budgets[0].category, "Food");
     }
 
+    #[test]
+    fn test_set_budget_period() {
+        let conn = establish_test_connection().unwrap();
+        set_budget_db(&conn, "Food", "100", Some("weekly"), None).unwrap();
+        assert_eq!(list_budgets_db(&conn).unwrap()[0].period, BudgetPeriod::Weekly);
+
+        // Changing only the amount keeps the period.
+        set_budget_db(&conn, "Food", "120", None, None).unwrap();
+        increase_budget_db(&conn, "Food", "5").unwrap();
+        let budget = &list_budgets_db(&conn).unwrap()[0];
+        assert_eq!(budget.period, BudgetPeriod::Weekly);
+        assert_eq!(budget.amount, Decimal::from_str("125").unwrap());
+
+        set_budget_db(&conn, "Travel", "900", None, None).unwrap();
+        let travel = budget_repository::get_budget(&conn, "Travel").unwrap().unwrap();
+        assert_eq!(travel.period, BudgetPeriod::Monthly);
+
+        let result = set_budget_db(&conn, "Food", "100", Some("daily"), None);
+        assert!(result.unwrap_err().contains("Invalid budget period"));
+    }
+
+    #[test]
+    fn test_get_budget_utilization_current_period() {
+        let conn = establish_test_connection().unwrap();
+        set_budget_db(&conn, "Food", "200", None, None).unwrap();
+        add_transaction_to_db(&conn, "2025-02-28,Groceries,80.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-03-01,Groceries,50.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-03-15,Dinner,100.00,expense,food").unwrap();
+        add_transaction_to_db(&conn, "2025-03-16,Refund,30.00,income,Food").unwrap();
+
+        let (spent, budget, percent) =
+            get_budget_utilization(&conn, "Food", NaiveDate::from_ymd_opt(2025, 3, 20).unwrap()).unwrap();
+        assert_eq!(spent, Decimal::from_str("150").unwrap());
+        assert_eq!(budget, Decimal::from_str("200").unwrap());
+        assert!((percent - 75.0).abs() < f64::EPSILON);
+
+        set_budget_db(&conn, "Food", "1000", Some("yearly"), None).unwrap();
+        let (spent, _, percent) =
+            get_budget_utilization(&conn, "Food", NaiveDate::from_ymd_opt(2025, 3, 20).unwrap()).unwrap();
+        assert_eq!(spent, Decimal::from_str("230").unwrap());
+        assert!((percent - 23.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_set_budget_alert_threshold() {
+        let conn = establish_test_connection().unwrap();
+        set_budget_db(&conn, "Food", "100", None, Some(90)).unwrap();
+        assert_eq!(list_budgets_db(&conn).unwrap()[0].alert_threshold, 90);
+
+        set_budget_db(&conn, "Food", "120", None, None).unwrap();
+        assert_eq!(list_budgets_db(&conn).unwrap()[0].alert_threshold, 90);
+
+        let err = set_budget_db(&conn, "Travel", "100", None, Some(150)).unwrap_err();
+        assert!(err.contains("Invalid alert threshold"));
+        assert!(list_budgets_db(&conn).unwrap().iter().all(|b| b.category != "Travel"));
+    }
+
+    #[test]
+    fn test_get_budgets_exceeding_threshold() {
+        let conn = establish_test_connection().unwrap();
+        set_budget_db(&conn, "Food", "100", None, None).unwrap();
+        set_budget_db(&conn, "Rent", "1000", None, Some(100)).unwrap();
+        set_budget_db(&conn, "Fun", "50", None, Some(50)).unwrap();
+        add_transaction_to_db(&conn, "2025-03-02,Groceries,80.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-03-01,Rent,990.00,expense,Rent").unwrap();
+        add_transaction_to_db(&conn, "2025-03-03,Cinema,20.00,expense,Fun").unwrap();
+        add_transaction_to_db(&conn, "2025-02-03,Concert,60.00,expense,Fun").unwrap();
+
+        let exceeding =
+            get_budgets_exceeding_threshold(&conn, NaiveDate::from_ymd_opt(2025, 3, 20).unwrap()).unwrap();
+        assert_eq!(exceeding.len(), 1);
+        let (budget, spent, percent) = &exceeding[0];
+        assert_eq!(budget.category, "Food");
+        assert_eq!(*spent, Decimal::from_str("80").unwrap());
+        assert!((percent - 80.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_carry_forward_under_budget() {
+        let conn = establish_test_connection().unwrap();
+        set_budget_db(&conn, "Food", "200", None, None).unwrap();
+        add_transaction_to_db(&conn, "2025-02-10,Groceries,170.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-03-05,Groceries,115.00,expense,Food").unwrap();
+        let as_of = NaiveDate::from_ymd_opt(2025, 3, 20).unwrap();
+
+        assert_eq!(compute_carried_amount(&conn, "Food", as_of).unwrap(), Decimal::ZERO);
+
+        set_carry_forward_db(&conn, "Food", true).unwrap();
+        assert_eq!(compute_carried_amount(&conn, "Food", as_of).unwrap(), Decimal::from_str("30").unwrap());
+
+        let (spent, budget, percent) = get_budget_utilization(&conn, "Food", as_of).unwrap();
+        assert_eq!(spent, Decimal::from_str("115").unwrap());
+        assert_eq!(budget, Decimal::from_str("230").unwrap());
+        assert!((percent - 50.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_carry_forward_over_budget() {
+        let conn = establish_test_connection().unwrap();
+        set_budget_db(&conn, "Food", "200", Some("weekly"), None).unwrap();
+        set_carry_forward_db(&conn, "Food", true).unwrap();
+        // 2025-03-10 is a Monday; the previous week ran over.
+        add_transaction_to_db(&conn, "2025-03-07,Party,260.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-03-11,Groceries,50.00,expense,Food").unwrap();
+        let as_of = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+
+        assert_eq!(compute_carried_amount(&conn, "Food", as_of).unwrap(), Decimal::ZERO);
+        let (_, budget, percent) = get_budget_utilization(&conn, "Food", as_of).unwrap();
+        assert_eq!(budget, Decimal::from_str("200").unwrap());
+        assert!((percent - 25.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_carry_forward_unknown_budget() {
+        let conn = establish_test_connection().unwrap();
+        assert!(set_carry_forward_db(&conn, "Food", true).unwrap_err().contains("not found"));
+        let result = compute_carried_amount(&conn, "Food", NaiveDate::from_ymd_opt(2025, 3, 20).unwrap());
+        assert!(result.unwrap_err().contains("No budget set"));
+    }
+
+    #[test]
+    fn test_get_budget_utilization_without_budget() {
+        let conn = establish_test_connection().unwrap();
+        let result = get_budget_utilization(&conn, "Food", NaiveDate::from_ymd_opt(2025, 3, 20).unwrap());
+        assert!(result.unwrap_err().contains("No budget set"));
+    }
+
     #[test]
     fn test_set_budget_invalid_amount() {
         let conn = establish_test_connection().unwrap();
-        let result = set_budget_db(&conn, "Food", "not-a-number");
+        let result = set_budget_db(&conn, "Food", "not-a-number", None, None);
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Invalid budget amount"));
+        let err = set_budget_db(&conn, "Food", "10.00001", None, None).unwrap_err();
+        assert!(err.contains("too many decimal places"));
+        assert!(increase_budget_db(&conn, "Food", "0.123456").unwrap_err().contains("too many decimal places"));
+        assert!(budget_repository::get_budget(&conn, "Food").unwrap().is_none());
     }
 
     #[test]
     fn test_set_budget_empty_category() {
         let conn = establish_test_connection().unwrap();
-        let result = set_budget_db(&conn, "", "100");
+        let result = set_budget_db(&conn, "", "100", None, None);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), "Category cannot be empty");
     }
@@ -100,7 +389,7 @@ mod tests {
     #[test]
     fn test_increase_budget_existing() {
         let conn = establish_test_connection().unwrap();
-        set_budget_db(&conn, "Food", "10").unwrap();
+        set_budget_db(&conn, "Food", "10", None, None).unwrap();
 
         let result = increase_budget_db(&conn, "Food", "5.25");
         assert!(result.is_ok());
@@ -113,7 +402,7 @@ mod tests {
     #[test]
     fn test_decrease_budget_success() {
         let conn = establish_test_connection().unwrap();
-        set_budget_db(&conn, "Food", "20").unwrap();
+        set_budget_db(&conn, "Food", "20", None, None).unwrap();
 
         let result = decrease_budget_db(&conn, "Food", "7.50");
         assert!(result.is_ok());
@@ -125,7 +414,7 @@ mod tests {
     #[test]
     fn test_decrease_budget_negative_error() {
         let conn = establish_test_connection().unwrap();
-        set_budget_db(&conn, "Food", "5").unwrap();
+        set_budget_db(&conn, "Food", "5", None, None).unwrap();
 
         let result = decrease_budget_db(&conn, "Food", "10");
         assert!(result.is_err());
@@ -142,7 +431,7 @@ mod tests {
     #[test]
     fn test_delete_budget_success() {
         let conn = establish_test_connection().unwrap();
-        set_budget_db(&conn, "Food", "10").unwrap();
+        set_budget_db(&conn, "Food", "10", None, None).unwrap();
 
         let result = delete_budget_db(&conn, "Food");
         assert!(result.is_ok());
@@ -158,4 +447,74 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("not found"));
     }
+
+    #[test]
+    fn test_get_budget_utilization_all() {
+        let conn = establish_test_connection().unwrap();
+        set_budget_db(&conn, "Food", "50", None, None).unwrap();
+        set_budget_db(&conn, "Rent", "800", None, None).unwrap();
+        set_budget_db(&conn, "Travel", "200", None, None).unwrap();
+        add_transaction_to_db(&conn, "2025-03-02,Groceries,50.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-03-01,Rent,400.00,expense,Rent").unwrap();
+        add_transaction_to_db(&conn, "2025-03-05,Book,15.00,expense,Hobby").unwrap();
+        add_transaction_to_db(&conn, "2025-03-06,Cinema,25.00,expense,Fun").unwrap();
+
+        let rows = get_budget_utilization_all(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+        )
+        .unwrap();
+
+        let categories: Vec<&str> = rows.iter().map(|r| r.0.as_str()).collect();
+        assert_eq!(categories, vec!["Food", "Rent", "Travel", "Fun", "Hobby"]);
+        assert_eq!(rows[0], ("Food".to_string(), Decimal::from(50), Some(Decimal::from(50)), 100.0));
+        assert_eq!(rows[1].3, 50.0);
+        assert_eq!(rows[2], ("Travel".to_string(), Decimal::ZERO, Some(Decimal::from(200)), 0.0));
+        assert_eq!(rows[3], ("Fun".to_string(), Decimal::from(25), None, 0.0));
+    }
+
+    #[test]
+    fn test_get_budget_utilization_all_scales_weekly_budget() {
+        let conn = establish_test_connection().unwrap();
+        set_budget_db(&conn, "Coffee", "70", Some("weekly"), None).unwrap();
+        set_budget_db(&conn, "Travel", "1200", Some("yearly"), None).unwrap();
+        add_transaction_to_db(&conn, "2025-03-03,Espresso,155.00,expense,Coffee").unwrap();
+        let march = (NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(), NaiveDate::from_ymd_opt(2025, 3, 31).unwrap());
+
+        let rows = get_budget_utilization_all(&conn, march.0, march.1).unwrap();
+        // 31 days of a 70-per-week budget, not a single week.
+        assert_eq!(rows[0], ("Coffee".to_string(), Decimal::from(155), Some(Decimal::from(310)), 50.0));
+        assert_eq!(rows[1].2, Some(Decimal::new(10192, 2)));
+
+        let one_week = get_budget_utilization_all(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 3, 3).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 9).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(one_week[0].2, Some(Decimal::from(70)));
+    }
+
+    #[test]
+    fn test_budget_for_range() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+        let hundred = Decimal::from(100);
+        assert_eq!(budget_for_range(hundred, BudgetPeriod::Monthly, date(2, 1), date(2, 28)), hundred);
+        assert_eq!(budget_for_range(hundred, BudgetPeriod::Monthly, date(1, 1), date(3, 31)), Decimal::from(300));
+        assert_eq!(budget_for_range(hundred, BudgetPeriod::Monthly, date(2, 15), date(3, 14)), Decimal::new(9516, 2));
+        assert_eq!(budget_for_range(hundred, BudgetPeriod::Quarterly, date(1, 1), date(12, 31)), Decimal::from(400));
+        assert_eq!(budget_for_range(Decimal::from(7), BudgetPeriod::Weekly, date(3, 5), date(3, 5)), Decimal::ONE);
+    }
+
+    #[test]
+    fn test_get_budget_utilization_all_invalid_range() {
+        let conn = establish_test_connection().unwrap();
+        let result = get_budget_utilization_all(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+        );
+        assert!(result.is_err());
+    }
 }
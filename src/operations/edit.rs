@@ -0,0 +1,156 @@
+use super::add::create_transaction_from_fields;
+use crate::db::repository;
+use crate::models::transaction::Transaction;
+use rusqlite::Connection;
+use uuid::Uuid;
+
+/// Tags or notes field value that removes them instead of keeping the old ones.
+const CLEAR_FIELD: &str = "-";
+
+/// Replaces the date, description, amount, type and category of transaction `id` with the
+/// ones in `raw_input` (same format as `add`). Tags and notes are only replaced when the input
+/// has a non-empty field for them, and a `-` field clears them; the status, account and currency
+/// are always kept.
+pub fn edit_transaction_in_db(conn: &Connection, id: &str, raw_input: &str) -> Result<(), String> {
+    if id.is_empty() {
+        return Err("Transaction ID cannot be empty.".to_string());
+    }
+    let id = match Uuid::parse_str(id) {
+        Ok(parsed_id) => parsed_id.to_string(),
+        Err(_) => return Err("Invalid transaction ID format. Please provide a valid UUID.".to_string()),
+    };
+
+    let existing = repository::get_transaction_by_id(conn, &id)?
+        .ok_or_else(|| format!("Transaction with ID {} not found", id))?;
+
+    let fields: Vec<&str> = raw_input.trim().splitn(7, ',').map(|s| s.trim()).collect();
+    let edited = create_transaction_from_fields(&fields)?;
+    let given = |index: usize| fields.get(index).is_some_and(|field| !field.is_empty());
+    let cleared = |index: usize| fields.get(index) == Some(&CLEAR_FIELD);
+    let updated = Transaction {
+        date: edited.date,
+        description: edited.description,
+        amount: edited.amount,
+        transaction_type: edited.transaction_type,
+        category: edited.category,
+        tags: if cleared(5) {
+            Vec::new()
+        } else if given(5) {
+            edited.tags
+        } else {
+            existing.tags.clone()
+        },
+        notes: if cleared(6) {
+            String::new()
+        } else if given(6) {
+            edited.notes
+        } else {
+            existing.notes.clone()
+        },
+        ..existing
+    };
+    repository::update_transaction(conn, &id, &updated).map_err(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::establish_test_connection;
+    use crate::models::transaction::{TransactionStatus, TransactionType};
+    use crate::operations::add::add_transaction_to_db_with_id;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_edit_transaction_success() {
+        let conn = establish_test_connection().unwrap();
+        let (id, _) = add_transaction_to_db_with_id(&conn, "2025-11-10,Salary,1500.00,income,Job").unwrap();
+
+        let result = edit_transaction_in_db(&conn, &id, "2025-11-12,Lunch,12.50,expense,Food");
+        assert!(result.is_ok());
+
+        let transaction = repository::get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert_eq!(transaction.description, "Lunch");
+        assert_eq!(transaction.amount, Decimal::new(1250, 2));
+        assert_eq!(transaction.transaction_type, TransactionType::Expense);
+        assert_eq!(transaction.category, "Food");
+    }
+
+    #[test]
+    fn test_edit_transaction_invalid_uuid() {
+        let conn = establish_test_connection().unwrap();
+        let result = edit_transaction_in_db(&conn, "invalid-uuid", "2025-11-12,Lunch,12.50,expense,Food");
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Invalid transaction ID format. Please provide a valid UUID."
+        );
+    }
+
+    #[test]
+    fn test_edit_transaction_not_found() {
+        let conn = establish_test_connection().unwrap();
+        let non_existent_id = "550e8400-e29b-41d4-a716-446655440999";
+
+        let result = edit_transaction_in_db(&conn, non_existent_id, "2025-11-12,Lunch,12.50,expense,Food");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("not found"));
+    }
+
+    #[test]
+    fn test_edit_transaction_invalid_details() {
+        let conn = establish_test_connection().unwrap();
+        let (id, _) = add_transaction_to_db_with_id(&conn, "2025-11-10,Salary,1500.00,income,Job").unwrap();
+
+        let result = edit_transaction_in_db(&conn, &id, "2025-11-12,Lunch,abc,expense,Food");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Invalid amount"));
+
+        let transaction = repository::get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert_eq!(transaction.description, "Salary");
+    }
+
+    #[test]
+    fn test_edit_keeps_status_tags_and_notes() {
+        let conn = establish_test_connection().unwrap();
+        let (id, _) =
+            add_transaction_to_db_with_id(&conn, "2025-11-10,Team lunch,40.00,expense,Food,work;team,memo text").unwrap();
+        repository::update_transaction_status(&conn, &id, TransactionStatus::Reconciled).unwrap();
+
+        // The CLI sends empty tag and note fields when --tags/--notes are left out.
+        edit_transaction_in_db(&conn, &id, "2025-11-10,Team dinner,40.00,expense,Food,,").unwrap();
+        let transaction = repository::get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert_eq!(transaction.description, "Team dinner");
+        assert_eq!(transaction.status, TransactionStatus::Reconciled);
+        assert_eq!(transaction.tags, vec!["work", "team"]);
+        assert_eq!(transaction.notes, "memo text");
+
+        edit_transaction_in_db(&conn, &id, "2025-11-10,Team dinner,40.00,expense,Food").unwrap();
+        let transaction = repository::get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert_eq!(transaction.tags, vec!["work", "team"]);
+        assert_eq!(transaction.notes, "memo text");
+
+        edit_transaction_in_db(&conn, &id, "2025-11-10,Team dinner,40.00,expense,Food,client,Paid by card").unwrap();
+        let transaction = repository::get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert_eq!(transaction.status, TransactionStatus::Reconciled);
+        assert_eq!(transaction.tags, vec!["client"]);
+        assert_eq!(transaction.notes, "Paid by card");
+    }
+
+    #[test]
+    fn test_edit_clears_tags_and_notes_with_dash() {
+        let conn = establish_test_connection().unwrap();
+        let (id, _) =
+            add_transaction_to_db_with_id(&conn, "2025-11-10,Team lunch,40.00,expense,Food,work;team,memo text").unwrap();
+
+        edit_transaction_in_db(&conn, &id, "2025-11-10,Team lunch,40.00,expense,Food,-,").unwrap();
+        let transaction = repository::get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert!(transaction.tags.is_empty());
+        assert_eq!(transaction.notes, "memo text");
+
+        edit_transaction_in_db(&conn, &id, "2025-11-10,Team lunch,40.00,expense,Food,,-").unwrap();
+        let transaction = repository::get_transaction_by_id(&conn, &id).unwrap().unwrap();
+        assert!(transaction.tags.is_empty());
+        assert_eq!(transaction.notes, "");
+    }
+}
@@ -0,0 +1,220 @@
+use crate::db::repository;
+use crate::models::transaction::{Transaction, TransactionType};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::fs::File;
+
+#[derive(Debug)]
+pub enum ExportFormat {
+    Csv,
+    Tsv,
+    Json,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonTransactionRecord<'a> {
+    date: String,
+    description: &'a str,
+    amount: String,
+    #[serde(rename = "type")]
+    transaction_type: &'a str,
+    category: &'a str,
+    tags: &'a [String],
+    notes: &'a str,
+}
+
+pub fn export_transactions(conn: &Connection, format: ExportFormat, path: &str) -> Result<usize, String> {
+    match format {
+        ExportFormat::Csv => export_transactions_to_csv(conn, path),
+        ExportFormat::Tsv => export_transactions_to_tsv(conn, path),
+        ExportFormat::Json => export_transactions_to_json(conn, path),
+    }
+}
+
+pub fn export_transactions_to_csv(conn: &Connection, path: &str) -> Result<usize, String> {
+    export_delimited(conn, path, b',')
+}
+
+pub fn export_transactions_to_tsv(conn: &Connection, path: &str) -> Result<usize, String> {
+    export_delimited(conn, path, b'\t')
+}
+
+fn export_delimited(conn: &Connection, path: &str, delimiter: u8) -> Result<usize, String> {
+    let transactions = repository::get_all_transactions(conn)?;
+
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .delimiter(delimiter)
+        .from_path(path)
+        .map_err(|e| format!("Failed to create file '{}': {}", path, e))?;
+
+    // Same column order as the CSV import so an exported file can be imported again.
+    for transaction in &transactions {
+        writer
+            .write_record([
+                transaction.date.format("%Y-%m-%d").to_string(),
+                transaction.description.clone(),
+                transaction.amount.to_string(),
+                transaction_type_str(transaction).to_string(),
+                transaction.category.clone(),
+                transaction.tags.join(";"),
+                transaction.notes.clone(),
+            ])
+            .map_err(|e| format!("Failed to write CSV record: {}", e))?;
+    }
+
+    writer
+        .flush()
+        .map_err(|e| format!("Failed to write file '{}': {}", path, e))?;
+
+    Ok(transactions.len())
+}
+
+pub fn export_transactions_to_json(conn: &Connection, path: &str) -> Result<usize, String> {
+    let transactions = repository::get_all_transactions(conn)?;
+
+    let records: Vec<JsonTransactionRecord> = transactions
+        .iter()
+        .map(|transaction| JsonTransactionRecord {
+            date: transaction.date.format("%Y-%m-%d").to_string(),
+            description: &transaction.description,
+            amount: transaction.amount.to_string(),
+            transaction_type: transaction_type_str(transaction),
+            category: &transaction.category,
+            tags: &transaction.tags,
+            notes: &transaction.notes,
+        })
+        .collect();
+
+    let file = File::create(path).map_err(|e| format!("Failed to create file '{}': {}", path, e))?;
+    serde_json::to_writer_pretty(file, &records)
+        .map_err(|e| format!("Failed to write JSON: {}", e))?;
+
+    Ok(records.len())
+}
+
+fn transaction_type_str(transaction: &Transaction) -> &'static str {
+    match transaction.transaction_type {
+        TransactionType::Income => "income",
+        TransactionType::Expense => "expense",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::establish_test_connection;
+    use crate::operations::add::add_transaction_to_db;
+    use crate::operations::import::{import_transactions_to_db, ImportFormat, ImportOptions};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+    use tempfile::NamedTempFile;
+
+    fn seed(conn: &Connection) {
+        add_transaction_to_db(conn, "2025-11-10,Salary,1500.00,income,Job").unwrap();
+        add_transaction_to_db(conn, "2025-11-11,Coffee,3.50,expense,Food,work;daily,Team meeting, paid for two").unwrap();
+    }
+
+    #[test]
+    fn test_export_csv_round_trip() {
+        let conn = establish_test_connection().unwrap();
+        seed(&conn);
+
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path().to_str().unwrap();
+        let count = export_transactions_to_csv(&conn, path).unwrap();
+        assert_eq!(count, 2);
+
+        let contents = std::fs::read_to_string(path).unwrap();
+        assert!(contents.contains("2025-11-10,Salary,1500.00,income,Job,,\n"));
+        assert!(contents.contains("2025-11-11,Coffee,3.50,expense,Food,work;daily,\"Team meeting, paid for two\""));
+
+        let other = establish_test_connection().unwrap();
+        let imported = import_transactions_to_db(&other, ImportFormat::Csv, path, &ImportOptions::default()).unwrap().imported;
+        assert_eq!(imported, 2);
+
+        let all = repository::get_all_transactions(&other).unwrap();
+        let coffee = all.iter().find(|t| t.description == "Coffee").unwrap();
+        assert_eq!(coffee.amount, Decimal::from_str("3.50").unwrap());
+        assert_eq!(coffee.transaction_type, TransactionType::Expense);
+        assert_eq!(coffee.category, "Food");
+        assert_eq!(coffee.tags, vec!["work", "daily"]);
+        assert_eq!(coffee.notes, "Team meeting, paid for two");
+    }
+
+    #[test]
+    fn test_export_tsv_round_trip() {
+        let conn = establish_test_connection().unwrap();
+        seed(&conn);
+        add_transaction_to_db(&conn, "2025-11-12,Dinner\tout,40.00,expense,Food").unwrap();
+
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path().to_str().unwrap();
+        let count = export_transactions(&conn, ExportFormat::Tsv, path).unwrap();
+        assert_eq!(count, 3);
+
+        let contents = std::fs::read_to_string(path).unwrap();
+        assert!(contents.contains("2025-11-10\tSalary\t1500.00\tincome\tJob\t\t\n"));
+        assert!(contents.contains("2025-11-11\tCoffee\t3.50\texpense\tFood\twork;daily\tTeam meeting, paid for two\n"));
+
+        let other = establish_test_connection().unwrap();
+        let imported = import_transactions_to_db(&other, ImportFormat::Tsv, path, &ImportOptions::default()).unwrap().imported;
+        assert_eq!(imported, 3);
+
+        let all = repository::get_all_transactions(&other).unwrap();
+        let coffee = all.iter().find(|t| t.description == "Coffee").unwrap();
+        assert_eq!(coffee.amount, Decimal::from_str("3.50").unwrap());
+        assert_eq!(coffee.transaction_type, TransactionType::Expense);
+        assert_eq!(coffee.tags, vec!["work", "daily"]);
+        assert_eq!(coffee.notes, "Team meeting, paid for two");
+        assert!(all.iter().any(|t| t.description == "Dinner\tout"));
+    }
+
+    #[test]
+    fn test_export_json_round_trip() {
+        let conn = establish_test_connection().unwrap();
+        seed(&conn);
+
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path().to_str().unwrap();
+        let count = export_transactions_to_json(&conn, path).unwrap();
+        assert_eq!(count, 2);
+
+        let contents = std::fs::read_to_string(path).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        let records = parsed.as_array().unwrap();
+        assert_eq!(records.len(), 2);
+        let salary = records.iter().find(|r| r["description"] == "Salary").unwrap();
+        assert_eq!(salary["date"], "2025-11-10");
+        assert_eq!(salary["amount"], "1500.00");
+        assert_eq!(salary["type"], "income");
+        assert_eq!(salary["category"], "Job");
+        assert_eq!(salary["tags"], serde_json::json!([]));
+
+        let other = establish_test_connection().unwrap();
+        let imported = import_transactions_to_db(&other, ImportFormat::Json, path, &ImportOptions::default()).unwrap().imported;
+        assert_eq!(imported, 2);
+        let all = repository::get_all_transactions(&other).unwrap();
+        let coffee = all.iter().find(|t| t.description == "Coffee").unwrap();
+        assert_eq!(coffee.tags, vec!["work", "daily"]);
+        assert_eq!(coffee.notes, "Team meeting, paid for two");
+    }
+
+    #[test]
+    fn test_export_empty_database() {
+        let conn = establish_test_connection().unwrap();
+        let tmp = NamedTempFile::new().unwrap();
+
+        let count = export_transactions(&conn, ExportFormat::Csv, tmp.path().to_str().unwrap()).unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_export_invalid_path() {
+        let conn = establish_test_connection().unwrap();
+        let result = export_transactions_to_json(&conn, "/nonexistent-dir/out.json");
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Failed to create file"));
+    }
+}
@@ -0,0 +1,324 @@
+use crate::config::Config;
+use crate::format::format_amount;
+use crate::models::currency::Currency;
+use crate::models::transaction::{Transaction, TransactionType};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Serialize)]
+struct TransactionRecord<'a> {
+    id: &'a str,
+    date: String,
+    description: &'a str,
+    amount: String,
+    #[serde(rename = "type")]
+    transaction_type: &'static str,
+    category: &'a str,
+    tags: &'a [String],
+    status: &'static str,
+    notes: &'a str,
+}
+
+impl<'a> From<&'a Transaction> for TransactionRecord<'a> {
+    fn from(transaction: &'a Transaction) -> Self {
+        Self {
+            id: &transaction.id,
+            date: transaction.date.format("%Y-%m-%d").to_string(),
+            description: &transaction.description,
+            amount: transaction.amount.to_string(),
+            transaction_type: transaction_type_str(transaction),
+            category: &transaction.category,
+            tags: &transaction.tags,
+            status: transaction.status.as_str(),
+            notes: &transaction.notes,
+        }
+    }
+}
+
+// Used when the terminal size cannot be read, e.g. when output is piped.
+const FALLBACK_TABLE_WIDTH: usize = 120;
+// Descriptions are never cut shorter than their header.
+const MIN_DESCRIPTION_WIDTH: usize = "Description".len();
+
+/// Renders `txs` for the terminal or for scripts. JSON and CSV always use ISO dates and plain
+/// amounts; the table uses the default settings (see `format_table` for configured ones).
+pub fn format_transactions(txs: &[Transaction], fmt: OutputFormat) -> String {
+    match fmt {
+        OutputFormat::Table => format_transactions_as_table(txs),
+        OutputFormat::Json => format_json(txs),
+        OutputFormat::Csv => format_csv(txs),
+    }
+}
+
+/// Box-drawn table of date, type, amount, category and description, fitted to the terminal.
+pub fn format_transactions_as_table(txs: &[Transaction]) -> String {
+    box_table(txs, &Config::default(), false, terminal_width())
+}
+
+/// The same table with an ID column in front (needed for `edit`/`remove`) and dates and
+/// amounts formatted by `config`.
+pub fn format_table(txs: &[Transaction], config: &Config) -> String {
+    box_table(txs, config, true, terminal_width())
+}
+
+fn terminal_width() -> usize {
+    crossterm::terminal::size()
+        .map(|(columns, _)| columns as usize)
+        .unwrap_or(FALLBACK_TABLE_WIDTH)
+}
+
+/// Every column is as wide as its widest cell; only the description is cut (with `…`) so the
+/// table fits in `max_width`.
+fn box_table(txs: &[Transaction], config: &Config, with_ids: bool, max_width: usize) -> String {
+    let mut headers = vec!["Date", "Type", "Amount", "Category", "Description"];
+    let mut min_widths = vec![10, 7, 12, 0, 0];
+    if with_ids {
+        headers.insert(0, "ID");
+        min_widths.insert(0, 0);
+    }
+    let description_column = headers.len() - 1;
+    let amount_column = description_column - 2;
+
+    let rows: Vec<Vec<String>> = txs
+        .iter()
+        .map(|transaction| {
+            let mut row = vec![
+                transaction.date.format(&config.date_format).to_string(),
+                transaction_type_str(transaction).to_string(),
+                display_amount(transaction, config),
+                transaction.category.clone(),
+                transaction.description.clone(),
+            ];
+            if with_ids {
+                row.insert(0, transaction.id.clone());
+            }
+            row
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = headers
+        .iter()
+        .zip(&min_widths)
+        .map(|(header, min)| header.chars().count().max(*min))
+        .collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    // Each column adds "│ " before and " " after its cell, plus the closing "│".
+    let fixed: usize = widths[..description_column].iter().map(|width| width + 3).sum::<usize>() + 4;
+    let available = max_width.saturating_sub(fixed).max(MIN_DESCRIPTION_WIDTH);
+    widths[description_column] = widths[description_column].min(available);
+
+    let border = |left: &str, middle: &str, right: &str| {
+        let segments: Vec<String> = widths.iter().map(|width| "─".repeat(width + 2)).collect();
+        format!("{}{}{}\n", left, segments.join(middle), right)
+    };
+    let line = |cells: Vec<String>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, width))| {
+                let cell = truncate(cell, *width);
+                if i == amount_column {
+                    format!(" {:>width$} ", cell, width = width)
+                } else {
+                    format!(" {:<width$} ", cell, width = width)
+                }
+            })
+            .collect();
+        format!("│{}│\n", padded.join("│"))
+    };
+
+    let mut out = border("┌", "┬", "┐");
+    out.push_str(&line(headers.iter().map(|header| header.to_string()).collect()));
+    out.push_str(&border("├", "┼", "┤"));
+    for row in rows {
+        out.push_str(&line(row));
+    }
+    out.push_str(&border("└", "┴", "┘"));
+    out
+}
+
+/// The amount in the transaction's currency; without one, `config.currency_symbol` is used.
+fn display_amount(transaction: &Transaction, config: &Config) -> String {
+    let currency = if transaction.currency_code.is_empty() {
+        Currency { symbol: config.currency_symbol.clone(), ..Currency::default() }
+    } else {
+        Currency::from_code(&transaction.currency_code)
+    };
+    format_amount(transaction.amount, &currency)
+}
+
+/// `text` cut to `width` characters, ending in `…` when something was removed.
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+fn format_json(txs: &[Transaction]) -> String {
+    let records: Vec<TransactionRecord> = txs.iter().map(TransactionRecord::from).collect();
+    // Serializing plain strings and slices cannot fail.
+    serde_json::to_string_pretty(&records).unwrap_or_default()
+}
+
+fn format_csv(txs: &[Transaction]) -> String {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let _ = writer.write_record(["id", "date", "description", "amount", "type", "category", "tags", "status", "notes"]);
+    for transaction in txs {
+        let _ = writer.write_record([
+            transaction.id.clone(),
+            transaction.date.format("%Y-%m-%d").to_string(),
+            transaction.description.clone(),
+            transaction.amount.to_string(),
+            transaction_type_str(transaction).to_string(),
+            transaction.category.clone(),
+            transaction.tags.join(";"),
+            transaction.status.as_str().to_string(),
+            transaction.notes.clone(),
+        ]);
+    }
+    writer
+        .into_inner()
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default()
+}
+
+fn transaction_type_str(transaction: &Transaction) -> &'static str {
+    match transaction.transaction_type {
+        TransactionType::Income => "income",
+        TransactionType::Expense => "expense",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::add::create_transaction;
+
+    fn sample() -> Vec<Transaction> {
+        vec![
+            create_transaction("2025-11-10,Salary,1500.00,income,Job").unwrap(),
+            create_transaction("2025-11-11,Coffee,3.50,expense,Food,work;daily,Team meeting, paid for two").unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_format_json_round_trip() {
+        let txs = sample();
+        let output = format_transactions(&txs, OutputFormat::Json);
+
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let records = parsed.as_array().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["id"], txs[0].id.as_str());
+        assert_eq!(records[0]["date"], "2025-11-10");
+        assert_eq!(records[0]["amount"], "1500.00");
+        assert_eq!(records[0]["type"], "income");
+        assert_eq!(records[0]["status"], "cleared");
+        assert_eq!(records[1]["category"], "Food");
+        assert_eq!(records[1]["tags"], serde_json::json!(["work", "daily"]));
+        assert_eq!(records[1]["notes"], "Team meeting, paid for two");
+    }
+
+    #[test]
+    fn test_format_json_empty() {
+        assert_eq!(format_transactions(&[], OutputFormat::Json), "[]");
+    }
+
+    #[test]
+    fn test_format_csv_quotes_fields() {
+        let txs = sample();
+        let output = format_transactions(&txs, OutputFormat::Csv);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines[0], "id,date,description,amount,type,category,tags,status,notes");
+        assert_eq!(
+            lines[2],
+            format!("{},2025-11-11,Coffee,3.50,expense,Food,work;daily,cleared,\"Team meeting, paid for two\"", txs[1].id)
+        );
+    }
+
+    #[test]
+    fn test_table_columns_line_up() {
+        let txs = sample();
+        let output = box_table(&txs, &Config::default(), false, 120);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "┌────────────┬─────────┬──────────────┬──────────┬─────────────┐");
+        assert_eq!(lines[1], "│ Date       │ Type    │       Amount │ Category │ Description │");
+        assert_eq!(lines[2], "├────────────┼─────────┼──────────────┼──────────┼─────────────┤");
+        assert_eq!(lines[3], "│ 2025-11-10 │ income  │     1,500.00 │ Job      │ Salary      │");
+        assert_eq!(lines[4], "│ 2025-11-11 │ expense │         3.50 │ Food     │ Coffee      │");
+        assert_eq!(lines[5], "└────────────┴─────────┴──────────────┴──────────┴─────────────┘");
+        // Every line has the same display width.
+        assert!(lines.iter().all(|line| line.chars().count() == lines[0].chars().count()));
+    }
+
+    #[test]
+    fn test_table_truncates_description() {
+        let mut txs = sample();
+        txs[1].description = "A very long description that does not fit".to_string();
+        let output = box_table(&txs, &Config::default(), false, 70);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines.iter().all(|line| line.chars().count() == 70));
+        assert!(lines[4].ends_with("│ A very long desc… │"));
+        assert!(lines[3].ends_with("│ Salary            │"));
+    }
+
+    #[test]
+    fn test_table_keeps_description_header_on_narrow_terminals() {
+        let mut txs = sample();
+        txs[0].description = "Monthly salary payment".to_string();
+        let output = box_table(&txs, &Config::default(), false, 20);
+        assert!(output.contains("│ Monthly sa… │"));
+    }
+
+    #[test]
+    fn test_table_empty_list() {
+        let output = format_transactions_as_table(&[]);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with("│ Date "));
+        assert!(lines[3].starts_with("└"));
+    }
+
+    #[test]
+    fn test_table_with_ids_and_config() {
+        let txs = sample();
+        let config = Config { date_format: "%d.%m.%Y".to_string(), currency_symbol: "$".to_string(), ..Config::default() };
+        let output = box_table(&txs, &config, true, 200);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines[1].starts_with("│ ID "));
+        assert!(lines[3].starts_with(&format!("│ {} │ 10.11.2025 │", txs[0].id)));
+        assert!(lines[4].contains("│        $3.50 │"));
+    }
+
+    #[test]
+    fn test_table_uses_transaction_currency() {
+        let mut txs = sample();
+        txs[0].currency_code = "EUR".to_string();
+        let config = Config { currency_symbol: "$".to_string(), ..Config::default() };
+        let output = box_table(&txs, &config, false, 200);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines[3].contains("│    €1,500.00 │"));
+        assert!(lines[4].contains("│        $3.50 │"));
+    }
+}
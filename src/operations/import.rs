@@ -1,63 +1,465 @@
-use super::add::{create_transaction, check_budget_and_alert};
-use crate::db::repository;
-use crate::models::transaction::{Transaction, TransactionType};
+use super::add::{check_budget_and_alert, create_transaction_from_fields, parse_tags};
+use crate::db::{budget_repository, repository};
+use crate::models::transaction::{Transaction, TransactionStatus, TransactionType};
 use chrono::NaiveDate;
 use quick_xml::events::Event;
 use quick_xml::reader::Reader;
-use regex::Regex;
+use super::rule_apply::RuleSet;
 use rusqlite::Connection;
 use rust_decimal::Decimal;
-use std::fs::File;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 use uuid::Uuid;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum ImportFormat {
-    CSV,
-    OFX,
+    Csv,
+    Tsv,
+    Ofx,
+    Json,
+    YnabCsv,
+    MintCsv,
 }
 
+/// What to do when a row fails to parse or insert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportErrorBehavior {
+    /// Roll back every row of the file.
+    #[default]
+    AbortAll,
+    /// Record the error, leave the row out and keep importing.
+    SkipRow,
+}
+
+/// What to do with a row that matches an existing transaction on date, description and amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicatePolicy {
+    /// Treat the row as a failed row (handled according to `ImportErrorBehavior`).
+    #[default]
+    Error,
+    /// Leave the row out and count it in `ImportResult::duplicates`.
+    Skip,
+    /// Insert it anyway.
+    Allow,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImportOptions {
+    /// Parse and validate every row without writing anything.
+    pub dry_run: bool,
+    pub on_error: ImportErrorBehavior,
+    /// `DuplicatePolicy::Skip` skips rows that already exist.
+    pub duplicates: DuplicatePolicy,
+    /// Column positions for CSV/TSV files. `None` uses the header row if there is one, else the default order.
+    pub column_map: Option<ImportColumnMap>,
+    /// Separator of CSV files (`Config::csv_delimiter`). `None` uses a comma.
+    pub csv_delimiter: Option<u8>,
+}
+
+/// 0-based positions of the CSV/TSV columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImportColumnMap {
+    pub date: usize,
+    pub description: usize,
+    pub amount: usize,
+    pub transaction_type: usize,
+    pub category: usize,
+    pub tags: Option<usize>,
+    pub notes: Option<usize>,
+}
+
+impl Default for ImportColumnMap {
+    fn default() -> Self {
+        ImportColumnMap {
+            date: 0,
+            description: 1,
+            amount: 2,
+            transaction_type: 3,
+            category: 4,
+            tags: Some(5),
+            notes: Some(6),
+        }
+    }
+}
+
+impl ImportColumnMap {
+    /// Parses a column order such as `description,date,amount,type,category`; `-` marks a column to ignore.
+    pub fn from_order(order: &str) -> Result<Self, String> {
+        let (mut date, mut description, mut amount, mut transaction_type, mut category) = (None, None, None, None, None);
+        let (mut tags, mut notes) = (None, None);
+
+        for (index, name) in order.split(',').map(|n| n.trim().to_lowercase()).enumerate() {
+            let slot = match name.as_str() {
+                "date" => &mut date,
+                "description" => &mut description,
+                "amount" => &mut amount,
+                "type" => &mut transaction_type,
+                "category" => &mut category,
+                "tags" => &mut tags,
+                "notes" => &mut notes,
+                "-" | "" => continue,
+                other => return Err(format!("Unknown column '{}'", other)),
+            };
+            if slot.replace(index).is_some() {
+                return Err(format!("Column '{}' is listed twice", name));
+            }
+        }
+
+        let required = |slot: Option<usize>, name: &str| slot.ok_or_else(|| format!("Column order is missing '{}'", name));
+        Ok(ImportColumnMap {
+            date: required(date, "date")?,
+            description: required(description, "description")?,
+            amount: required(amount, "amount")?,
+            transaction_type: required(transaction_type, "type")?,
+            category: required(category, "category")?,
+            tags,
+            notes,
+        })
+    }
+
+    /// Builds the map from a header row, or returns `None` if the row does not look like a header
+    /// (it needs a column mentioning "date" and one mentioning "amount").
+    fn from_header(record: &csv::StringRecord) -> Option<Result<Self, String>> {
+        let headers: Vec<String> = record.iter().map(|h| h.trim_start_matches('\u{feff}').to_lowercase()).collect();
+        // Keywords are tried in order, so "Description" wins over "Account Name".
+        let find = |keywords: &[&str]| {
+            keywords
+                .iter()
+                .find_map(|k| headers.iter().position(|h| h.contains(k)))
+        };
+
+        let (date, amount) = match (find(&["date"]), find(&["amount"])) {
+            (Some(date), Some(amount)) => (date, amount),
+            _ => return None,
+        };
+        let required = |keywords: &[&str], name: &str| {
+            find(keywords).ok_or_else(|| format!("Header row has no '{}' column", name))
+        };
+
+        Some((|| {
+            Ok(ImportColumnMap {
+                date,
+                description: required(&["desc", "payee", "name"], "description")?,
+                amount,
+                transaction_type: required(&["type"], "type")?,
+                category: required(&["categ"], "category")?,
+                tags: find(&["tag"]),
+                notes: find(&["note", "memo"]),
+            })
+        })())
+    }
+
+    fn required_len(&self) -> usize {
+        [self.date, self.description, self.amount, self.transaction_type, self.category]
+            .into_iter()
+            .max()
+            .unwrap_or(0)
+            + 1
+    }
+}
+
+/// Writes the map back as a column order accepted by `ImportColumnMap::from_order`.
+impl fmt::Display for ImportColumnMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut columns = vec![
+            (self.date, "date"),
+            (self.description, "description"),
+            (self.amount, "amount"),
+            (self.transaction_type, "type"),
+            (self.category, "category"),
+        ];
+        columns.extend(self.tags.map(|i| (i, "tags")));
+        columns.extend(self.notes.map(|i| (i, "notes")));
+
+        let width = columns.iter().map(|(i, _)| i + 1).max().unwrap_or(0);
+        let order: Vec<&str> = (0..width)
+            .map(|i| columns.iter().find(|(c, _)| *c == i).map_or("-", |(_, name)| *name))
+            .collect();
+        write!(f, "{}", order.join(","))
+    }
+}
+
+/// Reads the header row of a CSV/TSV file, if it has one, into a column map.
+/// Other formats and headerless files return `None`.
+pub fn detect_column_map(format: ImportFormat, path: &str, csv_delimiter: u8) -> Result<Option<ImportColumnMap>, String> {
+    let delimiter = match format {
+        ImportFormat::Csv => csv_delimiter,
+        ImportFormat::Tsv => b'\t',
+        _ => return Ok(None),
+    };
+    let file = File::open(path).map_err(|e| format!("Failed to open file '{}': {}", path, e))?;
+
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .has_headers(false)
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(file);
+
+    match reader.records().next() {
+        Some(Ok(record)) => ImportColumnMap::from_header(&record).transpose(),
+        Some(Err(e)) => Err(format!("CSV parse error: {}", e)),
+        None => Ok(None),
+    }
+}
+
+/// What an import would do, from `preview_import`.
+#[derive(Debug, Default)]
+pub struct ImportPreview {
+    pub valid: usize,
+    pub errors: Vec<(usize, String)>,
+    pub duplicates: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportResult {
+    pub imported: usize,
+    pub duplicates: usize,
+    /// 1-based row (CSV line, JSON entry, OFX transaction or YNAB/Mint data row) and the reason it was skipped.
+    pub errors: Vec<(usize, String)>,
+    pub alert_ids: Vec<i32>,
+}
+
+/// `import_transactions_to_db_with_progress` calls its callback after this many rows.
+pub const IMPORT_PROGRESS_INTERVAL: usize = 100;
+
 pub fn import_transactions_to_db(
     conn: &Connection,
     format: ImportFormat,
     path: &str,
-) -> Result<(usize, Vec<i32>), String> {
-    let mut transactions = match format {
-        ImportFormat::CSV => import_csv(path)?,
-        ImportFormat::OFX => import_ofx(path)?,
+    options: &ImportOptions,
+) -> Result<ImportResult, String> {
+    import_transactions_to_db_with_progress(conn, format, path, options, |_, _| {})
+}
+
+/// Same as `import_transactions_to_db`, calling `on_progress(current_row, total_rows)` after
+/// every `IMPORT_PROGRESS_INTERVAL` rows and after the last one. Rows that are skipped count too.
+pub fn import_transactions_to_db_with_progress<F: Fn(usize, usize)>(
+    conn: &Connection,
+    format: ImportFormat,
+    path: &str,
+    options: &ImportOptions,
+    on_progress: F,
+) -> Result<ImportResult, String> {
+    let (row_label, rows) = match format {
+        ImportFormat::Csv => ("Line", import_csv(path, options.csv_delimiter.unwrap_or(b','), options.column_map)?),
+        ImportFormat::Tsv => ("Line", import_tsv(path, options.column_map)?),
+        ImportFormat::Ofx => ("Transaction", import_ofx(path)?),
+        ImportFormat::Json => ("Entry", import_json(path)?),
+        ImportFormat::YnabCsv => ("Row", import_ynab_csv(path)?),
+        ImportFormat::MintCsv => ("Row", import_mint_csv(path)?),
     };
 
-    let rules = crate::db::rule_repository::get_all_rules(conn).unwrap_or_default();
-    let compiled_rules: Vec<(Regex, String)> = rules
-        .into_iter()
-        .filter_map(|r| Regex::new(&r.pattern).ok().map(|re| (re, r.category)))
-        .collect();
+    let rules = RuleSet::new(
+        crate::db::rule_repository::get_all_rules(conn).map_err(|e| format!("Failed to load categorization rules: {}", e))?,
+    );
 
-    let mut count = 0;
-    let mut alert_ids = Vec::new();
-    for transaction in &mut transactions {
-        if transaction.category == "Uncategorized"
-            || transaction.category.is_empty()
-            || transaction.category == "null"
-        {
-            for (re, cat) in &compiled_rules {
-                if re.is_match(&transaction.description) {
-                    transaction.category = cat.clone();
-                    break;
-                }
+    if options.dry_run {
+        return insert_rows(conn, rows, &rules, options, &on_progress)
+            .map_err(|error| error.describe(row_label));
+    }
+
+    conn.execute("BEGIN", [])
+        .map_err(|e| format!("Failed to start import transaction: {}", e))?;
+
+    match insert_rows(conn, rows, &rules, options, &on_progress) {
+        Ok(result) => {
+            conn.execute("COMMIT", [])
+                .map_err(|e| format!("Failed to commit import: {}", e))?;
+            Ok(result)
+        }
+        Err(error) => {
+            let _ = conn.execute("ROLLBACK", []);
+            Err(error.describe(row_label))
+        }
+    }
+}
+
+/// Parses and validates the file like `import_transactions_to_db`, without writing to the database.
+/// Every failing row is reported, whatever `options.on_error` says.
+pub fn preview_import(
+    conn: &Connection,
+    format: ImportFormat,
+    path: &str,
+    options: &ImportOptions,
+) -> Result<ImportPreview, String> {
+    let options = ImportOptions {
+        dry_run: true,
+        on_error: ImportErrorBehavior::SkipRow,
+        ..*options
+    };
+    let result = import_transactions_to_db(conn, format, path, &options)?;
+
+    Ok(ImportPreview {
+        valid: result.imported,
+        errors: result.errors,
+        duplicates: result.duplicates,
+    })
+}
+
+/// Why `insert_rows` gave up: a failing row under `AbortAll`, or a database error on save.
+enum InsertError {
+    Row(usize, String),
+    Save(String),
+}
+
+impl InsertError {
+    fn describe(self, row_label: &str) -> String {
+        match self {
+            InsertError::Row(row, message) => format!("{} {}: {}", row_label, row, message),
+            InsertError::Save(message) => message,
+        }
+    }
+}
+
+/// Inserts parsed rows inside the caller's SQLite transaction: checks every row first, then
+/// saves the accepted ones with one `add_transaction_batch` and checks their budgets.
+/// With `AbortAll` the first failing row is returned as the error.
+/// In a dry run `imported` counts the rows that would be inserted.
+fn insert_rows(
+    conn: &Connection,
+    rows: Vec<Result<Transaction, String>>,
+    rules: &RuleSet,
+    options: &ImportOptions,
+    on_progress: &impl Fn(usize, usize),
+) -> Result<ImportResult, InsertError> {
+    let mut result = ImportResult::default();
+    let total_rows = rows.len();
+    let mut accepted = AcceptedRows::default();
+
+    for (index, row) in rows.into_iter().enumerate() {
+        let row_number = index + 1;
+        let outcome = row.and_then(|mut transaction| {
+            check_row(conn, &mut transaction, rules, options, &accepted).map(|outcome| (outcome, transaction))
+        });
+
+        match outcome {
+            Ok((RowOutcome::Accepted, transaction)) => {
+                result.imported += 1;
+                accepted.push(transaction);
             }
+            Ok((RowOutcome::Duplicate, _)) => result.duplicates += 1,
+            Err(message) => match options.on_error {
+                ImportErrorBehavior::AbortAll => return Err(InsertError::Row(row_number, message)),
+                ImportErrorBehavior::SkipRow => result.errors.push((row_number, message)),
+            },
+        }
+        if row_number % IMPORT_PROGRESS_INTERVAL == 0 || row_number == total_rows {
+            on_progress(row_number, total_rows);
         }
+    }
+
+    if !options.dry_run {
+        repository::add_transaction_batch(conn, &accepted.transactions)
+            .map_err(|e| InsertError::Save(format!("Failed to save imported transactions: {}", e)))?;
+        result.alert_ids = budget_alerts(conn, &accepted.transactions).map_err(InsertError::Save)?;
+    }
+    Ok(result)
+}
 
-        repository::add_transaction(conn, transaction)?;
-        if let Some(alert_id) = check_budget_and_alert(conn, transaction)? {
-            alert_ids.push(alert_id);
+enum RowOutcome {
+    Accepted,
+    Duplicate,
+}
+
+/// Rows that passed `check_row` and are not saved yet, with their IDs and duplicate keys so
+/// later rows of the same file are checked against them too.
+#[derive(Default)]
+struct AcceptedRows {
+    transactions: Vec<Transaction>,
+    ids: HashSet<String>,
+    keys: HashSet<(NaiveDate, String, Decimal)>,
+}
+
+impl AcceptedRows {
+    fn duplicate_key(transaction: &Transaction) -> (NaiveDate, String, Decimal) {
+        (transaction.date, transaction.description.clone(), transaction.amount.normalize())
+    }
+
+    fn push(&mut self, transaction: Transaction) {
+        self.ids.insert(transaction.id.clone());
+        self.keys.insert(Self::duplicate_key(&transaction));
+        self.transactions.push(transaction);
+    }
+}
+
+fn check_row(
+    conn: &Connection,
+    transaction: &mut Transaction,
+    rules: &RuleSet,
+    options: &ImportOptions,
+    accepted: &AcceptedRows,
+) -> Result<RowOutcome, String> {
+    repository::check_amount_precision(&transaction.amount)?;
+    if options.duplicates != DuplicatePolicy::Allow
+        && (accepted.keys.contains(&AcceptedRows::duplicate_key(transaction))
+            || is_duplicate(conn, transaction.date, &transaction.description, transaction.amount)?)
+    {
+        if options.duplicates == DuplicatePolicy::Skip {
+            return Ok(RowOutcome::Duplicate);
         }
-        count += 1;
+        return Err(format!(
+            "Duplicate of an existing transaction ({}, {}, {})",
+            transaction.date.format("%Y-%m-%d"),
+            transaction.description,
+            transaction.amount
+        ));
+    }
+
+    if (transaction.category == "Uncategorized"
+        || transaction.category.is_empty()
+        || transaction.category == "null")
+        && let Some(category) = rules.category_for(&transaction.description)
+    {
+        transaction.category = category.to_string();
+    }
+
+    // The insert would fail on an id that is already taken (e.g. a re-imported OFX FITID).
+    if accepted.ids.contains(&transaction.id) || repository::get_transaction_by_id(conn, &transaction.id)?.is_some() {
+        return Err(format!("Transaction with ID {} already exists", transaction.id));
     }
-    Ok((count, alert_ids))
+    Ok(RowOutcome::Accepted)
+}
+
+/// Checks each budget touched by `transactions` once per budget period, after they are all
+/// saved, so a period that went over budget gets one alert with its final total.
+fn budget_alerts(conn: &Connection, transactions: &[Transaction]) -> Result<Vec<i32>, String> {
+    let mut checked = HashSet::new();
+    let mut alert_ids = Vec::new();
+    for transaction in transactions.iter().filter(|t| t.transaction_type == TransactionType::Expense) {
+        let Some(budget) = budget_repository::get_budget(conn, &transaction.category)? else {
+            continue;
+        };
+        if checked.insert((budget.id, budget.period.bounds(transaction.date))) {
+            alert_ids.extend(check_budget_and_alert(conn, transaction)?);
+        }
+    }
+    Ok(alert_ids)
+}
+
+/// Amounts are compared numerically so `3.5` and `3.50` count as the same value.
+fn is_duplicate(conn: &Connection, date: NaiveDate, description: &str, amount: Decimal) -> Result<bool, String> {
+    let count: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM transactions WHERE date = ?1 AND description = ?2 AND amount = ?3",
+            rusqlite::params![date.to_string(), description, repository::amount_to_sql(&amount)?],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to check for duplicates: {}", e))?;
+    Ok(count > 0)
 }
 
-fn import_ofx(path: &str) -> Result<Vec<Transaction>, String> {
+fn import_ofx(path: &str) -> Result<Vec<Result<Transaction, String>>, String> {
     let mut reader = Reader::from_file(path).map_err(|e| format!("Failed to open OFX file: {}", e))?;
     reader.config_mut().trim_text(true);
 
@@ -93,19 +495,17 @@ fn import_ofx(path: &str) -> Result<Vec<Transaction>, String> {
                 }
                 current_tag = name;
             }
-            Ok(Event::Text(e)) => {
-                if inside_transaction {
-                    let text = String::from_utf8_lossy(&e).into_owned();
-                    match current_tag.as_str() {
-                        "TRNTYPE" => t_type = text,
-                        "DTPOSTED" => t_date = text,
-                        "TRNAMT" => t_amount = text,
-                        "NAME" => t_name = text,
-                        "MEMO" => t_memo = text,
-                        "FITID" => t_fitid = text,
-                        "CATEGORY" => t_category = text,
-                        _ => {}
-                    }
+            Ok(Event::Text(e)) if inside_transaction => {
+                let text = String::from_utf8_lossy(&e).into_owned();
+                match current_tag.as_str() {
+                    "TRNTYPE" => t_type = text,
+                    "DTPOSTED" => t_date = text,
+                    "TRNAMT" => t_amount = text,
+                    "NAME" => t_name = text,
+                    "MEMO" => t_memo = text,
+                    "FITID" => t_fitid = text,
+                    "CATEGORY" => t_category = text,
+                    _ => {}
                 }
             }
             Ok(Event::End(ref e)) => {
@@ -115,48 +515,52 @@ fn import_ofx(path: &str) -> Result<Vec<Transaction>, String> {
                 if name == "STMTTRN" && inside_transaction {
                     inside_transaction = false;
 
-                    if t_date.len() < 8 {
-                         return Err(format!("Invalid date format in OFX: {}", t_date));
-                    }
-                    let date_str = &t_date[0..8]; // Take first 8 chars
-                    let date = NaiveDate::parse_from_str(date_str, "%Y%m%d")
-                        .map_err(|e| format!("Invalid date format {}: {}", t_date, e))?;
-
-                    let amount_dec = Decimal::from_str(&t_amount)
-                        .map_err(|e| format!("Invalid amount {}: {}", t_amount, e))?;
-
-                    let (parsed_type, final_amount) = if amount_dec.is_sign_negative() {
-                        (TransactionType::Expense, amount_dec.abs())
-                    } else {
-                        (TransactionType::Income, amount_dec)
-                    };
-
-                    let description = if !t_memo.is_empty() {
-                        format!("{} - {}", t_name, t_memo)
-                    } else {
-                        t_name.clone()
-                    };
-
-                    let id = if !t_fitid.is_empty() {
-                        t_fitid.clone()
-                    } else {
-                        Uuid::new_v4().to_string()
-                    };
-
-                    let category = if !t_category.is_empty() {
-                        t_category.clone()
-                    } else {
-                        "Uncategorized".to_string()
-                    };
-
-                    transactions.push(Transaction::new(
-                        id,
-                        date,
-                        description,
-                        final_amount,
-                        parsed_type,
-                        category,
-                    ));
+                    // A bad transaction is reported as a row error; the XML itself is still read to the end.
+                    let parsed = (|| {
+                        if t_date.len() < 8 {
+                            return Err(format!("Invalid date format in OFX: {}", t_date));
+                        }
+                        let date_str = &t_date[0..8]; // Take first 8 chars
+                        let date = NaiveDate::parse_from_str(date_str, "%Y%m%d")
+                            .map_err(|e| format!("Invalid date format {}: {}", t_date, e))?;
+
+                        let amount_dec = Decimal::from_str(&t_amount)
+                            .map_err(|e| format!("Invalid amount {}: {}", t_amount, e))?;
+
+                        let (parsed_type, final_amount) = if amount_dec.is_sign_negative() {
+                            (TransactionType::Expense, amount_dec.abs())
+                        } else {
+                            (TransactionType::Income, amount_dec)
+                        };
+
+                        let description = if !t_memo.is_empty() {
+                            format!("{} - {}", t_name, t_memo)
+                        } else {
+                            t_name.clone()
+                        };
+
+                        let id = if !t_fitid.is_empty() {
+                            t_fitid.clone()
+                        } else {
+                            Uuid::new_v4().to_string()
+                        };
+
+                        let category = if !t_category.is_empty() {
+                            t_category.clone()
+                        } else {
+                            "Uncategorized".to_string()
+                        };
+
+                        Ok(Transaction::new(
+                            id,
+                            date,
+                            description,
+                            final_amount,
+                            parsed_type,
+                            category,
+                        ))
+                    })();
+                    transactions.push(parsed);
                 }
             }
             Ok(Event::Eof) => break,
@@ -169,59 +573,510 @@ fn import_ofx(path: &str) -> Result<Vec<Transaction>, String> {
     Ok(transactions)
 }
 
-fn import_csv(path: &str) -> Result<Vec<Transaction>, String> {
+/// How often `watch_import` scans the directory and checks for a key press.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Imported files are moved to this subfolder of the watched directory.
+pub const PROCESSED_DIR: &str = "processed";
+
+/// Watches `dir_path` for new `.csv` files until `q` (or Esc) is pressed. Each new file is
+/// imported with `options` and moved to `dir_path/processed/`; a file that fails to import
+/// stays where it is. Files already in the directory when the watch starts are left alone.
+pub fn watch_import(conn: &Connection, dir_path: &str, options: &ImportOptions) -> Result<(), String> {
+    let dir = Path::new(dir_path);
+    if !dir.is_dir() {
+        return Err(format!("'{}' is not a directory", dir_path));
+    }
+    let existing = csv_files_in(dir)
+        .map_err(|e| format!("Failed to read directory '{}': {}", dir_path, e))?
+        .into_keys()
+        .collect();
+
+    println!("Watching {} for new CSV files. Press q to stop.", dir_path);
+    crossterm::terminal::enable_raw_mode().map_err(|e| format!("Failed to enable raw mode: {}", e))?;
+
+    let (sender, receiver) = mpsc::channel();
+    let stop = Arc::new(AtomicBool::new(false));
+    let watcher = {
+        let (dir, stop) = (dir.to_path_buf(), Arc::clone(&stop));
+        thread::spawn(move || poll_new_csv_files(&dir, existing, &sender, &stop))
+    };
+    let result = run_watch_loop(conn, dir, options, &receiver, quit_pressed, &mut io::stdout());
+    let raw_mode = crossterm::terminal::disable_raw_mode().map_err(|e| format!("Failed to disable raw mode: {}", e));
+    // Stop the watcher before any error is returned, so it never outlives this call.
+    stop.store(true, Ordering::Relaxed);
+    let _ = watcher.join();
+    raw_mode?;
+
+    println!("Stopped watching. {} files imported.", result?);
+    Ok(())
+}
+
+/// Imports every path received from `files` until `quit_requested` says so or the sender is
+/// dropped, writing one line per file to `out`. Returns how many files were imported.
+fn run_watch_loop(
+    conn: &Connection,
+    dir: &Path,
+    options: &ImportOptions,
+    files: &Receiver<PathBuf>,
+    mut quit_requested: impl FnMut() -> Result<bool, String>,
+    out: &mut impl Write,
+) -> Result<usize, String> {
+    let mut imported_files = 0;
+    loop {
+        if quit_requested()? {
+            return Ok(imported_files);
+        }
+        let path = match files.recv_timeout(WATCH_POLL_INTERVAL) {
+            Ok(path) => path,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => return Ok(imported_files),
+        };
+        let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let line = match import_and_move(conn, dir, &path, options) {
+            Ok(result) => {
+                imported_files += 1;
+                format!("{}: imported {} transactions, moved to {}/", name, result.imported, PROCESSED_DIR)
+            }
+            Err(e) => format!("{}: {}", name, e),
+        };
+        // Raw mode does not translate "\n", so every line ends with "\r\n".
+        write!(out, "{}\r\n", line)
+            .and_then(|_| out.flush())
+            .map_err(|e| format!("Failed to write to terminal: {}", e))?;
+    }
+}
+
+fn import_and_move(conn: &Connection, dir: &Path, path: &Path, options: &ImportOptions) -> Result<ImportResult, String> {
+    let path_str = path.to_str().ok_or_else(|| "Invalid file path (non-UTF8).".to_string())?;
+    let result = import_transactions_to_db(conn, ImportFormat::Csv, path_str, options)?;
+
+    let processed = dir.join(PROCESSED_DIR);
+    fs::create_dir_all(&processed)
+        .map_err(|e| format!("Imported, but failed to create '{}': {}", processed.display(), e))?;
+    let target = processed.join(path.file_name().unwrap_or_default());
+    fs::rename(path, &target).map_err(|e| format!("Imported, but failed to move the file: {}", e))?;
+    Ok(result)
+}
+
+/// `true` once q, Esc or Ctrl+C has been pressed; does not wait for input.
+fn quit_pressed() -> Result<bool, String> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+
+    while event::poll(Duration::ZERO).map_err(|e| format!("Failed to read input: {}", e))? {
+        if let Event::Key(key) = event::read().map_err(|e| format!("Failed to read input: {}", e))?
+            && key.kind != KeyEventKind::Release
+        {
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => return Ok(true),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(true),
+                _ => {}
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Scans `dir` until `stop` is set and sends every `.csv` file that is not in `known`. A new
+/// file is only sent once its size is the same on two scans in a row, so a file that is still
+/// being copied is not imported half-written.
+fn poll_new_csv_files(dir: &Path, mut known: HashSet<PathBuf>, files: &Sender<PathBuf>, stop: &AtomicBool) {
+    let mut pending: HashMap<PathBuf, u64> = HashMap::new();
+    while !stop.load(Ordering::Relaxed) {
+        thread::sleep(WATCH_POLL_INTERVAL);
+        let Ok(current) = csv_files_in(dir) else {
+            continue;
+        };
+        // Forget files that were moved away, so a new file with the same name is picked up.
+        known.retain(|path| current.contains_key(path));
+        pending.retain(|path, _| current.contains_key(path));
+        for (path, size) in current {
+            if known.contains(&path) {
+                continue;
+            }
+            if pending.get(&path) == Some(&size) {
+                pending.remove(&path);
+                known.insert(path.clone());
+                if files.send(path).is_err() {
+                    return;
+                }
+            } else {
+                pending.insert(path, size);
+            }
+        }
+    }
+}
+
+/// The `.csv` files (any case) directly inside `dir`, with their sizes.
+fn csv_files_in(dir: &Path) -> io::Result<HashMap<PathBuf, u64>> {
+    let mut files = HashMap::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let metadata = entry.metadata()?;
+        let is_csv = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+        if metadata.is_file() && is_csv {
+            files.insert(path, metadata.len());
+        }
+    }
+    Ok(files)
+}
+
+/// Parses the CSV file at `path` into one result per data row. Only a file that cannot be
+/// opened is an error; malformed rows (bad quoting, wrong column count, invalid UTF-8) are
+/// returned as failed rows.
+pub fn import_csv(
+    path: &str,
+    delimiter: u8,
+    column_map: Option<ImportColumnMap>,
+) -> Result<Vec<Result<Transaction, String>>, String> {
+    import_delimited(path, delimiter, column_map)
+}
+
+/// Same columns as the CSV import, separated by tabs.
+fn import_tsv(path: &str, column_map: Option<ImportColumnMap>) -> Result<Vec<Result<Transaction, String>>, String> {
+    import_delimited(path, b'\t', column_map)
+}
+
+/// A header row is skipped; without an explicit map its column names decide the order.
+fn import_delimited(
+    path: &str,
+    delimiter: u8,
+    column_map: Option<ImportColumnMap>,
+) -> Result<Vec<Result<Transaction, String>>, String> {
     let file = File::open(path).map_err(|e| format!("Failed to open file '{}': {}", path, e))?;
 
     let mut reader = csv::ReaderBuilder::new()
         .trim(csv::Trim::All)
         .has_headers(false)
+        .delimiter(delimiter)
+        .flexible(true)
         .from_reader(file);
 
-    let mut transactions = Vec::new();
+    let mut records = reader.records().peekable();
+    let mut map = column_map.unwrap_or_default();
+    let mut headed = false;
+    if let Some(Ok(first)) = records.peek()
+        && let Some(detected) = ImportColumnMap::from_header(first)
+    {
+        map = column_map.map_or(detected, Ok)?;
+        headed = true;
+        records.next();
+    }
 
-    for (line_index, result) in reader.records().enumerate() {
-        let record =
-            result.map_err(|e| format!("CSV parse error on line {}: {}", line_index + 1, e))?;
+    let transactions = records
+        .map(|result| {
+            result
+                .map_err(|e| format!("CSV parse error: {}", e))
+                .and_then(|record| parse_csv_record(&record, &map, headed))
+        })
+        .collect();
 
-        if record.len() != 5 {
+    Ok(transactions)
+}
+
+/// Files with a header row may carry extra columns; headerless files are limited to the 7 known ones.
+fn parse_csv_record(record: &csv::StringRecord, map: &ImportColumnMap, headed: bool) -> Result<Transaction, String> {
+    if *map == ImportColumnMap::default() && !headed {
+        if !(5..=7).contains(&record.len()) {
             return Err(format!(
-                "Invalid number of columns on line {}: expected 5, got {}",
-                line_index + 1,
+                "Invalid number of columns: expected 5 to 7, got {}",
                 record.len()
             ));
         }
+    } else if record.len() < map.required_len() {
+        return Err(format!(
+            "Invalid number of columns: expected at least {}, got {}",
+            map.required_len(),
+            record.len()
+        ));
+    }
 
-        let date = record.get(0).unwrap_or("");
-        let description = record.get(1).unwrap_or("");
-        let amount = record.get(2).unwrap_or("");
-        let transaction_type = record.get(3).unwrap_or("");
-        let category = record.get(4).unwrap_or("");
-        let final_category = if category.trim().is_empty() {
-            "Uncategorized"
-        } else {
-            category
-        };
+    let field = |index: Option<usize>| index.and_then(|i| record.get(i)).unwrap_or("");
+    let date = field(Some(map.date));
+    let description = field(Some(map.description));
+    let amount = field(Some(map.amount));
+    let transaction_type = field(Some(map.transaction_type));
+    let category = field(Some(map.category));
+    let tags = field(map.tags);
+    let final_category = if category.trim().is_empty() {
+        "Uncategorized"
+    } else {
+        category
+    };
 
-        let raw_input = format!(
-            "{},{},{},{},{}",
-            date, description, amount, transaction_type, final_category
-        );
+    let notes = field(map.notes);
+
+    create_transaction_from_fields(&[date, description, amount, transaction_type, final_category, tags, notes])
+        .map_err(String::from)
+}
+
+/// A record from a CSV export with a header row, read by column name.
+struct HeadedRecord<'a> {
+    record: &'a csv::StringRecord,
+    columns: &'a [(&'static str, usize)],
+}
+
+impl HeadedRecord<'_> {
+    /// Returns an empty string for a column the file does not have.
+    fn get(&self, name: &str) -> &str {
+        self.columns
+            .iter()
+            .find(|(column, _)| *column == name)
+            .and_then(|(_, index)| self.record.get(*index))
+            .unwrap_or("")
+    }
+}
+
+/// Reads a CSV export whose first line names the columns; extra columns are ignored.
+fn import_headed_csv(
+    path: &str,
+    required: &[&'static str],
+    optional: &[&'static str],
+    parse: fn(&HeadedRecord) -> Result<Transaction, String>,
+) -> Result<Vec<Result<Transaction, String>>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open file '{}': {}", path, e))?;
+
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .has_headers(true)
+        .flexible(true)
+        .from_reader(file);
 
-        let transaction = create_transaction(&raw_input)
-            .map_err(|e| format!("Line {}: {}", line_index + 1, e))?;
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("CSV parse error: {}", e))?
+        .clone();
+    let position = |name: &str| {
+        headers
+            .iter()
+            .position(|h| h.trim_start_matches('\u{feff}') == name)
+    };
 
-        transactions.push(transaction);
+    let mut columns = Vec::new();
+    for name in required {
+        let index = position(name).ok_or_else(|| format!("Missing column '{}'", name))?;
+        columns.push((*name, index));
+    }
+    for name in optional {
+        if let Some(index) = position(name) {
+            columns.push((*name, index));
+        }
     }
 
+    let transactions = reader
+        .records()
+        .map(|result| {
+            result
+                .map_err(|e| format!("CSV parse error: {}", e))
+                .and_then(|record| {
+                    parse(&HeadedRecord {
+                        record: &record,
+                        columns: &columns,
+                    })
+                })
+        })
+        .collect();
+
     Ok(transactions)
 }
 
+/// Reads a YNAB register export.
+fn import_ynab_csv(path: &str) -> Result<Vec<Result<Transaction, String>>, String> {
+    import_headed_csv(
+        path,
+        &[
+            "Account",
+            "Flag",
+            "Date",
+            "Payee",
+            "Category Group/Category",
+            "Memo",
+            "Outflow",
+            "Inflow",
+            "Cleared",
+        ],
+        &[],
+        parse_ynab_record,
+    )
+}
+
+fn parse_ynab_record(record: &HeadedRecord) -> Result<Transaction, String> {
+    let raw_date = record.get("Date");
+    let date = NaiveDate::parse_from_str(raw_date, "%m/%d/%Y")
+        .or_else(|_| NaiveDate::parse_from_str(raw_date, "%Y-%m-%d"))
+        .map_err(|_| format!("Invalid date format: {}", raw_date))?;
+
+    let outflow = parse_ynab_amount(record.get("Outflow"))?;
+    let inflow = parse_ynab_amount(record.get("Inflow"))?;
+    let (transaction_type, amount) = if outflow > Decimal::ZERO && inflow > Decimal::ZERO {
+        return Err("Both Outflow and Inflow are set".to_string());
+    } else if outflow > Decimal::ZERO {
+        (TransactionType::Expense, outflow)
+    } else if inflow > Decimal::ZERO {
+        (TransactionType::Income, inflow)
+    } else {
+        return Err("Missing amount: Outflow and Inflow are both empty or zero".to_string());
+    };
+
+    let description = record.get("Payee");
+    if description.is_empty() {
+        return Err("Description cannot be empty.".to_string());
+    }
+    let category = match record.get("Category Group/Category") {
+        "" => "Uncategorized",
+        category => category,
+    };
+
+    let mut transaction = Transaction::new(
+        Uuid::new_v4().to_string(),
+        date,
+        description.to_string(),
+        amount,
+        transaction_type,
+        category.to_string(),
+    );
+    transaction.notes = record.get("Memo").to_string();
+    transaction.status = match record.get("Cleared") {
+        "Reconciled" => TransactionStatus::Reconciled,
+        "Uncleared" => TransactionStatus::Pending,
+        _ => TransactionStatus::Cleared,
+    };
+
+    Ok(transaction)
+}
+
+/// YNAB writes amounts with a currency symbol and thousands separators, e.g. `$1,234.56`.
+fn parse_ynab_amount(raw: &str) -> Result<Decimal, String> {
+    let cleaned: String = raw
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '-')
+        .collect();
+    if cleaned.is_empty() {
+        return Ok(Decimal::ZERO);
+    }
+    Decimal::from_str(&cleaned).map_err(|_| format!("Invalid amount: {}", raw))
+}
+
+/// Reads a Mint transactions export.
+fn import_mint_csv(path: &str) -> Result<Vec<Result<Transaction, String>>, String> {
+    import_headed_csv(
+        path,
+        &["Date", "Description", "Amount", "Transaction Type", "Category"],
+        &["Original Description", "Account Name", "Labels", "Notes"],
+        parse_mint_record,
+    )
+}
+
+fn parse_mint_record(record: &HeadedRecord) -> Result<Transaction, String> {
+    let raw_date = record.get("Date");
+    let date = NaiveDate::parse_from_str(raw_date, "%m/%d/%Y")
+        .or_else(|_| NaiveDate::parse_from_str(raw_date, "%Y-%m-%d"))
+        .map_err(|_| format!("Invalid date format: {}", raw_date))?;
+
+    let transaction_type = match record.get("Transaction Type").to_lowercase().as_str() {
+        "debit" => TransactionType::Expense,
+        "credit" => TransactionType::Income,
+        other => return Err(format!("Invalid transaction type: {} (expected debit or credit)", other)),
+    };
+
+    // Mint writes amounts unsigned; the direction comes from the transaction type.
+    let raw_amount = record.get("Amount");
+    let amount = Decimal::from_str(&raw_amount.replace(',', ""))
+        .map_err(|_| format!("Invalid amount: {}", raw_amount))?
+        .abs();
+    if amount.is_zero() {
+        return Err("Amount must be greater than zero.".to_string());
+    }
+
+    let description = record.get("Description");
+    if description.is_empty() {
+        return Err("Description cannot be empty.".to_string());
+    }
+    let category = match record.get("Category") {
+        "" => "Uncategorized",
+        category => category,
+    };
+
+    let mut transaction = Transaction::new(
+        Uuid::new_v4().to_string(),
+        date,
+        description.to_string(),
+        amount,
+        transaction_type,
+        category.to_string(),
+    );
+    transaction.tags = parse_tags(&record.get("Labels").split_whitespace().collect::<Vec<_>>().join(";"));
+    transaction.notes = match (record.get("Account Name"), record.get("Notes")) {
+        ("", notes) => notes.to_string(),
+        (account, "") => account.to_string(),
+        (account, notes) => format!("{} - {}", account, notes),
+    };
+
+    Ok(transaction)
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonTransactionRecord {
+    date: String,
+    description: String,
+    amount: serde_json::Value,
+    #[serde(rename = "type")]
+    transaction_type: String,
+    #[serde(default)]
+    category: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    notes: Option<String>,
+}
+
+fn import_json(path: &str) -> Result<Vec<Result<Transaction, String>>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open file '{}': {}", path, e))?;
+
+    let records: Vec<JsonTransactionRecord> = serde_json::from_reader(std::io::BufReader::new(file))
+        .map_err(|e| format!("JSON parse error: {}", e))?;
+
+    Ok(records.iter().map(parse_json_record).collect())
+}
+
+fn parse_json_record(record: &JsonTransactionRecord) -> Result<Transaction, String> {
+    // Amounts may be written either as JSON numbers or as strings.
+    let amount = match &record.amount {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    let category = record.category.as_deref().unwrap_or("").trim();
+    let final_category = if category.is_empty() {
+        "Uncategorized"
+    } else {
+        category
+    };
+
+    let tags = record.tags.join(";");
+
+    create_transaction_from_fields(&[
+        record.date.trim(),
+        record.description.trim(),
+        amount.trim(),
+        record.transaction_type.trim(),
+        final_category,
+        &tags,
+        record.notes.as_deref().unwrap_or(""),
+    ])
+    .map_err(String::from)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::db::connection::establish_test_connection;
     use crate::db::budget_repository;
     use crate::db::alert_repository;
+    use crate::models::alert::AlertFilter;
+    use crate::models::rule::RuleMatchType;
     use std::io::Write;
     use tempfile::{NamedTempFile};
 
@@ -240,15 +1095,135 @@ mod tests {
 ";
 
         let tmp = write_temp_csv(csv_data);
-        let result = import_transactions_to_db(&conn, ImportFormat::CSV, tmp.path().to_str().unwrap());
+        let result = import_transactions_to_db(&conn, ImportFormat::Csv, tmp.path().to_str().unwrap(), &ImportOptions::default());
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().0, 2);
+        assert_eq!(result.unwrap().imported, 2);
 
         let all = crate::db::repository::get_all_transactions(&conn).unwrap();
         assert_eq!(all.len(), 2);
     }
 
+    #[test]
+    fn test_import_csv_with_tags_column() {
+        let conn = establish_test_connection().unwrap();
+        let csv_data = "\
+2025-07-01,Hotel,240.00,expense,Travel,vacation;tax-deductible
+2025-07-02,Coffee,3.50,expense,Food
+";
+
+        let tmp = write_temp_csv(csv_data);
+        let count = import_transactions_to_db(&conn, ImportFormat::Csv, tmp.path().to_str().unwrap(), &ImportOptions::default()).unwrap().imported;
+        assert_eq!(count, 2);
+
+        let all = crate::db::repository::get_all_transactions(&conn).unwrap();
+        let hotel = all.iter().find(|t| t.description == "Hotel").unwrap();
+        assert_eq!(hotel.tags, vec!["vacation", "tax-deductible"]);
+        let coffee = all.iter().find(|t| t.description == "Coffee").unwrap();
+        assert!(coffee.tags.is_empty());
+    }
+
+    #[test]
+    fn test_import_csv_with_notes_column() {
+        let conn = establish_test_connection().unwrap();
+        let csv_data = "\
+2025-07-01,Hotel,240.00,expense,Travel,vacation,\"Booked via agency, paid in full\"
+2025-07-02,Coffee,3.50,expense,Food,work
+";
+
+        let tmp = write_temp_csv(csv_data);
+        let count = import_transactions_to_db(&conn, ImportFormat::Csv, tmp.path().to_str().unwrap(), &ImportOptions::default()).unwrap().imported;
+        assert_eq!(count, 2);
+
+        let all = crate::db::repository::get_all_transactions(&conn).unwrap();
+        let hotel = all.iter().find(|t| t.description == "Hotel").unwrap();
+        assert_eq!(hotel.notes, "Booked via agency, paid in full");
+        let coffee = all.iter().find(|t| t.description == "Coffee").unwrap();
+        assert_eq!(coffee.notes, "");
+    }
+
+    #[test]
+    fn test_import_tsv_to_db_success() {
+        let conn = establish_test_connection().unwrap();
+        let tsv_data = "2025-11-10\tSalary, November\t1500.00\tincome\tJob\n2025-11-11\tCoffee\t3.50\texpense\tFood\twork\tPaid, with card\n";
+
+        let tmp = write_temp_csv(tsv_data);
+        let result = import_transactions_to_db(&conn, ImportFormat::Tsv, tmp.path().to_str().unwrap(), &ImportOptions::default()).unwrap();
+        assert_eq!(result.imported, 2);
+
+        let all = crate::db::repository::get_all_transactions(&conn).unwrap();
+        let coffee = all.iter().find(|t| t.description == "Coffee").unwrap();
+        assert_eq!(coffee.tags, vec!["work"]);
+        assert_eq!(coffee.notes, "Paid, with card");
+    }
+
+    #[test]
+    fn test_column_map_from_order() {
+        let map = ImportColumnMap::from_order("description, date, -, amount, type, category").unwrap();
+        assert_eq!(map.description, 0);
+        assert_eq!(map.date, 1);
+        assert_eq!(map.amount, 3);
+        assert_eq!(map.tags, None);
+        assert_eq!(map.to_string(), "description,date,-,amount,type,category");
+        assert_eq!(ImportColumnMap::default().to_string(), "date,description,amount,type,category,tags,notes");
+
+        assert!(ImportColumnMap::from_order("date,description,amount,type").unwrap_err().contains("category"));
+        assert!(ImportColumnMap::from_order("date,date,amount,type,category").unwrap_err().contains("twice"));
+        assert!(ImportColumnMap::from_order("date,payee,amount,type,category").unwrap_err().contains("Unknown"));
+    }
+
+    #[test]
+    fn test_import_csv_with_detected_header() {
+        let conn = establish_test_connection().unwrap();
+        let csv_data = "\
+Category,Amount,Transaction Date,Description,Type,Account,Notes
+Job,1500.00,2025-11-10,Salary,income,Checking,
+Food,3.50,2025-11-11,Coffee,expense,Checking,With Sam
+";
+        let tmp = write_temp_csv(csv_data);
+        let path = tmp.path().to_str().unwrap();
+
+        let map = detect_column_map(ImportFormat::Csv, path, b',').unwrap().unwrap();
+        assert_eq!(map.to_string(), "category,amount,date,description,type,-,notes");
+
+        let result = import_transactions_to_db(&conn, ImportFormat::Csv, path, &ImportOptions::default()).unwrap();
+        assert_eq!(result.imported, 2);
+
+        let all = crate::db::repository::get_all_transactions(&conn).unwrap();
+        let coffee = all.iter().find(|t| t.description == "Coffee").unwrap();
+        assert_eq!(coffee.amount, Decimal::new(350, 2));
+        assert_eq!(coffee.category, "Food");
+        assert_eq!(coffee.notes, "With Sam");
+    }
+
+    #[test]
+    fn test_import_csv_with_explicit_column_map() {
+        let conn = establish_test_connection().unwrap();
+        let csv_data = "Salary,2025-11-10,income,1500.00,Job\n";
+        let tmp = write_temp_csv(csv_data);
+        let path = tmp.path().to_str().unwrap();
+
+        assert!(detect_column_map(ImportFormat::Csv, path, b',').unwrap().is_none());
+
+        let options = ImportOptions {
+            column_map: Some(ImportColumnMap::from_order("description,date,type,amount,category").unwrap()),
+            ..Default::default()
+        };
+        let result = import_transactions_to_db(&conn, ImportFormat::Csv, path, &options).unwrap();
+        assert_eq!(result.imported, 1);
+
+        let all = crate::db::repository::get_all_transactions(&conn).unwrap();
+        assert_eq!(all[0].description, "Salary");
+        assert_eq!(all[0].amount, Decimal::new(150000, 2));
+    }
+
+    #[test]
+    fn test_detect_column_map_incomplete_header() {
+        let tmp = write_temp_csv("Date,Amount,Description\n2025-11-10,1500.00,Salary\n");
+        let error = detect_column_map(ImportFormat::Csv, tmp.path().to_str().unwrap(), b',').unwrap_err();
+        assert!(error.contains("'type'"));
+    }
+
     #[test]
     fn test_import_csv_invalid_data() {
         let conn = establish_test_connection().unwrap();
@@ -257,7 +1232,7 @@ bad-date,Salary,1500.00,income,Job
 ";
 
         let tmp = write_temp_csv(csv_data);
-        let result = import_transactions_to_db(&conn, ImportFormat::CSV, tmp.path().to_str().unwrap());
+        let result = import_transactions_to_db(&conn, ImportFormat::Csv, tmp.path().to_str().unwrap(), &ImportOptions::default());
         
         assert!(result.is_err());
         let error = result.unwrap_err();
@@ -265,10 +1240,156 @@ bad-date,Salary,1500.00,income,Job
         assert!(error.contains("Invalid date"));
     }
 
+    #[test]
+    fn test_import_abort_all_rolls_back() {
+        let conn = establish_test_connection().unwrap();
+        budget_repository::set_budget(&conn, "Food", &Decimal::new(100, 2), None).unwrap();
+        let csv_data = "\
+2025-11-10,Salary,1500.00,income,Job
+2025-11-11,Dinner,6.00,expense,Food
+2025-11-12,Coffee,abc,expense,Food
+2025-11-13,Lunch,8.00,expense,Food
+";
+
+        let tmp = write_temp_csv(csv_data);
+        let result = import_transactions_to_db(&conn, ImportFormat::Csv, tmp.path().to_str().unwrap(), &ImportOptions::default());
+
+        let error = result.unwrap_err();
+        assert!(error.contains("Line 3"));
+        assert!(error.contains("Invalid amount"));
+        assert!(crate::db::repository::get_all_transactions(&conn).unwrap().is_empty());
+        assert_eq!(alert_repository::get_last_alert_id(&conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_import_abort_all_rolls_back_on_insert_failure() {
+        let conn = establish_test_connection().unwrap();
+        let ofx_data = r#"
+<OFX>
+  <STMTTRN><DTPOSTED>20260111</DTPOSTED><TRNAMT>-1.00</TRNAMT><FITID>dup</FITID><NAME>First</NAME></STMTTRN>
+  <STMTTRN><DTPOSTED>20260112</DTPOSTED><TRNAMT>-2.00</TRNAMT><FITID>dup</FITID><NAME>Second</NAME></STMTTRN>
+</OFX>
+"#;
+        let tmp = write_temp_csv(ofx_data);
+        let result = import_transactions_to_db(&conn, ImportFormat::Ofx, tmp.path().to_str().unwrap(), &ImportOptions::default());
+
+        let error = result.unwrap_err();
+        assert!(error.contains("Transaction 2"));
+        assert!(crate::db::repository::get_all_transactions(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_import_skip_row_keeps_valid_rows() {
+        let conn = establish_test_connection().unwrap();
+        let csv_data = "\
+2025-11-10,Salary,1500.00,income,Job
+2025-11-11,Coffee,abc,expense,Food
+2025-11-12,Lunch
+2025-11-13,Dinner,6.00,expense,Food
+";
+
+        let tmp = write_temp_csv(csv_data);
+        let result = import_transactions_to_db(&conn, ImportFormat::Csv, tmp.path().to_str().unwrap(), &ImportOptions { on_error: ImportErrorBehavior::SkipRow, ..Default::default() }).unwrap();
+
+        assert_eq!(result.imported, 2);
+        assert_eq!(result.errors.len(), 2);
+        assert_eq!(result.errors[0].0, 2);
+        assert!(result.errors[0].1.contains("Invalid amount"));
+        assert_eq!(result.errors[1].0, 3);
+        assert!(result.errors[1].1.contains("Invalid number of columns"));
+
+        let all = crate::db::repository::get_all_transactions(&conn).unwrap();
+        assert_eq!(all.len(), 2);
+    }
+
+    #[test]
+    fn test_is_duplicate() {
+        let conn = establish_test_connection().unwrap();
+        crate::operations::add::add_transaction_to_db(&conn, "2025-11-11,Coffee,3.50,expense,Food").unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 11, 11).unwrap();
+
+        assert!(is_duplicate(&conn, date, "Coffee", Decimal::new(35, 1)).unwrap());
+        assert!(!is_duplicate(&conn, date, "Tea", Decimal::new(350, 2)).unwrap());
+        assert!(!is_duplicate(&conn, date, "Coffee", Decimal::new(351, 2)).unwrap());
+    }
+
+    #[test]
+    fn test_import_same_file_twice_with_duplicate_policies() {
+        let conn = establish_test_connection().unwrap();
+        let csv_data = "\
+2025-11-10,Salary,1500.00,income,Job
+2025-11-11,Coffee,3.50,expense,Food
+";
+        let tmp = write_temp_csv(csv_data);
+        let path = tmp.path().to_str().unwrap();
+
+        let first = import_transactions_to_db(&conn, ImportFormat::Csv, path, &ImportOptions::default()).unwrap();
+        assert_eq!(first.imported, 2);
+
+        let skipped = import_transactions_to_db(&conn, ImportFormat::Csv, path, &ImportOptions { duplicates: DuplicatePolicy::Skip, ..Default::default() }).unwrap();
+        assert_eq!(skipped.imported, 0);
+        assert_eq!(skipped.duplicates, 2);
+
+        let error = import_transactions_to_db(&conn, ImportFormat::Csv, path, &ImportOptions::default()).unwrap_err();
+        assert!(error.contains("Line 1"));
+        assert!(error.contains("Duplicate"));
+
+        let allowed = import_transactions_to_db(&conn, ImportFormat::Csv, path, &ImportOptions { duplicates: DuplicatePolicy::Allow, ..Default::default() }).unwrap();
+        assert_eq!(allowed.imported, 2);
+        assert_eq!(crate::db::repository::get_all_transactions(&conn).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_preview_import_writes_nothing() {
+        let conn = establish_test_connection().unwrap();
+        crate::operations::add::add_transaction_to_db(&conn, "2025-11-11,Coffee,3.50,expense,Food").unwrap();
+        budget_repository::set_budget(&conn, "Food", &Decimal::new(1, 0), None).unwrap();
+        let csv_data = "\
+2025-11-10,Salary,1500.00,income,Job
+2025-11-11,Coffee,3.50,expense,Food
+2025-11-12,Lunch,abc,expense,Food
+2025-11-13,Dinner,20.00,expense,Food
+";
+        let tmp = write_temp_csv(csv_data);
+        let options = ImportOptions {
+            duplicates: DuplicatePolicy::Skip,
+            ..Default::default()
+        };
+
+        let preview = preview_import(&conn, ImportFormat::Csv, tmp.path().to_str().unwrap(), &options).unwrap();
+        assert_eq!(preview.valid, 2);
+        assert_eq!(preview.duplicates, 1);
+        assert_eq!(preview.errors.len(), 1);
+        assert_eq!(preview.errors[0].0, 3);
+
+        assert_eq!(crate::db::repository::get_all_transactions(&conn).unwrap().len(), 1);
+        assert!(alert_repository::get_all_alerts(&conn, AlertFilter { include_read: true }).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_dry_run_reports_existing_ids() {
+        let conn = establish_test_connection().unwrap();
+        let ofx_data = r#"<OFX><BANKTRANLIST>
+<STMTTRN><TRNTYPE>DEBIT</TRNTYPE><DTPOSTED>20251111</DTPOSTED><TRNAMT>-3.50</TRNAMT><FITID>FIT-1</FITID><NAME>Coffee</NAME></STMTTRN>
+</BANKTRANLIST></OFX>"#;
+        let tmp = write_temp_csv(ofx_data);
+        let path = tmp.path().to_str().unwrap();
+
+        let dry_run = ImportOptions { dry_run: true, ..Default::default() };
+        assert_eq!(import_transactions_to_db(&conn, ImportFormat::Ofx, path, &dry_run).unwrap().imported, 1);
+        assert!(crate::db::repository::get_all_transactions(&conn).unwrap().is_empty());
+
+        import_transactions_to_db(&conn, ImportFormat::Ofx, path, &ImportOptions::default()).unwrap();
+        let allow = ImportOptions { duplicates: DuplicatePolicy::Allow, ..Default::default() };
+        let preview = preview_import(&conn, ImportFormat::Ofx, path, &allow).unwrap();
+        assert_eq!(preview.valid, 0);
+        assert!(preview.errors[0].1.contains("already exists"));
+    }
+
     #[test]
     fn test_import_nonexistent_file() {
         let conn = establish_test_connection().unwrap();
-        let result = import_transactions_to_db(&conn, ImportFormat::CSV, "nonexistent.csv");
+        let result = import_transactions_to_db(&conn, ImportFormat::Csv, "nonexistent.csv", &ImportOptions::default());
         
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Failed to open file"));
@@ -298,10 +1419,10 @@ bad-date,Salary,1500.00,income,Job
 </OFX>
 "#;
         let tmp = write_temp_csv(ofx_data);
-        let result = import_transactions_to_db(&conn, ImportFormat::OFX, tmp.path().to_str().unwrap());
+        let result = import_transactions_to_db(&conn, ImportFormat::Ofx, tmp.path().to_str().unwrap(), &ImportOptions::default());
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().0, 1);
+        assert_eq!(result.unwrap().imported, 1);
 
         let txs = crate::db::repository::get_all_transactions(&conn).unwrap();
         assert_eq!(txs[0].amount, Decimal::new(1050, 2));
@@ -332,7 +1453,7 @@ bad-date,Salary,1500.00,income,Job
 </OFX>
 "#;
         let tmp = write_temp_csv(ofx_data);
-        let result = import_transactions_to_db(&conn, ImportFormat::OFX, tmp.path().to_str().unwrap());
+        let result = import_transactions_to_db(&conn, ImportFormat::Ofx, tmp.path().to_str().unwrap(), &ImportOptions::default());
 
         assert!(result.is_ok());
         
@@ -340,33 +1461,255 @@ bad-date,Salary,1500.00,income,Job
         assert_eq!(txs[0].category, "Groceries");
     }
 
+    #[test]
+    fn test_import_json_success() {
+        let conn = establish_test_connection().unwrap();
+        let json_data = r#"[
+  {"date": "2025-11-10", "description": "Salary", "amount": "1500.00", "type": "income", "category": "Job"},
+  {"date": "2025-11-11", "description": "Coffee", "amount": 3.5, "type": "expense", "category": "Food"}
+]"#;
+
+        let tmp = write_temp_csv(json_data);
+        let result = import_transactions_to_db(&conn, ImportFormat::Json, tmp.path().to_str().unwrap(), &ImportOptions::default());
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().imported, 2);
+
+        let all = crate::db::repository::get_all_transactions(&conn).unwrap();
+        assert_eq!(all.len(), 2);
+        let coffee = all.iter().find(|t| t.description == "Coffee").unwrap();
+        assert_eq!(coffee.amount, Decimal::from_str("3.5").unwrap());
+        assert_eq!(coffee.transaction_type, TransactionType::Expense);
+        assert_eq!(coffee.category, "Food");
+    }
+
+    #[test]
+    fn test_import_json_invalid_entry() {
+        let conn = establish_test_connection().unwrap();
+        let json_data = r#"[
+  {"date": "2025-11-10", "description": "Salary", "amount": "1500.00", "type": "income", "category": "Job"},
+  {"date": "2025-11-11", "description": "Coffee", "amount": "3.50", "type": "refund", "category": "Food"}
+]"#;
+
+        let tmp = write_temp_csv(json_data);
+        let result = import_transactions_to_db(&conn, ImportFormat::Json, tmp.path().to_str().unwrap(), &ImportOptions::default());
+
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(error.contains("Entry 2"));
+        assert!(error.contains("Invalid transaction type"));
+    }
+
+    #[test]
+    fn test_import_json_malformed_file() {
+        let conn = establish_test_connection().unwrap();
+        let tmp = write_temp_csv("this is not json");
+
+        let result = import_transactions_to_db(&conn, ImportFormat::Json, tmp.path().to_str().unwrap(), &ImportOptions::default());
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("JSON parse error"));
+    }
+
+    const YNAB_HEADER: &str =
+        "\"Account\",\"Flag\",\"Date\",\"Payee\",\"Category Group/Category\",\"Category Group\",\"Category\",\"Memo\",\"Outflow\",\"Inflow\",\"Cleared\"\n";
+
+    #[test]
+    fn test_import_ynab_inflow_and_outflow() {
+        let ynab_data = format!(
+            "{}{}{}",
+            YNAB_HEADER,
+            "\"Checking\",\"\",\"03/01/2025\",\"Employer, Inc\",\"Inflow: Ready to Assign\",\"Inflow\",\"Ready to Assign\",\"March pay\",\"$0.00\",\"$2,000.00\",\"Reconciled\"\n",
+            "\"Checking\",\"\",\"03/02/2025\",\"Grocer\",\"Everyday: Food\",\"Everyday\",\"Food\",\"\",\"$45.25\",\"$0.00\",\"Uncleared\"\n",
+        );
+        let tmp = write_temp_csv(&ynab_data);
+
+        let rows = import_ynab_csv(tmp.path().to_str().unwrap()).unwrap();
+        assert_eq!(rows.len(), 2);
+
+        let salary = rows[0].as_ref().unwrap();
+        assert_eq!(salary.date, NaiveDate::from_ymd_opt(2025, 3, 1).unwrap());
+        assert_eq!(salary.description, "Employer, Inc");
+        assert_eq!(salary.amount, Decimal::new(200000, 2));
+        assert_eq!(salary.transaction_type, TransactionType::Income);
+        assert_eq!(salary.category, "Inflow: Ready to Assign");
+        assert_eq!(salary.notes, "March pay");
+        assert_eq!(salary.status, TransactionStatus::Reconciled);
+
+        let groceries = rows[1].as_ref().unwrap();
+        assert_eq!(groceries.amount, Decimal::new(4525, 2));
+        assert_eq!(groceries.transaction_type, TransactionType::Expense);
+        assert_eq!(groceries.category, "Everyday: Food");
+        assert_eq!(groceries.status, TransactionStatus::Pending);
+    }
+
+    #[test]
+    fn test_import_ynab_empty_amounts() {
+        let ynab_data = format!(
+            "{}{}{}",
+            YNAB_HEADER,
+            "\"Checking\",\"\",\"03/03/2025\",\"Nothing\",\"\",\"\",\"\",\"\",\"\",\"\",\"Cleared\"\n",
+            "\"Checking\",\"\",\"03/04/2025\",\"Bakery\",\"\",\"\",\"\",\"\",\"3.10\",\"\",\"Cleared\"\n",
+        );
+        let tmp = write_temp_csv(&ynab_data);
+
+        let rows = import_ynab_csv(tmp.path().to_str().unwrap()).unwrap();
+        assert!(rows[0].as_ref().unwrap_err().contains("Missing amount"));
+
+        let bakery = rows[1].as_ref().unwrap();
+        assert_eq!(bakery.amount, Decimal::new(310, 2));
+        assert_eq!(bakery.transaction_type, TransactionType::Expense);
+        assert_eq!(bakery.category, "Uncategorized");
+    }
+
+    #[test]
+    fn test_import_ynab_skips_header() {
+        let conn = establish_test_connection().unwrap();
+        let header_only = write_temp_csv(YNAB_HEADER);
+        let result = import_transactions_to_db(&conn, ImportFormat::YnabCsv, header_only.path().to_str().unwrap(), &ImportOptions::default()).unwrap();
+        assert_eq!(result.imported, 0);
+
+        let ynab_data = format!(
+            "{}{}",
+            YNAB_HEADER,
+            "\"Checking\",\"\",\"03/02/2025\",\"Grocer\",\"Everyday: Food\",\"Everyday\",\"Food\",\"\",\"$45.25\",\"$0.00\",\"Cleared\"\n",
+        );
+        let tmp = write_temp_csv(&ynab_data);
+        let result = import_transactions_to_db(&conn, ImportFormat::YnabCsv, tmp.path().to_str().unwrap(), &ImportOptions::default()).unwrap();
+        assert_eq!(result.imported, 1);
+        assert_eq!(crate::db::repository::get_all_transactions(&conn).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_import_ynab_missing_column() {
+        let tmp = write_temp_csv("Date,Payee,Amount\n03/02/2025,Grocer,45.25\n");
+        let result = import_ynab_csv(tmp.path().to_str().unwrap());
+        assert!(result.unwrap_err().contains("Missing column 'Account'"));
+    }
+
+    const MINT_HEADER: &str =
+        "\"Date\",\"Description\",\"Original Description\",\"Amount\",\"Transaction Type\",\"Category\",\"Account Name\",\"Labels\",\"Notes\"\n";
+
+    #[test]
+    fn test_import_mint_debit_and_credit() {
+        let mint_data = format!(
+            "{}{}{}",
+            MINT_HEADER,
+            "\"1/05/2025\",\"Starbucks\",\"STARBUCKS STORE 00123 SEATTLE WA\",\"4.65\",\"debit\",\"Coffee Shops\",\"Chase Checking\",\"work daily\",\"\"\n",
+            "\"1/15/2025\",\"Acme Corp\",\"ACME CORP PAYROLL PPD ID: 123\",\"2,150.00\",\"credit\",\"Paycheck\",\"Chase Checking\",\"\",\"January salary\"\n",
+        );
+        let tmp = write_temp_csv(&mint_data);
+
+        let rows = import_mint_csv(tmp.path().to_str().unwrap()).unwrap();
+        assert_eq!(rows.len(), 2);
+
+        let coffee = rows[0].as_ref().unwrap();
+        assert_eq!(coffee.date, NaiveDate::from_ymd_opt(2025, 1, 5).unwrap());
+        assert_eq!(coffee.description, "Starbucks");
+        assert_eq!(coffee.amount, Decimal::new(465, 2));
+        assert_eq!(coffee.transaction_type, TransactionType::Expense);
+        assert_eq!(coffee.category, "Coffee Shops");
+        assert_eq!(coffee.tags, vec!["work", "daily"]);
+        assert_eq!(coffee.notes, "Chase Checking");
+
+        let salary = rows[1].as_ref().unwrap();
+        assert_eq!(salary.amount, Decimal::new(215000, 2));
+        assert_eq!(salary.transaction_type, TransactionType::Income);
+        assert_eq!(salary.notes, "Chase Checking - January salary");
+    }
+
+    #[test]
+    fn test_import_mint_without_account_column() {
+        let mint_data = "\
+Date,Description,Amount,Transaction Type,Category
+2/01/2025,Landlord,900.00,debit,Mortgage & Rent
+2/02/2025,Refund,10.00,transfer,Shopping
+";
+        let tmp = write_temp_csv(mint_data);
+
+        let rows = import_mint_csv(tmp.path().to_str().unwrap()).unwrap();
+        let rent = rows[0].as_ref().unwrap();
+        assert_eq!(rent.category, "Mortgage & Rent");
+        assert_eq!(rent.notes, "");
+        assert!(rows[1].as_ref().unwrap_err().contains("Invalid transaction type"));
+    }
+
+    #[test]
+    fn test_import_mint_to_db() {
+        let conn = establish_test_connection().unwrap();
+        let mint_data = format!(
+            "{}{}",
+            MINT_HEADER,
+            "\"3/10/2025\",\"Whole Foods\",\"WHOLEFDS MKT 10234\",\"82.17\",\"debit\",\"Groceries\",\"Amex Gold\",\"\",\"\"\n",
+        );
+        let tmp = write_temp_csv(&mint_data);
+
+        let result = import_transactions_to_db(&conn, ImportFormat::MintCsv, tmp.path().to_str().unwrap(), &ImportOptions::default()).unwrap();
+        assert_eq!(result.imported, 1);
+
+        let all = crate::db::repository::get_all_transactions(&conn).unwrap();
+        assert_eq!(all[0].description, "Whole Foods");
+        assert_eq!(all[0].notes, "Amex Gold");
+    }
+
+    #[test]
+    fn test_import_mint_rejects_amounts_that_cannot_be_stored() {
+        let conn = establish_test_connection().unwrap();
+        let mint_data = format!(
+            "{}{}{}",
+            MINT_HEADER,
+            "\"3/10/2025\",\"Whole Foods\",\"WHOLEFDS MKT 10234\",\"82.12345\",\"debit\",\"Groceries\",\"Amex Gold\",\"\",\"\"\n",
+            "\"3/11/2025\",\"Car\",\"DEALER\",\"12345678901234.5678\",\"debit\",\"Auto\",\"Amex Gold\",\"\",\"\"\n",
+        );
+        let tmp = write_temp_csv(&mint_data);
+
+        let options = ImportOptions { on_error: ImportErrorBehavior::SkipRow, ..Default::default() };
+        let result = import_transactions_to_db(&conn, ImportFormat::MintCsv, tmp.path().to_str().unwrap(), &options).unwrap();
+        assert_eq!(result.imported, 0);
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.errors[0].1.contains("too many decimal places"));
+        assert!(result.errors[1].1.contains("too large to store exactly"));
+    }
+
     #[test]
     fn test_import_with_rules() {
         let conn = establish_test_connection().unwrap();
-        crate::db::rule_repository::add_rule(&conn, "Coffee", "Social").unwrap();
+        crate::db::rule_repository::add_rule(&conn, "Coffee", "Social", RuleMatchType::Substring).unwrap();
 
         let csv_data = "2025-11-11,Morning Coffee,3.50,expense,";
         let tmp = write_temp_csv(csv_data);
 
-        let result = import_transactions_to_db(&conn, ImportFormat::CSV, tmp.path().to_str().unwrap());
+        let result = import_transactions_to_db(&conn, ImportFormat::Csv, tmp.path().to_str().unwrap(), &ImportOptions::default());
         assert!(result.is_ok());
 
         let txs = crate::db::repository::get_all_transactions(&conn).unwrap();
         assert_eq!(txs[0].category, "Social");
     }
 
+    #[test]
+    fn test_import_fails_when_rules_cannot_be_loaded() {
+        let conn = establish_test_connection().unwrap();
+        conn.execute_batch("DROP TABLE category_rules").unwrap();
+
+        let tmp = write_temp_csv("2025-11-11,Morning Coffee,3.50,expense,");
+        let err = import_transactions_to_db(&conn, ImportFormat::Csv, tmp.path().to_str().unwrap(), &ImportOptions::default())
+            .unwrap_err();
+        assert!(err.contains("Failed to load categorization rules"));
+        assert!(crate::db::repository::get_all_transactions(&conn).unwrap().is_empty());
+    }
+
     #[test]
     fn test_import_generates_budget_alerts() {
         let conn = establish_test_connection().unwrap();
-        budget_repository::set_budget(&conn, "Food", &Decimal::new(500, 2)).unwrap();
+        budget_repository::set_budget(&conn, "Food", &Decimal::new(500, 2), None).unwrap();
 
         let csv_data = "2025-11-11,Dinner,6.00,expense,Food";
         let tmp = write_temp_csv(csv_data);
 
-        let result = import_transactions_to_db(&conn, ImportFormat::CSV, tmp.path().to_str().unwrap());
+        let result = import_transactions_to_db(&conn, ImportFormat::Csv, tmp.path().to_str().unwrap(), &ImportOptions::default());
         assert!(result.is_ok());
 
-        let imported_alerts = result.unwrap().1;
+        let imported_alerts = result.unwrap().alert_ids;
         assert_eq!(imported_alerts.len(), 1);
         let alerts = alert_repository::get_alerts_by_ids(&conn, &imported_alerts).unwrap();
         assert_eq!(alerts.len(), 1);
@@ -376,18 +1719,165 @@ bad-date,Salary,1500.00,income,Job
     #[test]
     fn test_import_generates_multiple_budget_alerts() {
         let conn = establish_test_connection().unwrap();
-        budget_repository::set_budget(&conn, "Food", &Decimal::from_str("1.00").unwrap()).unwrap();
-        budget_repository::set_budget(&conn, "Travel", &Decimal::from_str("1.00").unwrap()).unwrap();
+        budget_repository::set_budget(&conn, "Food", &Decimal::from_str("1.00").unwrap(), None).unwrap();
+        budget_repository::set_budget(&conn, "Travel", &Decimal::from_str("1.00").unwrap(), None).unwrap();
 
         let csv_data = "2025-11-11,Dinner,2.00,expense,Food\n2025-11-12,Taxi,3.00,expense,Travel\n";
         let tmp = write_temp_csv(csv_data);
 
-        let result = import_transactions_to_db(&conn, ImportFormat::CSV, tmp.path().to_str().unwrap());
+        let result = import_transactions_to_db(&conn, ImportFormat::Csv, tmp.path().to_str().unwrap(), &ImportOptions::default());
         assert!(result.is_ok());
 
-        let imported_alerts = result.unwrap().1;
+        let imported_alerts = result.unwrap().alert_ids;
         assert_eq!(imported_alerts.len(), 2);
         let alerts = alert_repository::get_alerts_by_ids(&conn, &imported_alerts).unwrap();
         assert_eq!(alerts.len(), 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_import_alerts_once_per_budget_period() {
+        let conn = establish_test_connection().unwrap();
+        budget_repository::set_budget(&conn, "Food", &Decimal::new(5, 0), None).unwrap();
+
+        let csv_data = "2025-11-11,Dinner,6.00,expense,Food\n2025-11-12,Lunch,4.00,expense,food\n2025-12-01,Snack,6.00,expense,Food\n";
+        let tmp = write_temp_csv(csv_data);
+
+        let result = import_transactions_to_db(&conn, ImportFormat::Csv, tmp.path().to_str().unwrap(), &ImportOptions::default()).unwrap();
+        let alerts = alert_repository::get_alerts_by_ids(&conn, &result.alert_ids).unwrap();
+        assert_eq!(alerts.len(), 2);
+        assert!(alerts.iter().any(|alert| alert.message.contains("spent 10.00")));
+        assert!(alerts.iter().any(|alert| alert.message.contains("spent 6.00")));
+    }
+
+    #[test]
+    fn test_import_checks_rows_against_earlier_rows() {
+        let conn = establish_test_connection().unwrap();
+        let csv_data = "2025-11-11,Coffee,3.50,expense,Food\n2025-11-11,Coffee,3.5,expense,Food\n";
+        let tmp = write_temp_csv(csv_data);
+        let path = tmp.path().to_str().unwrap();
+
+        let error = import_transactions_to_db(&conn, ImportFormat::Csv, path, &ImportOptions::default()).unwrap_err();
+        assert!(error.contains("Line 2"));
+        assert!(error.contains("Duplicate"));
+        assert_eq!(crate::db::repository::count_transactions(&conn).unwrap(), 0);
+
+        let skipped = import_transactions_to_db(&conn, ImportFormat::Csv, path, &ImportOptions { duplicates: DuplicatePolicy::Skip, ..Default::default() }).unwrap();
+        assert_eq!((skipped.imported, skipped.duplicates), (1, 1));
+        assert_eq!(crate::db::repository::count_transactions(&conn).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_import_csv_malformed_input() {
+        let import = |input: &[u8]| {
+            let mut tmp = NamedTempFile::new().unwrap();
+            tmp.write_all(input).unwrap();
+            import_csv(tmp.path().to_str().unwrap(), b',', None)
+        };
+
+        let rows = import(b"2025-11-10,\"Unterminated,1.00,expense,Food\n").unwrap();
+        assert!(rows[0].as_ref().unwrap_err().contains("Invalid number of columns"));
+
+        let rows = import(b"2025-11-10,\"Two\nlines\",1.00,expense,Food\n").unwrap();
+        assert_eq!(rows[0].as_ref().unwrap().description, "Two\nlines");
+
+        let rows = import(b"\xff\xfe,\xc3\x28,1.00,expense,Food\n").unwrap();
+        assert!(rows[0].as_ref().unwrap_err().contains("invalid utf-8"));
+
+        assert!(import(b"date,amount\n,,,\n").unwrap_err().contains("no 'description' column"));
+
+        let long_line = format!("2025-11-10,{},1.00,expense,Food\n", "x".repeat(1 << 20));
+        let rows = import(long_line.as_bytes()).unwrap();
+        assert!(rows[0].as_ref().unwrap_err().contains("too long"));
+
+        // NUL bytes are ordinary characters; a trailing run of them is a short row.
+        let rows = import(b"2025-11-10,Nul\0byte,1.00,expense,Food\n\0\0\0").unwrap();
+        assert_eq!(rows[0].as_ref().unwrap().description, "Nul\0byte");
+        assert!(rows[1].is_err());
+    }
+
+    #[test]
+    fn test_import_reports_progress() {
+        let conn = establish_test_connection().unwrap();
+        let csv_data: String = (0..250)
+            .map(|i| format!("2025-11-10,Row {},1.00,expense,Food\n", i))
+            .collect();
+        let tmp = write_temp_csv(&csv_data);
+        let calls = std::cell::RefCell::new(Vec::new());
+
+        let result = import_transactions_to_db_with_progress(
+            &conn,
+            ImportFormat::Csv,
+            tmp.path().to_str().unwrap(),
+            &ImportOptions::default(),
+            |current, total| calls.borrow_mut().push((current, total)),
+        )
+        .unwrap();
+
+        assert_eq!(result.imported, 250);
+        assert_eq!(calls.into_inner(), vec![(100, 250), (200, 250), (250, 250)]);
+    }
+
+    #[test]
+    fn test_watch_loop_imports_and_moves_files() {
+        let conn = establish_test_connection().unwrap();
+        let dir = tempfile::TempDir::new().unwrap();
+        let good = dir.path().join("bank.csv");
+        let bad = dir.path().join("broken.csv");
+        std::fs::write(&good, "2025-11-10,Coffee,3.50,expense,Food\n2025-11-11,Salary,1500.00,income,Job\n").unwrap();
+        std::fs::write(&bad, "not,a,valid,row\n").unwrap();
+
+        // A channel stands in for the directory watcher.
+        let (sender, receiver) = mpsc::channel();
+        sender.send(good.clone()).unwrap();
+        sender.send(bad.clone()).unwrap();
+        drop(sender);
+        let mut out = Vec::new();
+        let imported =
+            run_watch_loop(&conn, dir.path(), &ImportOptions::default(), &receiver, || Ok(false), &mut out).unwrap();
+
+        assert_eq!(imported, 1);
+        assert_eq!(repository::count_transactions(&conn).unwrap(), 2);
+        assert!(!good.exists());
+        assert!(dir.path().join(PROCESSED_DIR).join("bank.csv").exists());
+        assert!(bad.exists());
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.split("\r\n").collect();
+        assert_eq!(lines[0], "bank.csv: imported 2 transactions, moved to processed/");
+        assert!(lines[1].starts_with("broken.csv: Line 1:"));
+    }
+
+    #[test]
+    fn test_watch_loop_stops_on_quit() {
+        let conn = establish_test_connection().unwrap();
+        let (_sender, receiver) = mpsc::channel();
+        let mut out = Vec::new();
+        let imported = run_watch_loop(&conn, Path::new("."), &ImportOptions::default(), &receiver, || Ok(true), &mut out)
+            .unwrap();
+        assert_eq!(imported, 0);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_poll_sends_new_csv_files_once_complete() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("old.csv"), "x").unwrap();
+        let existing: HashSet<PathBuf> = csv_files_in(dir.path()).unwrap().into_keys().collect();
+        std::fs::write(dir.path().join("notes.txt"), "x").unwrap();
+        std::fs::create_dir(dir.path().join(PROCESSED_DIR)).unwrap();
+        std::fs::write(dir.path().join(PROCESSED_DIR).join("done.csv"), "x").unwrap();
+        std::fs::write(dir.path().join("NEW.CSV"), "2025-11-10,Coffee,3.50,expense,Food\n").unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let watcher = {
+            let (path, stop) = (dir.path().to_path_buf(), Arc::clone(&stop));
+            thread::spawn(move || poll_new_csv_files(&path, existing, &sender, &stop))
+        };
+        let received = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        stop.store(true, Ordering::Relaxed);
+        watcher.join().unwrap();
+
+        assert_eq!(received, dir.path().join("NEW.CSV"));
+        assert!(receiver.try_recv().is_err());
+    }
+}
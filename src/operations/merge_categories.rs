@@ -0,0 +1,137 @@
+use rusqlite::{params_from_iter, Connection};
+
+/// Renames every category in `from_categories` (compared case-insensitively) to `to_category`
+/// in transactions, budgets, rules, alerts and recurring templates. `to_category` itself may
+/// be listed too, which folds different spellings of it into one.
+///
+/// Only one budget per category can exist: the target's budget is kept if it has one,
+/// otherwise the oldest of the merged budgets; the others are deleted.
+///
+/// Returns how many transactions were updated; on error nothing is changed.
+pub fn merge_categories(conn: &Connection, from_categories: &[&str], to_category: &str) -> Result<usize, String> {
+    let to_category = to_category.trim();
+    if to_category.is_empty() {
+        return Err("Target category cannot be empty".to_string());
+    }
+    let mut keys: Vec<String> = Vec::new();
+    for key in from_categories.iter().map(|category| category.trim().to_lowercase()) {
+        if !key.is_empty() && !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    if keys.is_empty() {
+        return Err("List at least one category to merge".to_string());
+    }
+
+    conn.execute("BEGIN", [])
+        .map_err(|e| format!("Failed to start merging categories: {}", e))?;
+    match merge_keys(conn, &keys, to_category) {
+        Ok(updated) => {
+            conn.execute("COMMIT", [])
+                .map_err(|e| format!("Failed to commit merged categories: {}", e))?;
+            Ok(updated)
+        }
+        Err(e) => {
+            let _ = conn.execute("ROLLBACK", []);
+            Err(format!("Failed to merge categories: {}", e))
+        }
+    }
+}
+
+fn merge_keys(conn: &Connection, keys: &[String], to_category: &str) -> rusqlite::Result<usize> {
+    // ?1 is the target; the lowercased source categories follow.
+    let placeholders = (2..keys.len() + 2).map(|i| format!("?{}", i)).collect::<Vec<_>>().join(", ");
+    let params = || params_from_iter(std::iter::once(to_category.to_string()).chain(keys.iter().cloned()));
+
+    let updated = conn.execute(
+        &format!("UPDATE transactions SET category = ?1 WHERE LOWER(category) IN ({})", placeholders),
+        params(),
+    )?;
+    for table in ["category_rules", "budget_alerts", "recurring_transactions"] {
+        conn.execute(
+            &format!("UPDATE {} SET category = ?1 WHERE LOWER(category) IN ({})", table, placeholders),
+            params(),
+        )?;
+    }
+
+    let budget_match = format!("LOWER(category) IN ({}) OR LOWER(category) = LOWER(?1)", placeholders);
+    conn.execute(
+        &format!(
+            "DELETE FROM category_budgets WHERE ({match_}) AND id NOT IN ( \
+                 SELECT id FROM category_budgets WHERE {match_} \
+                 ORDER BY LOWER(category) = LOWER(?1) DESC, id LIMIT 1 \
+             )",
+            match_ = budget_match
+        ),
+        params(),
+    )?;
+    conn.execute(
+        &format!("UPDATE category_budgets SET category = ?1 WHERE {}", budget_match),
+        params(),
+    )?;
+
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::establish_test_connection;
+    use crate::db::{budget_repository, repository, rule_repository};
+    use crate::models::budget::BudgetPeriod;
+    use crate::models::rule::RuleMatchType;
+    use crate::operations::add::add_transaction_to_db;
+    use rust_decimal::Decimal;
+
+    fn seed(conn: &Connection) {
+        add_transaction_to_db(conn, "2025-01-02,Bread,3.00,expense,food").unwrap();
+        add_transaction_to_db(conn, "2025-01-03,Lunch,12.00,expense,Food").unwrap();
+        add_transaction_to_db(conn, "2025-01-04,Market,40.00,expense,Groceries").unwrap();
+        add_transaction_to_db(conn, "2025-01-05,Bus,2.50,expense,Transport").unwrap();
+    }
+
+    #[test]
+    fn test_merge_categories_updates_all_tables() {
+        let conn = establish_test_connection().unwrap();
+        seed(&conn);
+        rule_repository::add_rule(&conn, "Aldi", "groceries", RuleMatchType::Substring).unwrap();
+        budget_repository::set_budget(&conn, "Groceries", &Decimal::new(200, 0), Some(BudgetPeriod::Monthly)).unwrap();
+
+        let updated = merge_categories(&conn, &["food", "GROCERIES"], "Food").unwrap();
+        assert_eq!(updated, 3);
+
+        let categories = repository::get_distinct_categories(&conn).unwrap();
+        assert_eq!(categories, vec!["Food", "Transport"]);
+        let rules = rule_repository::get_all_rules(&conn).unwrap();
+        assert_eq!(rules[0].category, "Food");
+        let budgets = budget_repository::get_all_budgets(&conn).unwrap();
+        assert_eq!(budgets.len(), 1);
+        assert_eq!(budgets[0].category, "Food");
+        assert_eq!(budgets[0].amount, Decimal::new(200, 0));
+    }
+
+    #[test]
+    fn test_merge_categories_keeps_target_budget() {
+        let conn = establish_test_connection().unwrap();
+        seed(&conn);
+        budget_repository::set_budget(&conn, "Groceries", &Decimal::new(200, 0), Some(BudgetPeriod::Monthly)).unwrap();
+        budget_repository::set_budget(&conn, "food", &Decimal::new(300, 0), Some(BudgetPeriod::Monthly)).unwrap();
+        budget_repository::set_budget(&conn, "Transport", &Decimal::new(50, 0), Some(BudgetPeriod::Monthly)).unwrap();
+
+        merge_categories(&conn, &["Groceries"], "Food").unwrap();
+
+        let mut budgets = budget_repository::get_all_budgets(&conn).unwrap();
+        budgets.sort_by(|a, b| a.category.cmp(&b.category));
+        assert_eq!(budgets.len(), 2);
+        assert_eq!(budgets[0].category, "Food");
+        assert_eq!(budgets[0].amount, Decimal::new(300, 0));
+        assert_eq!(budgets[1].category, "Transport");
+    }
+
+    #[test]
+    fn test_merge_categories_rejects_empty_input() {
+        let conn = establish_test_connection().unwrap();
+        assert!(merge_categories(&conn, &["Food"], " ").unwrap_err().contains("cannot be empty"));
+        assert!(merge_categories(&conn, &[" "], "Food").unwrap_err().contains("at least one"));
+    }
+}
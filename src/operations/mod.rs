@@ -1,7 +1,19 @@
 pub mod add;
 pub mod remove;
+pub mod edit;
 pub mod search_by_category;
+pub mod search_by_description;
+pub mod search_by_amount;
 pub mod import;
+pub mod export;
 pub mod budget;
 pub mod report;
-pub mod browse;
\ No newline at end of file
+pub mod stats;
+pub mod recurring;
+pub mod browse;
+pub mod rule_apply;
+pub mod reconcile;
+pub mod format;
+pub mod merge_categories;
+pub mod split;
+pub mod validate;
@@ -0,0 +1,153 @@
+use crate::db::repository;
+use crate::models::transaction::{Transaction, TransactionStatus, TransactionType};
+use chrono::NaiveDate;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use rusqlite::Connection;
+use rust_decimal::Decimal;
+use std::io::{self, Write};
+
+enum Decision {
+    Reconcile,
+    Skip,
+    Quit,
+}
+
+/// Walks through the pending and cleared transactions in the range one at a time.
+/// `y` marks a transaction reconciled, `n` skips it and `q` stops early; the choices
+/// are saved together once the walk ends.
+pub fn reconcile_interactive(conn: &Connection, start: NaiveDate, end: NaiveDate) -> Result<(), String> {
+    if start > end {
+        return Err("Start date must be before end date.".to_string());
+    }
+
+    let mut candidates: Vec<Transaction> = repository::get_transactions_in_date_range(conn, start, end)?
+        .into_iter()
+        .filter(|t| t.status != TransactionStatus::Reconciled)
+        .collect();
+    candidates.sort_by_key(|t| t.date);
+
+    if candidates.is_empty() {
+        println!("No pending or cleared transactions between {} and {}.", start, end);
+        return Ok(());
+    }
+
+    enable_raw_mode().map_err(|e| format!("Failed to enable raw mode: {}", e))?;
+    let selected = prompt_for_transactions(&candidates);
+    disable_raw_mode().map_err(|e| format!("Failed to disable raw mode: {}", e))?;
+    let selected = selected?;
+
+    let ids: Vec<&str> = selected.iter().map(|t| t.id.as_str()).collect();
+    let reconciled = repository::batch_update_status(conn, &ids, TransactionStatus::Reconciled)?;
+
+    println!("{} transactions reconciled.", reconciled);
+    println!("Reconciled balance as of {}: {}", end, reconciled_balance(conn, end)?);
+    Ok(())
+}
+
+fn prompt_for_transactions(candidates: &[Transaction]) -> Result<Vec<&Transaction>, String> {
+    let mut stdout = io::stdout();
+    let write_err = |e: io::Error| format!("Failed to write to terminal: {}", e);
+
+    // Raw mode does not translate "\n", so every line ends with "\r\n".
+    write!(stdout, "y = reconciled, n = skip, q = quit\r\n").map_err(write_err)?;
+    let mut selected = Vec::new();
+    for (index, transaction) in candidates.iter().enumerate() {
+        write!(
+            stdout,
+            "[{}/{}] {}  {:<30}  {:>10}  {:<8}  {:<15}  ({}) ? ",
+            index + 1,
+            candidates.len(),
+            transaction.date.format("%Y-%m-%d"),
+            transaction.description,
+            transaction.amount,
+            type_str(transaction),
+            transaction.category,
+            transaction.status.as_str()
+        )
+        .map_err(write_err)?;
+        stdout.flush().map_err(write_err)?;
+
+        match read_decision()? {
+            Decision::Reconcile => {
+                write!(stdout, "y\r\n").map_err(write_err)?;
+                selected.push(transaction);
+            }
+            Decision::Skip => write!(stdout, "n\r\n").map_err(write_err)?,
+            Decision::Quit => {
+                write!(stdout, "q\r\n").map_err(write_err)?;
+                break;
+            }
+        }
+    }
+    Ok(selected)
+}
+
+fn read_decision() -> Result<Decision, String> {
+    loop {
+        let event = event::read().map_err(|e| format!("Failed to read input: {}", e))?;
+        if let Event::Key(key) = event
+            && key.kind != KeyEventKind::Release
+        {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => return Ok(Decision::Reconcile),
+                KeyCode::Char('n') | KeyCode::Char('N') => return Ok(Decision::Skip),
+                KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => return Ok(Decision::Quit),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Income minus expenses over every reconciled transaction dated on or before `end`.
+fn reconciled_balance(conn: &Connection, end: NaiveDate) -> Result<Decimal, String> {
+    let reconciled = repository::get_transactions_by_status(conn, TransactionStatus::Reconciled)?;
+    Ok(reconciled
+        .iter()
+        .filter(|t| t.date <= end)
+        .fold(Decimal::ZERO, |acc, t| match t.transaction_type {
+            TransactionType::Income => acc + t.amount,
+            TransactionType::Expense => acc - t.amount,
+        }))
+}
+
+fn type_str(transaction: &Transaction) -> &'static str {
+    match transaction.transaction_type {
+        TransactionType::Income => "income",
+        TransactionType::Expense => "expense",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::establish_test_connection;
+    use crate::operations::add::add_transaction_to_db_with_id;
+
+    #[test]
+    fn test_reconciled_balance() {
+        let conn = establish_test_connection().unwrap();
+        let (salary, _) = add_transaction_to_db_with_id(&conn, "2025-03-01,Salary,1000.00,income,Job").unwrap();
+        let (rent, _) = add_transaction_to_db_with_id(&conn, "2025-03-02,Rent,400.00,expense,Housing").unwrap();
+        add_transaction_to_db_with_id(&conn, "2025-03-03,Lunch,12.00,expense,Food").unwrap();
+        let (later, _) = add_transaction_to_db_with_id(&conn, "2025-04-01,Bonus,50.00,income,Job").unwrap();
+
+        repository::batch_update_status(&conn, &[&salary, &rent, &later], TransactionStatus::Reconciled).unwrap();
+
+        let balance = reconciled_balance(&conn, NaiveDate::from_ymd_opt(2025, 3, 31).unwrap()).unwrap();
+        assert_eq!(balance, Decimal::new(60000, 2));
+    }
+
+    #[test]
+    fn test_reconcile_invalid_range() {
+        let conn = establish_test_connection().unwrap();
+        let result = reconcile_interactive(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,139 @@
+use super::add::{check_budget_and_alert, create_transaction};
+use crate::db::{recurring_repository, repository};
+use crate::models::recurring::RecurringTransaction;
+use crate::models::transaction::Transaction;
+use chrono::{Duration, NaiveDate};
+use rusqlite::Connection;
+use uuid::Uuid;
+
+/// `raw_input` uses the same `date,description,amount,type,category` layout as `add`;
+/// the date is the first due date.
+pub fn add_recurring_db(conn: &Connection, raw_input: &str, interval_days: i64) -> Result<i32, String> {
+    if interval_days <= 0 {
+        return Err("Interval must be a positive number of days".to_string());
+    }
+    let template = create_transaction(raw_input)?;
+
+    let recurring = RecurringTransaction {
+        id: 0,
+        template_description: template.description,
+        amount: template.amount,
+        transaction_type: template.transaction_type,
+        category: template.category,
+        interval_days,
+        next_due: template.date,
+        active: true,
+    };
+    recurring_repository::add_recurring(conn, &recurring).map_err(String::from)
+}
+
+pub fn list_recurring_db(conn: &Connection) -> Result<Vec<RecurringTransaction>, String> {
+    recurring_repository::get_all_recurring(conn).map_err(String::from)
+}
+
+pub fn delete_recurring_db(conn: &Connection, id: i32) -> Result<(), String> {
+    recurring_repository::delete_recurring(conn, id).map_err(String::from)
+}
+
+/// Creates a transaction for every occurrence due on or before `as_of`.
+/// Items that are several intervals behind get one transaction per missed occurrence.
+/// Returns the number of generated transactions and any budget alert ids.
+pub fn generate_due_transactions(conn: &Connection, as_of: NaiveDate) -> Result<(usize, Vec<i32>), String> {
+    let due = recurring_repository::get_due_recurring(conn, as_of)?;
+
+    let mut count = 0;
+    let mut alert_ids = Vec::new();
+    for recurring in due {
+        let mut next_due = recurring.next_due;
+        while next_due <= as_of {
+            let transaction = Transaction::new(
+                Uuid::new_v4().to_string(),
+                next_due,
+                recurring.template_description.clone(),
+                recurring.amount,
+                recurring.transaction_type,
+                recurring.category.clone(),
+            );
+            repository::add_transaction(conn, &transaction)?;
+            if let Some(alert_id) = check_budget_and_alert(conn, &transaction)? {
+                alert_ids.push(alert_id);
+            }
+            recurring_repository::mark_generated(conn, recurring.id)?;
+
+            next_due += Duration::days(recurring.interval_days);
+            count += 1;
+        }
+    }
+    Ok((count, alert_ids))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::budget_repository;
+    use crate::db::connection::establish_test_connection;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_add_recurring_validation() {
+        let conn = establish_test_connection().unwrap();
+
+        let result = add_recurring_db(&conn, "2025-01-01,Rent,900.00,expense,Housing", 0);
+        assert!(result.unwrap_err().contains("Interval"));
+
+        let result = add_recurring_db(&conn, "2025-01-01,Rent,abc,expense,Housing", 30);
+        assert!(result.unwrap_err().contains("Invalid amount"));
+
+        assert!(list_recurring_db(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_generate_due_creates_missed_occurrences() {
+        let conn = establish_test_connection().unwrap();
+        let id = add_recurring_db(&conn, "2025-01-01,Rent,900.00,expense,Housing", 30).unwrap();
+        add_recurring_db(&conn, "2025-06-01,Gym,25.00,expense,Health", 30).unwrap();
+
+        let (count, _) = generate_due_transactions(&conn, NaiveDate::from_ymd_opt(2025, 3, 5).unwrap()).unwrap();
+        assert_eq!(count, 3);
+
+        let mut dates: Vec<NaiveDate> = repository::get_all_transactions(&conn)
+            .unwrap()
+            .iter()
+            .map(|t| t.date)
+            .collect();
+        dates.sort();
+        assert_eq!(
+            dates,
+            vec![
+                NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 3, 2).unwrap(),
+            ]
+        );
+
+        let rent = list_recurring_db(&conn).unwrap().into_iter().find(|r| r.id == id).unwrap();
+        assert_eq!(rent.next_due, NaiveDate::from_ymd_opt(2025, 4, 1).unwrap());
+
+        // Running again on the same day generates nothing new.
+        let (count, _) = generate_due_transactions(&conn, NaiveDate::from_ymd_opt(2025, 3, 5).unwrap()).unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_generate_due_triggers_budget_alert() {
+        let conn = establish_test_connection().unwrap();
+        budget_repository::set_budget(&conn, "Housing", &Decimal::new(50000, 2), None).unwrap();
+        add_recurring_db(&conn, "2025-01-01,Rent,900.00,expense,Housing", 30).unwrap();
+
+        let (count, alerts) = generate_due_transactions(&conn, NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()).unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(alerts.len(), 1);
+    }
+
+    #[test]
+    fn test_delete_recurring_not_found() {
+        let conn = establish_test_connection().unwrap();
+        let result = delete_recurring_db(&conn, 42);
+        assert!(result.unwrap_err().contains("not found"));
+    }
+}
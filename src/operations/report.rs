@@ -1,58 +1,493 @@
+use crate::config::Theme;
 use crate::db::repository;
-use crate::models::transaction::Transaction;
-use chrono::{Duration, NaiveDate};
+use crate::error::AppError;
+use crate::operations::budget::{get_budget_utilization_all, BUDGET_WARNING_PERCENT};
+use crate::format::format_amount;
+use crate::models::currency::Currency;
+use crate::models::budget::BudgetPeriod;
+use crate::models::transaction::{Transaction, TransactionType};
+use crate::operations::browse::centered_rect;
+use chrono::{Datelike, Duration, Months, NaiveDate};
 use crossterm::{
-    event::{self, Event, KeyCode},
+    event::{self, Event, KeyCode, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     prelude::{Alignment, Color, Constraint, Direction, Layout, Rect, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
 };
 use ratatui::widgets::canvas::{Canvas, Points};
 use rusqlite::Connection;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
 use std::io;
+use std::time::{Duration as StdDuration, Instant};
 
-pub fn run_report(conn: &Connection, start_date: NaiveDate, end_date: NaiveDate) -> Result<(), String> {
+/// Which transactions a report charts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportKind {
+    Expense,
+    Income,
+    /// Income as bars above a zero line, expenses below it.
+    Net,
+}
+
+impl ReportKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportKind::Expense => "expense",
+            ReportKind::Income => "income",
+            ReportKind::Net => "net",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "expense" => Some(ReportKind::Expense),
+            "income" => Some(ReportKind::Income),
+            "net" => Some(ReportKind::Net),
+            _ => None,
+        }
+    }
+
+    /// Income and net reports chart income above the zero line; expense reports chart expenses.
+    fn is_positive(&self, transaction: &Transaction) -> bool {
+        match self {
+            ReportKind::Expense => transaction.transaction_type == TransactionType::Expense,
+            ReportKind::Income | ReportKind::Net => transaction.transaction_type == TransactionType::Income,
+        }
+    }
+
+    fn empty_message(&self) -> &'static str {
+        match self {
+            ReportKind::Expense => "No expenses in this range",
+            ReportKind::Income => "No income in this range",
+            ReportKind::Net => "No transactions in this range",
+        }
+    }
+}
+
+/// How a report's range is split into bars. `Auto` picks one from the length of the range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BucketMode {
+    Day,
+    Week,
+    Fortnight,
+    /// Calendar months; the first and last bucket may be partial.
+    Month,
+    /// Calendar quarters (Q1 = Jan-Mar); the first and last bucket may be partial.
+    Quarter,
+    Auto,
+}
+
+impl BucketMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BucketMode::Day => "day",
+            BucketMode::Week => "week",
+            BucketMode::Fortnight => "fortnight",
+            BucketMode::Month => "month",
+            BucketMode::Quarter => "quarter",
+            BucketMode::Auto => "auto",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "day" => Some(BucketMode::Day),
+            "week" => Some(BucketMode::Week),
+            "fortnight" => Some(BucketMode::Fortnight),
+            "month" => Some(BucketMode::Month),
+            "quarter" => Some(BucketMode::Quarter),
+            "auto" => Some(BucketMode::Auto),
+            _ => None,
+        }
+    }
+
+    /// The mode to use for a range of `total_days`; only `Auto` depends on it.
+    fn resolve(self, total_days: i64) -> Self {
+        match self {
+            BucketMode::Auto if total_days <= 7 => BucketMode::Day,
+            BucketMode::Auto if total_days <= 90 => BucketMode::Week,
+            BucketMode::Auto if total_days <= 365 => BucketMode::Fortnight,
+            BucketMode::Auto if total_days <= 3 * 365 => BucketMode::Month,
+            BucketMode::Auto => BucketMode::Quarter,
+            mode => mode,
+        }
+    }
+
+    /// Length of fixed-size buckets; `None` for calendar months and quarters.
+    fn fixed_days(self) -> Option<i64> {
+        match self {
+            BucketMode::Day => Some(1),
+            BucketMode::Week => Some(7),
+            BucketMode::Fortnight => Some(14),
+            BucketMode::Month | BucketMode::Quarter | BucketMode::Auto => None,
+        }
+    }
+}
+
+// Percent labels are drawn on the pie only when the panel is at least this big.
+const MIN_PIE_LABEL_WIDTH: u16 = 24;
+const MIN_PIE_LABEL_HEIGHT: u16 = 8;
+// Slices smaller than this share have no room for a label.
+const MIN_PIE_LABEL_SHARE: f64 = 0.05;
+const FLASH_DURATION: StdDuration = StdDuration::from_millis(1500);
+const DEFAULT_EXPORT_FILE: &str = "report.txt";
+
+pub fn run_report(
+    conn: &Connection,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    kind: ReportKind,
+    bucket_mode: Option<BucketMode>,
+    theme: &Theme,
+) -> Result<(), String> {
     if start_date > end_date {
         return Err("Start date must be before end date.".to_string());
     }
 
-    let total_days = (end_date - start_date).num_days().max(0) + 1;
-    let bucket_days = if total_days <= 7 {
-        1
-    } else if total_days <= 90 {
-        7
-    } else if total_days <= 365 {
-        14
-    } else {
-        ((total_days + 19) / 20) as i64
+    let bucket_mode = bucket_mode.unwrap_or(BucketMode::Auto);
+    let mut start_date = start_date;
+    let mut end_date = end_date;
+    let (mut title, mut report) = load_report(conn, kind, bucket_mode, start_date, end_date)?;
+    let mut ui = ReportUiState::new();
+    // Set when the range moved; the next draw shows a loading notice before the data is re-fetched.
+    let mut loading = false;
+
+    enable_raw_mode().map_err(|e| format!("Failed to enable raw mode: {}", e))?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)
+        .map_err(|e| format!("Failed to enter alternate screen: {}", e))?;
+
+    let result = (|| {
+        let backend = ratatui::backend::CrosstermBackend::new(stdout);
+        let mut terminal = ratatui::Terminal::new(backend)
+            .map_err(|e| format!("Failed to initialize terminal: {}", e))?;
+
+        loop {
+            ui.expire_flash(Instant::now());
+            terminal
+                .draw(|frame| draw_report(frame, &title, &report, &mut ui, loading, theme))
+                .map_err(|e| format!("Failed to draw terminal UI: {}", e))?;
+
+            if loading {
+                (title, report) = load_report(conn, kind, bucket_mode, start_date, end_date)?;
+                loading = false;
+                continue;
+            }
+
+            if event::poll(std::time::Duration::from_millis(250))
+                .map_err(|e| format!("Failed to poll input: {}", e))?
+            {
+                let action = match event::read().map_err(|e| format!("Failed to read input: {}", e))? {
+                    Event::Key(key) if key.kind != KeyEventKind::Release => {
+                        handle_key(conn, &mut ui, &report, key.code)?
+                    }
+                    _ => ReportAction::None,
+                };
+                match action {
+                    ReportAction::None => {}
+                    ReportAction::Quit => break,
+                    ReportAction::Export(path) => {
+                        let message = match export_report_as_text(&report, &title, &path) {
+                            Ok(()) => format!("Report written to {}", path),
+                            Err(e) => e,
+                        };
+                        ui.flash(&message, Instant::now());
+                    }
+                    ReportAction::Shift(shift) => match shift_range(start_date, end_date, shift) {
+                        Ok(range) => {
+                            (start_date, end_date) = range;
+                            // Bucket boundaries and categories change with the range.
+                            ui.selected_bucket = None;
+                            ui.legend_offset = 0;
+                            loading = true;
+                        }
+                        Err(e) => ui.flash(&e.to_string(), Instant::now()),
+                    },
+                }
+            }
+        }
+
+        Ok(())
+    })();
+
+    disable_raw_mode().map_err(|e| format!("Failed to disable raw mode: {}", e))?;
+    let mut stdout = io::stdout();
+    execute!(stdout, LeaveAlternateScreen)
+        .map_err(|e| format!("Failed to leave alternate screen: {}", e))?;
+
+    result
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Report,
+    BucketDetail,
+    ExportPrompt,
+}
+
+/// View state that survives moving between periods.
+struct ReportUiState {
+    mode: Mode,
+    // Bar chart column picked with Tab/Shift+Tab, opened with Enter
+    selected_bucket: Option<usize>,
+    bucket_transactions: Vec<Transaction>,
+    detail_table: TableState,
+    // Category table shows shares of the total instead of amounts (`p`)
+    show_percent: bool,
+    // First category table row shown, scrolled with ↑/↓ and clamped while drawing
+    legend_offset: usize,
+    // File name typed into the export prompt
+    input_buffer: String,
+    // Footer message and when it was set
+    flash_message: Option<(String, Instant)>,
+    // Moving-average line over the bars (`m`)
+    show_moving_avg: bool,
+}
+
+impl ReportUiState {
+    fn new() -> Self {
+        Self {
+            mode: Mode::Report,
+            selected_bucket: None,
+            bucket_transactions: Vec::new(),
+            detail_table: TableState::default(),
+            show_percent: false,
+            legend_offset: 0,
+            input_buffer: String::new(),
+            flash_message: None,
+            show_moving_avg: true,
+        }
+    }
+
+    fn flash(&mut self, message: &str, now: Instant) {
+        self.flash_message = Some((message.to_string(), now));
+    }
+
+    fn expire_flash(&mut self, now: Instant) {
+        if let Some((_, shown_at)) = self.flash_message
+            && now.duration_since(shown_at) >= FLASH_DURATION
+        {
+            self.flash_message = None;
+        }
+    }
+
+    fn move_bucket_selection(&mut self, delta: isize, bucket_count: usize) {
+        if bucket_count == 0 {
+            self.selected_bucket = None;
+            return;
+        }
+        let next = match self.selected_bucket {
+            None if delta < 0 => bucket_count - 1,
+            None => 0,
+            Some(current) => (current as isize + delta).rem_euclid(bucket_count as isize) as usize,
+        };
+        self.selected_bucket = Some(next);
+    }
+
+    /// Loads every transaction in the selected bucket's date range and switches to the detail table.
+    fn open_bucket(&mut self, conn: &Connection, data: &ReportData) -> Result<(), String> {
+        let Some(bucket) = self.selected_bucket.and_then(|idx| data.buckets.get(idx)) else {
+            return Ok(());
+        };
+        self.bucket_transactions = repository::get_transactions_in_date_range(conn, bucket.start, bucket.end)?;
+        self.detail_table = TableState::default();
+        if !self.bucket_transactions.is_empty() {
+            self.detail_table.select(Some(0));
+        }
+        self.mode = Mode::BucketDetail;
+        Ok(())
+    }
+
+    fn close_bucket(&mut self) {
+        self.bucket_transactions.clear();
+        self.mode = Mode::Report;
+    }
+
+    fn move_detail_selection(&mut self, delta: isize) {
+        if self.bucket_transactions.is_empty() {
+            return;
+        }
+        let current = self.detail_table.selected().unwrap_or(0) as isize;
+        let last = self.bucket_transactions.len() as isize - 1;
+        self.detail_table.select(Some((current + delta).clamp(0, last) as usize));
+    }
+}
+
+enum ReportAction {
+    None,
+    Quit,
+    Shift(PeriodShift),
+    // Write the report to this path
+    Export(String),
+}
+
+fn handle_key(conn: &Connection, ui: &mut ReportUiState, data: &ReportData, code: KeyCode) -> Result<ReportAction, String> {
+    match ui.mode {
+        Mode::Report => match code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(ReportAction::Quit),
+            KeyCode::Left => return Ok(ReportAction::Shift(PeriodShift::Back)),
+            KeyCode::Right => return Ok(ReportAction::Shift(PeriodShift::Forward)),
+            KeyCode::Char('h') => return Ok(ReportAction::Shift(PeriodShift::HalfBack)),
+            KeyCode::Char('l') => return Ok(ReportAction::Shift(PeriodShift::HalfForward)),
+            KeyCode::Tab => ui.move_bucket_selection(1, data.buckets.len()),
+            KeyCode::BackTab => ui.move_bucket_selection(-1, data.buckets.len()),
+            KeyCode::Enter => ui.open_bucket(conn, data)?,
+            KeyCode::Char('p') => ui.show_percent = !ui.show_percent,
+            KeyCode::Char('m') => ui.show_moving_avg = !ui.show_moving_avg,
+            KeyCode::Char('e') => {
+                ui.input_buffer = DEFAULT_EXPORT_FILE.to_string();
+                ui.mode = Mode::ExportPrompt;
+            }
+            KeyCode::Up => ui.legend_offset = ui.legend_offset.saturating_sub(1),
+            KeyCode::Down => ui.legend_offset += 1,
+            _ => {}
+        },
+        Mode::BucketDetail => match code {
+            KeyCode::Esc | KeyCode::Char('q') => ui.close_bucket(),
+            KeyCode::Up => ui.move_detail_selection(-1),
+            KeyCode::Down => ui.move_detail_selection(1),
+            _ => {}
+        },
+        Mode::ExportPrompt => match code {
+            KeyCode::Esc => ui.mode = Mode::Report,
+            KeyCode::Backspace => {
+                ui.input_buffer.pop();
+            }
+            KeyCode::Char(c) => ui.input_buffer.push(c),
+            KeyCode::Enter => {
+                let path = ui.input_buffer.trim().to_string();
+                if path.is_empty() {
+                    return Ok(ReportAction::None);
+                }
+                ui.mode = Mode::Report;
+                return Ok(ReportAction::Export(path));
+            }
+            _ => {}
+        },
+    }
+    Ok(ReportAction::None)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PeriodShift {
+    Back,
+    Forward,
+    HalfBack,
+    HalfForward,
+}
+
+/// Moves the range by its own length (or half of it, at least one day) keeping its size.
+/// Fails when the moved range would leave the dates chrono can represent.
+fn shift_range(start_date: NaiveDate, end_date: NaiveDate, shift: PeriodShift) -> Result<(NaiveDate, NaiveDate), AppError> {
+    let span = (end_date - start_date).num_days() + 1;
+    let days = match shift {
+        PeriodShift::Back => -span,
+        PeriodShift::Forward => span,
+        PeriodShift::HalfBack => -(span / 2).max(1),
+        PeriodShift::HalfForward => (span / 2).max(1),
     };
+    let moved = |date: NaiveDate| date.checked_add_signed(Duration::days(days));
+    match (moved(start_date), moved(end_date)) {
+        (Some(start), Some(end)) => Ok((start, end)),
+        _ => Err(AppError::InvalidInput("Cannot move the report range any further.".to_string())),
+    }
+}
+
+/// Writes the report as plain text: each bucket's date range with its category breakdown and
+/// total, followed by the category summary.
+pub fn export_report_as_text(data: &ReportData, title: &str, path: &str) -> Result<(), String> {
+    std::fs::write(path, report_text(data, title)).map_err(|e| format!("Failed to write file '{}': {}", path, e))
+}
+
+fn report_text(data: &ReportData, title: &str) -> String {
+    let mut out = String::new();
+    // Writing to a String cannot fail.
+    let _ = writeln!(out, "{}", title);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{:25} {:15} {:>12}", "Period", "Category", "Amount");
+    for bucket in &data.buckets {
+        let mut period = format!("{} - {}", bucket.start.format("%d.%m.%Y"), bucket.end.format("%d.%m.%Y"));
+        for (category, amount) in &bucket.totals {
+            let _ = writeln!(out, "{:25} {:15} {:>12.2}", period, category, amount);
+            period.clear();
+        }
+        if data.kind == ReportKind::Net {
+            let _ = writeln!(out, "{:25} {:15} {:>12.2}", period, "Expenses", -bucket.negative_total);
+            period.clear();
+        }
+        let _ = writeln!(out, "{:25} {:15} {:>12.2}", period, "Total", bucket.total - bucket.negative_total);
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Category summary");
+    let _ = writeln!(out, "{:15} {:>5} {:>12}", "Category", "Count", "Amount");
+    for (category, amount, count) in &data.category_summary {
+        let _ = writeln!(out, "{:15} {:>5} {:>12.2}", category, count, amount);
+    }
+    let total = data.category_summary.iter().fold(Decimal::ZERO, |acc, (_, amount, _)| acc + *amount);
+    let _ = writeln!(out, "{:15} {:>5} {:>12.2}", "Total", "", total);
+    out
+}
+
+/// Fetches the transactions for the range and returns the chart title with the report data.
+fn load_report(
+    conn: &Connection,
+    kind: ReportKind,
+    bucket_mode: BucketMode,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<(String, ReportData), String> {
+    let total_days = (end_date - start_date).num_days().max(0) + 1;
+    let bucket_mode = bucket_mode.resolve(total_days);
 
+    let bucket_label = match (bucket_mode, bucket_mode.fixed_days()) {
+        (_, Some(days)) => format!("{}-day buckets", days),
+        (BucketMode::Quarter, None) => "quarterly buckets".to_string(),
+        _ => "monthly buckets".to_string(),
+    };
     let title = format!(
-        "{} - {} ({}-day buckets)",
+        "{} {} - {} ({})",
+        kind.as_str(),
         start_date.format("%d.%m.%Y"),
         end_date.format("%d.%m.%Y"),
-        bucket_days
+        bucket_label
     );
 
-    let transactions = repository::get_expense_transactions_in_range(conn, start_date, end_date)?;
-    let report = build_report(&transactions, start_date, end_date, total_days, bucket_days);
+    let transactions = match kind {
+        ReportKind::Expense => repository::get_expense_transactions_in_range(conn, start_date, end_date)?,
+        ReportKind::Income => repository::get_income_transactions_in_range(conn, start_date, end_date)?,
+        ReportKind::Net => {
+            let mut all = repository::get_income_transactions_in_range(conn, start_date, end_date)?;
+            all.extend(repository::get_expense_transactions_in_range(conn, start_date, end_date)?);
+            all
+        }
+    };
+    let report = build_report(conn, kind, &transactions, start_date, end_date, bucket_mode)?;
 
-    render_report(&title, &report)?;
-    Ok(())
+    Ok((title, report))
 }
 
-struct ReportData {
+pub struct ReportData {
+    kind: ReportKind,
+    /// Resolved mode the buckets were built with; never `Auto`.
+    bucket_mode: BucketMode,
     buckets: Vec<BucketData>,
     category_totals: Vec<(String, Decimal)>,
+    category_summary: Vec<(String, Decimal, usize)>,
     category_colors: HashMap<String, Color>,
+    /// Lowercased categories that used more than `BUDGET_WARNING_PERCENT` of their budget.
+    near_budget_limit: HashSet<String>,
     total_spend: Decimal,
+    /// 3-bucket moving average of the charted totals (income minus expenses for net reports);
+    /// `None` for the first two buckets.
+    moving_avg: Vec<Option<f64>>,
 }
 
 struct BucketData {
@@ -60,28 +495,53 @@ struct BucketData {
     end: NaiveDate,
     totals: Vec<(String, Decimal)>,
     total: Decimal,
+    /// Expenses drawn below the zero line of a net report; zero for other kinds.
+    negative_total: Decimal,
 }
 
 fn build_report(
+    conn: &Connection,
+    kind: ReportKind,
     transactions: &[Transaction],
     start_date: NaiveDate,
     end_date: NaiveDate,
-    total_days: i64,
-    bucket_days: i64,
-) -> ReportData {
-    let bucket_count = ((total_days as f64) / (bucket_days as f64)).ceil() as usize;
+    bucket_mode: BucketMode,
+) -> Result<ReportData, String> {
+    // Month and quarter buckets take their bar totals straight from the monthly SQL totals.
+    let positive_type = match kind {
+        ReportKind::Expense => TransactionType::Expense,
+        ReportKind::Income | ReportKind::Net => TransactionType::Income,
+    };
+    let period_totals: Option<PeriodTotals> = if bucket_mode.fixed_days().is_none() {
+        let positive = repository::get_monthly_totals(conn, start_date, end_date, positive_type)?;
+        let negative = if kind == ReportKind::Net {
+            repository::get_monthly_totals(conn, start_date, end_date, TransactionType::Expense)?
+        } else {
+            Vec::new()
+        };
+        Some((sum_by_period(bucket_mode, positive), sum_by_period(bucket_mode, negative)))
+    } else {
+        None
+    };
 
-    let mut bucket_maps: Vec<HashMap<String, Decimal>> =
-        vec![HashMap::new(); bucket_count.max(1)];
+    let bucket_ranges = bucket_ranges(bucket_mode, start_date, end_date);
+    let bucket_count = bucket_ranges.len();
+
+    let mut bucket_maps: Vec<HashMap<String, Decimal>> = vec![HashMap::new(); bucket_count];
+    let mut negative_sums: Vec<Decimal> = vec![Decimal::ZERO; bucket_count];
     let mut category_totals: HashMap<String, Decimal> = HashMap::new();
 
     for transaction in transactions {
-        let idx = bucket_index(start_date, transaction.date, bucket_days, bucket_count);
+        let idx = bucket_index(bucket_mode, start_date, transaction.date, bucket_count);
         let amount = transaction.amount.abs();
-        let entry = bucket_maps[idx]
-            .entry(transaction.category.clone())
-            .or_insert(Decimal::ZERO);
-        *entry += amount;
+        if kind.is_positive(transaction) {
+            let entry = bucket_maps[idx]
+                .entry(transaction.category.clone())
+                .or_insert(Decimal::ZERO);
+            *entry += amount;
+        } else {
+            negative_sums[idx] += amount;
+        }
 
         let total_entry = category_totals
             .entry(transaction.category.clone())
@@ -91,28 +551,44 @@ fn build_report(
 
     let mut categories: Vec<String> = category_totals.keys().cloned().collect();
     categories.sort();
-    let category_colors = assign_colors(&categories);
+    let category_colors = assign_colors(&categories, supports_indexed_colors());
 
     let mut buckets = Vec::new();
-    for i in 0..bucket_count.max(1) {
-        let bucket_start = start_date + Duration::days(i as i64 * bucket_days);
-        let bucket_end = (bucket_start + Duration::days(bucket_days - 1)).min(end_date);
-        let mut totals: Vec<(String, Decimal)> = bucket_maps[i]
+    for ((bucket_map, negative_sum), (bucket_start, bucket_end)) in
+        bucket_maps.iter().zip(negative_sums).zip(bucket_ranges)
+    {
+        let mut totals: Vec<(String, Decimal)> = bucket_map
             .iter()
             .map(|(k, v)| (k.clone(), *v))
             .collect();
         // use partial_cmp so if they are not comparable NaN < 123 
         // => Equal (some() -> unwrap Less, Equal, Greater or from None -> Equal)
         totals.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)); 
-        let total = totals.iter().fold(Decimal::ZERO, |acc, (_, v)| acc + *v);
+        let (total, negative_total) = match &period_totals {
+            Some((positive, negative)) => {
+                let period = period_start(bucket_mode, bucket_start);
+                (
+                    positive.get(&period).copied().unwrap_or(Decimal::ZERO),
+                    negative.get(&period).copied().unwrap_or(Decimal::ZERO),
+                )
+            }
+            None => (totals.iter().fold(Decimal::ZERO, |acc, (_, v)| acc + *v), negative_sum),
+        };
         buckets.push(BucketData {
             start: bucket_start,
             end: bucket_end,
             totals,
             total,
+            negative_total,
         });
     }
 
+    let charted: Vec<f64> = buckets
+        .iter()
+        .map(|b| (b.total - b.negative_total).to_f64().unwrap_or(0.0))
+        .collect();
+    let moving_avg = moving_average(&charted);
+
     let mut category_totals_vec: Vec<(String, Decimal)> =
         category_totals.into_iter().collect();
     category_totals_vec
@@ -122,30 +598,158 @@ fn build_report(
         .iter()
         .fold(Decimal::ZERO, |acc, (_, v)| acc + *v);
 
-    ReportData {
+    let category_summary = match kind {
+        ReportKind::Expense => repository::get_category_summary(conn, start_date, end_date)?,
+        ReportKind::Income | ReportKind::Net => summarize_categories(kind, transactions),
+    };
+    // Budgets only cover spending.
+    let near_budget_limit = if kind == ReportKind::Income {
+        HashSet::new()
+    } else {
+        get_budget_utilization_all(conn, start_date, end_date)?
+            .into_iter()
+            .filter(|(_, _, budget, percent)| budget.is_some() && *percent > BUDGET_WARNING_PERCENT)
+            .map(|(category, _, _, _)| category.to_lowercase())
+            .collect()
+    };
+
+    Ok(ReportData {
+        kind,
+        bucket_mode,
         buckets,
         category_totals: category_totals_vec,
+        category_summary,
         category_colors,
+        near_budget_limit,
         total_spend,
+        moving_avg,
+    })
+}
+
+/// Per-category totals and counts grouped case-insensitively, largest first. Net reports
+/// count expenses as negative amounts.
+fn summarize_categories(kind: ReportKind, transactions: &[Transaction]) -> Vec<(String, Decimal, usize)> {
+    let mut summary: Vec<(String, Decimal, usize)> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for transaction in transactions {
+        let amount = if kind.is_positive(transaction) {
+            transaction.amount.abs()
+        } else {
+            -transaction.amount.abs()
+        };
+        let position = *positions
+            .entry(transaction.category.to_lowercase())
+            .or_insert_with(|| {
+                summary.push((transaction.category.clone(), Decimal::ZERO, 0));
+                summary.len() - 1
+            });
+        summary[position].1 += amount;
+        summary[position].2 += 1;
     }
+    summary.sort_by_key(|(_, total, _)| std::cmp::Reverse(total.abs()));
+    summary
 }
 
-fn bucket_index(
-    start_date: NaiveDate,
-    date: NaiveDate,
-    bucket_days: i64,
-    bucket_count: usize,
-) -> usize {
+const MOVING_AVERAGE_WINDOW: usize = 3;
+
+fn moving_average(values: &[f64]) -> Vec<Option<f64>> {
+    (0..values.len())
+        .map(|i| {
+            (i + 1 >= MOVING_AVERAGE_WINDOW).then(|| {
+                values[i + 1 - MOVING_AVERAGE_WINDOW..=i].iter().sum::<f64>() / MOVING_AVERAGE_WINDOW as f64
+            })
+        })
+        .collect()
+}
+
+/// Positive and negative (net expense) totals keyed by the first day of their month or quarter.
+type PeriodTotals = (HashMap<NaiveDate, Decimal>, HashMap<NaiveDate, Decimal>);
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn first_of_quarter(date: NaiveDate) -> NaiveDate {
+    NaiveDate::from_ymd_opt(date.year(), (date.month() - 1) / 3 * 3 + 1, 1).unwrap_or(date)
+}
+
+/// First day of the calendar month or quarter containing `date`.
+fn period_start(mode: BucketMode, date: NaiveDate) -> NaiveDate {
+    match mode {
+        BucketMode::Quarter => first_of_quarter(date),
+        _ => first_of_month(date),
+    }
+}
+
+/// Adds monthly totals up per month or quarter.
+fn sum_by_period(mode: BucketMode, monthly: Vec<(NaiveDate, Decimal)>) -> HashMap<NaiveDate, Decimal> {
+    let mut totals = HashMap::new();
+    for (month, total) in monthly {
+        *totals.entry(period_start(mode, month)).or_insert(Decimal::ZERO) += total;
+    }
+    totals
+}
+
+/// Inclusive start and end of every bucket. Calendar buckets are clipped to the range.
+fn bucket_ranges(mode: BucketMode, start_date: NaiveDate, end_date: NaiveDate) -> Vec<(NaiveDate, NaiveDate)> {
+    if let Some(bucket_days) = mode.fixed_days() {
+        let total_days = (end_date - start_date).num_days().max(0) + 1;
+        let bucket_count = (total_days as usize).div_ceil(bucket_days as usize);
+        return (0..bucket_count.max(1))
+            .map(|i| {
+                let bucket_start = start_date + Duration::days(i as i64 * bucket_days);
+                let bucket_end = bucket_start
+                    .checked_add_signed(Duration::days(bucket_days - 1))
+                    .map_or(end_date, |last| last.min(end_date));
+                (bucket_start, bucket_end)
+            })
+            .collect();
+    }
+
+    let months = if mode == BucketMode::Quarter { 3 } else { 1 };
+    let mut ranges = Vec::new();
+    let mut bucket_start = start_date;
+    while bucket_start <= end_date {
+        // Past the last representable month the bucket simply runs to `end_date`.
+        let next_period = period_start(mode, bucket_start).checked_add_months(chrono::Months::new(months));
+        let bucket_end = next_period
+            .and_then(|next| next.pred_opt())
+            .map_or(end_date, |last| last.min(end_date));
+        ranges.push((bucket_start, bucket_end));
+        match next_period {
+            Some(next) => bucket_start = next,
+            None => break,
+        }
+    }
+    if ranges.is_empty() {
+        ranges.push((start_date, end_date));
+    }
+    ranges
+}
+
+/// Bucket of `date` in a report starting at `start_date`, clamped to the existing buckets.
+fn bucket_index(mode: BucketMode, start_date: NaiveDate, date: NaiveDate, bucket_count: usize) -> usize {
     if date < start_date {
         return 0;
     }
-    let diff = (date - start_date).num_days();
-    let idx = (diff / bucket_days) as usize;
+    let month_number = |d: NaiveDate| d.year() * 12 + d.month0() as i32;
+    let idx = match mode.fixed_days() {
+        Some(bucket_days) => ((date - start_date).num_days() / bucket_days) as usize,
+        None if mode == BucketMode::Quarter => (month_number(date) / 3 - month_number(start_date) / 3) as usize,
+        None => (month_number(date) - month_number(start_date)) as usize,
+    };
     idx.min(bucket_count.saturating_sub(1))
 }
 
-fn assign_colors(categories: &[String]) -> HashMap<String, Color> {
-    let palette = vec![
+/// Whether the terminal advertises the 256-color palette needed for `Color::Indexed`.
+fn supports_indexed_colors() -> bool {
+    std::env::var("COLORTERM").is_ok_and(|v| !v.is_empty())
+        || std::env::var("TERM").is_ok_and(|term| term.contains("256color"))
+}
+
+/// Colors repeat after 11 categories, or after 22 when `extended` adds indexed colors 196-206.
+fn assign_colors(categories: &[String], extended: bool) -> HashMap<String, Color> {
+    let mut palette = vec![
         Color::Cyan,
         Color::Magenta,
         Color::Yellow,
@@ -158,6 +762,9 @@ fn assign_colors(categories: &[String]) -> HashMap<String, Color> {
         Color::LightGreen,
         Color::LightBlue,
     ];
+    if extended {
+        palette.extend((196..207).map(Color::Indexed));
+    }
 
     let mut map = HashMap::new();
     for (idx, category) in categories.iter().enumerate() {
@@ -166,68 +773,148 @@ fn assign_colors(categories: &[String]) -> HashMap<String, Color> {
     map
 }
 
-fn render_report(title: &str, data: &ReportData) -> Result<(), String> {
-    enable_raw_mode().map_err(|e| format!("Failed to enable raw mode: {}", e))?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)
-        .map_err(|e| format!("Failed to enter alternate screen: {}", e))?;
+fn draw_report(
+    frame: &mut ratatui::Frame,
+    title: &str,
+    data: &ReportData,
+    ui: &mut ReportUiState,
+    loading: bool,
+    theme: &Theme,
+) {
+    let size = frame.area();
+    if ui.mode == Mode::BucketDetail {
+        render_bucket_detail(frame, size, data, ui, theme);
+        return;
+    }
 
-    let result = (|| {
-        let backend = ratatui::backend::CrosstermBackend::new(stdout);
-        let mut terminal = ratatui::Terminal::new(backend)
-            .map_err(|e| format!("Failed to initialize terminal: {}", e))?;
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(60),
+            Constraint::Min(5),
+            Constraint::Length(1),
+        ])
+        .split(size);
 
-        loop {
-            terminal
-                .draw(|frame| {
-                    let size = frame.area();
-                    let layout = Layout::default()
-                        .direction(Direction::Vertical)
-                        .constraints([
-                            Constraint::Percentage(60),
-                            Constraint::Percentage(40),
-                        ])
-                        .split(size);
-
-                    render_bar_chart(frame, layout[0], title, data);
-
-                    let bottom = Layout::default()
-                        .direction(Direction::Horizontal)
-                        .constraints([
-                            Constraint::Percentage(55),
-                            Constraint::Percentage(45),
-                        ])
-                        .split(layout[1]);
-
-                    render_pie_chart(frame, bottom[0], data);
-                    render_category_table(frame, bottom[1], data);
-                })
-                .map_err(|e| format!("Failed to draw terminal UI: {}", e))?;
+    render_bar_chart(frame, layout[0], title, data, ui.selected_bucket, ui.show_moving_avg, theme);
+    render_footer(frame, layout[2], ui, theme);
 
-            if event::poll(std::time::Duration::from_millis(250))
-                .map_err(|e| format!("Failed to poll input: {}", e))?
-            {
-                match event::read().map_err(|e| format!("Failed to read input: {}", e))? {
-                    Event::Key(key) if key.code == KeyCode::Char('q') => break,
-                    Event::Key(key) if key.code == KeyCode::Esc => break,
-                    Event::Resize(_, _) => continue,
-                    _ => {}
-                }
-            }
-        }
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(55),
+            Constraint::Percentage(45),
+        ])
+        .split(layout[1]);
 
-        Ok(())
-    })();
+    render_pie_chart(frame, bottom[0], data, theme);
+    render_category_table(frame, bottom[1], data, ui, theme);
 
-    disable_raw_mode().map_err(|e| format!("Failed to disable raw mode: {}", e))?;
-    let mut stdout = io::stdout();
-    execute!(stdout, LeaveAlternateScreen)
-        .map_err(|e| format!("Failed to leave alternate screen: {}", e))?;
+    if loading {
+        let width = 24.min(size.width);
+        let area = Rect::new(size.x + (size.width - width) / 2, size.y + size.height / 2, width, 3.min(size.height));
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new("Loading...")
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL)),
+            area,
+        );
+    }
 
-    result
+    if ui.mode == Mode::ExportPrompt {
+        render_export_prompt(frame, size, ui);
+    }
+}
+
+fn render_footer(frame: &mut ratatui::Frame, area: Rect, ui: &ReportUiState, theme: &Theme) {
+    let footer = match &ui.flash_message {
+        Some((message, _)) => Paragraph::new(message.as_str()).style(Style::default().fg(theme.income_fg)),
+        None => Paragraph::new(
+            "←/→ period  h/l half period  Tab bucket  Enter details  p percent  m average  ↑/↓ scroll table  e export  q exit",
+        )
+        .style(Style::default().fg(theme.muted_fg)),
+    };
+    frame.render_widget(footer, area);
+}
+
+fn render_export_prompt(frame: &mut ratatui::Frame, area: Rect, ui: &ReportUiState) {
+    let popup_area = centered_rect(60, 25, area);
+    frame.render_widget(Clear, popup_area);
+
+    let lines = vec![
+        Line::from(vec![Span::styled("Export Report", Style::default().bold())]),
+        Line::from("Enter a file name, Enter write, Esc cancel"),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            format!("> {}", ui.input_buffer),
+            Style::default().fg(Color::Yellow),
+        )]),
+    ];
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("Input"))
+        .alignment(Alignment::Left);
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn render_bucket_detail(frame: &mut ratatui::Frame, area: Rect, data: &ReportData, ui: &mut ReportUiState, theme: &Theme) {
+    let bucket = ui.selected_bucket.and_then(|idx| data.buckets.get(idx));
+    let range = bucket
+        .map(|b| format!("{} - {}", b.start.format("%d.%m.%Y"), b.end.format("%d.%m.%Y")))
+        .unwrap_or_default();
+    let block = Block::default()
+        .title(format!("Transactions {}  (↑/↓ move, Esc back)", range))
+        .borders(Borders::ALL);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if ui.bucket_transactions.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No transactions in this bucket").alignment(Alignment::Center),
+            inner,
+        );
+        return;
+    }
+
+    let header = Row::new(["Date", "Description", "Amount", "Type", "Category"])
+        .style(Style::default().fg(theme.header_fg).bold());
+    let rows = ui.bucket_transactions.iter().map(|tx| {
+        let (ttype, color) = match tx.transaction_type {
+            TransactionType::Income => ("income", theme.income_fg),
+            TransactionType::Expense => ("expense", theme.expense_fg),
+        };
+        Row::new([
+            Cell::from(tx.date.format("%Y-%m-%d").to_string()),
+            Cell::from(tx.description.clone()),
+            Cell::from(tx.amount.to_string()),
+            Cell::from(ttype),
+            Cell::from(tx.category.clone()),
+        ])
+        .style(Style::default().fg(color))
+    });
+    let widths = [
+        Constraint::Length(10),
+        Constraint::Percentage(45),
+        Constraint::Length(12),
+        Constraint::Length(8),
+        Constraint::Length(16),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .row_highlight_style(Style::default().bg(theme.selected_bg).fg(theme.highlight_fg).bold())
+        .column_spacing(1);
+    frame.render_stateful_widget(table, inner, &mut ui.detail_table);
 }
 
-fn render_bar_chart(frame: &mut ratatui::Frame, area: Rect, title: &str, data: &ReportData) {
+fn render_bar_chart(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    title: &str,
+    data: &ReportData,
+    selected_bucket: Option<usize>,
+    show_moving_avg: bool,
+    theme: &Theme,
+) {
     let inner = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(3), Constraint::Length(2)])
@@ -236,7 +923,7 @@ fn render_bar_chart(frame: &mut ratatui::Frame, area: Rect, title: &str, data: &
     let block = Block::default()
         .title(Line::from(vec![Span::styled(
             format!("{}  (press q to exit)", title),
-            Style::default().fg(Color::White),
+            Style::default().fg(theme.header_fg),
         )]))
         .borders(Borders::ALL);
 
@@ -251,6 +938,27 @@ fn render_bar_chart(frame: &mut ratatui::Frame, area: Rect, title: &str, data: &
     let bucket_count = data.buckets.len();
     let bucket_width = std::cmp::max(1, chart_area.width as usize / bucket_count);
 
+    if data.kind == ReportKind::Net {
+        let mut lines = net_bar_lines(&data.buckets, bar_height, bucket_width, chart_area.width as usize, theme);
+        if show_moving_avg {
+            let half = bar_height.saturating_sub(1) / 2;
+            let max_total = net_max_total(&data.buckets);
+            overlay_moving_avg(&mut lines, &data.moving_avg, bucket_width, theme.highlight_fg, |avg| {
+                let height = (avg.abs() / max_total * half as f64).ceil() as usize;
+                match height {
+                    0 => None,
+                    _ if avg > 0.0 => Some(half - height.min(half)),
+                    _ => Some(half + height.min(half)),
+                }
+            });
+        }
+        highlight_bucket(&mut lines, selected_bucket, bucket_count, theme.selected_bg);
+        let chart = Paragraph::new(lines).alignment(Alignment::Left);
+        frame.render_widget(chart, chart_area);
+        render_bucket_labels(frame, inner[1], data, chart_area.width as usize, bucket_width, selected_bucket, theme);
+        return;
+    }
+
     let max_total = data
         .buckets
         .iter()
@@ -262,74 +970,187 @@ fn render_bar_chart(frame: &mut ratatui::Frame, area: Rect, title: &str, data: &
 
     for row in 0..bar_height {
         let mut spans: Vec<Span> = Vec::new();
-        let level = (bar_height - row) as f64;
 
         for bucket in &data.buckets {
-            let total = bucket.total.to_f64().unwrap_or(0.0);
-            if total <= 0.0 {
-                spans.push(Span::raw(" ".repeat(bucket_width)));
-                continue;
-            }
-
-            let scaled_height = (total / max_total * bar_height as f64).ceil();
-            if level > scaled_height {
-                spans.push(Span::raw(" ".repeat(bucket_width)));
-                continue;
-            }
-
-            let category_heights = compute_category_heights(&bucket.totals, total, bar_height);
-            let mut current_height = 0usize;
-            let mut color = Color::DarkGray;
-            for (category, height) in category_heights {
-                current_height += height;
-                if (bar_height - row) <= current_height {
-                    color = data
-                        .category_colors
-                        .get(&category)
-                        .copied()
-                        .unwrap_or(Color::White);
-                    break;
-                }
-            }
-            spans.push(Span::styled("█".repeat(bucket_width), Style::default().fg(color)));
+            spans.push(match stacked_bar_color(bucket, bar_height - row, bar_height, max_total, &data.category_colors, theme) {
+                Some(color) => Span::styled("█".repeat(bucket_width), Style::default().fg(color)),
+                None => Span::raw(" ".repeat(bucket_width)),
+            });
         }
         lines.push(Line::from(spans));
     }
 
+    if show_moving_avg {
+        overlay_moving_avg(&mut lines, &data.moving_avg, bucket_width, theme.highlight_fg, |avg| {
+            let height = (avg / max_total * bar_height as f64).ceil() as usize;
+            (height > 0).then(|| bar_height - height.min(bar_height))
+        });
+    }
+    highlight_bucket(&mut lines, selected_bucket, bucket_count, theme.selected_bg);
     let chart = Paragraph::new(lines).alignment(Alignment::Left);
     frame.render_widget(chart, chart_area);
 
-    let labels = build_bucket_labels(&data.buckets, chart_area.width as usize, bucket_width);
-    let label_paragraph = Paragraph::new(labels)
-        .alignment(Alignment::Left)
-        .block(Block::default().borders(Borders::NONE));
-    frame.render_widget(label_paragraph, inner[1]);
+    render_bucket_labels(frame, inner[1], data, chart_area.width as usize, bucket_width, selected_bucket, theme);
 }
 
-fn build_bucket_labels(buckets: &[BucketData], width: usize, bucket_width: usize) -> Vec<Line> {
-    if buckets.is_empty() {
-        return vec![Line::from("")];
+/// Color of a bucket's stacked bar on row `level` (1 is the bottom row), `None` above the bar.
+fn stacked_bar_color(
+    bucket: &BucketData,
+    level: usize,
+    bar_height: usize,
+    max_total: f64,
+    colors: &HashMap<String, Color>,
+    theme: &Theme,
+) -> Option<Color> {
+    let total = bucket.total.to_f64().unwrap_or(0.0);
+    let scaled_height = (total / max_total * bar_height as f64).ceil() as usize;
+    if total <= 0.0 || level > scaled_height {
+        return None;
     }
 
-    if bucket_width < 4 {
-        return vec![Line::from(" ".repeat(width))];
+    // Categories share the bar's own height, so shorter bars still show all of them.
+    let mut current_height = 0usize;
+    for (category, height) in compute_category_heights(&bucket.totals, total, scaled_height) {
+        current_height += height;
+        if level <= current_height {
+            return Some(colors.get(&category).copied().unwrap_or(theme.highlight_fg));
+        }
     }
+    Some(theme.muted_fg)
+}
 
-    let mut spans = Vec::new();
-    for bucket in buckets {
-        let label = if bucket.start == bucket.end {
-            bucket.start.format("%m-%d").to_string()
-        } else {
-            format!("{}", bucket.start.format("%m-%d"))
+/// Replaces, in each bucket's column, the cell on the line `row_for` picks for its average with a `—` mark.
+fn overlay_moving_avg(
+    lines: &mut [Line],
+    moving_avg: &[Option<f64>],
+    bucket_width: usize,
+    mark_color: Color,
+    row_for: impl Fn(f64) -> Option<usize>,
+) {
+    for (bucket, avg) in moving_avg.iter().enumerate() {
+        let Some(row) = avg.and_then(&row_for) else {
+            continue;
         };
-        let mut label = label;
-        if label.len() > bucket_width {
-            label.truncate(bucket_width);
+        if let Some(span) = lines.get_mut(row).and_then(|line| line.spans.get_mut(bucket)) {
+            *span = Span::styled("—".repeat(bucket_width), span.style.fg(mark_color));
         }
-        let padded = format!("{:width$}", label, width = bucket_width);
-        spans.push(Span::raw(padded));
     }
+}
 
+/// Gives the selected bucket's column the `bg` background on every line that has one span per bucket.
+fn highlight_bucket(lines: &mut [Line], selected_bucket: Option<usize>, bucket_count: usize, bg: Color) {
+    let Some(selected) = selected_bucket else {
+        return;
+    };
+    for line in lines.iter_mut().filter(|line| line.spans.len() == bucket_count) {
+        if let Some(span) = line.spans.get_mut(selected) {
+            span.style = span.style.bg(bg);
+        }
+    }
+}
+
+fn render_bucket_labels(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    data: &ReportData,
+    width: usize,
+    bucket_width: usize,
+    selected_bucket: Option<usize>,
+    theme: &Theme,
+) {
+    let mut labels = build_bucket_labels(&data.buckets, data.bucket_mode, width, bucket_width);
+    highlight_bucket(&mut labels, selected_bucket, data.buckets.len(), theme.selected_bg);
+    let label_paragraph = Paragraph::new(labels)
+        .alignment(Alignment::Left)
+        .block(Block::default().borders(Borders::NONE));
+    frame.render_widget(label_paragraph, area);
+}
+
+/// Net bars: income in the theme's income color above a zero line in the middle row, expenses
+/// in its expense color below it. Both sides share one scale so their heights can be compared.
+fn net_bar_lines(
+    buckets: &[BucketData],
+    bar_height: usize,
+    bucket_width: usize,
+    width: usize,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let half = bar_height.saturating_sub(1) / 2;
+    let max_total = net_max_total(buckets);
+    let scaled = |value: Decimal| (value.to_f64().unwrap_or(0.0) / max_total * half as f64).ceil() as usize;
+
+    let mut lines = Vec::new();
+    for level in (1..=half).rev() {
+        let spans: Vec<Span> = buckets
+            .iter()
+            .map(|bucket| net_bar_cell(scaled(bucket.total) >= level, theme.income_fg, bucket_width))
+            .collect();
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(Span::styled("─".repeat(width), Style::default().fg(theme.muted_fg))));
+    for level in 1..=half {
+        let spans: Vec<Span> = buckets
+            .iter()
+            .map(|bucket| net_bar_cell(scaled(bucket.negative_total) >= level, theme.expense_fg, bucket_width))
+            .collect();
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// Shared scale of both sides of a net chart.
+fn net_max_total(buckets: &[BucketData]) -> f64 {
+    buckets
+        .iter()
+        .flat_map(|b| [b.total, b.negative_total])
+        .map(|v| v.to_f64().unwrap_or(0.0))
+        .fold(0.0_f64, f64::max)
+        .max(1.0)
+}
+
+fn net_bar_cell(filled: bool, color: Color, bucket_width: usize) -> Span<'static> {
+    if filled {
+        Span::styled("█".repeat(bucket_width), Style::default().fg(color))
+    } else {
+        Span::raw(" ".repeat(bucket_width))
+    }
+}
+
+fn bucket_label(mode: BucketMode, bucket: &BucketData) -> String {
+    match mode {
+        BucketMode::Quarter => format!("Q{} {}", bucket.start.month0() / 3 + 1, bucket.start.year()),
+        _ => bucket.start.format("%m-%d").to_string(),
+    }
+}
+
+/// One span per bucket. When labels are wider than a bucket, only every few buckets get one
+/// and it runs on into the following buckets' columns.
+fn build_bucket_labels(buckets: &[BucketData], mode: BucketMode, width: usize, bucket_width: usize) -> Vec<Line<'static>> {
+    if buckets.is_empty() {
+        return vec![Line::from("")];
+    }
+
+    let labels: Vec<String> = buckets.iter().map(|bucket| bucket_label(mode, bucket)).collect();
+    let label_width = labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+    // Keep at least one blank column between neighbouring labels.
+    let step = if label_width <= bucket_width { 1 } else { (label_width + 1).div_ceil(bucket_width) };
+    let line_width = (bucket_width * buckets.len()).min(width.max(bucket_width));
+
+    let mut cells = vec![' '; bucket_width * buckets.len()];
+    for (i, label) in labels.iter().enumerate().step_by(step) {
+        let offset = i * bucket_width;
+        if offset + label.chars().count() > line_width {
+            break;
+        }
+        for (j, c) in label.chars().enumerate() {
+            cells[offset + j] = c;
+        }
+    }
+
+    let spans = cells
+        .chunks(bucket_width)
+        .map(|chunk| Span::raw(chunk.iter().collect::<String>()))
+        .collect::<Vec<_>>();
     vec![Line::from(spans)]
 }
 
@@ -359,11 +1180,11 @@ fn compute_category_heights(
     let mut remaining = bar_height.saturating_sub(used);
     heights.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
 
-    for idx in 0..heights.len() {
+    for height in heights.iter_mut() {
         if remaining == 0 {
             break;
         }
-        heights[idx].1 += 1;
+        height.1 += 1;
         remaining -= 1;
     }
 
@@ -373,13 +1194,13 @@ fn compute_category_heights(
         .collect()
 }
 
-fn render_pie_chart(frame: &mut ratatui::Frame, area: Rect, data: &ReportData) {
+fn render_pie_chart(frame: &mut ratatui::Frame, area: Rect, data: &ReportData, theme: &Theme) {
     let block = Block::default().title("Category Share").borders(Borders::ALL);
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
     if data.total_spend <= Decimal::ZERO {
-        let empty = Paragraph::new("No expenses in this range")
+        let empty = Paragraph::new(data.kind.empty_message())
             .alignment(Alignment::Center);
         frame.render_widget(empty, inner);
         return;
@@ -396,6 +1217,12 @@ fn render_pie_chart(frame: &mut ratatui::Frame, area: Rect, data: &ReportData) {
         start_angle += sweep;
     }
 
+    let labels = if inner.width >= MIN_PIE_LABEL_WIDTH && inner.height >= MIN_PIE_LABEL_HEIGHT {
+        pie_labels(&data.category_totals, total)
+    } else {
+        Vec::new()
+    };
+
     let canvas = Canvas::default()
         .x_bounds([-1.0, 1.0])
         .y_bounds([-1.0, 1.0])
@@ -406,7 +1233,7 @@ fn render_pie_chart(frame: &mut ratatui::Frame, area: Rect, data: &ReportData) {
                     .category_colors
                     .get(category)
                     .copied()
-                    .unwrap_or(Color::White);
+                    .unwrap_or(theme.highlight_fg);
                 let mut points = Vec::new();
                 let mut r = 0.0; // radius 0 center ... 1 edge
                 while r <= 1.0 {
@@ -421,20 +1248,68 @@ fn render_pie_chart(frame: &mut ratatui::Frame, area: Rect, data: &ReportData) {
                     ctx.draw(&Points { coords: &points, color });
                 }
             }
+            ctx.layer();
+            for (x, y, label) in &labels {
+                ctx.print(*x, *y, Span::styled(label.clone(), Style::default().fg(theme.highlight_fg).bold()));
+            }
         });
 
     frame.render_widget(canvas, inner);
 }
 
-fn render_category_table(frame: &mut ratatui::Frame, area: Rect, data: &ReportData) {
+/// Percent label for each slice big enough to hold one, placed halfway out along the
+/// slice's middle angle. Slices start at angle 0 in `category_totals` order, as drawn.
+fn pie_labels(category_totals: &[(String, Decimal)], total: f64) -> Vec<(f64, f64, String)> {
+    let mut labels = Vec::new();
+    let mut start_angle = 0.0_f64;
+    for (_, amount) in category_totals {
+        let ratio = amount.to_f64().unwrap_or(0.0) / total;
+        let sweep = ratio * std::f64::consts::TAU;
+        if ratio >= MIN_PIE_LABEL_SHARE {
+            let middle = start_angle + sweep / 2.0;
+            labels.push((0.5 * middle.cos(), 0.5 * middle.sin(), format!("{:.1}%", ratio * 100.0)));
+        }
+        start_angle += sweep;
+    }
+    labels
+}
+
+/// `amount` as a share of `total`, e.g. `12.5%`.
+fn format_percent(amount: Decimal, total: Decimal) -> String {
+    if total.is_zero() {
+        return "0.0%".to_string();
+    }
+    let percent = (amount / total * Decimal::ONE_HUNDRED).to_f64().unwrap_or(0.0);
+    format!("{:.1}%", percent)
+}
+
+/// Rows of a `len`-row table to show in `height` lines, scrolled to `offset`. When not everything
+/// fits, the first and last line are kept for the `↑ more`/`↓ more` markers. Returns the clamped
+/// offset and the end of the visible rows.
+fn legend_window(len: usize, offset: usize, height: usize) -> (usize, usize) {
+    if len <= height {
+        return (0, len);
+    }
+    let page = height.saturating_sub(2).max(1);
+    let start = offset.min(len - page);
+    (start, start + page)
+}
+
+fn render_category_table(frame: &mut ratatui::Frame, area: Rect, data: &ReportData, ui: &mut ReportUiState, theme: &Theme) {
+    let show_percent = ui.show_percent;
+    let title = match data.kind {
+        ReportKind::Expense => "Category Spend",
+        ReportKind::Income => "Category Income",
+        ReportKind::Net => "Category Net",
+    };
     let block = Block::default()
-        .title("Category Spend")
+        .title(title)
         .borders(Borders::ALL);
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    if data.category_totals.is_empty() {
-        let empty = Paragraph::new("No expenses in this range")
+    if data.category_summary.is_empty() {
+        let empty = Paragraph::new(data.kind.empty_message())
             .alignment(Alignment::Center);
         frame.render_widget(empty, inner);
         return;
@@ -442,26 +1317,1011 @@ fn render_category_table(frame: &mut ratatui::Frame, area: Rect, data: &ReportDa
 
     let mut lines = Vec::new();
     let header = Line::from(vec![
-        Span::styled("Category", Style::default().fg(Color::White).bold()),
+        Span::styled(format!("{:15}", "Category"), Style::default().fg(theme.header_fg).bold()),
+        Span::raw("  "),
+        Span::styled(format!("{:>5}", "Count"), Style::default().fg(theme.header_fg).bold()),
         Span::raw("  "),
-        Span::styled("Amount", Style::default().fg(Color::White).bold()),
+        Span::styled(
+            format!("{:>12}", if show_percent { "Share" } else { "Amount" }),
+            Style::default().fg(theme.header_fg).bold(),
+        ),
     ]);
     lines.push(header);
 
-    for (category, amount) in &data.category_totals {
+    let rows = data.category_summary.len();
+    let (start, end) = legend_window(rows, ui.legend_offset, inner.height.saturating_sub(1) as usize);
+    ui.legend_offset = start;
+    let scrolls = end - start < rows;
+    let marker = |text: &'static str, shown: bool| {
+        Line::from(Span::styled(if shown { text } else { "" }, Style::default().fg(theme.muted_fg)))
+    };
+    if scrolls {
+        lines.push(marker("↑ more", start > 0));
+    }
+
+    for (category, amount, count) in &data.category_summary[start..end] {
         let color = data
             .category_colors
             .get(category)
             .copied()
-            .unwrap_or(Color::White);
+            .unwrap_or(theme.highlight_fg);
+        let near_limit = data.near_budget_limit.contains(&category.to_lowercase());
+        let amount_style = if near_limit {
+            Style::default().fg(Color::Red).bold()
+        } else {
+            Style::default().fg(color)
+        };
         let line = Line::from(vec![
             Span::styled(format!("{:15}", category), Style::default().fg(color)),
             Span::raw("  "),
-            Span::styled(format!("{:>12}", amount), Style::default().fg(color)),
+            Span::styled(format!("{:>5}", count), Style::default().fg(color)),
+            Span::raw("  "),
+            Span::styled(
+                if show_percent {
+                    format!("{:>12}", format_percent(*amount, data.total_spend))
+                } else {
+                    format!("{:>12}", format_amount(*amount, &Currency::default()))
+                },
+                amount_style,
+            ),
+            Span::styled(if near_limit { " !" } else { "" }, amount_style),
         ]);
         lines.push(line);
     }
+    if scrolls {
+        lines.push(marker("↓ more", end < rows));
+    }
 
     let paragraph = Paragraph::new(lines).alignment(Alignment::Left);
     frame.render_widget(paragraph, inner);
 }
+
+/// Expense report for `reference_start..reference_end` next to the same range one year
+/// earlier: in each bucket the previous year's bar (`▒`) stands left of the current one (`█`),
+/// with one color per category across both. The table lists every category's change.
+pub fn run_comparison_report(
+    conn: &Connection,
+    reference_start: NaiveDate,
+    reference_end: NaiveDate,
+    theme: &Theme,
+) -> Result<(), String> {
+    if reference_start > reference_end {
+        return Err("Start date must be before end date.".to_string());
+    }
+    let (previous_start, previous_end) = previous_year_range(reference_start, reference_end)?;
+
+    // Both sides use the buckets of the reference range, even when a leap day makes one longer.
+    let total_days = (reference_end - reference_start).num_days() + 1;
+    let bucket_mode = BucketMode::Auto.resolve(total_days);
+    let (_, mut previous) = load_report(conn, ReportKind::Expense, bucket_mode, previous_start, previous_end)?;
+    let (_, mut current) = load_report(conn, ReportKind::Expense, bucket_mode, reference_start, reference_end)?;
+    share_category_colors(&mut previous, &mut current);
+
+    let title = comparison_title(previous_start, previous_end, reference_start, reference_end);
+    let rows = comparison_rows(&previous, &current);
+    let mut table_offset = 0usize;
+
+    enable_raw_mode().map_err(|e| format!("Failed to enable raw mode: {}", e))?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)
+        .map_err(|e| format!("Failed to enter alternate screen: {}", e))?;
+
+    let result = (|| {
+        let backend = ratatui::backend::CrosstermBackend::new(stdout);
+        let mut terminal = ratatui::Terminal::new(backend)
+            .map_err(|e| format!("Failed to initialize terminal: {}", e))?;
+
+        loop {
+            terminal
+                .draw(|frame| draw_comparison(frame, &title, &previous, &current, &rows, &mut table_offset, theme))
+                .map_err(|e| format!("Failed to draw terminal UI: {}", e))?;
+
+            if event::poll(std::time::Duration::from_millis(250))
+                .map_err(|e| format!("Failed to poll input: {}", e))?
+                && let Event::Key(key) = event::read().map_err(|e| format!("Failed to read input: {}", e))?
+                && key.kind != KeyEventKind::Release
+            {
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => break,
+                    KeyCode::Up => table_offset = table_offset.saturating_sub(1),
+                    KeyCode::Down => table_offset += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(())
+    })();
+
+    disable_raw_mode().map_err(|e| format!("Failed to disable raw mode: {}", e))?;
+    let mut stdout = io::stdout();
+    execute!(stdout, LeaveAlternateScreen)
+        .map_err(|e| format!("Failed to leave alternate screen: {}", e))?;
+
+    result
+}
+
+/// The same range one year earlier; 29 February becomes the 28th.
+fn previous_year_range(start: NaiveDate, end: NaiveDate) -> Result<(NaiveDate, NaiveDate), String> {
+    let back = |date: NaiveDate| {
+        date.checked_sub_months(Months::new(12))
+            .ok_or_else(|| format!("{} has no previous year to compare with.", date.format("%Y-%m-%d")))
+    };
+    Ok((back(start)?, back(end)?))
+}
+
+/// `Jan 2024 vs Jan 2025` for whole months, otherwise both ranges spelled out.
+fn comparison_title(previous_start: NaiveDate, previous_end: NaiveDate, start: NaiveDate, end: NaiveDate) -> String {
+    let whole_month = |from: NaiveDate, to: NaiveDate| BudgetPeriod::Monthly.bounds(from) == (from, to);
+    if whole_month(previous_start, previous_end) && whole_month(start, end) {
+        return format!("{} vs {}", previous_start.format("%b %Y"), start.format("%b %Y"));
+    }
+    format!(
+        "{} - {} vs {} - {}",
+        previous_start.format("%d.%m.%Y"),
+        previous_end.format("%d.%m.%Y"),
+        start.format("%d.%m.%Y"),
+        end.format("%d.%m.%Y")
+    )
+}
+
+/// Gives both reports one color per category, assigned over the categories of either range.
+fn share_category_colors(previous: &mut ReportData, current: &mut ReportData) {
+    let mut categories: Vec<String> = previous
+        .category_colors
+        .keys()
+        .chain(current.category_colors.keys())
+        .cloned()
+        .collect();
+    categories.sort();
+    categories.dedup();
+    let colors = assign_colors(&categories, supports_indexed_colors());
+    previous.category_colors = colors.clone();
+    current.category_colors = colors;
+}
+
+/// One row per category of either report: its previous and current total, largest current
+/// total first. Categories are matched case-insensitively and named as in the current range.
+fn comparison_rows(previous: &ReportData, current: &ReportData) -> Vec<(String, Decimal, Decimal)> {
+    let mut rows: Vec<(String, Decimal, Decimal)> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let sides = [(current, false), (previous, true)];
+    for (data, is_previous) in sides {
+        for (category, amount) in &data.category_totals {
+            let position = *positions.entry(category.to_lowercase()).or_insert_with(|| {
+                rows.push((category.clone(), Decimal::ZERO, Decimal::ZERO));
+                rows.len() - 1
+            });
+            if is_previous {
+                rows[position].1 += *amount;
+            } else {
+                rows[position].2 += *amount;
+            }
+        }
+    }
+    rows.sort_by(|a, b| b.2.cmp(&a.2).then(b.1.cmp(&a.1)).then(a.0.cmp(&b.0)));
+    rows
+}
+
+/// `120.00 → 150.00 (+25.0%)`; `new` instead of a percentage when nothing was spent before.
+fn format_change(previous: Decimal, current: Decimal) -> String {
+    let change = if previous.is_zero() {
+        "new".to_string()
+    } else {
+        let percent = ((current - previous) / previous * Decimal::ONE_HUNDRED).to_f64().unwrap_or(0.0);
+        format!("{:+.1}%", percent)
+    };
+    format!("{:.2} → {:.2} ({})", previous, current, change)
+}
+
+fn draw_comparison(
+    frame: &mut ratatui::Frame,
+    title: &str,
+    previous: &ReportData,
+    current: &ReportData,
+    rows: &[(String, Decimal, Decimal)],
+    table_offset: &mut usize,
+    theme: &Theme,
+) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Min(5), Constraint::Length(1)])
+        .split(frame.area());
+
+    render_comparison_chart(frame, layout[0], title, previous, current, theme);
+    render_comparison_table(frame, layout[1], current, rows, table_offset, theme);
+    frame.render_widget(
+        Paragraph::new("↑/↓ scroll table  q exit").style(Style::default().fg(theme.muted_fg)),
+        layout[2],
+    );
+}
+
+fn render_comparison_chart(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    title: &str,
+    previous: &ReportData,
+    current: &ReportData,
+    theme: &Theme,
+) {
+    let inner = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(2)])
+        .split(area);
+    let block = Block::default()
+        .title(Line::from(vec![Span::styled(
+            format!("{}  (▒ previous year, █ current)", title),
+            Style::default().fg(theme.header_fg),
+        )]))
+        .borders(Borders::ALL);
+    let chart_area = block.inner(inner[0]);
+    frame.render_widget(block, inner[0]);
+
+    let bar_height = chart_area.height.saturating_sub(1) as usize;
+    let bucket_count = current.buckets.len().max(previous.buckets.len());
+    if bar_height == 0 || bucket_count == 0 {
+        return;
+    }
+    // Each bucket holds both bars side by side, with a gap after them when there is room.
+    let bucket_width = std::cmp::max(2, chart_area.width as usize / bucket_count);
+    let bar_width = if bucket_width >= 4 { (bucket_width - 1) / 2 } else { bucket_width / 2 };
+    let gap = bucket_width - 2 * bar_width;
+
+    let max_total = previous
+        .buckets
+        .iter()
+        .chain(&current.buckets)
+        .map(|b| b.total.to_f64().unwrap_or(0.0))
+        .fold(0.0_f64, f64::max)
+        .max(1.0);
+    let bar = |data: &ReportData, idx: usize, level: usize, glyph: &str| {
+        let color = data
+            .buckets
+            .get(idx)
+            .and_then(|bucket| stacked_bar_color(bucket, level, bar_height, max_total, &data.category_colors, theme));
+        match color {
+            Some(color) => Span::styled(glyph.repeat(bar_width), Style::default().fg(color)),
+            None => Span::raw(" ".repeat(bar_width)),
+        }
+    };
+
+    let mut lines: Vec<Line> = Vec::new();
+    for row in 0..bar_height {
+        let level = bar_height - row;
+        let mut spans: Vec<Span> = Vec::new();
+        for idx in 0..bucket_count {
+            spans.push(bar(previous, idx, level, "▒"));
+            spans.push(bar(current, idx, level, "█"));
+            spans.push(Span::raw(" ".repeat(gap)));
+        }
+        lines.push(Line::from(spans));
+    }
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Left), chart_area);
+
+    let labels = build_bucket_labels(&current.buckets, current.bucket_mode, chart_area.width as usize, bucket_width);
+    frame.render_widget(Paragraph::new(labels).alignment(Alignment::Left), inner[1]);
+}
+
+fn render_comparison_table(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    current: &ReportData,
+    rows: &[(String, Decimal, Decimal)],
+    table_offset: &mut usize,
+    theme: &Theme,
+) {
+    let block = Block::default().title("Category Spend").borders(Borders::ALL);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if rows.is_empty() {
+        frame.render_widget(
+            Paragraph::new(ReportKind::Expense.empty_message()).alignment(Alignment::Center),
+            inner,
+        );
+        return;
+    }
+
+    let bold = Style::default().fg(theme.header_fg).bold();
+    let mut lines = vec![Line::from(vec![
+        Span::styled(format!("{:15}", "Category"), bold),
+        Span::raw("  "),
+        Span::styled("Previous → Current (Δ%)", bold),
+    ])];
+
+    let (start, end) = legend_window(rows.len(), *table_offset, inner.height.saturating_sub(1) as usize);
+    *table_offset = start;
+    let scrolls = end - start < rows.len();
+    let marker = |text: &'static str, shown: bool| {
+        Line::from(Span::styled(if shown { text } else { "" }, Style::default().fg(theme.muted_fg)))
+    };
+    if scrolls {
+        lines.push(marker("↑ more", start > 0));
+    }
+    for (category, previous_amount, current_amount) in &rows[start..end] {
+        let color = current.category_colors.get(category).copied().unwrap_or(theme.highlight_fg);
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:15}", category), Style::default().fg(color)),
+            Span::raw("  "),
+            Span::styled(format_change(*previous_amount, *current_amount), Style::default().fg(color)),
+        ]));
+    }
+    if scrolls {
+        lines.push(marker("↓ more", end < rows.len()));
+    }
+
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Left), inner);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::establish_test_connection;
+    use crate::operations::add::add_transaction_to_db;
+    use crate::operations::budget::set_budget_db;
+
+    #[test]
+    fn test_build_report_fixed_buckets() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-01-01,Coffee,3.50,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-01-09,Bus,2.00,expense,Transport").unwrap();
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 14).unwrap();
+        let transactions = repository::get_expense_transactions_in_range(&conn, start, end).unwrap();
+
+        let report = build_report(&conn, ReportKind::Expense, &transactions, start, end, BucketMode::Week).unwrap();
+        assert_eq!(report.buckets.len(), 2);
+        assert_eq!(report.buckets[0].total, Decimal::new(350, 2));
+        assert_eq!(report.buckets[1].total, Decimal::new(200, 2));
+        assert_eq!(report.total_spend, Decimal::new(550, 2));
+        assert_eq!(report.category_summary.len(), 2);
+        assert_eq!(report.category_summary[0], ("Food".to_string(), Decimal::new(350, 2), 1));
+        assert!(report.near_budget_limit.is_empty());
+    }
+
+    #[test]
+    fn test_build_report_flags_categories_near_budget_limit() {
+        let conn = establish_test_connection().unwrap();
+        set_budget_db(&conn, "Food", "10", None, None).unwrap();
+        set_budget_db(&conn, "Transport", "10", None, None).unwrap();
+        add_transaction_to_db(&conn, "2025-01-01,Groceries,9.50,expense,food").unwrap();
+        add_transaction_to_db(&conn, "2025-01-02,Bus,9.00,expense,Transport").unwrap();
+        add_transaction_to_db(&conn, "2025-01-03,Book,50.00,expense,Hobby").unwrap();
+        // A whole month, so the monthly budgets apply unscaled.
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+        let transactions = repository::get_expense_transactions_in_range(&conn, start, end).unwrap();
+
+        let report = build_report(&conn, ReportKind::Expense, &transactions, start, end, BucketMode::Day).unwrap();
+        assert_eq!(report.near_budget_limit, HashSet::from(["food".to_string()]));
+    }
+
+    #[test]
+    fn test_build_report_monthly_buckets() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2024-01-15,Rent,800.00,expense,Housing").unwrap();
+        add_transaction_to_db(&conn, "2024-01-20,Coffee,3.50,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2024-03-02,Rent,800.00,expense,Housing").unwrap();
+        add_transaction_to_db(&conn, "2024-03-05,Salary,1500.00,income,Job").unwrap();
+        let start = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 9, 30).unwrap();
+        let transactions = repository::get_expense_transactions_in_range(&conn, start, end).unwrap();
+
+        let report = build_report(&conn, ReportKind::Expense, &transactions, start, end, BucketMode::Month).unwrap();
+        assert_eq!(report.buckets.len(), 21);
+        assert_eq!(report.buckets[0].start, start);
+        assert_eq!(report.buckets[0].end, NaiveDate::from_ymd_opt(2024, 1, 31).unwrap());
+        assert_eq!(report.buckets[0].total, Decimal::new(80350, 2));
+        assert_eq!(report.buckets[1].total, Decimal::ZERO);
+        assert_eq!(report.buckets[2].start, NaiveDate::from_ymd_opt(2024, 3, 1).unwrap());
+        assert_eq!(report.buckets[2].total, Decimal::new(80000, 2));
+        assert_eq!(report.buckets[20].end, end);
+    }
+
+    fn seed_income_and_expenses(conn: &Connection) {
+        add_transaction_to_db(conn, "2025-01-01,Salary,1000.00,income,Job").unwrap();
+        add_transaction_to_db(conn, "2025-01-02,Refund,20.00,income,Shopping").unwrap();
+        add_transaction_to_db(conn, "2025-01-03,Coffee,3.50,expense,Food").unwrap();
+        add_transaction_to_db(conn, "2025-01-09,Shoes,60.00,expense,shopping").unwrap();
+    }
+
+    #[test]
+    fn test_build_report_expense_kind_ignores_income() {
+        let conn = establish_test_connection().unwrap();
+        seed_income_and_expenses(&conn);
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 14).unwrap();
+        let transactions = repository::get_expense_transactions_in_range(&conn, start, end).unwrap();
+
+        let report = build_report(&conn, ReportKind::Expense, &transactions, start, end, BucketMode::Week).unwrap();
+        assert_eq!(report.buckets[0].total, Decimal::new(350, 2));
+        assert_eq!(report.buckets[1].total, Decimal::new(6000, 2));
+        assert!(report.buckets.iter().all(|b| b.negative_total.is_zero()));
+        assert_eq!(report.total_spend, Decimal::new(6350, 2));
+    }
+
+    #[test]
+    fn test_build_report_income_kind() {
+        let conn = establish_test_connection().unwrap();
+        seed_income_and_expenses(&conn);
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 14).unwrap();
+        let transactions = repository::get_income_transactions_in_range(&conn, start, end).unwrap();
+
+        let report = build_report(&conn, ReportKind::Income, &transactions, start, end, BucketMode::Week).unwrap();
+        assert_eq!(report.buckets[0].total, Decimal::new(102000, 2));
+        assert_eq!(report.buckets[1].total, Decimal::ZERO);
+        assert_eq!(report.total_spend, Decimal::new(102000, 2));
+        assert_eq!(report.category_summary[0], ("Job".to_string(), Decimal::new(100000, 2), 1));
+        assert_eq!(report.category_summary[1], ("Shopping".to_string(), Decimal::new(2000, 2), 1));
+        assert!(report.near_budget_limit.is_empty());
+    }
+
+    #[test]
+    fn test_build_report_net_kind() {
+        let conn = establish_test_connection().unwrap();
+        seed_income_and_expenses(&conn);
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 14).unwrap();
+        let mut transactions = repository::get_income_transactions_in_range(&conn, start, end).unwrap();
+        transactions.extend(repository::get_expense_transactions_in_range(&conn, start, end).unwrap());
+
+        let report = build_report(&conn, ReportKind::Net, &transactions, start, end, BucketMode::Week).unwrap();
+        assert_eq!(report.buckets[0].total, Decimal::new(102000, 2));
+        assert_eq!(report.buckets[0].negative_total, Decimal::new(350, 2));
+        assert_eq!(report.buckets[1].total, Decimal::ZERO);
+        assert_eq!(report.buckets[1].negative_total, Decimal::new(6000, 2));
+
+        // Refund and shoes share a category, so the net for it is negative.
+        assert_eq!(report.category_summary[0], ("Job".to_string(), Decimal::new(100000, 2), 1));
+        assert_eq!(report.category_summary[1], ("Shopping".to_string(), Decimal::new(-4000, 2), 2));
+        assert_eq!(report.category_summary[2], ("Food".to_string(), Decimal::new(-350, 2), 1));
+    }
+
+    #[test]
+    fn test_build_report_net_kind_monthly_buckets() {
+        let conn = establish_test_connection().unwrap();
+        seed_income_and_expenses(&conn);
+        add_transaction_to_db(&conn, "2025-03-05,Rent,800.00,expense,Housing").unwrap();
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        let mut transactions = repository::get_income_transactions_in_range(&conn, start, end).unwrap();
+        transactions.extend(repository::get_expense_transactions_in_range(&conn, start, end).unwrap());
+
+        let report = build_report(&conn, ReportKind::Net, &transactions, start, end, BucketMode::Month).unwrap();
+        assert_eq!(report.buckets.len(), 3);
+        assert_eq!(report.buckets[0].total, Decimal::new(102000, 2));
+        assert_eq!(report.buckets[0].negative_total, Decimal::new(6350, 2));
+        assert_eq!(report.buckets[2].total, Decimal::ZERO);
+        assert_eq!(report.buckets[2].negative_total, Decimal::new(80000, 2));
+    }
+
+    #[test]
+    fn test_net_bar_lines_draws_zero_line() {
+        let bucket = |total: i64, negative: i64| BucketData {
+            start: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            end: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            totals: Vec::new(),
+            total: Decimal::from(total),
+            negative_total: Decimal::from(negative),
+        };
+        let theme = Theme::light();
+        let lines = net_bar_lines(&[bucket(100, 0), bucket(0, 50)], 5, 2, 4, &theme);
+        let text: Vec<String> = lines
+            .iter()
+            .map(|line| line.spans.iter().map(|span| span.content.to_string()).collect())
+            .collect();
+        assert_eq!(text, vec!["██  ", "██  ", "────", "  ██", "    "]);
+        // Bars take the theme's income and expense colors.
+        assert_eq!(lines[0].spans[0].style.fg, Some(theme.income_fg));
+        assert_eq!(lines[3].spans[1].style.fg, Some(theme.expense_fg));
+    }
+
+    #[test]
+    fn test_report_kind_parse() {
+        assert_eq!(ReportKind::parse("Income"), Some(ReportKind::Income));
+        assert_eq!(ReportKind::parse("net"), Some(ReportKind::Net));
+        assert_eq!(ReportKind::parse("expense"), Some(ReportKind::Expense));
+        assert_eq!(ReportKind::parse("savings"), None);
+    }
+
+    #[test]
+    fn test_shift_range_full_period() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+
+        assert_eq!(
+            shift_range(start, end, PeriodShift::Forward).unwrap(),
+            (NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(), NaiveDate::from_ymd_opt(2025, 3, 3).unwrap())
+        );
+        assert_eq!(
+            shift_range(start, end, PeriodShift::Back).unwrap(),
+            (NaiveDate::from_ymd_opt(2024, 12, 1).unwrap(), NaiveDate::from_ymd_opt(2024, 12, 31).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_shift_range_half_period() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 14).unwrap();
+
+        assert_eq!(
+            shift_range(start, end, PeriodShift::HalfForward).unwrap(),
+            (NaiveDate::from_ymd_opt(2025, 1, 8).unwrap(), NaiveDate::from_ymd_opt(2025, 1, 21).unwrap())
+        );
+        assert_eq!(
+            shift_range(start, end, PeriodShift::HalfBack).unwrap(),
+            (NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(), NaiveDate::from_ymd_opt(2025, 1, 7).unwrap())
+        );
+        // A single day still moves by one day.
+        assert_eq!(
+            shift_range(start, start, PeriodShift::HalfForward).unwrap(),
+            (NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(), NaiveDate::from_ymd_opt(2025, 1, 2).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_shift_range_past_supported_dates() {
+        let start = NaiveDate::MAX - Duration::days(9);
+
+        assert!(matches!(
+            shift_range(start, NaiveDate::MAX, PeriodShift::Forward),
+            Err(AppError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            shift_range(NaiveDate::MIN, NaiveDate::MIN, PeriodShift::HalfBack),
+            Err(AppError::InvalidInput(_))
+        ));
+        assert_eq!(
+            shift_range(start, NaiveDate::MAX, PeriodShift::Back).unwrap(),
+            (start - Duration::days(10), NaiveDate::MAX - Duration::days(10))
+        );
+    }
+
+    #[test]
+    fn test_load_report_for_range_ending_at_max_date() {
+        let conn = establish_test_connection().unwrap();
+        let (start, end) = shift_range(
+            NaiveDate::MAX - Duration::days(19),
+            NaiveDate::MAX - Duration::days(10),
+            PeriodShift::Forward,
+        )
+        .unwrap();
+        assert_eq!(end, NaiveDate::MAX);
+
+        for mode in [BucketMode::Auto, BucketMode::Day, BucketMode::Week, BucketMode::Month, BucketMode::Quarter] {
+            let (_, report) = load_report(&conn, ReportKind::Expense, mode, start, end).unwrap();
+            assert_eq!(report.buckets.first().map(|b| b.start), Some(start));
+            assert_eq!(report.buckets.last().map(|b| b.end), Some(end));
+        }
+    }
+
+    #[test]
+    fn test_load_report_after_shift() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-01-05,Coffee,3.50,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-02-05,Rent,800.00,expense,Housing").unwrap();
+        let (start, end) = shift_range(
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            PeriodShift::Forward,
+        )
+        .unwrap();
+
+        let (title, report) = load_report(&conn, ReportKind::Expense, BucketMode::Auto, start, end).unwrap();
+        assert!(title.starts_with("expense 01.02.2025 - 03.03.2025"));
+        assert_eq!(report.total_spend, Decimal::new(80000, 2));
+    }
+
+    #[test]
+    fn test_bucket_selection_wraps() {
+        let mut ui = ReportUiState::new();
+        ui.move_bucket_selection(-1, 3);
+        assert_eq!(ui.selected_bucket, Some(2));
+        ui.move_bucket_selection(1, 3);
+        assert_eq!(ui.selected_bucket, Some(0));
+        ui.move_bucket_selection(1, 0);
+        assert_eq!(ui.selected_bucket, None);
+    }
+
+    #[test]
+    fn test_open_bucket_lists_transactions_in_range() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-01-01,Coffee,3.50,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-01-03,Salary,1000.00,income,Job").unwrap();
+        add_transaction_to_db(&conn, "2025-01-09,Bus,2.00,expense,Transport").unwrap();
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 14).unwrap();
+        let (_, report) = load_report(&conn, ReportKind::Expense, BucketMode::Auto, start, end).unwrap();
+        let mut ui = ReportUiState::new();
+
+        // Enter without a selected bucket stays on the report.
+        handle_key(&conn, &mut ui, &report, KeyCode::Enter).unwrap();
+        assert_eq!(ui.mode, Mode::Report);
+
+        handle_key(&conn, &mut ui, &report, KeyCode::Tab).unwrap();
+        handle_key(&conn, &mut ui, &report, KeyCode::Enter).unwrap();
+        assert_eq!(ui.mode, Mode::BucketDetail);
+        let descriptions: Vec<&str> = ui.bucket_transactions.iter().map(|t| t.description.as_str()).collect();
+        assert_eq!(descriptions.len(), 2);
+        assert!(descriptions.contains(&"Coffee"));
+        assert!(descriptions.contains(&"Salary"));
+
+        // Esc goes back to the report instead of quitting.
+        assert!(matches!(handle_key(&conn, &mut ui, &report, KeyCode::Esc).unwrap(), ReportAction::None));
+        assert_eq!(ui.mode, Mode::Report);
+        assert!(matches!(handle_key(&conn, &mut ui, &report, KeyCode::Esc).unwrap(), ReportAction::Quit));
+    }
+
+    #[test]
+    fn test_highlight_bucket_styles_selected_column() {
+        let mut lines = vec![
+            Line::from(vec![Span::raw("a"), Span::raw("b")]),
+            Line::from(Span::raw("──")),
+        ];
+        highlight_bucket(&mut lines, Some(1), 2, Color::DarkGray);
+        assert_eq!(lines[0].spans[1].style.bg, Some(Color::DarkGray));
+        assert_eq!(lines[0].spans[0].style.bg, None);
+        assert_eq!(lines[1].spans[0].style.bg, None);
+    }
+
+    #[test]
+    fn test_format_percent() {
+        assert_eq!(format_percent(Decimal::new(125, 1), Decimal::from(100)), "12.5%");
+        assert_eq!(format_percent(Decimal::from(1), Decimal::from(3)), "33.3%");
+        assert_eq!(format_percent(Decimal::from(5), Decimal::ZERO), "0.0%");
+    }
+
+    #[test]
+    fn test_percent_toggle_key() {
+        let conn = establish_test_connection().unwrap();
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let (_, report) = load_report(&conn, ReportKind::Expense, BucketMode::Auto, start, start).unwrap();
+        let mut ui = ReportUiState::new();
+
+        handle_key(&conn, &mut ui, &report, KeyCode::Char('p')).unwrap();
+        assert!(ui.show_percent);
+        handle_key(&conn, &mut ui, &report, KeyCode::Char('p')).unwrap();
+        assert!(!ui.show_percent);
+    }
+
+    #[test]
+    fn test_pie_labels_skip_small_slices() {
+        let totals = vec![
+            ("Housing".to_string(), Decimal::from(75)),
+            ("Food".to_string(), Decimal::from(23)),
+            ("Misc".to_string(), Decimal::from(2)),
+        ];
+        let labels = pie_labels(&totals, 100.0);
+
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels[0].2, "75.0%");
+        assert_eq!(labels[1].2, "23.0%");
+        // The first slice spans 0..270 degrees, so its label sits at 135 degrees.
+        assert!(labels[0].0 < 0.0 && labels[0].1 > 0.0);
+        assert!((labels[0].0.hypot(labels[0].1) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_legend_window() {
+        // Everything fits, no markers.
+        assert_eq!(legend_window(5, 3, 5), (0, 5));
+        // 20 rows in 7 lines: 5 rows between the markers.
+        assert_eq!(legend_window(20, 0, 7), (0, 5));
+        assert_eq!(legend_window(20, 4, 7), (4, 9));
+        // Scrolling past the end stops at the last page.
+        assert_eq!(legend_window(20, 30, 7), (15, 20));
+        // Tiny panels still show one row.
+        assert_eq!(legend_window(20, 2, 1), (2, 3));
+    }
+
+    #[test]
+    fn test_assign_colors_extended_palette() {
+        let categories: Vec<String> = (0..23).map(|i| format!("C{:02}", i)).collect();
+
+        let basic = assign_colors(&categories, false);
+        assert_eq!(basic["C11"], basic["C00"]);
+
+        let extended = assign_colors(&categories, true);
+        assert_eq!(extended["C11"], Color::Indexed(196));
+        assert_eq!(extended["C21"], Color::Indexed(206));
+        assert_eq!(extended["C22"], extended["C00"]);
+    }
+
+    #[test]
+    fn test_legend_scroll_keys() {
+        let conn = establish_test_connection().unwrap();
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let (_, report) = load_report(&conn, ReportKind::Expense, BucketMode::Auto, start, start).unwrap();
+        let mut ui = ReportUiState::new();
+
+        handle_key(&conn, &mut ui, &report, KeyCode::Up).unwrap();
+        assert_eq!(ui.legend_offset, 0);
+        handle_key(&conn, &mut ui, &report, KeyCode::Down).unwrap();
+        handle_key(&conn, &mut ui, &report, KeyCode::Down).unwrap();
+        assert_eq!(ui.legend_offset, 2);
+    }
+
+    #[test]
+    fn test_export_report_as_text() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-01-01,Coffee,3.50,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-01-02,Bus,2.00,expense,Transport").unwrap();
+        add_transaction_to_db(&conn, "2025-01-09,Lunch,12.00,expense,Food").unwrap();
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 14).unwrap();
+        let (title, report) = load_report(&conn, ReportKind::Expense, BucketMode::Auto, start, end).unwrap();
+
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        let path = tmp.path().to_str().unwrap();
+        export_report_as_text(&report, &title, path).unwrap();
+        let text = std::fs::read_to_string(path).unwrap();
+
+        assert!(text.starts_with(&title));
+        assert!(text.contains("01.01.2025 - 07.01.2025   Food                    3.50\n"));
+        assert!(text.contains("                          Transport               2.00\n"));
+        assert!(text.contains("                          Total                   5.50\n"));
+        assert!(text.contains("08.01.2025 - 14.01.2025   Food                   12.00\n"));
+        let summary = text.split("Category summary").nth(1).unwrap();
+        assert!(summary.contains("Food                2        15.50\n"));
+        assert!(summary.contains(&format!("Total{}17.50\n", " ".repeat(24))));
+    }
+
+    #[test]
+    fn test_export_report_invalid_path() {
+        let conn = establish_test_connection().unwrap();
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let (title, report) = load_report(&conn, ReportKind::Expense, BucketMode::Auto, start, start).unwrap();
+
+        let err = export_report_as_text(&report, &title, "/nonexistent-dir/report.txt").unwrap_err();
+        assert!(err.contains("Failed to write file"));
+    }
+
+    #[test]
+    fn test_export_prompt_keys() {
+        let conn = establish_test_connection().unwrap();
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let (_, report) = load_report(&conn, ReportKind::Expense, BucketMode::Auto, start, start).unwrap();
+        let mut ui = ReportUiState::new();
+
+        handle_key(&conn, &mut ui, &report, KeyCode::Char('e')).unwrap();
+        assert_eq!(ui.mode, Mode::ExportPrompt);
+        assert_eq!(ui.input_buffer, DEFAULT_EXPORT_FILE);
+        // Keys that normally act on the report are typed into the prompt.
+        for _ in 0..4 {
+            handle_key(&conn, &mut ui, &report, KeyCode::Backspace).unwrap();
+        }
+        handle_key(&conn, &mut ui, &report, KeyCode::Char('q')).unwrap();
+        assert_eq!(ui.input_buffer, "reportq");
+
+        match handle_key(&conn, &mut ui, &report, KeyCode::Enter).unwrap() {
+            ReportAction::Export(path) => assert_eq!(path, "reportq"),
+            _ => panic!("expected an export action"),
+        }
+        assert_eq!(ui.mode, Mode::Report);
+    }
+
+    #[test]
+    fn test_report_flash_expires() {
+        let mut ui = ReportUiState::new();
+        let start = Instant::now();
+        ui.flash("Report written to report.txt", start);
+        ui.expire_flash(start + StdDuration::from_millis(500));
+        assert!(ui.flash_message.is_some());
+        ui.expire_flash(start + FLASH_DURATION);
+        assert!(ui.flash_message.is_none());
+    }
+
+    #[test]
+    fn test_build_report_moving_average() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-01-01,A,3.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-01-02,B,6.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-01-03,C,9.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-01-04,D,15.00,expense,Food").unwrap();
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 4).unwrap();
+        let transactions = repository::get_expense_transactions_in_range(&conn, start, end).unwrap();
+
+        let report = build_report(&conn, ReportKind::Expense, &transactions, start, end, BucketMode::Day).unwrap();
+        assert_eq!(report.moving_avg, vec![None, None, Some(6.0), Some(10.0)]);
+    }
+
+    #[test]
+    fn test_build_report_net_moving_average() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-01-01,Pay,30.00,income,Job").unwrap();
+        add_transaction_to_db(&conn, "2025-01-02,Rent,60.00,expense,Housing").unwrap();
+        add_transaction_to_db(&conn, "2025-01-03,Snack,3.00,expense,Food").unwrap();
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 3).unwrap();
+        let mut transactions = repository::get_income_transactions_in_range(&conn, start, end).unwrap();
+        transactions.extend(repository::get_expense_transactions_in_range(&conn, start, end).unwrap());
+
+        let report = build_report(&conn, ReportKind::Net, &transactions, start, end, BucketMode::Day).unwrap();
+        assert_eq!(report.moving_avg, vec![None, None, Some(-11.0)]);
+    }
+
+    #[test]
+    fn test_moving_average_short_input() {
+        assert!(moving_average(&[]).is_empty());
+        assert_eq!(moving_average(&[1.0, 2.0]), vec![None, None]);
+    }
+
+    #[test]
+    fn test_overlay_moving_avg_marks_row() {
+        let mut lines = vec![
+            Line::from(vec![Span::raw("  "), Span::raw("  ")]),
+            Line::from(vec![Span::raw("██"), Span::raw("██")]),
+        ];
+        overlay_moving_avg(&mut lines, &[None, Some(5.0)], 2, Color::White, |_| Some(0));
+        assert_eq!(lines[0].spans[1].content, "——");
+        assert_eq!(lines[0].spans[0].content, "  ");
+        assert_eq!(lines[1].spans[1].content, "██");
+    }
+
+    #[test]
+    fn test_bucket_index_quarter_boundaries() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        // The range starts mid-quarter; the first bucket is the rest of Q1.
+        let start = date(2025, 2, 15);
+        assert_eq!(bucket_index(BucketMode::Quarter, start, date(2025, 3, 31), 8), 0);
+        assert_eq!(bucket_index(BucketMode::Quarter, start, date(2025, 4, 1), 8), 1);
+        assert_eq!(bucket_index(BucketMode::Quarter, start, date(2025, 6, 30), 8), 1);
+        assert_eq!(bucket_index(BucketMode::Quarter, start, date(2025, 7, 1), 8), 2);
+        assert_eq!(bucket_index(BucketMode::Quarter, start, date(2025, 12, 31), 8), 3);
+        assert_eq!(bucket_index(BucketMode::Quarter, start, date(2026, 1, 1), 8), 4);
+        // Dates outside the range are clamped to the first and last bucket.
+        assert_eq!(bucket_index(BucketMode::Quarter, start, date(2025, 1, 1), 8), 0);
+        assert_eq!(bucket_index(BucketMode::Quarter, start, date(2030, 1, 1), 8), 7);
+    }
+
+    #[test]
+    fn test_build_report_quarter_buckets() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2024-02-10,Rent,800.00,expense,Housing").unwrap();
+        add_transaction_to_db(&conn, "2024-03-31,Coffee,3.50,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2024-04-01,Rent,800.00,expense,Housing").unwrap();
+        add_transaction_to_db(&conn, "2025-01-05,Rent,850.00,expense,Housing").unwrap();
+        let start = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+        let transactions = repository::get_expense_transactions_in_range(&conn, start, end).unwrap();
+
+        let report = build_report(&conn, ReportKind::Expense, &transactions, start, end, BucketMode::Quarter).unwrap();
+        assert_eq!(report.buckets.len(), 5);
+        assert_eq!(report.buckets[0].start, start);
+        assert_eq!(report.buckets[0].end, NaiveDate::from_ymd_opt(2024, 3, 31).unwrap());
+        assert_eq!(report.buckets[0].total, Decimal::new(80350, 2));
+        assert_eq!(report.buckets[1].start, NaiveDate::from_ymd_opt(2024, 4, 1).unwrap());
+        assert_eq!(report.buckets[1].total, Decimal::new(80000, 2));
+        assert_eq!(report.buckets[4].end, end);
+        assert_eq!(report.buckets[4].total, Decimal::new(85000, 2));
+
+        let labels = build_bucket_labels(&report.buckets, BucketMode::Quarter, 80, 16);
+        let text: String = labels[0].spans.iter().map(|span| span.content.as_ref()).collect();
+        assert!(text.starts_with("Q1 2024         Q2 2024"));
+        assert!(text.contains("Q1 2025"));
+    }
+
+    #[test]
+    fn test_bucket_labels_wider_than_buckets() {
+        let conn = establish_test_connection().unwrap();
+        let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let report = build_report(&conn, ReportKind::Expense, &[], start, end, BucketMode::Quarter).unwrap();
+        assert_eq!(report.buckets.len(), 20);
+
+        // 4 columns per bucket: each 7-character label spans two buckets plus a gap.
+        let labels = build_bucket_labels(&report.buckets, BucketMode::Quarter, 80, 4);
+        assert_eq!(labels[0].spans.len(), 20);
+        let text: String = labels[0].spans.iter().map(|span| span.content.as_ref()).collect();
+        assert!(text.starts_with("Q1 2020 Q3 2020 "));
+        assert!(!text.contains("Q2 2020"));
+    }
+
+    #[test]
+    fn test_auto_bucket_mode() {
+        assert_eq!(BucketMode::Auto.resolve(7), BucketMode::Day);
+        assert_eq!(BucketMode::Auto.resolve(60), BucketMode::Week);
+        assert_eq!(BucketMode::Auto.resolve(365), BucketMode::Fortnight);
+        assert_eq!(BucketMode::Auto.resolve(700), BucketMode::Month);
+        assert_eq!(BucketMode::Auto.resolve(2000), BucketMode::Quarter);
+        assert_eq!(BucketMode::Quarter.resolve(7), BucketMode::Quarter);
+        assert_eq!(BucketMode::parse(" Fortnight"), Some(BucketMode::Fortnight));
+    }
+
+    #[test]
+    fn test_previous_year_range() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(
+            previous_year_range(date(2025, 1, 1), date(2025, 1, 31)),
+            Ok((date(2024, 1, 1), date(2024, 1, 31)))
+        );
+        assert_eq!(
+            previous_year_range(date(2024, 2, 1), date(2024, 2, 29)),
+            Ok((date(2023, 2, 1), date(2023, 2, 28)))
+        );
+    }
+
+    #[test]
+    fn test_comparison_title() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(
+            comparison_title(date(2024, 1, 1), date(2024, 1, 31), date(2025, 1, 1), date(2025, 1, 31)),
+            "Jan 2024 vs Jan 2025"
+        );
+        assert_eq!(
+            comparison_title(date(2024, 1, 1), date(2024, 1, 15), date(2025, 1, 1), date(2025, 1, 15)),
+            "01.01.2024 - 15.01.2024 vs 01.01.2025 - 15.01.2025"
+        );
+    }
+
+    #[test]
+    fn test_comparison_rows_and_shared_colors() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2024-01-05,Groceries,120.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2024-01-09,Cinema,30.00,expense,Fun").unwrap();
+        add_transaction_to_db(&conn, "2025-01-05,Groceries,150.00,expense,food").unwrap();
+        add_transaction_to_db(&conn, "2025-01-20,Train,40.00,expense,Travel").unwrap();
+        let load = |start, end| {
+            load_report(&conn, ReportKind::Expense, BucketMode::Week, start, end).unwrap().1
+        };
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let mut previous = load(date(2024, 1, 1), date(2024, 1, 31));
+        let mut current = load(date(2025, 1, 1), date(2025, 1, 31));
+
+        share_category_colors(&mut previous, &mut current);
+        assert_eq!(previous.category_colors, current.category_colors);
+        assert_eq!(current.category_colors.len(), 4);
+
+        let rows = comparison_rows(&previous, &current);
+        assert_eq!(
+            rows,
+            vec![
+                ("food".to_string(), Decimal::new(120, 0), Decimal::new(150, 0)),
+                ("Travel".to_string(), Decimal::ZERO, Decimal::new(40, 0)),
+                ("Fun".to_string(), Decimal::new(30, 0), Decimal::ZERO),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_change() {
+        assert_eq!(format_change(Decimal::new(120, 0), Decimal::new(150, 0)), "120.00 → 150.00 (+25.0%)");
+        assert_eq!(format_change(Decimal::new(30, 0), Decimal::ZERO), "30.00 → 0.00 (-100.0%)");
+        assert_eq!(format_change(Decimal::ZERO, Decimal::new(40, 0)), "0.00 → 40.00 (new)");
+    }
+
+    #[test]
+    fn test_compute_category_heights_income_side() {
+        let totals = vec![("Job".to_string(), Decimal::new(1000, 0)), ("Freelance".to_string(), Decimal::new(500, 0))];
+        let heights = compute_category_heights(&totals, 1500.0, 6);
+        assert_eq!(heights, vec![("Job".to_string(), 4), ("Freelance".to_string(), 2)]);
+
+        // Rounding hands the leftover rows to the largest remainders.
+        let heights = compute_category_heights(&totals, 1500.0, 5);
+        assert_eq!(heights.iter().map(|(_, h)| h).sum::<usize>(), 5);
+
+        // A net bucket with expenses only has no income to stack.
+        let heights = compute_category_heights(&totals, 0.0, 5);
+        assert!(heights.iter().all(|(_, h)| *h == 0));
+    }
+
+    #[test]
+    fn test_stacked_bar_color_short_bar_shows_every_category() {
+        let bucket = BucketData {
+            start: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            end: NaiveDate::from_ymd_opt(2025, 1, 7).unwrap(),
+            totals: vec![("Job".to_string(), Decimal::new(300, 0)), ("Gifts".to_string(), Decimal::new(300, 0))],
+            total: Decimal::new(600, 0),
+            negative_total: Decimal::ZERO,
+        };
+        let colors = HashMap::from([("Job".to_string(), Color::Green), ("Gifts".to_string(), Color::Yellow)]);
+
+        // Half of the tallest bar: 4 of 8 rows, two per category.
+        let column: Vec<Option<Color>> = (1..=8).map(|level| stacked_bar_color(&bucket, level, 8, 1200.0, &colors, &Theme::dark())).collect();
+        assert_eq!(column.iter().filter(|c| c.is_some()).count(), 4);
+        assert!(column.contains(&Some(Color::Green)));
+        assert!(column.contains(&Some(Color::Yellow)));
+    }
+}
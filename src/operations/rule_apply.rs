@@ -0,0 +1,263 @@
+use crate::db::{repository, rule_repository};
+use crate::models::rule::{CategoryRule, RuleMatchType};
+use crate::models::transaction::Transaction;
+use regex::Regex;
+use rusqlite::Connection;
+
+/// Substring rules match case-insensitively anywhere in the description.
+/// Regex rules are unanchored unless the pattern uses `^`/`$`; an invalid pattern never matches.
+pub fn apply_rule(rule: &CategoryRule, description: &str) -> bool {
+    Matcher::new(rule).is_some_and(|matcher| matcher.is_match(description))
+}
+
+/// Rules prepared once so regexes are not recompiled for every transaction.
+pub struct RuleSet {
+    rules: Vec<(Matcher, CategoryRule)>,
+}
+
+impl RuleSet {
+    /// Rules whose regex does not compile are left out.
+    pub fn new(rules: Vec<CategoryRule>) -> Self {
+        let rules = rules
+            .into_iter()
+            .filter_map(|rule| Matcher::new(&rule).map(|matcher| (matcher, rule)))
+            .collect();
+        RuleSet { rules }
+    }
+
+    /// First matching rule, in the order given (`get_all_rules` sorts by priority).
+    pub fn matching_rule(&self, description: &str) -> Option<&CategoryRule> {
+        self.rules
+            .iter()
+            .find(|(matcher, _)| matcher.is_match(description))
+            .map(|(_, rule)| rule)
+    }
+
+    pub fn category_for(&self, description: &str) -> Option<&str> {
+        self.matching_rule(description).map(|rule| rule.category.as_str())
+    }
+}
+
+/// Pairs every uncategorized stored transaction with the rule `apply_rules_to_transactions` would use.
+pub fn preview_rule_application(conn: &Connection) -> Result<Vec<(Transaction, Option<CategoryRule>)>, String> {
+    let rules = RuleSet::new(rule_repository::get_all_rules(conn)?);
+    let transactions = repository::get_all_transactions(conn)?;
+
+    Ok(transactions
+        .into_iter()
+        .filter(|t| t.category.is_empty() || t.category == "Uncategorized")
+        .map(|t| {
+            let rule = rules.matching_rule(&t.description).cloned();
+            (t, rule)
+        })
+        .collect())
+}
+
+/// Sets the category of every uncategorized stored transaction to its first matching rule.
+/// Returns how many transactions were updated; on error nothing is changed.
+pub fn apply_rules_to_transactions(conn: &Connection) -> Result<usize, String> {
+    let matches = preview_rule_application(conn)?;
+
+    conn.execute("BEGIN", [])
+        .map_err(|e| format!("Failed to start categorization: {}", e))?;
+
+    let mut updated = 0;
+    for (mut transaction, rule) in matches {
+        let Some(rule) = rule else {
+            continue;
+        };
+
+        transaction.category = rule.category;
+        if let Err(e) = repository::update_transaction(conn, &transaction.id, &transaction) {
+            let _ = conn.execute("ROLLBACK", []);
+            return Err(String::from(e));
+        }
+        updated += 1;
+    }
+
+    conn.execute("COMMIT", [])
+        .map_err(|e| format!("Failed to commit categorization: {}", e))?;
+    Ok(updated)
+}
+
+enum Matcher {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl Matcher {
+    fn new(rule: &CategoryRule) -> Option<Self> {
+        match rule.match_type {
+            RuleMatchType::Substring => Some(Matcher::Substring(rule.pattern.to_lowercase())),
+            RuleMatchType::Regex => Regex::new(&rule.pattern).ok().map(Matcher::Regex),
+        }
+    }
+
+    fn is_match(&self, description: &str) -> bool {
+        match self {
+            Matcher::Substring(needle) => description.to_lowercase().contains(needle),
+            Matcher::Regex(re) => re.is_match(description),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::establish_test_connection;
+    use crate::operations::add::add_transaction_to_db;
+
+    fn rule(pattern: &str, category: &str, match_type: RuleMatchType) -> CategoryRule {
+        CategoryRule {
+            id: 0,
+            pattern: pattern.to_string(),
+            category: category.to_string(),
+            match_type,
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn test_substring_rule_is_case_insensitive() {
+        let coffee = rule("coffee", "Food", RuleMatchType::Substring);
+        assert!(apply_rule(&coffee, "Morning COFFEE"));
+        assert!(!apply_rule(&coffee, "Tea"));
+
+        // Regex syntax is taken literally.
+        let literal = rule("^Uber", "Transport", RuleMatchType::Substring);
+        assert!(!apply_rule(&literal, "Uber ride"));
+        assert!(apply_rule(&literal, "paid ^uber"));
+    }
+
+    #[test]
+    fn test_regex_rule_anchored_and_unanchored() {
+        let unanchored = rule("Uber", "Transport", RuleMatchType::Regex);
+        assert!(apply_rule(&unanchored, "Trip with Uber"));
+        assert!(!apply_rule(&unanchored, "trip with uber"));
+
+        let anchored = rule("^Uber.*Eats$", "Food", RuleMatchType::Regex);
+        assert!(apply_rule(&anchored, "Uber Eats"));
+        assert!(!apply_rule(&anchored, "Paid Uber Eats"));
+        assert!(!apply_rule(&anchored, "Uber Eats refund"));
+
+        let case_insensitive = rule("(?i)^lidl", "Groceries", RuleMatchType::Regex);
+        assert!(apply_rule(&case_insensitive, "LIDL Berlin"));
+    }
+
+    #[test]
+    fn test_invalid_regex_never_matches() {
+        let broken = rule("(unclosed", "Broken", RuleMatchType::Regex);
+        assert!(!apply_rule(&broken, "(unclosed"));
+    }
+
+    #[test]
+    fn test_apply_rules_to_transactions() {
+        let conn = establish_test_connection().unwrap();
+        rule_repository::add_rule(&conn, "coffee", "Food", RuleMatchType::Substring).unwrap();
+        rule_repository::add_rule(&conn, "^Uber", "Transport", RuleMatchType::Regex).unwrap();
+        add_transaction_to_db(&conn, "2025-03-01,Morning Coffee,3.50,expense,Uncategorized").unwrap();
+        add_transaction_to_db(&conn, "2025-03-02,Uber ride,12.00,expense,Uncategorized").unwrap();
+        add_transaction_to_db(&conn, "2025-03-03,Coffee beans,9.00,expense,Groceries").unwrap();
+        add_transaction_to_db(&conn, "2025-03-04,Bookshop,20.00,expense,Uncategorized").unwrap();
+
+        let updated = apply_rules_to_transactions(&conn).unwrap();
+        assert_eq!(updated, 2);
+
+        let all = repository::get_all_transactions(&conn).unwrap();
+        let category_of = |description: &str| all.iter().find(|t| t.description == description).unwrap().category.clone();
+        assert_eq!(category_of("Morning Coffee"), "Food");
+        assert_eq!(category_of("Uber ride"), "Transport");
+        assert_eq!(category_of("Coffee beans"), "Groceries");
+        assert_eq!(category_of("Bookshop"), "Uncategorized");
+
+        assert_eq!(apply_rules_to_transactions(&conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_apply_rules_higher_priority_wins() {
+        let conn = establish_test_connection().unwrap();
+        rule_repository::add_rule(&conn, "uber", "Transport", RuleMatchType::Substring).unwrap();
+        rule_repository::add_rule(&conn, "uber eats", "Food", RuleMatchType::Substring).unwrap();
+        add_transaction_to_db(&conn, "2025-03-01,Uber Eats order,18.00,expense,Uncategorized").unwrap();
+
+        let food_rule = rule_repository::get_all_rules(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|r| r.category == "Food")
+            .unwrap();
+        rule_repository::set_rule_priority(&conn, food_rule.id, 5).unwrap();
+
+        assert_eq!(apply_rules_to_transactions(&conn).unwrap(), 1);
+        assert_eq!(repository::get_all_transactions(&conn).unwrap()[0].category, "Food");
+    }
+
+    #[test]
+    fn test_apply_rules_equal_priority_uses_oldest_rule() {
+        let conn = establish_test_connection().unwrap();
+        rule_repository::add_rule(&conn, "uber", "Transport", RuleMatchType::Substring).unwrap();
+        rule_repository::add_rule(&conn, "uber eats", "Food", RuleMatchType::Substring).unwrap();
+        add_transaction_to_db(&conn, "2025-03-01,Uber Eats order,18.00,expense,Uncategorized").unwrap();
+
+        apply_rules_to_transactions(&conn).unwrap();
+        assert_eq!(repository::get_all_transactions(&conn).unwrap()[0].category, "Transport");
+    }
+
+    #[test]
+    fn test_preview_rule_application() {
+        let conn = establish_test_connection().unwrap();
+        rule_repository::add_rule(&conn, "uber", "Transport", RuleMatchType::Substring).unwrap();
+        rule_repository::add_rule(&conn, "uber eats", "Food", RuleMatchType::Substring).unwrap();
+        let food_rule = rule_repository::get_all_rules(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|r| r.category == "Food")
+            .unwrap();
+        rule_repository::set_rule_priority(&conn, food_rule.id, 1).unwrap();
+
+        add_transaction_to_db(&conn, "2025-03-01,Uber ride,12.00,expense,Uncategorized").unwrap();
+        add_transaction_to_db(&conn, "2025-03-02,Uber Eats order,18.00,expense,Uncategorized").unwrap();
+        add_transaction_to_db(&conn, "2025-03-03,Bookshop,20.00,expense,Uncategorized").unwrap();
+        add_transaction_to_db(&conn, "2025-03-04,Uber to airport,30.00,expense,Travel").unwrap();
+
+        let preview = preview_rule_application(&conn).unwrap();
+        assert_eq!(preview.len(), 3);
+
+        let rule_for = |description: &str| {
+            preview
+                .iter()
+                .find(|(t, _)| t.description == description)
+                .unwrap()
+                .1
+                .as_ref()
+                .map(|r| (r.pattern.clone(), r.category.clone()))
+        };
+        assert_eq!(rule_for("Uber ride"), Some(("uber".to_string(), "Transport".to_string())));
+        assert_eq!(rule_for("Uber Eats order"), Some(("uber eats".to_string(), "Food".to_string())));
+        assert_eq!(rule_for("Bookshop"), None);
+
+        // Previewing changes nothing.
+        let all = repository::get_all_transactions(&conn).unwrap();
+        assert_eq!(all.iter().filter(|t| t.category == "Uncategorized").count(), 3);
+    }
+
+    #[test]
+    fn test_apply_rules_without_rules() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-03-01,Morning Coffee,3.50,expense,Uncategorized").unwrap();
+
+        assert_eq!(apply_rules_to_transactions(&conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_rule_set_uses_first_match() {
+        let rules = RuleSet::new(vec![
+            rule("(unclosed", "Broken", RuleMatchType::Regex),
+            rule("uber eats", "Food", RuleMatchType::Substring),
+            rule("^Uber", "Transport", RuleMatchType::Regex),
+        ]);
+
+        assert_eq!(rules.category_for("Uber Eats order"), Some("Food"));
+        assert_eq!(rules.category_for("Uber trip"), Some("Transport"));
+        assert_eq!(rules.category_for("Bus ticket"), None);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::db::repository;
+use crate::models::transaction::{Transaction, TransactionType};
+use rusqlite::Connection;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Parses `MIN..MAX`, where either side may be left out (`100..`, `..20`).
+pub fn parse_amount_range(input: &str) -> Result<(Option<Decimal>, Option<Decimal>), String> {
+    let s = input.trim();
+    let (min_str, max_str) = s
+        .split_once("..")
+        .ok_or_else(|| format!("Invalid amount range '{}'. Use MIN..MAX, MIN.. or ..MAX, e.g. 100..500.", s))?;
+    let bound = |text: &str| -> Result<Option<Decimal>, String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        Decimal::from_str(text)
+            .map(Some)
+            .map_err(|_| format!("Invalid amount '{}' in range '{}'. Must be a valid number", text, s))
+    };
+    let (min, max) = (bound(min_str)?, bound(max_str)?);
+    if let (Some(min), Some(max)) = (min, max)
+        && min > max
+    {
+        return Err(format!("Minimum amount {} is larger than maximum amount {}.", min, max));
+    }
+    Ok((min, max))
+}
+
+pub fn search_transactions_by_amount_db(
+    conn: &Connection,
+    range: &str,
+    tx_type: Option<TransactionType>,
+) -> Result<Vec<Transaction>, String> {
+    let (min, max) = parse_amount_range(range)?;
+    repository::get_transactions_with_amount_range(conn, min, max, tx_type).map_err(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::establish_test_connection;
+    use crate::operations::add::add_transaction_to_db;
+
+    #[test]
+    fn test_parse_amount_range() {
+        assert_eq!(parse_amount_range("10..20.5"), Ok((Some(Decimal::new(10, 0)), Some(Decimal::new(205, 1)))));
+        assert_eq!(parse_amount_range(" 100.. "), Ok((Some(Decimal::new(100, 0)), None)));
+        assert_eq!(parse_amount_range("..20"), Ok((None, Some(Decimal::new(20, 0)))));
+        assert_eq!(parse_amount_range(".."), Ok((None, None)));
+        assert!(parse_amount_range("20..10").unwrap_err().contains("larger than"));
+        assert!(parse_amount_range("abc..10").unwrap_err().contains("Invalid amount 'abc'"));
+        assert!(parse_amount_range("100").unwrap_err().contains("Invalid amount range"));
+    }
+
+    #[test]
+    fn test_search_transactions_by_amount() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Coffee,4.50,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-11,Laptop,1200.00,expense,Tech").unwrap();
+        add_transaction_to_db(&conn, "2025-11-12,Salary,1500.00,income,Job").unwrap();
+
+        let large = search_transactions_by_amount_db(&conn, "1000..", None).unwrap();
+        assert_eq!(large.len(), 2);
+        let large_expenses = search_transactions_by_amount_db(&conn, "1000..", Some(TransactionType::Expense)).unwrap();
+        assert_eq!(large_expenses.len(), 1);
+        assert_eq!(large_expenses[0].description, "Laptop");
+    }
+}
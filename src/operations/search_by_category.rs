@@ -1,5 +1,6 @@
 use crate::db::repository;
 use crate::models::transaction::Transaction;
+use crate::operations::add::suggest_categories;
 use rusqlite::Connection;
 
 pub fn search_transactions_by_category_db(
@@ -9,7 +10,16 @@ pub fn search_transactions_by_category_db(
     if category.trim().is_empty() {
         return Err("Category cannot be empty".to_string());
     }
-    repository::search_by_category(conn, category)
+    repository::search_by_category(conn, category).map_err(String::from)
+}
+
+/// Categories to offer when a search finds nothing: similar ones if any, otherwise all of them.
+pub fn suggest_alternative_categories(conn: &Connection, category: &str) -> Result<Vec<String>, String> {
+    let similar = suggest_categories(conn, category)?;
+    if !similar.is_empty() {
+        return Ok(similar);
+    }
+    repository::get_distinct_categories(conn).map_err(String::from)
 }
 
 #[cfg(test)]
@@ -56,6 +66,17 @@ mod tests {
         assert_eq!(result.unwrap().len(), 1);
     }
 
+    #[test]
+    fn test_suggest_alternative_categories() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-11-10,Coffee,4.50,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-11,Uber,12.00,expense,Transport").unwrap();
+        add_transaction_to_db(&conn, "2025-11-12,Taxi,20.00,expense,transport").unwrap();
+
+        assert_eq!(suggest_alternative_categories(&conn, "Transp").unwrap(), vec!["Transport"]);
+        assert_eq!(suggest_alternative_categories(&conn, "Shopping").unwrap(), vec!["Food", "Transport"]);
+    }
+
     #[test]
     fn test_search_transactions_empty_category() {
         let conn = establish_test_connection().unwrap();
@@ -0,0 +1,67 @@
+use crate::db::repository;
+use crate::models::transaction::Transaction;
+use rusqlite::Connection;
+
+pub fn search_transactions_by_description_db(
+    conn: &Connection,
+    keyword: &str,
+) -> Result<Vec<Transaction>, String> {
+    if keyword.trim().is_empty() {
+        return Err("Keyword cannot be empty".to_string());
+    }
+    repository::search_by_description(conn, keyword.trim()).map_err(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::establish_test_connection;
+    use crate::operations::add::add_transaction_to_db;
+
+    #[test]
+    fn test_search_transactions_by_description_found() {
+        let conn = establish_test_connection().unwrap();
+
+        add_transaction_to_db(&conn, "2025-11-10,Coffee,4.50,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-11-11,Uber,12.00,expense,Transport").unwrap();
+        add_transaction_to_db(&conn, "2025-11-12,Coffee beans,15.00,expense,Food").unwrap();
+
+        let result = search_transactions_by_description_db(&conn, "Coffee");
+        assert!(result.is_ok());
+
+        let transactions = result.unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert!(transactions.iter().all(|t| t.description.contains("Coffee")));
+    }
+
+    #[test]
+    fn test_search_transactions_by_description_not_found() {
+        let conn = establish_test_connection().unwrap();
+
+        add_transaction_to_db(&conn, "2025-11-10,Coffee,4.50,expense,Food").unwrap();
+
+        let result = search_transactions_by_description_db(&conn, "Rent");
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_search_transactions_by_description_partial_word() {
+        let conn = establish_test_connection().unwrap();
+
+        add_transaction_to_db(&conn, "2025-11-10,Supermarket,45.20,expense,Groceries").unwrap();
+
+        let result = search_transactions_by_description_db(&conn, "MARK");
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_search_transactions_empty_keyword() {
+        let conn = establish_test_connection().unwrap();
+
+        let result = search_transactions_by_description_db(&conn, "  ");
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Keyword cannot be empty");
+    }
+}
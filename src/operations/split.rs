@@ -0,0 +1,134 @@
+use crate::db::repository;
+use crate::models::transaction::Transaction;
+use rusqlite::Connection;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// Replaces transaction `id` with two transactions that keep its date, description, type,
+/// tags, status and notes but get new IDs and the given amounts and categories. The amounts
+/// must be positive and add up to the original amount. Returns the IDs of the two new
+/// transactions; on error nothing is changed.
+pub fn split_transaction(
+    conn: &Connection,
+    id: &str,
+    amount_a: Decimal,
+    category_a: &str,
+    amount_b: Decimal,
+    category_b: &str,
+) -> Result<(String, String), String> {
+    let id = Uuid::parse_str(id.trim())
+        .map_err(|_| "Invalid transaction ID format. Please provide a valid UUID.".to_string())?
+        .to_string();
+    let original = repository::get_transaction_by_id(conn, &id)?
+        .ok_or_else(|| format!("Transaction with ID {} not found", id))?;
+
+    if amount_a <= Decimal::ZERO || amount_b <= Decimal::ZERO {
+        return Err("Both split amounts must be greater than zero.".to_string());
+    }
+    repository::check_amount_precision(&amount_a)?;
+    repository::check_amount_precision(&amount_b)?;
+    if amount_a + amount_b != original.amount {
+        return Err(format!(
+            "Split amounts {} + {} = {} do not add up to the original amount {}.",
+            amount_a,
+            amount_b,
+            amount_a + amount_b,
+            original.amount
+        ));
+    }
+    let (category_a, category_b) = (category_a.trim(), category_b.trim());
+    if category_a.is_empty() || category_b.is_empty() {
+        return Err("Categories cannot be empty.".to_string());
+    }
+
+    let part = |amount: Decimal, category: &str| Transaction {
+        id: Uuid::new_v4().to_string(),
+        amount,
+        category: category.to_string(),
+        ..original.clone()
+    };
+    let first = part(amount_a, category_a);
+    let second = part(amount_b, category_b);
+
+    conn.execute("BEGIN", [])
+        .map_err(|e| format!("Failed to start split: {}", e))?;
+    let result = repository::remove_transaction(conn, &id)
+        .and_then(|_| repository::add_transaction(conn, &first))
+        .and_then(|_| repository::add_transaction(conn, &second));
+    match result {
+        Ok(()) => {
+            conn.execute("COMMIT", [])
+                .map_err(|e| format!("Failed to commit split: {}", e))?;
+            Ok((first.id, second.id))
+        }
+        Err(e) => {
+            let _ = conn.execute("ROLLBACK", []);
+            Err(format!("Failed to split transaction: {}", e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::establish_test_connection;
+    use crate::models::transaction::{TransactionStatus, TransactionType};
+    use crate::operations::add::add_transaction_to_db_with_id;
+
+    fn seed(conn: &Connection) -> String {
+        let (id, _) = add_transaction_to_db_with_id(conn, "2025-03-04,Supermarket,100.00,expense,Groceries,weekly,Receipt 42")
+            .unwrap();
+        id
+    }
+
+    #[test]
+    fn test_split_transaction() {
+        let conn = establish_test_connection().unwrap();
+        let id = seed(&conn);
+
+        let (first_id, second_id) =
+            split_transaction(&conn, &id, Decimal::new(7000, 2), "Food", Decimal::new(3000, 2), " Household ").unwrap();
+
+        assert!(repository::get_transaction_by_id(&conn, &id).unwrap().is_none());
+        assert_ne!(first_id, second_id);
+        let first = repository::get_transaction_by_id(&conn, &first_id).unwrap().unwrap();
+        let second = repository::get_transaction_by_id(&conn, &second_id).unwrap().unwrap();
+        assert_eq!(first.amount, Decimal::new(7000, 2));
+        assert_eq!(first.category, "Food");
+        assert_eq!(second.amount, Decimal::new(3000, 2));
+        assert_eq!(second.category, "Household");
+        for part in [&first, &second] {
+            assert_eq!(part.date, chrono::NaiveDate::from_ymd_opt(2025, 3, 4).unwrap());
+            assert_eq!(part.description, "Supermarket");
+            assert_eq!(part.transaction_type, TransactionType::Expense);
+            assert_eq!(part.tags, vec!["weekly"]);
+            assert_eq!(part.status, TransactionStatus::Cleared);
+            assert_eq!(part.notes, "Receipt 42");
+        }
+        assert_eq!(repository::count_transactions(&conn).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_split_transaction_amounts_must_add_up() {
+        let conn = establish_test_connection().unwrap();
+        let id = seed(&conn);
+
+        let err = split_transaction(&conn, &id, Decimal::new(70, 0), "Food", Decimal::new(20, 0), "Household").unwrap_err();
+        assert!(err.contains("do not add up"));
+        let err = split_transaction(&conn, &id, Decimal::new(100, 0), "Food", Decimal::ZERO, "Household").unwrap_err();
+        assert!(err.contains("greater than zero"));
+        let err = split_transaction(&conn, &id, Decimal::new(9999999, 5), "Food", Decimal::new(1, 5), "Household").unwrap_err();
+        assert!(err.contains("too many decimal places"));
+        assert!(repository::get_transaction_by_id(&conn, &id).unwrap().is_some());
+        assert_eq!(repository::count_transactions(&conn).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_split_transaction_unknown_id() {
+        let conn = establish_test_connection().unwrap();
+        let err = split_transaction(&conn, "550e8400-e29b-41d4-a716-446655440999", Decimal::ONE, "A", Decimal::ONE, "B")
+            .unwrap_err();
+        assert!(err.contains("not found"));
+        assert!(split_transaction(&conn, "nope", Decimal::ONE, "A", Decimal::ONE, "B").unwrap_err().contains("valid UUID"));
+    }
+}
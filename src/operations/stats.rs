@@ -0,0 +1,514 @@
+use crate::date_utils::all_time_range;
+use crate::db::{exchange_rate_repository, repository};
+use crate::models::transaction::{Transaction, TransactionType};
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+const TOP_CATEGORY_COUNT: usize = 5;
+
+pub fn print_stats(conn: &Connection, start_date: NaiveDate, end_date: NaiveDate) -> Result<(), String> {
+    let mut stdout = io::stdout();
+    write_stats(conn, start_date, end_date, &mut stdout)
+}
+
+pub fn write_stats<W: Write>(
+    conn: &Connection,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    out: &mut W,
+) -> Result<(), String> {
+    if start_date > end_date {
+        return Err("Start date must be before end date.".to_string());
+    }
+
+    let (total_income, total_expenses) = income_and_expenses(conn, start_date, end_date)?;
+    let transaction_count = repository::get_transactions_in_date_range(conn, start_date, end_date)?.len();
+    let daily_spend = calculate_average_daily_spend(conn, start_date, end_date)?;
+    let categories = repository::get_category_summary(conn, start_date, end_date)?;
+
+    let write_err = |e: io::Error| format!("Failed to write stats: {}", e);
+
+    writeln!(out, "Stats for {} - {}", start_date.format("%Y-%m-%d"), end_date.format("%Y-%m-%d")).map_err(write_err)?;
+    writeln!(out, "Transactions:   {}", transaction_count).map_err(write_err)?;
+    writeln!(out, "Total income:   {:.2}", total_income).map_err(write_err)?;
+    writeln!(out, "Total expenses: {:.2}", total_expenses).map_err(write_err)?;
+    writeln!(out, "Net balance:    {:.2}", total_income - total_expenses).map_err(write_err)?;
+    match savings_rate(total_income, total_expenses) {
+        Some(rate) => writeln!(out, "Savings rate:   {:.1}%", rate).map_err(write_err)?,
+        None => writeln!(out, "Savings rate:   n/a (no income)").map_err(write_err)?,
+    }
+    writeln!(out, "Daily spend:    {:.2}", daily_spend).map_err(write_err)?;
+
+    writeln!(out, "Top categories by spend:").map_err(write_err)?;
+    if categories.is_empty() {
+        writeln!(out, "  (no expenses)").map_err(write_err)?;
+    }
+    for (category, total, count) in categories.iter().take(TOP_CATEGORY_COUNT) {
+        writeln!(out, "  {:15} {:>12.2} ({} transactions)", category, total, count).map_err(write_err)?;
+    }
+
+    let counts = category_counts(conn)?;
+    if !counts.is_empty() {
+        writeln!(out, "Transactions per category (all time):").map_err(write_err)?;
+        for (category, count) in counts {
+            writeln!(out, "  {}: {} transactions", category, count).map_err(write_err)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Share of income that was not spent, in percent: `(income - expenses) / income * 100`.
+/// Negative when more was spent than earned; `None` when there was no income in the range.
+pub fn calculate_savings_rate(conn: &Connection, start: NaiveDate, end: NaiveDate) -> Result<Option<f64>, String> {
+    if start > end {
+        return Err("Start date must be before end date.".to_string());
+    }
+    let (income, expenses) = income_and_expenses(conn, start, end)?;
+    Ok(savings_rate(income, expenses))
+}
+
+/// Expenses in the range divided by its number of days, counting both ends. An open end of
+/// `all_time_range` is narrowed by `spend_window`; explicit dates are always kept.
+pub fn calculate_average_daily_spend(conn: &Connection, start: NaiveDate, end: NaiveDate) -> Result<Decimal, String> {
+    average_daily_spend_at(conn, start, end, chrono::Local::now().date_naive())
+}
+
+fn average_daily_spend_at(conn: &Connection, start: NaiveDate, end: NaiveDate, today: NaiveDate) -> Result<Decimal, String> {
+    if start > end {
+        return Err("Start date must be before end date.".to_string());
+    }
+    let (_, expenses) = income_and_expenses(conn, start, end)?;
+    let (spend_start, spend_end) = spend_window(start, end, repository::get_transaction_date_bounds(conn)?, today);
+    Ok(average_daily_spend(expenses, spend_start, spend_end))
+}
+
+/// Expenses in the range whose amount is more than `zscore_threshold` standard deviations
+/// away from the mean of their category (compared case-insensitively), oldest first.
+/// Categories with fewer than two expenses or identical amounts have nothing to compare against.
+pub fn detect_outliers(
+    conn: &Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+    zscore_threshold: f64,
+) -> Result<Vec<Transaction>, String> {
+    if start > end {
+        return Err("Start date must be before end date.".to_string());
+    }
+    if !zscore_threshold.is_finite() || zscore_threshold <= 0.0 {
+        return Err("The z-score threshold must be a positive number.".to_string());
+    }
+    let expenses = repository::get_expense_transactions_in_range(conn, start, end)?;
+
+    let mut by_category: HashMap<String, Vec<f64>> = HashMap::new();
+    for transaction in &expenses {
+        by_category
+            .entry(transaction.category.to_lowercase())
+            .or_default()
+            .push(transaction.amount.to_f64().unwrap_or_default());
+    }
+    let spread: HashMap<String, (f64, f64)> = by_category
+        .into_iter()
+        .filter(|(_, amounts)| amounts.len() >= 2)
+        .map(|(category, amounts)| {
+            let count = amounts.len() as f64;
+            let mean = amounts.iter().sum::<f64>() / count;
+            let variance = amounts.iter().map(|amount| (amount - mean).powi(2)).sum::<f64>() / count;
+            (category, (mean, variance.sqrt()))
+        })
+        .collect();
+
+    Ok(expenses
+        .into_iter()
+        .filter(|transaction| {
+            let Some((mean, std_dev)) = spread.get(&transaction.category.to_lowercase()) else {
+                return false;
+            };
+            let amount = transaction.amount.to_f64().unwrap_or_default();
+            *std_dev > 0.0 && ((amount - mean) / std_dev).abs() > zscore_threshold
+        })
+        .collect())
+}
+
+/// The middle expense amount in the range (the mean of the two middle ones for an even
+/// count), or `None` without expenses.
+pub fn get_median_expense(conn: &Connection, start: NaiveDate, end: NaiveDate) -> Result<Option<Decimal>, String> {
+    get_percentile_expense(conn, start, end, 50)
+}
+
+/// The expense amount below which `percentile` percent of the expenses in the range fall,
+/// interpolating linearly between neighbouring amounts: 0 is the smallest expense, 100 the
+/// largest. `None` without expenses.
+pub fn get_percentile_expense(
+    conn: &Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+    percentile: u8,
+) -> Result<Option<Decimal>, String> {
+    if start > end {
+        return Err("Start date must be before end date.".to_string());
+    }
+    if percentile > 100 {
+        return Err(format!("Percentile must be between 0 and 100, got {}.", percentile));
+    }
+    let mut amounts: Vec<Decimal> = repository::get_expense_transactions_in_range(conn, start, end)?
+        .into_iter()
+        .map(|transaction| transaction.amount)
+        .collect();
+    if amounts.is_empty() {
+        return Ok(None);
+    }
+    amounts.sort();
+
+    // Position on a 0..=len-1 scale; the fraction is the share of the next amount.
+    let position = Decimal::from(percentile) * Decimal::from(amounts.len() - 1) / Decimal::ONE_HUNDRED;
+    let lower = position.floor().to_usize().unwrap_or_default();
+    let fraction = position - position.floor();
+    let value = match amounts.get(lower + 1) {
+        Some(upper) if !fraction.is_zero() => amounts[lower] + (*upper - amounts[lower]) * fraction,
+        _ => amounts[lower],
+    };
+    Ok(Some(value))
+}
+
+/// Net balance of `start..=end` with every transaction converted to `base` at the latest stored
+/// rate for its currency. Transactions without a currency count as `base`. A rate stored only
+/// the other way round (`base` to the currency) is inverted.
+pub fn get_net_balance_in_base_currency(
+    conn: &Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+    base: &str,
+) -> Result<Decimal, String> {
+    let base = base.trim().to_uppercase();
+    if base.is_empty() {
+        return Err("Base currency cannot be empty".to_string());
+    }
+
+    let mut by_currency: HashMap<String, Decimal> = HashMap::new();
+    for transaction in repository::get_transactions_in_date_range(conn, start, end)? {
+        let signed = match transaction.transaction_type {
+            TransactionType::Income => transaction.amount,
+            TransactionType::Expense => -transaction.amount,
+        };
+        *by_currency.entry(transaction.currency_code.to_uppercase()).or_insert(Decimal::ZERO) += signed;
+    }
+
+    let mut balance = Decimal::ZERO;
+    for (currency, net) in by_currency {
+        balance += if currency.is_empty() || currency == base {
+            net
+        } else {
+            net * conversion_rate(conn, &currency, &base)?
+        };
+    }
+    Ok(balance)
+}
+
+fn conversion_rate(conn: &Connection, from: &str, to: &str) -> Result<Decimal, String> {
+    if let Some(rate) = exchange_rate_repository::get_latest_exchange_rate(conn, from, to)? {
+        return Ok(rate.rate);
+    }
+    if let Some(rate) = exchange_rate_repository::get_latest_exchange_rate(conn, to, from)? {
+        return Ok(Decimal::ONE / rate.rate);
+    }
+    Err(format!("No exchange rate from {} to {}. Add one with `fino rate set`.", from, to))
+}
+
+/// `repository::count_transactions_by_category` with each category under its stored spelling,
+/// most used first.
+fn category_counts(conn: &Connection) -> Result<Vec<(String, usize)>, String> {
+    let counts = repository::count_transactions_by_category(conn)?;
+    let mut named: Vec<(String, usize)> = repository::get_distinct_categories(conn)?
+        .into_iter()
+        .filter_map(|category| counts.get(&category.to_lowercase()).map(|count| (category, *count)))
+        .collect();
+    named.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(named)
+}
+
+fn income_and_expenses(conn: &Connection, start: NaiveDate, end: NaiveDate) -> Result<(Decimal, Decimal), String> {
+    let income = sum_totals(&repository::get_monthly_totals(conn, start, end, TransactionType::Income)?);
+    let expenses = sum_totals(&repository::get_monthly_totals(conn, start, end, TransactionType::Expense)?);
+    Ok((income, expenses))
+}
+
+fn savings_rate(income: Decimal, expenses: Decimal) -> Option<f64> {
+    if income.is_zero() {
+        return None;
+    }
+    ((income - expenses) / income * Decimal::ONE_HUNDRED).to_f64()
+}
+
+fn average_daily_spend(expenses: Decimal, start: NaiveDate, end: NaiveDate) -> Decimal {
+    let days = (end - start).num_days() + 1;
+    (expenses / Decimal::from(days)).round_dp(2)
+}
+
+/// The part of `start..=end` that the daily average is taken over. Only the open ends of
+/// `all_time_range` are narrowed: the start to the first stored transaction, the end to today
+/// or the last stored transaction, whichever is later. Keeps `stats` without a range from
+/// averaging over thousands of empty years.
+fn spend_window(
+    start: NaiveDate,
+    end: NaiveDate,
+    bounds: Option<(NaiveDate, NaiveDate)>,
+    today: NaiveDate,
+) -> (NaiveDate, NaiveDate) {
+    let Some((first, last)) = bounds else {
+        return (start, end);
+    };
+    let (open_start, open_end) = all_time_range();
+    let window_start = if start == open_start { first } else { start };
+    let window_end = if end == open_end { last.max(today) } else { end };
+    if window_start > window_end {
+        return (start, end);
+    }
+    (window_start, window_end)
+}
+
+fn sum_totals(totals: &[(NaiveDate, Decimal)]) -> Decimal {
+    totals.iter().fold(Decimal::ZERO, |acc, (_, v)| acc + *v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::establish_test_connection;
+    use crate::operations::add::add_transaction_to_db;
+
+    fn stats_output(conn: &Connection, start: NaiveDate, end: NaiveDate) -> String {
+        let mut buffer = Vec::new();
+        write_stats(conn, start, end, &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_stats_totals() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-03-01,Salary,2000.00,income,Job").unwrap();
+        add_transaction_to_db(&conn, "2025-03-02,Rent,900.00,expense,Housing").unwrap();
+        add_transaction_to_db(&conn, "2025-03-03,Groceries,45.25,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-03-09,Lunch,12.00,expense,Food").unwrap();
+        add_transaction_to_db(&conn, "2025-04-01,Rent,900.00,expense,Housing").unwrap();
+
+        let output = stats_output(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+        );
+
+        assert!(output.contains("Transactions:   4"));
+        assert!(output.contains("Total income:   2000.00"));
+        assert!(output.contains("Total expenses: 957.25"));
+        assert!(output.contains("Net balance:    1042.75"));
+        assert!(output.contains("Savings rate:   52.1%"));
+        assert!(output.contains("Daily spend:    30.88"));
+        assert!(output.contains("57.25 (2 transactions)"));
+        assert!(output.contains("Transactions per category (all time):\n  Food: 2 transactions\n  Housing: 2 transactions\n  Job: 1 transactions\n"));
+
+        let housing = output.find("Housing").unwrap();
+        let food = output.find("Food").unwrap();
+        assert!(housing < food);
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn march(day: u32) -> NaiveDate {
+        date(2025, 3, day)
+    }
+
+    #[test]
+    fn test_savings_rate() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-03-01,Salary,2000.00,income,Job").unwrap();
+        add_transaction_to_db(&conn, "2025-03-02,Rent,1000.00,expense,Housing").unwrap();
+        add_transaction_to_db(&conn, "2025-03-20,Bonus,500.00,income,Job").unwrap();
+        add_transaction_to_db(&conn, "2025-03-21,Trip,500.00,expense,Travel").unwrap();
+
+        assert_eq!(calculate_savings_rate(&conn, march(1), march(10)).unwrap(), Some(50.0));
+        assert_eq!(calculate_savings_rate(&conn, march(20), march(31)).unwrap(), Some(0.0));
+        assert_eq!(calculate_savings_rate(&conn, march(2), march(19)).unwrap(), None);
+        assert!(calculate_savings_rate(&conn, march(31), march(1)).is_err());
+    }
+
+    #[test]
+    fn test_average_daily_spend() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-03-01,Salary,2000.00,income,Job").unwrap();
+        add_transaction_to_db(&conn, "2025-03-02,Rent,900.00,expense,Housing").unwrap();
+        add_transaction_to_db(&conn, "2025-03-05,Lunch,10.00,expense,Food").unwrap();
+
+        assert_eq!(calculate_average_daily_spend(&conn, march(1), march(10)).unwrap(), Decimal::new(9100, 2));
+        assert_eq!(calculate_average_daily_spend(&conn, march(1), march(3)).unwrap(), Decimal::new(30000, 2));
+        assert_eq!(calculate_average_daily_spend(&conn, march(20), march(31)).unwrap(), Decimal::ZERO);
+        // An explicit start before the first transaction still counts every day of the range.
+        assert_eq!(calculate_average_daily_spend(&conn, date(2025, 2, 24), march(5)).unwrap(), Decimal::new(9100, 2));
+        assert!(calculate_average_daily_spend(&conn, march(31), march(1)).is_err());
+
+        // Without a range the average starts at the first transaction and ends today.
+        let (all_start, all_end) = all_time_range();
+        assert_eq!(average_daily_spend_at(&conn, all_start, all_end, march(10)).unwrap(), Decimal::new(9100, 2));
+    }
+
+    #[test]
+    fn test_spend_window() {
+        let bounds = Some((march(5), date(2025, 4, 10)));
+        let all_time = all_time_range();
+
+        assert_eq!(spend_window(march(1), march(31), bounds, date(2025, 6, 1)), (march(1), march(31)));
+        assert_eq!(spend_window(all_time.0, all_time.1, bounds, date(2025, 6, 1)), (march(5), date(2025, 6, 1)));
+        assert_eq!(spend_window(all_time.0, all_time.1, bounds, march(20)), (march(5), date(2025, 4, 10)));
+        assert_eq!(spend_window(march(1), march(31), None, march(20)), (march(1), march(31)));
+        assert_eq!(spend_window(date(2024, 1, 1), date(2024, 1, 31), bounds, march(20)), (date(2024, 1, 1), date(2024, 1, 31)));
+    }
+
+    #[test]
+    fn test_detect_outliers() {
+        let conn = establish_test_connection().unwrap();
+        for day in 1..=20 {
+            add_transaction_to_db(&conn, &format!("2025-03-{:02},Coffee,10.00,expense,Food", day)).unwrap();
+        }
+        add_transaction_to_db(&conn, "2025-03-21,Catering,1000.00,expense,food").unwrap();
+        // Large, but the only one in its category and not an expense respectively.
+        add_transaction_to_db(&conn, "2025-03-22,Laptop,2000.00,expense,Tech").unwrap();
+        add_transaction_to_db(&conn, "2025-03-23,Bonus,5000.00,income,Job").unwrap();
+
+        let outliers = detect_outliers(&conn, march(1), march(31), 3.0).unwrap();
+        assert_eq!(outliers.len(), 1);
+        assert_eq!(outliers[0].description, "Catering");
+        assert_eq!(outliers[0].amount, Decimal::new(100000, 2));
+    }
+
+    #[test]
+    fn test_detect_outliers_uniform_spending() {
+        let conn = establish_test_connection().unwrap();
+        for day in 1..=5 {
+            add_transaction_to_db(&conn, &format!("2025-03-0{},Coffee,10.00,expense,Food", day)).unwrap();
+        }
+
+        assert!(detect_outliers(&conn, march(1), march(31), 2.0).unwrap().is_empty());
+        assert!(detect_outliers(&conn, march(1), march(31), 0.0).is_err());
+    }
+
+    #[test]
+    fn test_median_and_percentiles() {
+        let conn = establish_test_connection().unwrap();
+        for (day, amount) in [(1, "40.00"), (2, "10.00"), (3, "1000.00"), (4, "20.00")] {
+            add_transaction_to_db(&conn, &format!("2025-03-0{},Item,{},expense,Food", day, amount)).unwrap();
+        }
+        add_transaction_to_db(&conn, "2025-03-05,Salary,5000.00,income,Job").unwrap();
+
+        let median = get_median_expense(&conn, march(1), march(31)).unwrap();
+        assert_eq!(median, Some(Decimal::new(30, 0)));
+        assert_eq!(get_percentile_expense(&conn, march(1), march(31), 50).unwrap(), median);
+        assert_eq!(get_percentile_expense(&conn, march(1), march(31), 0).unwrap(), Some(Decimal::new(10, 0)));
+        assert_eq!(get_percentile_expense(&conn, march(1), march(31), 100).unwrap(), Some(Decimal::new(1000, 0)));
+        assert_eq!(get_percentile_expense(&conn, march(1), march(31), 75).unwrap(), Some(Decimal::new(280, 0)));
+
+        // Odd count: the middle amount itself.
+        assert_eq!(get_median_expense(&conn, march(1), march(3)).unwrap(), Some(Decimal::new(40, 0)));
+    }
+
+    #[test]
+    fn test_percentile_without_expenses() {
+        let conn = establish_test_connection().unwrap();
+        add_transaction_to_db(&conn, "2025-03-05,Salary,5000.00,income,Job").unwrap();
+
+        assert_eq!(get_median_expense(&conn, march(1), march(31)).unwrap(), None);
+        assert!(get_percentile_expense(&conn, march(1), march(31), 101).unwrap_err().contains("between 0 and 100"));
+    }
+
+    #[test]
+    fn test_stats_limits_top_categories() {
+        let conn = establish_test_connection().unwrap();
+        for (i, category) in ["A", "B", "C", "D", "E", "F"].iter().enumerate() {
+            let input = format!("2025-03-0{},Item,{}.00,expense,{}", i + 1, 10 * (i + 1), category);
+            add_transaction_to_db(&conn, &input).unwrap();
+        }
+
+        let output = stats_output(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+        );
+
+        assert!(output.contains("  F "));
+        assert!(output.contains("  B "));
+        assert!(!output.contains("  A "));
+    }
+
+    #[test]
+    fn test_stats_empty_range() {
+        let conn = establish_test_connection().unwrap();
+        let output = stats_output(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+        );
+
+        assert!(output.contains("Transactions:   0"));
+        assert!(output.contains("Net balance:    0.00"));
+        assert!(output.contains("Savings rate:   n/a (no income)"));
+        assert!(output.contains("(no expenses)"));
+    }
+
+    #[test]
+    fn test_stats_invalid_range() {
+        let conn = establish_test_connection().unwrap();
+        let mut buffer = Vec::new();
+        let result = write_stats(
+            &conn,
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            &mut buffer,
+        );
+        assert!(result.is_err());
+    }
+
+    fn seed_currencies(conn: &Connection) {
+        use crate::db::account_repository::add_account;
+        use crate::operations::add::add_transaction_to_account;
+        add_account(conn, "Euro", "EUR", &Decimal::ZERO).unwrap();
+        add_account(conn, "Dollar", "USD", &Decimal::ZERO).unwrap();
+        add_account(conn, "Pound", "GBP", &Decimal::ZERO).unwrap();
+        add_transaction_to_account(conn, "2025-03-01,Salary,1000.00,income,Job", "Euro").unwrap();
+        add_transaction_to_account(conn, "2025-03-02,Laptop,200.00,expense,Tech", "Dollar").unwrap();
+        add_transaction_to_account(conn, "2025-03-03,Gift,100.00,income,Gifts", "Pound").unwrap();
+        add_transaction_to_db(conn, "2025-03-04,Cash,10.00,expense,Food").unwrap();
+        add_transaction_to_account(conn, "2025-04-01,Rent,500.00,expense,Housing", "Dollar").unwrap();
+    }
+
+    #[test]
+    fn test_net_balance_in_base_currency() {
+        let conn = establish_test_connection().unwrap();
+        seed_currencies(&conn);
+        exchange_rate_repository::set_exchange_rate(&conn, "USD", "EUR", &Decimal::new(80, 2), march(1)).unwrap();
+        exchange_rate_repository::set_exchange_rate(&conn, "USD", "EUR", &Decimal::new(90, 2), march(20)).unwrap();
+        // Only stored the other way round: 1 EUR = 0.80 GBP, so 1 GBP = 1.25 EUR.
+        exchange_rate_repository::set_exchange_rate(&conn, "EUR", "GBP", &Decimal::new(80, 2), march(1)).unwrap();
+
+        // 1000 - 200 * 0.90 + 100 * 1.25 - 10
+        let balance = get_net_balance_in_base_currency(&conn, march(1), march(31), "eur").unwrap();
+        assert_eq!(balance, Decimal::new(935, 0));
+    }
+
+    #[test]
+    fn test_net_balance_in_base_currency_needs_rates() {
+        let conn = establish_test_connection().unwrap();
+        seed_currencies(&conn);
+        exchange_rate_repository::set_exchange_rate(&conn, "USD", "EUR", &Decimal::new(90, 2), march(1)).unwrap();
+
+        let err = get_net_balance_in_base_currency(&conn, march(1), march(31), "EUR").unwrap_err();
+        assert!(err.contains("No exchange rate from GBP to EUR"));
+        let april = get_net_balance_in_base_currency(&conn, date(2025, 4, 1), date(2025, 4, 30), "EUR").unwrap();
+        assert_eq!(april, Decimal::new(-450, 0));
+        assert!(get_net_balance_in_base_currency(&conn, march(1), march(31), " ").is_err());
+    }
+}
@@ -0,0 +1,171 @@
+use chrono::{Datelike, NaiveDate};
+use rusqlite::Connection;
+use rusqlite::types::Value;
+use std::fmt;
+
+/// Transactions dated outside these years are treated as typos.
+const MIN_YEAR: i32 = 1900;
+const MAX_YEAR: i32 = 2100;
+
+/// A problem in the stored data that the rest of the app would trip over or silently ignore.
+/// Transaction issues carry the transaction ID; rule and budget issues carry their row ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// The amount is not a number.
+    InvalidAmount(String),
+    /// The date is not `YYYY-MM-DD` or lies outside 1900-2100.
+    InvalidDate(String),
+    /// The transaction names an account that does not exist.
+    UnknownAccount(String),
+    /// A categorization rule assigns a category that no transaction has.
+    OrphanedRule(i32),
+    /// A budget is set for a category that no transaction has.
+    OrphanedBudget(i32),
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationIssue::InvalidAmount(id) => write!(f, "Transaction {} has an invalid amount", id),
+            ValidationIssue::InvalidDate(id) => write!(f, "Transaction {} has an invalid date", id),
+            ValidationIssue::UnknownAccount(id) => write!(f, "Transaction {} belongs to an account that does not exist", id),
+            ValidationIssue::OrphanedRule(id) => write!(f, "Rule {} assigns a category no transaction uses", id),
+            ValidationIssue::OrphanedBudget(id) => write!(f, "Budget {} is for a category no transaction uses", id),
+        }
+    }
+}
+
+/// Checks the stored data row by row. Reads the raw columns, so it also reports rows that
+/// the repository functions fail to parse. An empty list means nothing was found.
+pub fn validate_database(conn: &Connection) -> Result<Vec<ValidationIssue>, String> {
+    let mut issues = Vec::new();
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT t.id, t.date, t.amount, a.id IS NULL FROM transactions t \
+             LEFT JOIN accounts a ON a.name = t.account ORDER BY t.rowid",
+        )
+        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Value>(2)?,
+                row.get::<_, bool>(3)?,
+            ))
+        })
+        .map_err(|e| format!("Failed to read transactions: {}", e))?;
+    for row in rows {
+        let (id, date, amount, missing_account) = row.map_err(|e| format!("Failed to read transaction: {}", e))?;
+        // Amounts are REAL; text that SQLite could not convert to a number stays text.
+        if !matches!(amount, Value::Real(_) | Value::Integer(_)) {
+            issues.push(ValidationIssue::InvalidAmount(id.clone()));
+        }
+        let sane_date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .is_ok_and(|date| (MIN_YEAR..=MAX_YEAR).contains(&date.year()));
+        if !sane_date {
+            issues.push(ValidationIssue::InvalidDate(id.clone()));
+        }
+        if missing_account {
+            issues.push(ValidationIssue::UnknownAccount(id));
+        }
+    }
+
+    for (table, issue) in [
+        ("category_rules", ValidationIssue::OrphanedRule as fn(i32) -> ValidationIssue),
+        ("category_budgets", ValidationIssue::OrphanedBudget),
+    ] {
+        let sql = format!(
+            "SELECT id FROM {} c WHERE NOT EXISTS \
+             (SELECT 1 FROM transactions t WHERE LOWER(t.category) = LOWER(c.category)) ORDER BY id",
+            table
+        );
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+        let ids = stmt
+            .query_map([], |row| row.get::<_, i32>(0))
+            .map_err(|e| format!("Failed to read {}: {}", table, e))?;
+        for id in ids {
+            issues.push(issue(id.map_err(|e| format!("Failed to read {}: {}", table, e))?));
+        }
+    }
+
+    Ok(issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::establish_test_connection;
+    use crate::db::{budget_repository, rule_repository};
+    use crate::models::budget::BudgetPeriod;
+    use crate::models::rule::RuleMatchType;
+    use crate::operations::add::add_transaction_to_db_with_id;
+    use rust_decimal::Decimal;
+
+    fn add(conn: &Connection, input: &str) -> String {
+        add_transaction_to_db_with_id(conn, input).unwrap().0
+    }
+
+    #[test]
+    fn test_clean_database_has_no_issues() {
+        let conn = establish_test_connection().unwrap();
+        add(&conn, "2025-03-01,Groceries,45.25,expense,Food");
+        rule_repository::add_rule(&conn, "Aldi", "food", RuleMatchType::Substring).unwrap();
+        budget_repository::set_budget(&conn, "Food", &Decimal::new(200, 0), Some(BudgetPeriod::Monthly)).unwrap();
+
+        assert_eq!(validate_database(&conn).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_detects_corrupt_transactions() {
+        let conn = establish_test_connection().unwrap();
+        let bad_amount = add(&conn, "2025-03-01,Groceries,45.25,expense,Food");
+        let bad_date = add(&conn, "2025-03-02,Lunch,12.00,expense,Food");
+        let far_future = add(&conn, "2025-03-03,Bus,2.50,expense,Food");
+        let lost_account = add(&conn, "2025-03-04,Coffee,3.00,expense,Food");
+        conn.execute("UPDATE transactions SET amount = 'twelve' WHERE id = ?1", [&bad_amount]).unwrap();
+        conn.execute("UPDATE transactions SET date = '03/02/2025' WHERE id = ?1", [&bad_date]).unwrap();
+        conn.execute("UPDATE transactions SET date = '9999-01-01' WHERE id = ?1", [&far_future]).unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=OFF").unwrap();
+        conn.execute("UPDATE transactions SET account = 'gone' WHERE id = ?1", [&lost_account]).unwrap();
+
+        let issues = validate_database(&conn).unwrap();
+        assert_eq!(
+            issues,
+            vec![
+                ValidationIssue::InvalidAmount(bad_amount),
+                ValidationIssue::InvalidDate(bad_date),
+                ValidationIssue::InvalidDate(far_future),
+                ValidationIssue::UnknownAccount(lost_account.clone()),
+            ]
+        );
+        assert_eq!(
+            issues[3].to_string(),
+            format!("Transaction {} belongs to an account that does not exist", lost_account)
+        );
+    }
+
+    #[test]
+    fn test_detects_orphaned_rules_and_budgets() {
+        let conn = establish_test_connection().unwrap();
+        add(&conn, "2025-03-01,Groceries,45.25,expense,Food");
+        rule_repository::add_rule(&conn, "Aldi", "Food", RuleMatchType::Substring).unwrap();
+        rule_repository::add_rule(&conn, "Uber", "Transport", RuleMatchType::Substring).unwrap();
+        budget_repository::set_budget(&conn, "Travel", &Decimal::new(500, 0), Some(BudgetPeriod::Yearly)).unwrap();
+        let rule_id = rule_repository::get_all_rules(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|rule| rule.category == "Transport")
+            .unwrap()
+            .id;
+        let budget_id = budget_repository::get_budget(&conn, "Travel").unwrap().unwrap().id;
+
+        assert_eq!(
+            validate_database(&conn).unwrap(),
+            vec![ValidationIssue::OrphanedRule(rule_id), ValidationIssue::OrphanedBudget(budget_id)]
+        );
+    }
+}